@@ -0,0 +1,90 @@
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::slurm::{
+    command::{execute_command, shell_quote},
+    first_host, Job,
+};
+
+/// A user-defined command that can be run for a job, configured as
+/// `[[actions]]` in config.toml
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomAction {
+    pub name: String,
+    /// Shell command. `{job_id}`, `{user}`, `{node}` (first node), `{nodes}`
+    /// (node list) and `{workdir}` are replaced with the job's values.
+    pub command: String,
+}
+
+impl CustomAction {
+    /// The command line for a job, with placeholders replaced by quoted values
+    pub fn command_for(&self, job: &Job) -> String {
+        let node_list = job.node.clone().unwrap_or_default();
+        let node = first_host(&node_list).unwrap_or_default();
+        let workdir = job.work_dir.clone().unwrap_or_default();
+        [
+            ("{job_id}", job.id.as_str()),
+            ("{user}", job.user.as_str()),
+            ("{node}", node.as_str()),
+            ("{nodes}", node_list.as_str()),
+            ("{workdir}", workdir.as_str()),
+        ]
+        .iter()
+        .fold(self.command.clone(), |command, (placeholder, value)| {
+            command.replace(placeholder, &shell_quote(value))
+        })
+    }
+}
+
+/// Output of the last run of an action for a job
+#[derive(Debug, Clone)]
+pub struct ActionResult {
+    pub time: DateTime<Local>,
+    pub command: String,
+    pub success: bool,
+    /// stdout followed by stderr
+    pub output: String,
+}
+
+/// Last result of each action per job, so reopening an action is instant
+#[derive(Debug, Default)]
+pub struct ActionCache {
+    results: HashMap<(String, String), ActionResult>,
+}
+
+impl ActionCache {
+    pub fn get(&self, job_id: &str, action: &str) -> Option<&ActionResult> {
+        self.results.get(&(job_id.to_string(), action.to_string()))
+    }
+
+    pub fn insert(&mut self, job_id: &str, action: &str, result: ActionResult) {
+        self.results
+            .insert((job_id.to_string(), action.to_string()), result);
+    }
+}
+
+/// Run an action for a job through `sh -c`
+pub async fn run_action(action: &CustomAction, job: &Job) -> ActionResult {
+    let command = action.command_for(job);
+    let time = Local::now();
+    match execute_command("sh", vec!["-c".to_string(), command.clone()]).await {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            ActionResult {
+                time,
+                command,
+                success: output.status.success(),
+                output: text,
+            }
+        }
+        Err(e) => ActionResult {
+            time,
+            command,
+            success: false,
+            output: format!("Failed to run: {}", e),
+        },
+    }
+}
@@ -1,36 +1,324 @@
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
-use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    text::Line,
-    widgets::{Block, Borders, Clear, Paragraph},
-    Frame,
+use ratatui::{layout::Rect, Frame};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    io::Write,
+    time::{Duration, Instant},
 };
-use regex;
-use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 
 use crate::{
+    actions::run_action,
+    bookmarks,
+    cli::CliArgs,
+    config::{
+        AdminSettings, BalanceSettings, Config, ConfirmPolicy, ConfirmSettings, GpuSettings,
+        JobTemplate, Pipeline,
+    },
+    i18n::{tr, trf},
+    lineage::{submitted_job_id, Lineage},
+    macros,
+    manifest::Manifest,
+    modules::{insert_module_loads, search_modules},
+    notes::Notes,
+    pipeline::{step_command, template_args},
     slurm::{
-        command::{execute_scancel, get_partitions, get_qos},
-        squeue::{run_squeue, SqueueOptions},
-        JobState,
+        audit::{AuditEntry, AuditLog},
+        balance::get_balance,
+        batch::BatchRun,
+        burstbuffer::get_burst_buffers,
+        command::{
+            attach_shell_command, editor_command, get_batch_script, get_cluster_limits,
+            get_cluster_name, get_job_info, get_job_record, get_job_request, get_node_allocation,
+            get_partitions, get_qos, get_submit_paths, get_work_dir, job_action_commands,
+            modify_job_command, parse_scontrol_fields, resubmit_command, run_command_output,
+            shell_quote, signal_commands, ssh_command, test_submit, work_dir_shell_command,
+            CommandStatus, SlurmCommand,
+        },
+        dashboard::get_cluster_summary,
+        expand_hostlist, first_host, format_duration,
+        gpu::{get_gpu_inventory, gpu_node, GpuMonitor},
+        limits::ClusterLimits,
+        node::{get_node_detail, get_node_grid, node_update_command, NodeState},
+        normalize_signal, parse_time_limit,
+        partition::get_partition_overview,
+        qos::get_qos_overview,
+        reasons::explain_reason,
+        reservation::get_reservations,
+        retry::{is_transient_failure, RetryQueue},
+        sacct::{
+            get_job_accounting, get_job_environment, get_job_exits, get_job_state, get_job_states,
+            get_job_steps, get_job_usage, get_live_max_rss, get_timeline_jobs, parse_size,
+            run_sacct, MIB,
+        },
+        sdiag::get_diagnostics,
+        sprio::{get_priority_factors, get_priority_ranks},
+        squeue::{get_queue_dependencies, run_squeue, SqueueOptions},
+        sshare::get_fairshare_tree,
+        sstat::{query_job_stats, JobStats},
+        sweep::write_params,
+        users::get_queued_jobs,
+        Job, JobAction, JobState,
     },
+    submissions::{Submission, Submissions},
     ui::{
+        actions::{ActionsPopup, ActionsPopupAction},
+        alert::WatchAlert,
+        allocform::{AllocAction, AllocForm},
+        auditview::AuditPanel,
+        burstbuffers::{BurstBuffersAction, BurstBuffersView},
         columns::{ColumnsAction, ColumnsPopup, JobColumn, SortColumn, SortOrder},
+        confirm::{ConfirmAction, ConfirmDialog},
+        dashboard::{DashboardAction, DashboardView},
+        dependency::{DependencyAction, DependencyEditor},
+        depgraph::{missing_targets, DependencyGraph, GraphJob},
+        diagnostics::{DiagnosticsAction, DiagnosticsView},
+        efficiency::EfficiencyPanel,
+        eta::EtaPanel,
+        fairshare::{FairshareAction, FairshareView},
+        filebrowser::{BrowserAction, FileBrowser},
         filter::{FilterAction, FilterPopup},
+        gpus::{GpusAction, GpusView},
+        history::{HistoryAction, HistoryView},
+        incident::{IncidentAction, IncidentPanel},
+        jobdetail::{DetailAction, JobDetail},
+        jobedit::{JobEditAction, JobEditForm},
         jobscript::JobScript,
         jobslist::JobsList,
-        layout::{centered_popup_area, draw_footer, draw_header, draw_main_layout},
+        layout::{
+            centered_popup_area, draw_footer, draw_header, draw_main_layout, split_content_area,
+            HeaderModes,
+        },
+        leaderboard::{LeaderboardAction, LeaderboardView},
         logview::LogView,
+        manifest::{ManifestAction, ManifestPanel, ManifestRequest},
+        modules::{ModuleAction, ModulePanel},
+        nodedetail::{NodeAction, NodePanel},
+        nodegrid::{NodeGridAction, NodeGridView},
+        partitions::{PartitionsAction, PartitionsView},
+        picker::{Picker, PickerAction},
+        preview::{CommandPreview, PreviewAction},
+        prompt::{InputPrompt, PromptAction, PromptKind},
+        qos::{QosAction, QosView},
+        reservations::{ReservationsAction, ReservationsView},
+        retries::{RetryAction, RetryPanel},
+        scriptdiff::{unified_diff, ScriptDiff},
+        submissions::{SubmissionsAction, SubmissionsPanel},
+        submitform::{SubmitAction, SubmitForm},
+        timeline::{TimelineView, TIMELINE_START},
     },
     utils::{
+        clipboard::copy_to_clipboard,
+        deeplink::JobLink,
         event::{Event as AppEvent, EventConfig, EventHandler},
-        get_username,
+        expand_home, get_username,
+        poller::Poller,
+        shell::run_interactive,
     },
+    watch::{WatchEvent, Watchlist},
 };
 
+/// Bulk actions on more jobs than this run in the background with a progress panel
+const BACKGROUND_ABOVE: usize = 50;
+
+/// Job IDs per command of a background bulk action, small enough for steady progress
+const BACKGROUND_CHUNK: usize = 25;
+
+/// How often `sstat` is sampled for the live resources tab of the detail pane
+const LIVE_STATS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Jobs view keys that change jobs or run commands for them; refused in read-only mode
+const MUTATING_KEYS: &[char] = &[
+    'x', 'h', 'u', 'R', 'e', 'P', 's', 'p', 'o', 'N', 'T', ':', 'M', '!', 't', 'S', 'W', 'J', 'O',
+    'Z', 'Q', 'X', 'U', 'n',
+];
+
+/// Footer hint of the number keys, shown in every view
+const VIEW_KEYS: (&str, &str) = (
+    "1-9,0",
+    "Jobs/History/Partitions/GPUs/Reservations/QoS/Fairshare/Diagnostics/Dashboard/Users",
+);
+
+/// Entry of the profile picker for quick commands that leaves resources to Slurm's defaults
+const DEFAULT_PROFILE: &str = "Default resources";
+
+/// Top-level views, switched with the number keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    /// Live queue from squeue
+    Jobs,
+    /// Finished jobs from sacct
+    History,
+    /// Partitions with their limits and nodes
+    Partitions,
+    /// GPUs per type and the jobs holding them
+    Gpus,
+    /// Reservations, marking those that hold back my pending jobs
+    Reservations,
+    /// QoS with their limits, marking the ones I may use
+    Qos,
+    /// Fair-share tree of my accounts from sshare
+    Fairshare,
+    /// Scheduler statistics from sdiag
+    Diagnostics,
+    /// Headline numbers of the whole cluster
+    Dashboard,
+    /// Running and pending resources per user
+    Users,
+    /// Every node as a cell colored by its state, opened from the partitions view
+    Nodes,
+    /// Burst buffer pools and allocations, opened from the dashboard
+    BurstBuffers,
+}
+
+/// Where the log of the job under the cursor is shown next to the job list,
+/// cycled with L
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SplitLayout {
+    /// Job list only
+    Off,
+    /// Log pane below the job list
+    Bottom,
+    /// Log pane right of the job list
+    Right,
+}
+
+/// A bulk job action waiting for the user to confirm it
+#[derive(Debug, Clone)]
+struct PendingAction {
+    action: JobAction,
+    /// Job IDs handed to the Slurm command
+    job_ids: Vec<String>,
+    /// Set when the whole array is targeted through its parent ID: (parent, task count)
+    array: Option<(String, usize)>,
+}
+
+/// A state-changing action shown in the confirmation dialog, run once confirmed
+enum Deferred {
+    /// Cancel, hold, release or requeue
+    Action(PendingAction),
+    Signal {
+        job_ids: Vec<String>,
+        signal: String,
+        batch_only: bool,
+    },
+    Nice {
+        job_ids: Vec<String>,
+        nice: i32,
+    },
+    /// Jobs to move to a partition, and the ones left where they are
+    Move {
+        partition: String,
+        job_ids: Vec<String>,
+        skipped: Vec<String>,
+    },
+    /// Submit the last job from the submission form again
+    Submit(SlurmCommand),
+    /// Submit the configured pipeline of this name
+    Pipeline(String),
+    /// Submit the rows of a manifest
+    Manifest(ManifestRequest),
+}
+
+impl Deferred {
+    /// Verb for the dialog title
+    fn verb(&self) -> &'static str {
+        match self {
+            Deferred::Action(pending) => pending.action.verb(),
+            Deferred::Signal { .. } => "signal",
+            Deferred::Nice { .. } => "renice",
+            Deferred::Move { .. } => "move",
+            Deferred::Submit(_) | Deferred::Pipeline(_) | Deferred::Manifest(_) => "submit",
+        }
+    }
+
+    /// Jobs the action applies to
+    fn job_ids(&self) -> &[String] {
+        match self {
+            Deferred::Action(pending) => &pending.job_ids,
+            Deferred::Signal { job_ids, .. }
+            | Deferred::Nice { job_ids, .. }
+            | Deferred::Move { job_ids, .. } => job_ids,
+            Deferred::Submit(command) => &command.job_ids,
+            Deferred::Pipeline(_) | Deferred::Manifest(_) => &[],
+        }
+    }
+
+    /// Question shown above the affected jobs
+    fn question(&self) -> String {
+        let count = self.job_ids().len().to_string();
+        match self {
+            Deferred::Action(PendingAction {
+                action,
+                array: Some((parent, tasks)),
+                ..
+            }) => capitalize(&trf(
+                "{action} the ENTIRE array job {parent} ({count} tasks) with a single `{command}`?",
+                &[
+                    ("action", tr(action.verb())),
+                    ("parent", parent),
+                    ("count", &tasks.to_string()),
+                    ("command", &format!("{} {}", action.command(), parent)),
+                ],
+            )),
+            Deferred::Action(pending) => trf(
+                "Are you sure you want to {action} {count} job(s)?",
+                &[("action", tr(pending.action.verb())), ("count", &count)],
+            ),
+            Deferred::Signal {
+                signal, batch_only, ..
+            } => {
+                let question = if *batch_only {
+                    "Send SIG{signal} to the batch shell of {count} job(s)?"
+                } else {
+                    "Send SIG{signal} to {count} job(s)?"
+                };
+                trf(question, &[("signal", signal), ("count", &count)])
+            }
+            Deferred::Nice { nice, .. } => trf(
+                "Set nice value {nice} on {count} job(s)?",
+                &[("nice", &nice.to_string()), ("count", &count)],
+            ),
+            Deferred::Move {
+                partition, skipped, ..
+            } => {
+                let question = trf(
+                    "Move {count} pending job(s) to partition {partition}?",
+                    &[("count", &count), ("partition", partition)],
+                );
+                if skipped.is_empty() {
+                    question
+                } else {
+                    let skipped = trf("Skipped: {jobs}", &[("jobs", &skipped.join(", "))]);
+                    format!("{} {}", question, skipped)
+                }
+            }
+            Deferred::Submit(command) => trf(
+                "Submit `{command}` again?",
+                &[("command", &command.to_string())],
+            ),
+            Deferred::Pipeline(name) => trf("Submit pipeline {name}?", &[("name", name)]),
+            Deferred::Manifest(request) => {
+                let question = if request.array {
+                    "Submit {file} as a job array of {count} tasks from template {template}?"
+                } else {
+                    "Submit {count} job(s) from {file} with template {template}?"
+                };
+                trf(
+                    question,
+                    &[
+                        ("count", &request.rows.to_string()),
+                        ("file", &request.path),
+                        ("template", &request.template),
+                    ],
+                )
+            }
+        }
+    }
+}
+
 /// Application state and logic
 pub struct App {
     /// Is the application running?
@@ -52,8 +340,26 @@ pub struct App {
     pub columns_popup: ColumnsPopup,
     /// Log view state
     pub log_view: LogView,
+    /// Log of the job under the cursor in the split layout
+    log_pane: LogView,
+    split: SplitLayout,
+    /// Open the log pane on jobs submitted from the form once they start
+    follow_submitted: bool,
+    /// Submitted job whose log pane opens once it leaves the pending state
+    following: Option<String>,
+    gpu_settings: GpuSettings,
+    /// Fraction of the memory request at which running jobs are highlighted; 0 for off
+    oom_warn: f64,
+    /// `nvidia-smi` readout for the job in the detail pane
+    gpu_monitor: Option<GpuMonitor>,
+    /// `sstat` sampling of the running job in the detail pane, with its ID
+    live_stats: Option<(String, Poller<Result<JobStats, String>>)>,
     /// Script View state
     pub script_view: JobScript,
+    /// Job modification form state
+    pub edit_form: JobEditForm,
+    /// Single-line input prompt (signal name, ...)
+    pub prompt: InputPrompt,
     /// Status message to display in the status bar
     pub status_message: String,
     /// Status message display timeout
@@ -70,13 +376,117 @@ pub struct App {
     pub selected_columns: Vec<JobColumn>,
     /// Sort columns
     pub sort_columns: Vec<SortColumn>,
-    /// Confirm cancel popup state
-    cancel_confirm: bool,
+    /// Action awaiting confirmation in the confirm dialog
+    confirm: Option<Deferred>,
+    confirm_dialog: ConfirmDialog,
+    /// Which actions ask for confirmation, and when the job count must be typed
+    confirm_policies: ConfirmSettings,
+    /// When set, state-changing commands are only previewed, never run
+    dry_run: bool,
+    /// Panel showing the commands a dry-run action would have executed, or the
+    /// review and progress of a rate-limited batch
+    preview: CommandPreview,
+    /// Pacing of actions on other users' jobs
+    admin: AdminSettings,
+    /// Seconds the extend action adds to a job's time limit
+    extend_by: u64,
+    /// Batch of commands shown for review, run once confirmed: the verb for
+    /// progress messages, the commands and the pause between them
+    batch_pending: Option<(&'static str, Vec<SlurmCommand>, Duration)>,
+    /// Rate-limited batch running in the background
+    batch: Option<(&'static str, BatchRun)>,
+    /// Incident mode: banner shown and auto-refresh paused
+    incident: bool,
+    incident_panel: IncidentPanel,
+    /// User-defined actions for the job under the cursor, with cached results
+    actions_popup: ActionsPopup,
+    dependency_editor: DependencyEditor,
+    eta_panel: EtaPanel,
+    efficiency_panel: EfficiencyPanel,
+    dependency_graph: DependencyGraph,
+    timeline: TimelineView,
+    file_browser: FileBrowser,
+    submit_form: SubmitForm,
+    alloc_form: AllocForm,
+    manifest_panel: ManifestPanel,
+    /// Whether the file browser is choosing a manifest rather than a script
+    picking_manifest: bool,
+    template_picker: Picker,
+    pipeline_picker: Picker,
+    /// Resource profile for a quick `--wrap` command, and the profile and command used last
+    wrap_picker: Picker,
+    wrap_profile: Option<String>,
+    wrap_command: String,
+    /// Configured pipelines, and the templates their steps are built from
+    pipelines: Vec<Pipeline>,
+    templates: Vec<JobTemplate>,
+    /// Where the submission form looks up account balances
+    balance: BalanceSettings,
+    /// All `scontrol show job` fields of one job
+    job_detail: JobDetail,
+    /// Partition picker for moving jobs, and the jobs to move
+    partition_picker: Picker,
+    move_jobs: Vec<Job>,
+    /// Jobs to alert about when they change state or leave the queue
+    watchlist: Watchlist,
+    watch_alert: WatchAlert,
+    /// Diff of the batch scripts of two jobs
+    script_diff: ScriptDiff,
+    /// View shown in the main area
+    view: View,
+    history: HistoryView,
+    partitions_view: PartitionsView,
+    gpus_view: GpusView,
+    reservations_view: ReservationsView,
+    qos_view: QosView,
+    fairshare_view: FairshareView,
+    diagnostics_view: DiagnosticsView,
+    dashboard_view: DashboardView,
+    leaderboard: LeaderboardView,
+    node_grid: NodeGridView,
+    burst_buffers: BurstBuffersView,
+    /// State-changing commands run in this session
+    audit_log: AuditLog,
+    audit_panel: AuditPanel,
+    submissions_panel: SubmissionsPanel,
+    /// Submissions that failed transiently, and the panel listing them
+    retries: RetryQueue,
+    retry_panel: RetryPanel,
+    /// Environment module search of the submission form
+    module_panel: ModulePanel,
+    /// Detail of a node, from the job detail pane or `:node`
+    node_panel: NodePanel,
+    /// Partition/QoS limits for form validation, fetched on first use
+    cluster_limits: Option<ClusterLimits>,
+    /// Name of the cluster, used in shareable job links
+    pub cluster_name: String,
+    /// Job link passed on the command line, opened after the first refresh
+    pending_link: Option<JobLink>,
+    /// Interactive command to run on the real terminal after the current event
+    pending_shell: Option<SlurmCommand>,
+    /// Started with --read-only: every mutating action is refused
+    read_only: bool,
+    /// sbatch command to run once the editor in `pending_shell` exits
+    pending_resubmit: Option<SlurmCommand>,
+    /// Job name of the allocation `pending_shell` asks for, to open a shell in it
+    pending_allocation: Option<String>,
+    /// Keys replayed by `@`, kept across sessions
+    macro_keys: Vec<KeyEvent>,
+    /// Jobs resubmitted through slurmer and what they were resubmitted from
+    lineage: Lineage,
+    /// Local notes on jobs
+    notes: Notes,
+    /// Last submission from the form, for submitting it again
+    submissions: Submissions,
+    /// Template the next sbatch run was filled from, recorded with its submission
+    submit_template: Option<String>,
+    /// Keys pressed since `q` started recording a macro
+    recording: Option<Vec<KeyEvent>>,
 }
 
 impl App {
     /// Create a new application instance
-    pub fn new() -> Result<Self> {
+    pub fn new(args: CliArgs, config: Config) -> Result<Self> {
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .enable_all()
             .build()
@@ -86,6 +496,7 @@ impl App {
         let username = get_username();
         let squeue_options = SqueueOptions {
             user: Some(username),
+            command: config.jobs_command.clone(),
             ..Default::default()
         };
 
@@ -93,6 +504,17 @@ impl App {
         let available_partitions = runtime.block_on(async { get_partitions().await })?;
         let available_qos = runtime.block_on(async { get_qos().await })?;
         let available_states = JobState::get_available_states();
+        let cluster_name = runtime
+            .block_on(async { get_cluster_name().await })
+            .unwrap_or_else(|_| "default".to_string());
+
+        let bookmarks = bookmarks::load()?;
+        let macro_keys = macros::load()?;
+        let lineage = Lineage::load()?;
+        let notes = Notes::load()?;
+        let submissions = Submissions::load()?;
+        let mut jobs_list = JobsList::new();
+        jobs_list.notes = notes.for_cluster(&cluster_name);
 
         // Default columns and sort options
         let selected_columns = JobColumn::defaults();
@@ -104,14 +526,24 @@ impl App {
         Ok(Self {
             running: true,
             event_handler: EventHandler::new(EventConfig::default()),
-            jobs_list: JobsList::new(),
+            jobs_list,
             squeue_options,
             runtime,
             last_refresh: Instant::now(),
             filter_popup: FilterPopup::new(),
             columns_popup: ColumnsPopup::new(selected_columns.clone(), sort_columns.clone()),
-            log_view: LogView::new(),
+            log_view: LogView::new(config.strip_ansi),
+            log_pane: LogView::new(config.strip_ansi),
+            split: SplitLayout::Off,
+            follow_submitted: config.follow_submitted,
+            following: None,
+            gpu_settings: config.gpu,
+            oom_warn: config.oom_warn_percent.unwrap_or(90) as f64 / 100.0,
+            gpu_monitor: None,
+            live_stats: None,
             script_view: JobScript::new(),
+            edit_form: JobEditForm::new(config.partitions.clone()),
+            prompt: InputPrompt::new(),
             status_message: String::new(),
             status_timeout: None,
             job_refresh_interval: 10, // Default to 10 seconds refresh
@@ -120,7 +552,83 @@ impl App {
             available_states,
             selected_columns,
             sort_columns,
-            cancel_confirm: false,
+            confirm: None,
+            confirm_dialog: ConfirmDialog::new(),
+            confirm_policies: config.confirm,
+            dry_run: config.dry_run,
+            preview: CommandPreview::new(),
+            admin: config.admin.clone(),
+            extend_by: config
+                .extend_by
+                .as_deref()
+                .and_then(parse_time_limit)
+                .unwrap_or(3600),
+            batch_pending: None,
+            batch: None,
+            incident: false,
+            incident_panel: IncidentPanel::new(),
+            actions_popup: ActionsPopup::new(config.actions),
+            dependency_editor: DependencyEditor::new(),
+            eta_panel: EtaPanel::new(),
+            efficiency_panel: EfficiencyPanel::new(),
+            dependency_graph: DependencyGraph::new(),
+            timeline: TimelineView::new(),
+            file_browser: FileBrowser::new(),
+            pipeline_picker: Picker::new(),
+            wrap_picker: Picker::new(),
+            wrap_profile: None,
+            wrap_command: String::new(),
+            alloc_form: AllocForm::new(),
+            manifest_panel: ManifestPanel::new(config.templates.clone()),
+            picking_manifest: false,
+            pipelines: config.pipelines,
+            templates: config.templates.clone(),
+            balance: config.balance,
+            submit_form: SubmitForm::new(
+                config.templates,
+                config.scripts_dir,
+                config.partitions,
+                config.mail_user,
+            ),
+            job_detail: JobDetail::new(),
+            partition_picker: Picker::new(),
+            template_picker: Picker::new(),
+            move_jobs: Vec::new(),
+            watchlist: Watchlist::default(),
+            watch_alert: WatchAlert::new(),
+            cluster_limits: None,
+            script_diff: ScriptDiff::new(),
+            view: View::Jobs,
+            history: HistoryView::new(bookmarks, args.read_only),
+            partitions_view: PartitionsView::new(),
+            gpus_view: GpusView::new(),
+            reservations_view: ReservationsView::new(),
+            qos_view: QosView::new(),
+            fairshare_view: FairshareView::new(),
+            diagnostics_view: DiagnosticsView::new(),
+            dashboard_view: DashboardView::new(),
+            leaderboard: LeaderboardView::new(),
+            node_grid: NodeGridView::new(),
+            burst_buffers: BurstBuffersView::new(),
+            audit_log: AuditLog::new(),
+            audit_panel: AuditPanel::new(),
+            submissions_panel: SubmissionsPanel::new(),
+            retries: RetryQueue::new(config.retry),
+            retry_panel: RetryPanel::new(),
+            module_panel: ModulePanel::new(),
+            node_panel: NodePanel::new(config.admin.node_actions, args.read_only),
+            cluster_name,
+            pending_link: args.open_job,
+            pending_shell: None,
+            read_only: args.read_only,
+            pending_resubmit: None,
+            pending_allocation: None,
+            macro_keys,
+            lineage,
+            notes,
+            submissions,
+            submit_template: None,
+            recording: None,
         })
     }
 
@@ -132,11 +640,58 @@ impl App {
         // Initial job loading
         self.refresh_jobs()?;
 
+        // Jump to the job given on the command line, if any
+        if let Some(link) = self.pending_link.take() {
+            self.open_job_link(link);
+        }
+
         while self.running {
             terminal.draw(|frame| self.render(frame))?;
             self.handle_events()?;
+            if let Some(command) = self.pending_shell.take() {
+                self.run_shell(terminal, command)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Suspend the TUI, run an interactive command, then restore the TUI
+    fn run_shell<B: ratatui::backend::Backend>(
+        &mut self,
+        terminal: &mut ratatui::Terminal<B>,
+        command: SlurmCommand,
+    ) -> Result<()> {
+        if self.refuse_read_only() {
+            return Ok(());
         }
+        let status = self
+            .event_handler
+            .suspend(|| run_interactive(&command.program, &command.args));
+        terminal.clear()?;
+        terminal.hide_cursor()?;
 
+        let resubmit = self.pending_resubmit.take();
+        let succeeded = matches!(&status, Ok(status) if status.success());
+        match status {
+            Ok(status) if status.success() => {
+                if let Some(resubmit) = resubmit {
+                    self.submit_again(resubmit);
+                }
+            }
+            Ok(status) => {
+                self.set_status_message(format!("`{}` exited with {}", command, status), 5)
+            }
+            Err(e) => self.set_status_message(format!("Failed to run `{}`: {}", command, e), 5),
+        }
+        if self.submit_form.visible {
+            self.submit_form.refresh_preview();
+        }
+        let allocation = self.pending_allocation.take();
+        self.refresh_jobs()?;
+        if let Some(name) = allocation.filter(|_| succeeded) {
+            self.open_allocation_shell(&name);
+        }
         Ok(())
     }
 
@@ -240,11 +795,314 @@ impl App {
         }
 
         self.jobs_list.update_jobs(jobs);
+        self.refresh_steps();
+        self.check_memory();
+        self.check_exits();
+        self.check_priority_ranks();
         self.last_refresh = Instant::now();
+        self.check_watched();
+        self.follow_started_job();
 
         Ok(())
     }
 
+    /// Compare the live MaxRSS of running jobs with their memory request and
+    /// flag those above the warning threshold in the list
+    fn check_memory(&mut self) {
+        if self.oom_warn <= 0.0 {
+            return;
+        }
+        let requested: HashMap<String, u64> = self
+            .jobs_list
+            .jobs
+            .iter()
+            .filter(|job| job.state == JobState::Running)
+            .filter_map(|job| Some((job.id.clone(), parse_size(&job.memory, MIB)?)))
+            .filter(|(_, bytes)| *bytes > 0)
+            .collect();
+        if requested.is_empty() {
+            self.jobs_list.memory_risk.clear();
+            return;
+        }
+        let ids: Vec<String> = requested.keys().cloned().collect();
+        let Ok(max_rss) = self
+            .runtime
+            .block_on(async { get_live_max_rss(&ids).await })
+        else {
+            return;
+        };
+        self.jobs_list.memory_risk = max_rss
+            .into_iter()
+            .filter_map(|(id, rss)| {
+                let used = rss as f64 / *requested.get(&id)? as f64;
+                (used >= self.oom_warn).then_some((id, used))
+            })
+            .collect();
+    }
+
+    /// Look up the exit codes of finished jobs in the list from accounting,
+    /// while the ExitCode or Failure column is shown
+    fn check_exits(&mut self) {
+        let shown = self
+            .selected_columns
+            .iter()
+            .any(|col| matches!(col, JobColumn::ExitCode | JobColumn::Failure));
+        if !shown {
+            self.jobs_list.exits.clear();
+            return;
+        }
+        let finished: Vec<String> = self
+            .jobs_list
+            .jobs
+            .iter()
+            .filter(|job| !matches!(job.state, JobState::Pending | JobState::Running))
+            .map(|job| job.id.clone())
+            .collect();
+        self.jobs_list.exits.retain(|id, _| finished.contains(id));
+        // The exit of a finished job does not change, so only new ones are asked for
+        let unknown: Vec<String> = finished
+            .into_iter()
+            .filter(|id| !self.jobs_list.exits.contains_key(id))
+            .collect();
+        if unknown.is_empty() {
+            return;
+        }
+        if let Ok(exits) = self
+            .runtime
+            .block_on(async { get_job_exits(&unknown).await })
+        {
+            self.jobs_list.exits.extend(exits);
+        }
+    }
+
+    /// Rank pending jobs in their partition's queue with sprio, while the
+    /// Rank column is shown
+    fn check_priority_ranks(&mut self) {
+        let shown = self.selected_columns.contains(&JobColumn::PriorityRank);
+        let pending = self
+            .jobs_list
+            .jobs
+            .iter()
+            .any(|job| job.state == JobState::Pending);
+        if !shown || !pending {
+            self.jobs_list.priority_ranks.clear();
+            return;
+        }
+        // Ranks shift with every job that starts, so they are fetched anew each time
+        if let Ok(ranks) = self.runtime.block_on(async { get_priority_ranks().await }) {
+            self.jobs_list.priority_ranks = ranks;
+        }
+    }
+
+    /// List the steps of a job below it, or hide them again
+    fn toggle_steps(&mut self, job_id: &str) {
+        if self.jobs_list.steps_shown(job_id) {
+            self.jobs_list.set_steps(job_id, None);
+        } else {
+            match self.runtime.block_on(async { get_job_steps(job_id).await }) {
+                Ok(steps) if steps.is_empty() => {
+                    let message = trf("Job {id} has no steps yet", &[("id", job_id)]);
+                    self.set_status_message(message, 3);
+                    return;
+                }
+                Ok(steps) => self.jobs_list.set_steps(job_id, Some(steps)),
+                Err(e) => {
+                    let message = format!("Failed to get the steps of job {}: {}", job_id, e);
+                    self.set_status_message(message, 5);
+                    return;
+                }
+            }
+        }
+        self.jobs_list.select_job_by_id(job_id);
+    }
+
+    /// Fetch the steps of expanded jobs again; jobs that left the list are collapsed
+    fn refresh_steps(&mut self) {
+        for job_id in self.jobs_list.step_job_ids() {
+            let listed = self.jobs_list.jobs.iter().any(|job| job.id == job_id);
+            let steps = if listed {
+                self.runtime
+                    .block_on(async { get_job_steps(&job_id).await })
+                    .ok()
+            } else {
+                None
+            };
+            self.jobs_list.set_steps(&job_id, steps);
+        }
+    }
+
+    /// Select the followed job once it is no longer pending and show its log in
+    /// the split pane; array jobs are followed to their first task that starts
+    fn follow_started_job(&mut self) {
+        let Some(job_id) = self.following.clone() else {
+            return;
+        };
+        let task_prefix = format!("{}_", job_id);
+        let jobs: Vec<&Job> = self
+            .jobs_list
+            .jobs
+            .iter()
+            .filter(|job| job.id == job_id || job.id.starts_with(&task_prefix))
+            .collect();
+        if jobs.is_empty() {
+            self.following = None;
+            let message = format!("Stopped following job {}: it left the list", job_id);
+            self.set_status_message(message, 5);
+            return;
+        }
+        let Some(started) = jobs
+            .iter()
+            .find(|job| job.state != JobState::Pending)
+            .map(|job| job.id.clone())
+        else {
+            return;
+        };
+        self.following = None;
+        if !self.jobs_list.select_job_by_id(&started) {
+            return;
+        }
+        if self.split == SplitLayout::Off {
+            self.split = SplitLayout::Bottom;
+        }
+        self.sync_log_pane();
+        self.set_status_message(format!("Job {} started; showing its log", started), 5);
+    }
+
+    /// Look up the watched jobs regardless of the list's filters and alert
+    /// about any that changed state or left the queue
+    fn check_watched(&mut self) {
+        if self.watchlist.is_empty() {
+            return;
+        }
+        let ids = self.watchlist.ids();
+        let options = SqueueOptions {
+            user: None,
+            job_ids: ids.clone(),
+            format: "%i|%T".to_string(),
+            command: self.squeue_options.command.clone(),
+            ..Default::default()
+        };
+        let Ok(queued) = self.runtime.block_on(async { run_squeue(&options).await }) else {
+            return;
+        };
+
+        // Final states of the jobs squeue no longer lists. If accounting still
+        // has one as active, squeue answered incompletely; try again next time.
+        let mut final_states = HashMap::new();
+        for id in ids
+            .iter()
+            .filter(|id| !queued.iter().any(|job| job.id == **id))
+        {
+            let state = self
+                .runtime
+                .block_on(async { get_job_state(id).await })
+                .ok()
+                .flatten();
+            if let Some(state) = &state {
+                if matches!(
+                    state.as_str(),
+                    "PENDING" | "RUNNING" | "SUSPENDED" | "REQUEUED"
+                ) {
+                    return;
+                }
+            }
+            final_states.insert(id.clone(), state);
+        }
+
+        let messages: Vec<String> = self
+            .watchlist
+            .update(&queued)
+            .into_iter()
+            .map(|event| match event {
+                WatchEvent::Changed { id, name, from, to } => trf(
+                    "Job {id} ({name}): {from} → {to}",
+                    &[
+                        ("id", &id),
+                        ("name", &name),
+                        ("from", &from.to_string()),
+                        ("to", &to.to_string()),
+                    ],
+                ),
+                WatchEvent::Left { id, name, last } => {
+                    let state = match final_states.remove(&id).flatten() {
+                        Some(state) => state,
+                        None => trf("last seen {state}", &[("state", &last.to_string())]),
+                    };
+                    trf(
+                        "Job {id} ({name}) left the queue: {state}",
+                        &[("id", &id), ("name", &name), ("state", &state)],
+                    )
+                }
+            })
+            .collect();
+        self.jobs_list.watched = self.watchlist.ids().into_iter().collect();
+        if !messages.is_empty() {
+            self.watch_alert.push(messages);
+            // Ring the terminal bell so the alert is noticed from another window
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+        }
+    }
+
+    /// Watch the selected jobs (or the job under the cursor), or stop watching them
+    fn toggle_watch(&mut self) {
+        let ids = self.target_job_ids();
+        let jobs: Vec<Job> = self
+            .jobs_list
+            .jobs
+            .iter()
+            .filter(|job| ids.contains(&job.id))
+            .cloned()
+            .collect();
+        let Some(first) = jobs.first() else {
+            return;
+        };
+        let mut watched = 0;
+        for job in &jobs {
+            if self.watchlist.toggle(job) {
+                watched += 1;
+            }
+        }
+        self.jobs_list.watched = self.watchlist.ids().into_iter().collect();
+
+        let message = match (jobs.len(), watched) {
+            (1, 1) => trf("Watching job {id}", &[("id", &first.id)]),
+            (1, _) => trf("Stopped watching job {id}", &[("id", &first.id)]),
+            (count, watched) => trf(
+                "Watching {watched} of {count} job(s)",
+                &[
+                    ("watched", &watched.to_string()),
+                    ("count", &count.to_string()),
+                ],
+            ),
+        };
+        self.set_status_message(message, 3);
+    }
+
+    /// Pin the selected jobs (or the array under the cursor) to the top of the
+    /// list, or unpin them
+    fn toggle_pins(&mut self) {
+        let selected = self.jobs_list.get_selected_jobs();
+        let ids = match self.jobs_list.selected_group() {
+            Some((parent, _))
+                if selected.is_empty() || self.jobs_list.selection_is_group(&parent) =>
+            {
+                vec![parent]
+            }
+            _ => self.target_job_ids(),
+        };
+        if ids.is_empty() {
+            return;
+        }
+        let count = ids.len().to_string();
+        let message = if self.jobs_list.toggle_pins(&ids) {
+            trf("Pinned {count} job(s) to the top", &[("count", &count)])
+        } else {
+            trf("Unpinned {count} job(s)", &[("count", &count)])
+        };
+        self.set_status_message(message, 3);
+    }
+
     /// Render the application UI
     pub fn render(&mut self, frame: &mut Frame) {
         let areas = draw_main_layout(frame);
@@ -255,7 +1113,25 @@ impl App {
         // Draw jobs list in the main content area with current column settings
         // Make sure to still render the jobs list even when log view is visible
         // so that the jobs list is updated when user navigates with SHIFT+arrow keys
-        self.render_joblist(frame, areas[1]);
+        match (self.view, self.split) {
+            (View::Jobs, SplitLayout::Off) => self.render_joblist(frame, areas[1]),
+            (View::Jobs, split) => {
+                let [list, pane] = split_content_area(areas[1], split == SplitLayout::Right);
+                self.render_joblist(frame, list);
+                self.log_pane.render_pane(frame, pane);
+            }
+            (View::History, _) => self.history.render(frame, areas[1]),
+            (View::Partitions, _) => self.partitions_view.render(frame, areas[1]),
+            (View::Gpus, _) => self.gpus_view.render(frame, areas[1]),
+            (View::Reservations, _) => self.reservations_view.render(frame, areas[1]),
+            (View::Qos, _) => self.qos_view.render(frame, areas[1]),
+            (View::Fairshare, _) => self.fairshare_view.render(frame, areas[1]),
+            (View::Diagnostics, _) => self.diagnostics_view.render(frame, areas[1]),
+            (View::Dashboard, _) => self.dashboard_view.render(frame, areas[1]),
+            (View::Users, _) => self.leaderboard.render(frame, areas[1]),
+            (View::Nodes, _) => self.node_grid.render(frame, areas[1]),
+            (View::BurstBuffers, _) => self.burst_buffers.render(frame, areas[1]),
+        }
 
         // Draw the footer with controls
         self.render_footer(frame, areas[2]);
@@ -284,10 +1160,148 @@ impl App {
             self.render_log_view(frame, popup_area);
         }
 
-        // If cancel confirm popup is visible, draw it
-        if self.cancel_confirm {
-            let popup_area = centered_popup_area(frame.area(), 50, 30);
-            self.render_cancel_confirm(frame, popup_area);
+        // If job edit form is visible, draw it
+        if self.edit_form.visible {
+            let popup_area = centered_popup_area(frame.area(), 60, 60);
+            self.edit_form.render(frame, popup_area);
+        }
+
+        if self.submit_form.visible {
+            let popup_area = centered_popup_area(frame.area(), 60, 80);
+            self.submit_form
+                .render(frame, popup_area, self.cluster_limits.as_ref());
+        }
+
+        if self.module_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 50, 60);
+            self.module_panel.render(frame, popup_area);
+        }
+
+        if self.alloc_form.visible {
+            let popup_area = centered_popup_area(frame.area(), 50, 80);
+            self.alloc_form.render(frame, popup_area);
+        }
+
+        if self.manifest_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 80, 80);
+            self.manifest_panel.render(frame, popup_area);
+        }
+
+        // If the input prompt is visible, draw it
+        if self.prompt.visible {
+            let popup_area = centered_popup_area(frame.area(), 50, 25);
+            self.prompt.render(frame, popup_area);
+        }
+
+        // If the confirm dialog is visible, draw it
+        if self.confirm_dialog.visible {
+            let popup_area = centered_popup_area(frame.area(), 60, 50);
+            self.confirm_dialog.render(frame, popup_area);
+        }
+
+        // If the script diff is visible, draw it
+        if self.script_diff.visible {
+            let popup_area = centered_popup_area(frame.area(), 80, 80);
+            self.script_diff.render(frame, popup_area);
+        }
+
+        // If the audit panel is visible, draw it
+        if self.audit_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 90, 70);
+            self.audit_panel.render(frame, popup_area, &self.audit_log);
+        }
+
+        if self.retry_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 90, 50);
+            self.retry_panel
+                .render(frame, popup_area, &self.retries, self.incident);
+        }
+
+        if self.submissions_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 90, 70);
+            self.submissions_panel.render(frame, popup_area);
+        }
+
+        if self.partition_picker.visible {
+            let popup_area = centered_popup_area(frame.area(), 40, 50);
+            self.partition_picker.render(frame, popup_area);
+        }
+
+        if self.template_picker.visible {
+            let popup_area = centered_popup_area(frame.area(), 40, 50);
+            self.template_picker.render(frame, popup_area);
+        }
+
+        if self.wrap_picker.visible {
+            let popup_area = centered_popup_area(frame.area(), 40, 40);
+            self.wrap_picker.render(frame, popup_area);
+        }
+
+        if self.pipeline_picker.visible {
+            let popup_area = centered_popup_area(frame.area(), 40, 50);
+            self.pipeline_picker.render(frame, popup_area);
+        }
+
+        if self.job_detail.visible {
+            let popup_area = centered_popup_area(frame.area(), 80, 85);
+            self.job_detail.render(frame, popup_area);
+        }
+
+        if self.node_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 70, 75);
+            self.node_panel.render(frame, popup_area);
+        }
+
+        if self.efficiency_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 80, 70);
+            self.efficiency_panel.render(frame, popup_area);
+        }
+
+        if self.dependency_graph.visible {
+            let popup_area = centered_popup_area(frame.area(), 80, 80);
+            self.dependency_graph.render(frame, popup_area);
+        }
+
+        if self.timeline.visible {
+            let popup_area = centered_popup_area(frame.area(), 90, 85);
+            self.timeline.render(frame, popup_area);
+        }
+
+        if self.file_browser.visible {
+            let popup_area = centered_popup_area(frame.area(), 90, 85);
+            self.file_browser.render(frame, popup_area);
+        }
+
+        if self.eta_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 80, 70);
+            self.eta_panel.render(frame, popup_area);
+        }
+
+        if self.dependency_editor.visible {
+            let popup_area = centered_popup_area(frame.area(), 60, 60);
+            self.dependency_editor.render(frame, popup_area);
+        }
+
+        if self.actions_popup.visible {
+            let popup_area = centered_popup_area(frame.area(), 80, 70);
+            self.actions_popup.render(frame, popup_area);
+        }
+
+        if self.incident_panel.visible {
+            let popup_area = centered_popup_area(frame.area(), 70, 60);
+            self.incident_panel.render(frame, popup_area);
+        }
+
+        // If the command preview is visible, draw it
+        if self.preview.visible {
+            let popup_area = centered_popup_area(frame.area(), 70, 40);
+            self.preview.render(frame, popup_area);
+        }
+
+        // Alerts about watched jobs go on top of everything else
+        if self.watch_alert.visible {
+            let popup_area = centered_popup_area(frame.area(), 60, 30);
+            self.watch_alert.render(frame, popup_area);
         }
     }
 
@@ -346,7 +1360,126 @@ impl App {
         let job_stat = (pending_count, running_count, other_count);
 
         // Draw the footer
-        draw_footer(frame, area, job_stat);
+        let hints: &[(&str, &str)] = match self.view {
+            View::Jobs => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("Space", "Select"),
+                ("Enter", "Script"),
+                ("f", "Filter"),
+                ("/", "Quick filter"),
+                ("c", "Columns"),
+                ("v", "Log"),
+                ("a", "SelectAll"),
+                ("r", "Refresh"),
+                ("x", "Cancel"),
+                ("e", "Edit"),
+            ],
+            View::History => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("f", "Query"),
+                ("/", "Quick filter"),
+                ("r", "Rerun"),
+                ("Enter", "Details"),
+                ("b", "Bookmarks"),
+                ("B", "Save bookmark"),
+            ],
+            View::Partitions => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("s", "Sort by load"),
+                ("g", "Node grid"),
+                ("r", "Refresh"),
+            ],
+            View::Nodes => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("←/→/↑/↓", "Navigate"),
+                ("Enter", "Node detail"),
+                ("t", "Group by switch"),
+                ("g", "Partitions"),
+                ("r", "Refresh"),
+            ],
+            View::Gpus | View::Reservations => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("r", "Refresh"),
+            ],
+            View::Qos => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("m", "Only mine"),
+                ("r", "Refresh"),
+            ],
+            View::Fairshare => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("Enter", "Collapse"),
+                ("r", "Refresh"),
+            ],
+            View::Diagnostics => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("s", "Sort RPCs"),
+                ("r", "Refresh"),
+            ],
+            View::Dashboard => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("b", "Burst buffers"),
+                ("r", "Refresh"),
+            ],
+            View::BurstBuffers => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("Enter", "Job detail"),
+                ("b", "Dashboard"),
+                ("r", "Refresh"),
+            ],
+            View::Users => &[
+                ("Esc", "Quit"),
+                VIEW_KEYS,
+                ("↑/↓", "Navigate"),
+                ("s", "Sort"),
+                ("p", "Partition"),
+                ("r", "Refresh"),
+            ],
+        };
+        // Keys of disabled actions are not advertised in read-only mode; only
+        // the Jobs view refuses them, the other views reuse some of these keys
+        let refused = self.read_only && self.view == View::Jobs;
+        let hints: Vec<(&str, &str)> = hints
+            .iter()
+            .filter(|(key, _)| !refused || !key.chars().all(|c| MUTATING_KEYS.contains(&c)))
+            .copied()
+            .collect();
+        draw_footer(frame, area, &hints, job_stat, self.reason_tooltip());
+    }
+
+    /// Explanation of the pending reason of the job under the cursor, while
+    /// the Reason column is shown
+    fn reason_tooltip(&self) -> Option<(String, String)> {
+        if self.view != View::Jobs || !self.selected_columns.contains(&JobColumn::PReason) {
+            return None;
+        }
+        let job = self.jobs_list.selected_job()?;
+        if job.state != JobState::Pending {
+            return None;
+        }
+        let reason = job.pending_reason.as_deref()?;
+        let help = explain_reason(reason)?;
+        Some((
+            reason.trim_matches(['(', ')']).to_string(),
+            format!("{} {}", tr(help.explanation), tr(help.remedy)),
+        ))
     }
 
     /// Render the header with status information
@@ -360,18 +1493,18 @@ impl App {
                 // Show filter information
                 let filter_desc = self.get_filter_description();
                 if !filter_desc.is_empty() {
-                    format!("Filters: {}", filter_desc)
+                    trf("Filters: {filters}", &[("filters", &filter_desc)])
                 } else {
-                    "No filters applied".to_string()
+                    tr("No filters applied").to_string()
                 }
             }
         } else {
             // Show filter information if there's no status message
             let filter_desc = self.get_filter_description();
             if !filter_desc.is_empty() {
-                format!("Filters: {}", filter_desc)
+                trf("Filters: {filters}", &[("filters", &filter_desc)])
             } else {
-                "No filters applied".to_string()
+                tr("No filters applied").to_string()
             }
         };
 
@@ -382,35 +1515,15 @@ impl App {
             &status_text,
             self.last_refresh.elapsed(),
             self.job_refresh_interval,
+            HeaderModes {
+                dry_run: self.dry_run,
+                incident: self.incident,
+                read_only: self.read_only,
+                recording: self.recording.is_some(),
+            },
         );
     }
 
-    fn render_cancel_confirm(&self, frame: &mut Frame, area: Rect) {
-        frame.render_widget(Clear, area);
-        // Render the cancel confirm popup
-        let selected_count = self.jobs_list.get_selected_jobs().len();
-        let cancel_text = if selected_count == 0 {
-            "No jobs selected for cancellation.".to_string()
-        } else {
-            format!(
-                "Are you sure you want to cancel {} selected job(s)? (y/n)",
-                selected_count
-            )
-        };
-
-        let block = Block::default()
-            .title(Line::from("Confirm Cancel").centered())
-            .borders(Borders::NONE)
-            .style(Style::default().bg(Color::Black));
-
-        let cancel_popup = Paragraph::new(cancel_text)
-            .style(Style::default().fg(Color::Cyan))
-            .block(block)
-            .centered();
-
-        frame.render_widget(cancel_popup, area);
-    }
-
     /// Handle application events
     fn handle_events(&mut self) -> Result<()> {
         match self.event_handler.rx.recv()? {
@@ -424,39 +1537,618 @@ impl App {
         Ok(())
     }
 
-    /// Handle key events
+    /// Handle a key press: `q` and `@` in a view (outside popups and the filter
+    /// bar) record and replay the macro, other keys are recorded while recording
     fn handle_key_event(&mut self, key: KeyEvent) {
+        let macro_key = !key.modifiers.contains(KeyModifiers::CONTROL)
+            && !self.any_popup_visible()
+            && !self.jobs_list.filter.editing
+            && !self.history.filter.editing;
+        match key.code {
+            KeyCode::Char('q') if macro_key => self.toggle_recording(),
+            KeyCode::Char('@') if macro_key => self.replay_macro(),
+            _ => {
+                if let Some(keys) = &mut self.recording {
+                    if macros::key_name(key).is_some() {
+                        keys.push(key);
+                    }
+                }
+                self.dispatch_key(key);
+            }
+        }
+        self.sync_log_pane();
+    }
+
+    /// Switch the split layout to its next arrangement
+    fn cycle_split(&mut self) {
+        self.split = match self.split {
+            SplitLayout::Off => SplitLayout::Bottom,
+            SplitLayout::Bottom => SplitLayout::Right,
+            SplitLayout::Right => SplitLayout::Off,
+        };
+        if self.split == SplitLayout::Off {
+            self.log_pane.hide();
+        }
+        self.sync_log_pane();
+    }
+
+    /// Point the log pane at the job under the cursor when that job changed
+    fn sync_log_pane(&mut self) {
+        if self.split == SplitLayout::Off {
+            return;
+        }
+        let Some(job_id) = self.jobs_list.selected_job().map(|job| job.id.clone()) else {
+            return;
+        };
+        if self.log_pane.job_id.as_deref() != Some(job_id.as_str()) || !self.log_pane.visible {
+            self.log_pane.show(job_id);
+        }
+    }
+
+    /// Start recording a macro, or stop and save it
+    fn toggle_recording(&mut self) {
+        match self.recording.take() {
+            None => {
+                self.recording = Some(Vec::new());
+                let message = tr("Recording macro, press q to stop");
+                self.set_status_message(message.to_string(), 3);
+            }
+            Some(keys) if keys.is_empty() => {
+                let message = tr("Nothing recorded, the previous macro is kept");
+                self.set_status_message(message.to_string(), 3);
+            }
+            Some(keys) => {
+                let count = keys.len().to_string();
+                self.macro_keys = keys;
+                match macros::save(&self.macro_keys) {
+                    Ok(()) => self.set_status_message(
+                        trf(
+                            "Recorded a macro of {count} key(s), replay it with @",
+                            &[("count", &count)],
+                        ),
+                        3,
+                    ),
+                    Err(e) => self.set_status_message(format!("Failed to save macro: {}", e), 5),
+                }
+            }
+        }
+    }
+
+    /// Press the keys of the recorded macro in order
+    fn replay_macro(&mut self) {
+        if self.recording.is_some() {
+            let message = tr("Stop recording with q before replaying");
+            self.set_status_message(message.to_string(), 3);
+            return;
+        }
+        if self.macro_keys.is_empty() {
+            let message = tr("No macro recorded; press q to record one");
+            self.set_status_message(message.to_string(), 3);
+            return;
+        }
+        // Messages of the replayed actions replace this one
+        let count = self.macro_keys.len().to_string();
+        let message = trf("Replayed a macro of {count} key(s)", &[("count", &count)]);
+        self.set_status_message(message, 3);
+        for key in self.macro_keys.clone() {
+            if !self.running {
+                break;
+            }
+            self.dispatch_key(key);
+        }
+    }
+
+    /// Route a key to the popup that has focus or to the current view
+    fn dispatch_key(&mut self, key: KeyEvent) {
         match (key.modifiers, key.code) {
+            // The quick filter bar takes all keys while typing, Esc clears it
+            (modifiers, _)
+                if (self.jobs_list.filter.editing || self.history.filter.editing)
+                    && modifiers != KeyModifiers::CONTROL =>
+            {
+                self.edit_quick_filter(key);
+            }
+
             // Quit application
             (_, KeyCode::Esc) | (KeyModifiers::CONTROL, KeyCode::Char('c')) => {
-                if self.filter_popup.visible
-                    || self.script_view.visible
-                    || self.columns_popup.visible
-                    || self.log_view.visible
-                    || self.cancel_confirm
-                {
+                if self.any_popup_visible() {
                     self.filter_popup.visible = false;
                     self.script_view.visible = false;
                     self.columns_popup.visible = false;
                     self.log_view.hide();
-                    self.cancel_confirm = false;
+                    self.edit_form.hide();
+                    self.submit_form.hide();
+                    self.prompt.hide();
+                    self.preview.hide();
+                    self.audit_panel.hide();
+                    self.submissions_panel.hide();
+                    self.retry_panel.hide();
+                    self.script_diff.hide();
+                    self.history.hide_popups();
+                    self.incident_panel.hide();
+                    self.actions_popup.hide();
+                    self.dependency_editor.hide();
+                    self.eta_panel.hide();
+                    self.efficiency_panel.hide();
+                    self.dependency_graph.hide();
+                    self.timeline.hide();
+                    self.file_browser.hide();
+                    self.job_detail.hide();
+                    self.partition_picker.hide();
+                    self.template_picker.hide();
+                    self.pipeline_picker.hide();
+                    self.wrap_picker.hide();
+                    self.alloc_form.hide();
+                    self.manifest_panel.hide();
+                    self.module_panel.hide();
+                    self.node_panel.hide();
+                    self.watch_alert.hide();
+                    self.confirm_dialog.hide();
+                    self.confirm = None;
+                    self.batch_pending = None;
                 } else {
                     self.quit();
                 }
             }
 
-            // Filter toggle
-            (_, KeyCode::Char('f')) if !self.script_view.visible && !self.filter_popup.visible => {
-                self.filter_popup.visible = true;
-                // Initialize filter popup with current options
-                self.filter_popup.initialize(&self.squeue_options);
-            }
+            // An alert about watched jobs takes all keys until dismissed
+            _ if self.watch_alert.visible => self.watch_alert.handle_key(key),
 
-            // Navigation
-            (_, KeyCode::Up)
-                if !self.filter_popup.visible
-                    && !self.script_view.visible
-                    && !self.columns_popup.visible
+            // Command preview takes all keys until closed
+            _ if self.preview.visible => match self.preview.handle_key(key) {
+                PreviewAction::None => {}
+                PreviewAction::Run => self.start_batch(),
+                PreviewAction::Abort => self.abort_batch(),
+            },
+            _ if self.confirm_dialog.visible => match self.confirm_dialog.handle_key(key) {
+                ConfirmAction::None => {}
+                ConfirmAction::Confirm => {
+                    if let Some(deferred) = self.confirm.take() {
+                        self.run_deferred(deferred);
+                    }
+                }
+                ConfirmAction::Abort => self.confirm = None,
+            },
+            _ if self.audit_panel.visible => self.audit_panel.handle_key(key, &self.audit_log),
+            _ if self.retry_panel.visible => {
+                match self.retry_panel.handle_key(key, &self.retries) {
+                    RetryAction::None => {}
+                    RetryAction::RetryNow(id) => {
+                        self.retries.retry_now(id);
+                        if self.incident {
+                            let message =
+                                "Incident mode on, the retry waits until it ends".to_string();
+                            self.set_status_message(message, 3);
+                        }
+                        self.retry_submission();
+                    }
+                    RetryAction::Cancel(id) => {
+                        if self.retries.remove(id).is_some() {
+                            let message = "Cancelled the pending submission".to_string();
+                            self.set_status_message(message, 3);
+                        }
+                    }
+                }
+            }
+            _ if self.submissions_panel.visible => {
+                if let SubmissionsAction::Resubmit(args) = self.submissions_panel.handle_key(key) {
+                    let deferred = Deferred::Submit(SlurmCommand::new("sbatch", args, Vec::new()));
+                    let question = deferred.question();
+                    self.ask_confirmation(deferred, &question, false);
+                }
+            }
+            _ if self.eta_panel.visible => self.eta_panel.handle_key(key),
+            _ if self.efficiency_panel.visible => self.efficiency_panel.handle_key(key),
+            _ if self.dependency_graph.visible => self.dependency_graph.handle_key(key),
+            _ if self.timeline.visible => self.timeline.handle_key(key),
+            _ if self.file_browser.visible => {
+                if let BrowserAction::Chosen(path) = self.file_browser.handle_key(key) {
+                    if self.picking_manifest {
+                        self.manifest_panel
+                            .set_path(&path, &self.available_partitions);
+                    } else {
+                        self.submit_form.set_script(&path);
+                    }
+                }
+            }
+            _ if self.node_panel.visible => match self.node_panel.handle_key(key) {
+                NodeAction::None => {}
+                NodeAction::Load(node) => self.load_node_detail(&node),
+                NodeAction::Update {
+                    node,
+                    state,
+                    reason,
+                } => self.update_node(&node, state, reason.as_deref()),
+            },
+            _ if self.job_detail.visible => match self.job_detail.handle_key(key) {
+                DetailAction::None => {}
+                DetailAction::Nodes(nodes) => self.show_node_detail(nodes),
+                DetailAction::Open(job_id) => self.show_job_detail(&job_id),
+                DetailAction::Copy(text) => self.copy_with_status(&text, "job detail"),
+                DetailAction::Save(file, text) => match std::fs::write(&file, text + "\n") {
+                    Ok(()) => self.set_status_message(format!("Saved {}", file), 3),
+                    Err(e) => {
+                        self.set_status_message(format!("Failed to write {}: {}", file, e), 5)
+                    }
+                },
+            },
+            _ if self.partition_picker.visible => {
+                if let PickerAction::Chosen(partition) = self.partition_picker.handle_key(key) {
+                    self.request_partition_move(partition);
+                }
+            }
+            _ if self.template_picker.visible => {
+                if let PickerAction::Chosen(name) = self.template_picker.handle_key(key) {
+                    self.submit_form.apply_template(&name);
+                }
+            }
+            _ if self.wrap_picker.visible => {
+                if let PickerAction::Chosen(name) = self.wrap_picker.handle_key(key) {
+                    self.wrap_profile = (name != DEFAULT_PROFILE).then_some(name);
+                    self.show_wrap_prompt();
+                }
+            }
+            _ if self.pipeline_picker.visible => {
+                if let PickerAction::Chosen(name) = self.pipeline_picker.handle_key(key) {
+                    let deferred = Deferred::Pipeline(name);
+                    let question = deferred.question();
+                    self.ask_confirmation(deferred, &question, false);
+                }
+            }
+            _ if self.dependency_editor.visible => {
+                if let DependencyAction::Save(spec) = self.dependency_editor.handle_key(key) {
+                    if self.dependency_editor.job_id.is_some() {
+                        self.save_dependency(spec);
+                    } else {
+                        self.dependency_editor.hide();
+                        self.submit_form.set_dependency(spec);
+                    }
+                }
+            }
+            _ if self.module_panel.visible => match self.module_panel.handle_key(key) {
+                ModuleAction::None => {}
+                ModuleAction::Close => self.module_panel.hide(),
+                ModuleAction::Search(query) => {
+                    let results = self
+                        .runtime
+                        .block_on(async { search_modules(&query).await })
+                        .map_err(|e| e.to_string());
+                    self.module_panel.set_results(query, results);
+                }
+                ModuleAction::Insert(modules) => {
+                    self.module_panel.hide();
+                    let script = self.module_panel.script.clone();
+                    let message = match insert_module_loads(&script, &modules) {
+                        Ok(0) => format!("{} already loads these modules", script),
+                        Ok(count) => format!("Added {} module load line(s) to {}", count, script),
+                        Err(e) => e.to_string(),
+                    };
+                    self.submit_form.refresh_preview();
+                    self.submit_form.set_message(message);
+                }
+            },
+            _ if self.actions_popup.visible => {
+                if let ActionsPopupAction::Run(index) = self.actions_popup.handle_key(key) {
+                    let Some((action, job)) = self.actions_popup.target(index) else {
+                        return;
+                    };
+                    if self.refuse_read_only() {
+                        return;
+                    }
+                    let result = self
+                        .runtime
+                        .block_on(async { run_action(&action, &job).await });
+                    self.actions_popup.set_result(&job.id, &action.name, result);
+                }
+            }
+            _ if self.incident_panel.visible => match self.incident_panel.handle_key(key) {
+                IncidentAction::None => {}
+                IncidentAction::Hold(jobs) => self.hold_for_incident(jobs),
+                IncidentAction::End => {
+                    self.incident = false;
+                    let message = tr("Incident mode ended, auto-refresh resumed");
+                    self.set_status_message(message.to_string(), 3);
+                }
+            },
+            _ if self.script_diff.visible => self.script_diff.handle_key(key),
+
+            // Handle job edit form key events before any list shortcuts
+            _ if self.edit_form.visible => {
+                let limits = self
+                    .cluster_limits
+                    .get_or_insert_with(ClusterLimits::default);
+                let action = self.edit_form.handle_key(
+                    key,
+                    &self.available_partitions,
+                    &self.available_qos,
+                    limits,
+                );
+                match action {
+                    JobEditAction::Close => self.edit_form.hide(),
+                    JobEditAction::Submit(changes) => self.submit_job_edit(changes),
+                    JobEditAction::None => {}
+                }
+            }
+
+            _ if self.alloc_form.visible => {
+                match self.alloc_form.handle_key(key, &self.available_partitions) {
+                    AllocAction::Close => self.alloc_form.hide(),
+                    AllocAction::Allocate { command, name } => {
+                        self.alloc_form.hide();
+                        self.pending_allocation = Some(name);
+                        self.pending_shell = Some(command);
+                    }
+                    AllocAction::None => {}
+                }
+            }
+
+            _ if self.manifest_panel.visible => {
+                match self
+                    .manifest_panel
+                    .handle_key(key, &self.available_partitions)
+                {
+                    ManifestAction::None => {}
+                    ManifestAction::Close => self.manifest_panel.hide(),
+                    ManifestAction::Browse(dir) => {
+                        self.picking_manifest = true;
+                        self.file_browser.show_picker(dir);
+                    }
+                    ManifestAction::Submit(request) => {
+                        let deferred = Deferred::Manifest(request);
+                        let question = deferred.question();
+                        self.ask_confirmation(deferred, &question, false);
+                    }
+                }
+            }
+
+            _ if self.submit_form.visible => {
+                if let Some(account) = self.submit_form.balance_needed(key) {
+                    let balance = self
+                        .runtime
+                        .block_on(async { get_balance(&account, &self.balance).await })
+                        .map_err(|e| e.to_string());
+                    self.submit_form.set_balance(account, balance);
+                }
+                let limits = self
+                    .cluster_limits
+                    .get_or_insert_with(ClusterLimits::default);
+                match self
+                    .submit_form
+                    .handle_key(key, &self.available_partitions, limits)
+                {
+                    SubmitAction::Close => self.submit_form.hide(),
+                    SubmitAction::Submit(command) => self.submit_batch_job(command),
+                    SubmitAction::BrowseScript(dir) => {
+                        self.picking_manifest = false;
+                        self.file_browser.show_picker(dir);
+                    }
+                    SubmitAction::EditScript(path) => {
+                        self.pending_shell = Some(editor_command(&path))
+                    }
+                    SubmitAction::SearchModules(script) => self.module_panel.show(script),
+                    SubmitAction::PickTemplate(names) => {
+                        self.template_picker.show(tr("Job templates"), names)
+                    }
+                    SubmitAction::EditDependency(spec) => {
+                        let candidates = self
+                            .jobs_list
+                            .jobs
+                            .iter()
+                            .map(|job| {
+                                let label = format!("{}  {}  {}", job.id, job.state, job.name);
+                                (job.id.clone(), label)
+                            })
+                            .collect();
+                        let picked = self.jobs_list.get_selected_jobs();
+                        self.dependency_editor
+                            .show_for_submission(&spec, candidates, picked);
+                    }
+                    SubmitAction::TestOnly(command) => {
+                        let estimate = self.runtime.block_on(async { test_submit(&command).await });
+                        self.submit_form
+                            .set_estimate(estimate.map_err(|e| e.to_string()));
+                    }
+                    SubmitAction::None => {}
+                }
+            }
+
+            // Handle input prompt key events
+            _ if self.prompt.visible => match self.prompt.handle_key(key) {
+                PromptAction::Close => self.prompt.hide(),
+                PromptAction::Submit(input) => self.submit_prompt(input),
+                PromptAction::None => {}
+            },
+
+            // Refuse mutating keys up front in read-only mode instead of opening their forms
+            (_, KeyCode::Char(c))
+                if self.read_only
+                    && self.view == View::Jobs
+                    && !self.any_popup_visible()
+                    && MUTATING_KEYS.contains(&c) =>
+            {
+                self.refuse_read_only();
+            }
+
+            // Toggle dry-run mode for state-changing actions
+            (_, KeyCode::Char('D')) if !self.any_popup_visible() => {
+                self.dry_run = !self.dry_run;
+                let message = if self.dry_run {
+                    "Dry-run mode on"
+                } else {
+                    "Dry-run mode off"
+                };
+                self.set_status_message(tr(message).to_string(), 3);
+            }
+
+            // Show the commands run so far
+            (_, KeyCode::Char('A')) if !self.any_popup_visible() => {
+                self.audit_panel.show(&self.audit_log);
+            }
+
+            // Show the submissions waiting to be retried
+            (_, KeyCode::Char('b')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.retry_panel.show();
+            }
+
+            // Show everything submitted through slurmer
+            (_, KeyCode::Char('H')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.open_submission_history();
+            }
+
+            // Switch views
+            (_, KeyCode::Char('1')) if !self.any_popup_visible() => self.view = View::Jobs,
+            (_, KeyCode::Char('2')) if !self.any_popup_visible() => self.show_history(),
+            (_, KeyCode::Char('3')) if !self.any_popup_visible() => self.show_partitions(),
+            (_, KeyCode::Char('4')) if !self.any_popup_visible() => self.show_gpus(),
+            (_, KeyCode::Char('5')) if !self.any_popup_visible() => self.show_reservations(),
+            (_, KeyCode::Char('6')) if !self.any_popup_visible() => self.show_qos(),
+            (_, KeyCode::Char('7')) if !self.any_popup_visible() => self.show_fairshare(),
+            (_, KeyCode::Char('8')) if !self.any_popup_visible() => self.show_diagnostics(),
+            (_, KeyCode::Char('9')) if !self.any_popup_visible() => self.show_dashboard(),
+            (_, KeyCode::Char('0')) if !self.any_popup_visible() => self.show_leaderboard(),
+
+            // Command line, e.g. `:cancel-filtered`
+            (_, KeyCode::Char(':')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.prompt.show(
+                    PromptKind::Command,
+                    tr("Command"),
+                    tr("cancel-filtered: cancel every job matching the current filters | node <name>: node detail"),
+                    "",
+                );
+            }
+
+            // Narrow the jobs list as you type
+            (_, KeyCode::Char('/')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.jobs_list.filter.editing = true;
+            }
+
+            // The cluster views have no popups of their own
+            _ if self.view == View::BurstBuffers && !self.any_popup_visible() => {
+                match self.burst_buffers.handle_key(key) {
+                    BurstBuffersAction::Refresh => self.load_burst_buffers(),
+                    BurstBuffersAction::Open(job_id) => self.show_job_detail(&job_id),
+                    BurstBuffersAction::Close => self.view = View::Dashboard,
+                    BurstBuffersAction::None => {}
+                }
+            }
+            _ if self.view == View::Nodes && !self.any_popup_visible() => {
+                match self.node_grid.handle_key(key) {
+                    NodeGridAction::Refresh => self.load_node_grid(),
+                    NodeGridAction::Open(node) => self.show_node_detail(vec![node]),
+                    NodeGridAction::Close => self.view = View::Partitions,
+                    NodeGridAction::None => {}
+                }
+            }
+            _ if self.view == View::Users && !self.any_popup_visible() => {
+                match self.leaderboard.handle_key(key) {
+                    LeaderboardAction::Refresh => self.load_leaderboard(),
+                    LeaderboardAction::None => {}
+                }
+            }
+            _ if self.view == View::Dashboard && !self.any_popup_visible() => {
+                match self.dashboard_view.handle_key(key) {
+                    DashboardAction::Refresh => self.load_dashboard(),
+                    DashboardAction::BurstBuffers => self.show_burst_buffers(),
+                    DashboardAction::None => {}
+                }
+            }
+            _ if self.view == View::Diagnostics && !self.any_popup_visible() => {
+                match self.diagnostics_view.handle_key(key) {
+                    DiagnosticsAction::Refresh => self.load_diagnostics(),
+                    DiagnosticsAction::None => {}
+                }
+            }
+            _ if self.view == View::Fairshare && !self.any_popup_visible() => {
+                match self.fairshare_view.handle_key(key) {
+                    FairshareAction::Refresh => self.load_fairshare(),
+                    FairshareAction::None => {}
+                }
+            }
+            _ if self.view == View::Qos && !self.any_popup_visible() => {
+                match self.qos_view.handle_key(key) {
+                    QosAction::Refresh => self.load_qos(),
+                    QosAction::None => {}
+                }
+            }
+            _ if self.view == View::Reservations && !self.any_popup_visible() => {
+                match self.reservations_view.handle_key(key) {
+                    ReservationsAction::Refresh => self.load_reservations(),
+                    ReservationsAction::None => {}
+                }
+            }
+            _ if self.view == View::Gpus && !self.any_popup_visible() => {
+                match self.gpus_view.handle_key(key) {
+                    GpusAction::Refresh => self.load_gpus(),
+                    GpusAction::None => {}
+                }
+            }
+            _ if self.view == View::Partitions && !self.any_popup_visible() => {
+                match self.partitions_view.handle_key(key) {
+                    PartitionsAction::Refresh => self.load_partitions(),
+                    PartitionsAction::NodeGrid => self.show_node_grid(),
+                    PartitionsAction::None => {}
+                }
+            }
+
+            // The history view handles its own keys, including its popups
+            _ if self.view == View::History
+                && (self.history.popup_visible() || !self.any_popup_visible()) =>
+            {
+                match self.history.handle_key(key) {
+                    HistoryAction::None => {}
+                    HistoryAction::RunQuery => self.run_history_query(),
+                    HistoryAction::SaveBookmark => self.prompt.show(
+                        PromptKind::BookmarkName,
+                        tr("Save Query as Bookmark"),
+                        tr("Name for the current history query, e.g. \"lab failures this week\""),
+                        "",
+                    ),
+                    HistoryAction::BookmarksChanged => self.save_bookmarks(),
+                    HistoryAction::Efficiency => {
+                        if let Some(job) = self.history.selected_job() {
+                            let ids = vec![job.id.clone()];
+                            self.open_efficiency_panel(&ids);
+                        }
+                    }
+                    HistoryAction::Details => {
+                        if let Some(job) = self.history.selected_job() {
+                            let job_id = job.id.clone();
+                            self.open_accounting_detail(&job_id);
+                        }
+                    }
+                    HistoryAction::Environment => {
+                        if let Some(job) = self.history.selected_job() {
+                            let job_id = job.id.clone();
+                            self.open_environment(&job_id);
+                        }
+                    }
+                    HistoryAction::Clone => {
+                        if let Some(job) = self.history.selected_job() {
+                            let job_id = job.id.clone();
+                            self.open_submit_form(Some(&job_id));
+                        }
+                    }
+                    HistoryAction::Resubmit { edit } => {
+                        if let Some(job) = self.history.selected_job() {
+                            let job_id = job.id.clone();
+                            self.resubmit_job(&job_id, edit);
+                        }
+                    }
+                }
+            }
+
+            // Filter toggle
+            (_, KeyCode::Char('f')) if !self.script_view.visible && !self.filter_popup.visible => {
+                self.filter_popup.visible = true;
+                // Initialize filter popup with current options
+                self.filter_popup.initialize(&self.squeue_options);
+            }
+
+            // Navigation
+            (_, KeyCode::Up)
+                if !self.filter_popup.visible
+                    && !self.script_view.visible
+                    && !self.columns_popup.visible
                     && !self.log_view.visible =>
             {
                 self.jobs_list.previous();
@@ -477,7 +2169,10 @@ impl App {
                     && !self.columns_popup.visible
                     && !self.log_view.visible =>
             {
-                self.jobs_list.toggle_group_expand();
+                match self.jobs_list.steps_job() {
+                    Some(job_id) => self.toggle_steps(&job_id),
+                    None => self.jobs_list.toggle_group_expand(),
+                }
             }
 
             // Selection
@@ -507,26 +2202,273 @@ impl App {
                     && !self.columns_popup.visible =>
             {
                 // scancel the selected jobs and remove them
-                self.cancel_confirm = true;
+                self.request_job_action(JobAction::Cancel);
             }
-            (_, KeyCode::Char('y'))
-                if self.cancel_confirm
-                    && !self.filter_popup.visible
-                    && !self.script_view.visible
-                    && !self.columns_popup.visible =>
-            {
-                // Confirm cancel selected jobs
-                self.cancel_selected_jobs();
-                self.cancel_confirm = false;
+            // Hold, release or requeue the selected jobs
+            (_, KeyCode::Char('h')) if !self.any_popup_visible() => {
+                self.request_job_action(JobAction::Hold);
             }
-            (_, KeyCode::Char('n'))
-                if self.cancel_confirm
-                    && !self.filter_popup.visible
-                    && !self.script_view.visible
-                    && !self.columns_popup.visible =>
-            {
-                // Cancel the cancel confirmation
-                self.cancel_confirm = false;
+            (_, KeyCode::Char('u')) if !self.any_popup_visible() => {
+                self.request_job_action(JobAction::Release);
+            }
+            (_, KeyCode::Char('R')) if !self.any_popup_visible() => {
+                self.request_job_action(JobAction::Requeue);
+            }
+            // Diff the batch scripts of two jobs
+            (_, KeyCode::Char('d')) if !self.any_popup_visible() => {
+                self.compare_job_scripts();
+            }
+
+            // Edit the job under the cursor
+            (_, KeyCode::Char('e')) if !self.any_popup_visible() => {
+                self.open_edit_form();
+            }
+
+            // Submit a new batch job
+            (_, KeyCode::Char('J')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.open_submit_form(None);
+            }
+
+            // Submit a changed copy of the job under the cursor
+            (_, KeyCode::Char('Z')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if let Some(job) = self.jobs_list.selected_job() {
+                    let job_id = job.id.clone();
+                    self.open_submit_form(Some(&job_id));
+                }
+            }
+
+            // Submit the last job from the form again
+            (_, KeyCode::Char('O')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.submit_last_again();
+            }
+
+            // Start an interactive allocation and open a shell in it
+            (_, KeyCode::Char('X')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.alloc_form.show();
+            }
+
+            // Submit a job per row of a manifest file
+            (_, KeyCode::Char('U')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if self.templates.is_empty() {
+                    let message = "No templates configured; add [[templates]] to config.toml";
+                    self.set_status_message(message.to_string(), 3);
+                } else {
+                    self.manifest_panel.show(&self.available_partitions);
+                }
+            }
+
+            // Submit a one-line command without a script, with a resource profile
+            (_, KeyCode::Char('n')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if self.templates.is_empty() {
+                    self.wrap_profile = None;
+                    self.show_wrap_prompt();
+                } else {
+                    let mut names = vec![DEFAULT_PROFILE.to_string()];
+                    names.extend(self.templates.iter().map(|t| t.name.clone()));
+                    self.wrap_picker.show(tr("Resource profile"), names);
+                }
+            }
+
+            // Submit a configured pipeline
+            (_, KeyCode::Char('Q')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if self.pipelines.is_empty() {
+                    let message = "No pipelines configured; add [[pipelines]] to config.toml";
+                    self.set_status_message(message.to_string(), 3);
+                } else {
+                    let names = self.pipelines.iter().map(|p| p.name.clone()).collect();
+                    self.pipeline_picker.show(tr("Pipelines"), names);
+                }
+            }
+
+            // Send a signal to the selected jobs
+            (_, KeyCode::Char('s')) if !self.any_popup_visible() => {
+                let count = self.target_job_ids().len();
+                if count > 0 {
+                    self.prompt.show(
+                        PromptKind::Signal,
+                        &trf("Send signal to {count} job(s)", &[("count", &count.to_string())]),
+                        tr("Signal name or number, e.g. USR1, TERM, 10. Prefix with B: to signal only the batch shell (B:USR1)."),
+                        "",
+                    );
+                }
+            }
+
+            // Move the selected pending jobs to another partition
+            (_, KeyCode::Char('P')) if !self.any_popup_visible() => {
+                self.open_partition_move();
+            }
+
+            // Change the nice value of the selected jobs
+            (_, KeyCode::Char('p')) if !self.any_popup_visible() => {
+                let count = self.target_job_ids().len();
+                if count > 0 {
+                    self.prompt.show(
+                        PromptKind::Nice,
+                        &trf("Set nice value of {count} job(s)", &[("count", &count.to_string())]),
+                        tr("Integer; higher values lower the priority of pending jobs (0 is the default, negative values need admin rights)."),
+                        "",
+                    );
+                }
+            }
+
+            // Edit the dependencies of the pending job under the cursor
+            (_, KeyCode::Char('o')) if !self.any_popup_visible() => {
+                self.open_dependency_editor();
+            }
+
+            // Jobs the job under the cursor waits on and jobs waiting on it
+            (_, KeyCode::Char('G')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.open_dependency_graph();
+            }
+
+            // Alert when the selected jobs change state or leave the queue
+            (_, KeyCode::Char('w')) if !self.any_popup_visible() => {
+                self.toggle_watch();
+            }
+
+            // Show the log of the job under the cursor next to the list
+            (_, KeyCode::Char('L')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.cycle_split();
+            }
+
+            // Keep the selected jobs at the top of the list
+            (_, KeyCode::Char('m')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.toggle_pins();
+            }
+
+            // Every field of the job under the cursor
+            (_, KeyCode::Tab) if self.view == View::Jobs && !self.any_popup_visible() => {
+                self.open_job_detail();
+            }
+
+            // How much of their requested CPU and memory the selected jobs used
+            (_, KeyCode::Char('F')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                let ids = self.target_job_ids();
+                self.open_efficiency_panel(&ids);
+            }
+
+            // Environment the job under the cursor was started with
+            (_, KeyCode::Char('V')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if let Some(job) = self.jobs_list.selected_job() {
+                    let job_id = job.id.clone();
+                    self.open_environment(&job_id);
+                }
+            }
+
+            // When the selected jobs should be done
+            (_, KeyCode::Char('E')) if !self.any_popup_visible() => {
+                self.open_eta_panel();
+            }
+
+            // Gantt chart of my recent jobs
+            (_, KeyCode::Char('g')) if !self.any_popup_visible() => {
+                self.open_timeline();
+            }
+
+            // Ask for a longer time limit for the running job under the cursor
+            (_, KeyCode::Char('T')) if !self.any_popup_visible() => {
+                self.open_extend_prompt();
+            }
+
+            // Rename the job under the cursor
+            (_, KeyCode::Char('N')) if !self.any_popup_visible() => {
+                if let Some(job) = self.jobs_list.selected_job() {
+                    let title = trf("Rename job {id}", &[("id", &job.id)]);
+                    let name = job.name.clone();
+                    self.prompt.show(
+                        PromptKind::JobName,
+                        &title,
+                        tr("New job name (scontrol update JobName=...)"),
+                        &name,
+                    );
+                }
+            }
+
+            // Write a local note on the job under the cursor
+            (_, KeyCode::Char('K')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if let Some(job) = self.jobs_list.selected_job() {
+                    let title = trf("Note on job {id}", &[("id", &job.id)]);
+                    let note = self.notes.get(&self.cluster_name, &job.id);
+                    let note = note.unwrap_or_default().to_string();
+                    self.prompt.show(
+                        PromptKind::JobNote,
+                        &title,
+                        tr("Kept locally and shown in the detail pane; empty removes it"),
+                        &note,
+                    );
+                }
+            }
+
+            // Copy a shareable link to the job under the cursor
+            (_, KeyCode::Char('l')) if !self.any_popup_visible() => {
+                self.copy_job_link();
+            }
+
+            // Copy the ID or the full scontrol record of the job under the cursor
+            (_, KeyCode::Char('i')) if !self.any_popup_visible() => {
+                if let Some(job) = self.jobs_list.selected_job() {
+                    let id = job.id.clone();
+                    self.copy_with_status(&id, &format!("job ID {}", id));
+                }
+            }
+            (_, KeyCode::Char('I')) if !self.any_popup_visible() => {
+                self.copy_job_record();
+            }
+
+            // Open a shell in the working directory of the job under the cursor,
+            // or copy a `cd` command to it
+            (_, KeyCode::Char('W')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if let Some(work_dir) = self.selected_work_dir() {
+                    let job_id = self.jobs_list.selected_job().map(|job| job.id.clone());
+                    let job_id = job_id.unwrap_or_default();
+                    self.pending_shell = Some(work_dir_shell_command(&work_dir, &job_id));
+                }
+            }
+            (_, KeyCode::Char('C')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if let Some(work_dir) = self.selected_work_dir() {
+                    let command = format!("cd {}", shell_quote(&work_dir));
+                    self.copy_with_status(&command, &command);
+                }
+            }
+            (_, KeyCode::Char('B')) if self.view == View::Jobs && !self.any_popup_visible() => {
+                if let Some(work_dir) = self.selected_work_dir() {
+                    let job_id = self.jobs_list.selected_job().map(|job| job.id.clone());
+                    self.file_browser
+                        .show(&job_id.unwrap_or_default(), &work_dir);
+                }
+            }
+
+            // Copy the selected job IDs as a space-separated list
+            (_, KeyCode::Char('Y')) if !self.any_popup_visible() => {
+                let ids = self.target_job_ids();
+                if !ids.is_empty() {
+                    self.copy_with_status(&ids.join(" "), &format!("{} job ID(s)", ids.len()));
+                }
+            }
+
+            // Open a shell inside the allocation of the job under the cursor
+            (_, KeyCode::Char('t')) if !self.any_popup_visible() => {
+                self.attach_shell();
+            }
+
+            // User-defined actions for the job under the cursor
+            (_, KeyCode::Char('!')) if !self.any_popup_visible() => {
+                if !self.actions_popup.has_actions() {
+                    let message = "No actions configured; add [[actions]] to config.toml";
+                    self.set_status_message(message.to_string(), 3);
+                } else if let Some(job) = self.jobs_list.selected_job() {
+                    self.actions_popup.show(job.clone());
+                }
+            }
+
+            // Incident mode screen for operators
+            (_, KeyCode::Char('M')) if !self.any_popup_visible() => {
+                self.open_incident_panel();
+            }
+
+            // SSH to the first node of the job under the cursor
+            (_, KeyCode::Char('S')) if !self.any_popup_visible() => {
+                self.ssh_to_node();
             }
 
             // Column management popup
@@ -534,7 +2476,7 @@ impl App {
                 if !self.filter_popup.visible
                     && !self.script_view.visible
                     && !self.columns_popup.visible
-                    && !self.cancel_confirm =>
+                    && !self.confirm_dialog.visible =>
             {
                 self.columns_popup =
                     ColumnsPopup::new(self.selected_columns.clone(), self.sort_columns.clone());
@@ -603,6 +2545,9 @@ impl App {
             }
 
             // Change job for script view
+            // The moves stay in the bodies, not the guards, so Shift+arrow is consumed
+            // even at the first or last job
+            #[allow(clippy::collapsible_match)]
             (KeyModifiers::SHIFT, KeyCode::Up) if self.script_view.visible => {
                 // If Shift is pressed, switch to previous job and show its script
                 if self.jobs_list.previous() {
@@ -612,6 +2557,7 @@ impl App {
                     }
                 }
             }
+            #[allow(clippy::collapsible_match)]
             (KeyModifiers::SHIFT, KeyCode::Down) if self.script_view.visible => {
                 // If Shift is pressed, switch to next job and show its script
                 if self.jobs_list.next() {
@@ -645,6 +2591,7 @@ impl App {
             }
 
             // Change job for log view
+            #[allow(clippy::collapsible_match)]
             (KeyModifiers::SHIFT, KeyCode::Up) if self.log_view.visible => {
                 // If Shift is pressed, switch to previous job and show its logs
                 if self.jobs_list.previous() {
@@ -653,6 +2600,7 @@ impl App {
                     }
                 }
             }
+            #[allow(clippy::collapsible_match)]
             (KeyModifiers::SHIFT, KeyCode::Down) if self.log_view.visible => {
                 // If Shift is pressed, switch to next job and show its logs
                 if self.jobs_list.next() {
@@ -720,6 +2668,17 @@ impl App {
         }
     }
 
+    /// Pass a key to the quick filter bar being typed in and re-filter on changes
+    fn edit_quick_filter(&mut self, key: KeyEvent) {
+        if self.jobs_list.filter.editing {
+            if self.jobs_list.filter.handle_key(key) {
+                self.jobs_list.apply_quick_filter();
+            }
+        } else if self.history.filter.handle_key(key) {
+            self.history.apply_quick_filter();
+        }
+    }
+
     /// Handle mouse events
     fn handle_mouse_event(&mut self, _mouse: MouseEvent) {
         // TODO: Implement mouse event handling for TUI interactions
@@ -728,8 +2687,11 @@ impl App {
 
     /// Handle tick events (called periodically)
     fn handle_tick(&mut self) {
-        // Check if it's time to auto-refresh
-        if !self.filter_popup.visible
+        self.retry_submission();
+
+        // Check if it's time to auto-refresh; incident mode pauses it
+        if !self.incident
+            && !self.filter_popup.visible
             && !self.script_view.visible
             && !self.columns_popup.visible
             && self.last_refresh.elapsed().as_secs() >= self.job_refresh_interval
@@ -739,10 +2701,59 @@ impl App {
             }
         }
 
+        // The diagnostics view follows the jobs refresh interval while shown
+        if self.view == View::Diagnostics
+            && !self.any_popup_visible()
+            && self
+                .diagnostics_view
+                .is_stale(Duration::from_secs(self.job_refresh_interval))
+        {
+            self.load_diagnostics();
+        }
+
         // Check for log view updates and refresh content
         if self.log_view.visible {
             self.log_view.check_refresh();
         }
+        if self.split != SplitLayout::Off {
+            self.sync_log_pane();
+            self.log_pane.check_refresh();
+        }
+
+        // Live GPU readout of the detail pane; it stops when the pane closes
+        let gpu_job = self.job_detail.gpu_job().map(str::to_string);
+        match &self.gpu_monitor {
+            Some(monitor) if gpu_job.as_deref() == Some(monitor.job_id.as_str()) => {
+                if let Some(reading) = monitor.latest() {
+                    self.job_detail.set_gpus(reading);
+                }
+            }
+            Some(_) => self.gpu_monitor = None,
+            None => {}
+        }
+
+        // Same for the live resources tab
+        let stats_job = self.job_detail.stats_job().map(str::to_string);
+        match &self.live_stats {
+            Some((job_id, poller)) if stats_job.as_ref() == Some(job_id) => {
+                for stats in poller.drain() {
+                    self.job_detail.push_stats(stats);
+                }
+            }
+            Some(_) => self.live_stats = None,
+            None => {}
+        }
+
+        self.poll_batch();
+    }
+
+    /// In read-only mode, say that the action is disabled and return true
+    fn refuse_read_only(&mut self) -> bool {
+        if self.read_only {
+            let message = tr("Read-only mode: actions that change jobs are disabled");
+            self.set_status_message(message.to_string(), 3);
+        }
+        self.read_only
     }
 
     /// Set a temporary status message
@@ -836,6 +2847,42 @@ impl App {
         parts.join(", ")
     }
 
+    /// Whether any popup or overlay is currently shown on top of the jobs list
+    fn any_popup_visible(&self) -> bool {
+        self.filter_popup.visible
+            || self.script_view.visible
+            || self.columns_popup.visible
+            || self.log_view.visible
+            || self.edit_form.visible
+            || self.submit_form.visible
+            || self.prompt.visible
+            || self.confirm_dialog.visible
+            || self.preview.visible
+            || self.incident_panel.visible
+            || self.actions_popup.visible
+            || self.dependency_editor.visible
+            || self.eta_panel.visible
+            || self.efficiency_panel.visible
+            || self.dependency_graph.visible
+            || self.timeline.visible
+            || self.file_browser.visible
+            || self.job_detail.visible
+            || self.partition_picker.visible
+            || self.template_picker.visible
+            || self.pipeline_picker.visible
+            || self.wrap_picker.visible
+            || self.alloc_form.visible
+            || self.manifest_panel.visible
+            || self.module_panel.visible
+            || self.node_panel.visible
+            || self.watch_alert.visible
+            || self.audit_panel.visible
+            || self.submissions_panel.visible
+            || self.retry_panel.visible
+            || self.script_diff.visible
+            || self.history.popup_visible()
+    }
+
     /// Set running to false to quit the application
     fn quit(&mut self) {
         self.running = false;
@@ -854,6 +2901,7 @@ impl App {
             .selected_columns
             .iter()
             .map(|col| col.format_code())
+            .filter(|code| !code.is_empty())
             .collect::<Vec<&str>>()
             .join("|");
         self.squeue_options.format = format_string;
@@ -863,7 +2911,11 @@ impl App {
         self.squeue_options.sorts.clear();
         if !self.sort_columns.is_empty() {
             // Add sort colsumns to the squeue options
-            for sort_col in &self.sort_columns {
+            for sort_col in self
+                .sort_columns
+                .iter()
+                .filter(|sort_col| !sort_col.column.format_code().is_empty())
+            {
                 // get the format code for the column, removing any leading '%'
                 let sort_code = sort_col.column.format_code().trim_start_matches('%');
                 // set the sort order
@@ -896,18 +2948,2000 @@ impl App {
         }
     }
 
-    fn cancel_selected_jobs(&mut self) {
-        // Get selected job IDs
-        let selected_jobs = self.jobs_list.get_selected_jobs();
-        let selecteed_count = selected_jobs.len();
-        let _ = self
-            .runtime
-            .block_on(async { execute_scancel(selected_jobs).await });
-        // refresh the jobs list after cancellation
-        if let Err(e) = self.refresh_jobs() {
-            self.set_status_message(format!("Failed to refresh after cancel: {}", e), 3);
-        } else {
-            self.set_status_message(format!("Cancelled {} job(s)", selecteed_count), 3);
-        }
+    /// Work out which jobs a bulk action applies to and ask for confirmation.
+    ///
+    /// With the cursor on an array group header (and no other selection), the action
+    /// targets the whole array through its parent ID in a single command.
+    fn request_job_action(&mut self, action: JobAction) {
+        let selected = self.jobs_list.get_selected_jobs();
+        let pending = match self.jobs_list.selected_group() {
+            Some((parent, count))
+                if selected.is_empty() || self.jobs_list.selection_is_group(&parent) =>
+            {
+                PendingAction {
+                    action,
+                    job_ids: vec![parent.clone()],
+                    array: Some((parent, count)),
+                }
+            }
+            _ => PendingAction {
+                action,
+                job_ids: self.target_job_ids(),
+                array: None,
+            },
+        };
+
+        if pending.job_ids.is_empty() {
+            let message = trf(
+                "No jobs selected to {action}",
+                &[("action", tr(action.verb()))],
+            );
+            self.set_status_message(message, 3);
+            return;
+        }
+
+        let owners = self.job_owners(&pending.job_ids);
+        let me = get_username();
+        if !self.dry_run && owners.iter().any(|user| *user != me) {
+            self.review_admin_action(pending, &owners);
+        } else {
+            let policy = self.confirm_policies.for_action(action);
+            self.confirm_or_run(policy, Deferred::Action(pending));
+        }
+    }
+
+    /// Run an action right away or ask first, as configured for its kind.
+    /// Above the policy's threshold the job count has to be typed.
+    fn confirm_or_run(&mut self, policy: ConfirmPolicy, deferred: Deferred) {
+        let typed_above = match policy {
+            ConfirmPolicy::Never => {
+                self.run_deferred(deferred);
+                return;
+            }
+            ConfirmPolicy::Always => usize::MAX,
+            ConfirmPolicy::TypedAbove(limit) => limit,
+        };
+        let count = self.job_summary(deferred.job_ids()).len();
+        let question = deferred.question();
+        self.ask_confirmation(deferred, &question, count > typed_above);
+    }
+
+    /// Show the confirmation dialog for an action, listing the affected jobs
+    /// and their current states
+    fn ask_confirmation(&mut self, deferred: Deferred, question: &str, typed: bool) {
+        let jobs = self.job_summary(deferred.job_ids());
+        let title = trf(
+            "Confirm {action}",
+            &[("action", &capitalize(tr(deferred.verb())))],
+        );
+        self.confirm_dialog.show(&title, question, jobs, typed);
+        self.confirm = Some(deferred);
+    }
+
+    /// Run an action confirmed in the dialog (or not needing confirmation)
+    fn run_deferred(&mut self, deferred: Deferred) {
+        match deferred {
+            Deferred::Action(pending) => {
+                // Other users' jobs still go through the paced admin review
+                let owners = self.job_owners(&pending.job_ids);
+                let me = get_username();
+                if !self.dry_run && owners.iter().any(|user| *user != me) {
+                    self.review_admin_action(pending, &owners);
+                } else {
+                    self.run_job_action(pending);
+                }
+            }
+            Deferred::Signal {
+                job_ids,
+                signal,
+                batch_only,
+            } => self.signal_jobs(&job_ids, &signal, batch_only),
+            Deferred::Nice { job_ids, nice } => self.set_nice(&job_ids, nice),
+            Deferred::Move {
+                partition,
+                job_ids,
+                skipped,
+            } => self.move_to_partition(&partition, &job_ids, &skipped),
+            Deferred::Submit(command) => self.submit_batch_job(command),
+            Deferred::Pipeline(name) => self.submit_pipeline(&name),
+            Deferred::Manifest(request) => self.submit_manifest(&request),
+        }
+    }
+
+    /// ID and state of every listed job covered by the given IDs; an array
+    /// parent ID covers all of its tasks
+    fn job_summary(&self, job_ids: &[String]) -> Vec<(String, String)> {
+        job_ids
+            .iter()
+            .flat_map(|id| {
+                let jobs: Vec<(String, String)> = self
+                    .jobs_list
+                    .jobs
+                    .iter()
+                    .filter(|job| {
+                        job.id == *id
+                            || job
+                                .id
+                                .strip_prefix(id.as_str())
+                                .is_some_and(|rest| rest.starts_with('_'))
+                    })
+                    .map(|job| (job.id.clone(), job.state.to_string()))
+                    .collect();
+                if jobs.is_empty() {
+                    vec![(id.clone(), "-".to_string())]
+                } else {
+                    jobs
+                }
+            })
+            .collect()
+    }
+
+    /// Users owning the given jobs; an array parent ID covers all of its tasks
+    fn job_owners(&self, job_ids: &[String]) -> BTreeSet<String> {
+        self.jobs_list
+            .jobs
+            .iter()
+            .filter(|job| {
+                job_ids.iter().any(|id| {
+                    job.id == *id
+                        || job
+                            .id
+                            .strip_prefix(id.as_str())
+                            .is_some_and(|rest| rest.starts_with('_'))
+                })
+            })
+            .map(|job| job.user.clone())
+            .collect()
+    }
+
+    /// Show the exact commands of an action on other users' jobs for review;
+    /// once confirmed they run one at a time in the background
+    fn review_admin_action(&mut self, pending: PendingAction, owners: &BTreeSet<String>) {
+        if self.batch.is_some() {
+            self.set_status_message("Wait for the running batch to finish".to_string(), 3);
+            return;
+        }
+
+        let action = pending.action;
+        let commands: Vec<SlurmCommand> = pending
+            .job_ids
+            .chunks(self.admin.batch_size)
+            .flat_map(|chunk| job_action_commands(action, chunk))
+            .collect();
+        let users = owners.iter().cloned().collect::<Vec<_>>().join(", ");
+        let note = format!(
+            "{} job(s) owned by {}. Nothing has run yet; {} command(s) will run one at a time, {} ms apart.",
+            pending.job_ids.len(),
+            users,
+            commands.len(),
+            self.admin.delay_ms,
+        );
+        let lines = commands.iter().map(|c| c.to_string()).collect();
+        let title = format!("{} {} job(s)", action.verb(), pending.job_ids.len());
+        self.preview.show_confirm(&title, &note, lines);
+        let delay = Duration::from_millis(self.admin.delay_ms);
+        self.batch_pending = Some((action.verb(), commands, delay));
+    }
+
+    /// Enter incident mode and show pending jobs of all users per partition
+    fn open_incident_panel(&mut self) {
+        if !self.incident {
+            self.incident = true;
+            let message = tr("Incident mode on, auto-refresh paused");
+            self.set_status_message(message.to_string(), 3);
+        }
+
+        let options = SqueueOptions {
+            user: None,
+            states: vec![JobState::Pending],
+            format: "%i|%u|%P".to_string(),
+            command: self.squeue_options.command.clone(),
+            ..Default::default()
+        };
+        match self.runtime.block_on(async { run_squeue(&options).await }) {
+            Ok(jobs) => {
+                let jobs = jobs
+                    .into_iter()
+                    .filter(|job| job.state == JobState::Pending)
+                    .collect();
+                self.incident_panel.show(&self.available_partitions, jobs);
+            }
+            Err(e) => self.set_status_message(format!("Failed to list pending jobs: {}", e), 5),
+        }
+    }
+
+    /// Hold the pending jobs chosen in the incident panel
+    fn hold_for_incident(&mut self, jobs: Vec<Job>) {
+        let owners = jobs.iter().map(|job| job.user.clone()).collect();
+        let pending = PendingAction {
+            action: JobAction::Hold,
+            job_ids: jobs.into_iter().map(|job| job.id).collect(),
+            array: None,
+        };
+        if self.dry_run {
+            let title = format!("hold {} job(s)", pending.job_ids.len());
+            let commands = job_action_commands(JobAction::Hold, &pending.job_ids);
+            self.run_mutation(&title, commands);
+        } else {
+            self.review_admin_action(pending, &owners);
+        }
+    }
+
+    /// Run commands in the background right away, showing their progress in the
+    /// preview panel
+    fn run_in_background(
+        &mut self,
+        verb: &'static str,
+        title: &str,
+        note: &str,
+        commands: Vec<SlurmCommand>,
+    ) {
+        if self.refuse_read_only() {
+            return;
+        }
+        if self.batch.is_some() {
+            self.set_status_message("Wait for the running batch to finish".to_string(), 3);
+            return;
+        }
+        let lines = commands.iter().map(|c| c.to_string()).collect();
+        self.preview.show_progress(title, note, lines);
+        let run = BatchRun::spawn(&self.runtime, commands, Duration::ZERO);
+        self.batch = Some((verb, run));
+    }
+
+    /// Start the batch confirmed in the preview
+    fn start_batch(&mut self) {
+        let Some((verb, commands, delay)) = self.batch_pending.take() else {
+            return;
+        };
+        if self.refuse_read_only() {
+            self.preview.hide();
+            return;
+        }
+        let run = BatchRun::spawn(&self.runtime, commands, delay);
+        self.preview.set_running();
+        self.batch = Some((verb, run));
+    }
+
+    /// Drop a batch awaiting review, or stop the running one after its current command
+    fn abort_batch(&mut self) {
+        self.batch_pending = None;
+        if let Some((_, run)) = &self.batch {
+            run.cancel();
+        }
+    }
+
+    /// Collect results of the running batch, audit them and report progress
+    fn poll_batch(&mut self) {
+        let Some((verb, run)) = &mut self.batch else {
+            return;
+        };
+        let verb = *verb;
+
+        let mut audit_error = None;
+        for (index, status) in run.poll() {
+            let entry = AuditEntry::new(&run.commands[index], status.clone());
+            if let Err(e) = self.audit_log.record(entry) {
+                audit_error = Some(e);
+            }
+            if self.preview.is_running() {
+                self.preview.set_status(index, status);
+            }
+        }
+
+        let total = run.commands.len();
+        let (finished, failed) = (run.finished, run.failed);
+        let summary = format!(
+            "{}: {}/{} command(s) ran, {} failed{}",
+            capitalize(verb),
+            finished,
+            total,
+            failed,
+            if run.is_cancelled() {
+                ", rest stopped"
+            } else {
+                ""
+            }
+        );
+
+        if run.is_done() {
+            self.batch = None;
+            if self.preview.is_running() {
+                self.preview.set_finished(summary.clone());
+            }
+            if let Err(e) = self.refresh_jobs() {
+                self.set_status_message(format!("Failed to refresh after {}: {}", verb, e), 3);
+                return;
+            }
+            self.set_status_message(summary, 5);
+        } else if !self.preview.visible {
+            self.set_status_message(summary, 2);
+        }
+
+        if let Some(e) = audit_error {
+            self.set_status_message(format!("Failed to write audit log: {}", e), 5);
+        }
+    }
+
+    /// Run a confirmed bulk action and refresh the list
+    fn run_job_action(&mut self, pending: PendingAction) {
+        let action = pending.action;
+        let count = pending.job_ids.len();
+        if count > BACKGROUND_ABOVE && !self.dry_run {
+            let commands = pending
+                .job_ids
+                .chunks(BACKGROUND_CHUNK)
+                .flat_map(|chunk| job_action_commands(action, chunk))
+                .collect();
+            let title = format!("{} {} job(s)", action.verb(), count);
+            let note = format!(
+                "{} job(s) in chunks of {}; the list refreshes when all are done.",
+                count, BACKGROUND_CHUNK
+            );
+            self.run_in_background(action.verb(), &title, &note, commands);
+            return;
+        }
+        let commands = job_action_commands(action, &pending.job_ids);
+        let Some(result) = self.run_mutation(&format!("{} job(s)", action.verb()), commands) else {
+            return;
+        };
+
+        let done = match &pending.array {
+            Some((parent, tasks)) => format!(
+                "{} array job {} ({} tasks)",
+                action.past_tense(),
+                parent,
+                tasks
+            ),
+            None => format!("{} {} job(s)", action.past_tense(), count),
+        };
+
+        if let Err(e) = result {
+            self.set_status_message(format!("Failed to {}: {}", action.verb(), e), 5);
+        } else if let Err(e) = self.refresh_jobs() {
+            self.set_status_message(
+                format!("Failed to refresh after {}: {}", action.verb(), e),
+                3,
+            );
+        } else {
+            self.set_status_message(done, 3);
+        }
+    }
+
+    /// Open the edit form for the job under the cursor
+    fn open_edit_form(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        let job_id = job.id.clone();
+        if self.cluster_limits.is_none() {
+            // Without limits the form still validates the syntax of each field
+            let limits = self.runtime.block_on(async { get_cluster_limits().await });
+            self.cluster_limits = Some(limits.unwrap_or_default());
+        }
+        match self.runtime.block_on(async { get_job_info(&job_id).await }) {
+            Ok(info) => self.edit_form.show(job_id, &info),
+            Err(e) => self.set_status_message(format!("Failed to load job {}: {}", job_id, e), 3),
+        }
+    }
+
+    /// Send the changes from the edit form to `scontrol update`
+    fn submit_job_edit(&mut self, changes: Vec<(String, String)>) {
+        let Some(job_id) = self.edit_form.job_id.clone() else {
+            return;
+        };
+        let summary = changes
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let command = modify_job_command(&job_id, &changes);
+        self.edit_form.hide();
+        let Some(result) = self.run_mutation(&format!("update job {}", job_id), vec![command])
+        else {
+            return;
+        };
+
+        match result {
+            Ok(()) => {
+                if let Err(e) = self.refresh_jobs() {
+                    self.set_status_message(format!("Failed to refresh after update: {}", e), 3);
+                } else {
+                    self.set_status_message(format!("Updated job {}: {}", job_id, summary), 5);
+                }
+            }
+            Err(e) => {
+                self.set_status_message(format!("Failed to update job {}: {}", job_id, e), 5);
+            }
+        }
+    }
+
+    /// Run state-changing Slurm commands, or only show them when dry-run is on.
+    ///
+    /// Returns `None` if nothing was executed.
+    fn run_mutation(&mut self, title: &str, commands: Vec<SlurmCommand>) -> Option<Result<()>> {
+        self.run_mutation_output(title, commands)
+            .map(|result| result.map(|_| ()))
+    }
+
+    /// [`Self::run_mutation`], returning what the last command printed
+    fn run_mutation_output(
+        &mut self,
+        title: &str,
+        commands: Vec<SlurmCommand>,
+    ) -> Option<Result<String>> {
+        // Taken first, so a refused or dry run leaves none for the next command
+        let template = self.submit_template.take();
+        if self.read_only {
+            return Some(Err(eyre!("{}", tr("read-only mode"))));
+        }
+        if self.dry_run {
+            let lines = commands.iter().map(|c| c.to_string()).collect();
+            self.preview.show(title, lines);
+            return None;
+        }
+
+        // Run in order, stopping at the first failure; every attempt is audited
+        let mut printed = String::new();
+        for command in &commands {
+            let (status, stdout) = self
+                .runtime
+                .block_on(async { run_command_output(command).await });
+            printed = stdout;
+            let entry = AuditEntry::new(command, status.clone());
+            if let Err(e) = self.audit_log.record(entry) {
+                self.set_status_message(format!("Failed to write audit log: {}", e), 5);
+            }
+            if command.program == "sbatch" {
+                let result = match &status {
+                    CommandStatus::Success => Ok(submitted_job_id(&printed)),
+                    CommandStatus::Failed { stderr, .. } => Err(stderr.trim().to_string()),
+                    CommandStatus::NotRun(e) => Err(e.clone()),
+                };
+                let mut submission = Submission::new(&self.cluster_name, &command.args, result);
+                submission.template = template.clone();
+                if let Err(e) = self.submissions.log(submission) {
+                    let message = format!("Failed to write submission history: {}", e);
+                    self.set_status_message(message, 5);
+                }
+            }
+            match status {
+                CommandStatus::Success => {}
+                CommandStatus::Failed { stderr, .. } => return Some(Err(eyre!("{}", stderr))),
+                CommandStatus::NotRun(e) => return Some(Err(eyre!("{}", e))),
+            }
+        }
+        Some(Ok(printed))
+    }
+
+    /// Diff the batch scripts of the two selected jobs, or of the single selected
+    /// job and the job under the cursor. The older job is shown as the base.
+    fn compare_job_scripts(&mut self) {
+        let mut ids = self.jobs_list.get_selected_jobs();
+        if ids.len() == 1 {
+            if let Some(job) = self.jobs_list.selected_job() {
+                if job.id != ids[0] {
+                    ids.push(job.id.clone());
+                }
+            }
+        }
+        if ids.len() != 2 {
+            self.set_status_message(
+                "Select two jobs (or one job and move the cursor to another) to compare"
+                    .to_string(),
+                3,
+            );
+            return;
+        }
+        ids.sort_by_key(|id| job_id_sort_key(id));
+
+        let result = self.runtime.block_on(async {
+            let old = get_batch_script(&ids[0]).await?;
+            let new = get_batch_script(&ids[1]).await?;
+            Ok::<_, color_eyre::Report>((old, new))
+        });
+        match result {
+            Ok((old, new)) => self.script_diff.show(&ids[0], &old, &ids[1], &new),
+            Err(e) => self.set_status_message(format!("Failed to get batch script: {}", e), 5),
+        }
+    }
+
+    /// Copy a `slurmer://` link plus a short plain-text summary of the job under the cursor
+    fn copy_job_link(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        let link = JobLink::new(&self.cluster_name, &job.id);
+        let text = format!(
+            "{}\nJob {} ({}) user={} state={} partition={} node={} time={}",
+            link,
+            job.id,
+            job.name,
+            job.user,
+            job.state,
+            job.partition,
+            job.node.as_deref().unwrap_or("-"),
+            job.time,
+        );
+
+        self.copy_with_status(&text, &link.to_string());
+    }
+
+    /// Copy the `scontrol show job` record of the job under the cursor
+    fn copy_job_record(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        let job_id = job.id.clone();
+        match self
+            .runtime
+            .block_on(async { get_job_record(&job_id).await })
+        {
+            Ok(record) => self.copy_with_status(&record, &format!("details of job {}", job_id)),
+            Err(e) => self.set_status_message(format!("Failed to load job {}: {}", job_id, e), 3),
+        }
+    }
+
+    /// Copy text to the clipboard and report it as "Copied <what>"
+    fn copy_with_status(&mut self, text: &str, what: &str) {
+        match copy_to_clipboard(text) {
+            Ok(()) => self.set_status_message(format!("Copied {}", what), 3),
+            Err(e) => self.set_status_message(format!("Failed to copy {}: {}", what, e), 3),
+        }
+    }
+
+    /// Working directory of the job under the cursor, from the WorkDir column
+    /// or else from scontrol or accounting, if it exists here
+    fn selected_work_dir(&mut self) -> Option<String> {
+        let job = self.jobs_list.selected_job()?;
+        let job_id = job.id.clone();
+        let known = job.work_dir.clone().filter(|dir| !dir.is_empty());
+        let work_dir = match known {
+            Some(dir) => dir,
+            None => match self.runtime.block_on(async { get_work_dir(&job_id).await }) {
+                Ok(dir) => dir,
+                Err(e) => {
+                    self.set_status_message(e.to_string(), 3);
+                    return None;
+                }
+            },
+        };
+        if !std::path::Path::new(&work_dir).is_dir() {
+            let message = format!("Working directory {} does not exist here", work_dir);
+            self.set_status_message(message, 3);
+            return None;
+        }
+        Some(work_dir)
+    }
+
+    /// Queue `srun --pty bash` into the job under the cursor, if it is running
+    fn attach_shell(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        if job.state != JobState::Running {
+            let message = format!("Job {} is {}, not running", job.id, job.state);
+            self.set_status_message(message, 3);
+            return;
+        }
+        self.pending_shell = Some(attach_shell_command(&job.id));
+    }
+
+    /// Select the allocation just granted by `salloc --no-shell` and queue a shell
+    /// in it; the newest running job of that name is taken to be it
+    fn open_allocation_shell(&mut self, name: &str) {
+        let me = get_username();
+        let job_id = self
+            .jobs_list
+            .jobs
+            .iter()
+            .filter(|job| job.name == name && job.user == me && job.state == JobState::Running)
+            .max_by_key(|job| job_id_sort_key(&job.id))
+            .map(|job| job.id.clone());
+        let Some(job_id) = job_id else {
+            let message = format!(
+                "Allocation {} was granted, but it is not in the job list; check the filters",
+                name
+            );
+            self.set_status_message(message, 5);
+            return;
+        };
+        self.jobs_list.select_job_by_id(&job_id);
+        let message = format!(
+            "Allocation {} stays in the list after the shell exits: t opens another shell, x releases it",
+            job_id
+        );
+        self.set_status_message(message, 8);
+        self.pending_shell = Some(attach_shell_command(&job_id));
+    }
+
+    /// Queue an SSH session to the first node of the job under the cursor
+    fn ssh_to_node(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        if job.state != JobState::Running {
+            let message = format!("Job {} is {}, not running", job.id, job.state);
+            self.set_status_message(message, 3);
+            return;
+        }
+        match job.node.as_deref().and_then(first_host) {
+            Some(node) => self.pending_shell = Some(ssh_command(&node, &job.id)),
+            None => {
+                let message = format!("No node known for job {}; show the Node column", job.id);
+                self.set_status_message(message, 3);
+            }
+        }
+    }
+
+    /// Select the job referenced by a link, widening the user filter to its owner if needed
+    fn open_job_link(&mut self, link: JobLink) {
+        if let Some(cluster) = &link.cluster {
+            if *cluster != self.cluster_name {
+                self.set_status_message(
+                    format!(
+                        "Link is for cluster '{}', but this is '{}'",
+                        cluster, self.cluster_name
+                    ),
+                    5,
+                );
+                return;
+            }
+        }
+
+        if self.jobs_list.select_job_by_id(&link.job_id) {
+            self.set_status_message(format!("Jumped to job {}", link.job_id), 3);
+            return;
+        }
+
+        // The job may belong to someone else: show its owner's jobs instead
+        let job_id = link.job_id.clone();
+        let owner = self
+            .runtime
+            .block_on(async { get_job_info(&job_id).await })
+            .ok()
+            .and_then(|info| info.get("UserId").cloned())
+            .map(|user| user.split('(').next().unwrap_or_default().to_string());
+
+        match owner {
+            Some(user) if !user.is_empty() => {
+                self.squeue_options = SqueueOptions {
+                    user: Some(user),
+                    ..Default::default()
+                };
+                if let Err(e) = self.refresh_jobs() {
+                    self.set_status_message(format!("Failed to refresh: {}", e), 3);
+                } else if self.jobs_list.select_job_by_id(&link.job_id) {
+                    self.set_status_message(format!("Jumped to job {}", link.job_id), 3);
+                } else {
+                    self.set_status_message(format!("Job {} not found", link.job_id), 3);
+                }
+            }
+            _ => {
+                self.set_status_message(
+                    format!("Job {} is no longer in the queue", link.job_id),
+                    5,
+                );
+            }
+        }
+    }
+
+    /// Jobs an action applies to: the selection, or the job under the cursor if nothing is selected
+    fn target_job_ids(&self) -> Vec<String> {
+        let selected = self.jobs_list.get_selected_jobs();
+        if !selected.is_empty() {
+            return selected;
+        }
+        self.jobs_list
+            .selected_job()
+            .map(|job| vec![job.id.clone()])
+            .unwrap_or_default()
+    }
+
+    /// Act on the text entered in the input prompt
+    fn submit_prompt(&mut self, input: String) {
+        match self.prompt.kind {
+            PromptKind::Signal => {
+                let (batch_only, name) = match input.strip_prefix("B:") {
+                    Some(rest) => (true, rest),
+                    None => (false, input.as_str()),
+                };
+                match normalize_signal(name) {
+                    Some(signal) => {
+                        self.prompt.hide();
+                        let deferred = Deferred::Signal {
+                            job_ids: self.target_job_ids(),
+                            signal,
+                            batch_only,
+                        };
+                        self.confirm_or_run(self.confirm_policies.signal, deferred);
+                    }
+                    None => self.prompt.set_error(format!("Unknown signal: {}", input)),
+                }
+            }
+            PromptKind::BookmarkName => {
+                if input.is_empty() {
+                    self.prompt
+                        .set_error("Enter a name for the bookmark".to_string());
+                    return;
+                }
+                self.prompt.hide();
+                self.history.add_bookmark(&input);
+                self.save_bookmarks();
+            }
+            PromptKind::JobName => {
+                if input.is_empty() {
+                    self.prompt.set_error("Enter a job name".to_string());
+                    return;
+                }
+                self.prompt.hide();
+                self.rename_job(input);
+            }
+            PromptKind::JobNote => {
+                self.prompt.hide();
+                self.save_note(&input);
+            }
+            PromptKind::Wrap => {
+                if input.is_empty() {
+                    self.prompt.set_error("Enter a command to run".to_string());
+                    return;
+                }
+                self.prompt.hide();
+                self.submit_wrap(input);
+            }
+            PromptKind::TimeLimit => match parse_time_limit(&input) {
+                Some(secs) if secs != u64::MAX => {
+                    self.prompt.hide();
+                    self.extend_time_limit(input.trim());
+                }
+                _ => self
+                    .prompt
+                    .set_error(format!("Not a valid time limit: {}", input)),
+            },
+            PromptKind::Nice => match input.parse::<i32>() {
+                // Slurm limits nice values to +/-(NICE_OFFSET - 3)
+                Ok(nice) if nice.unsigned_abs() <= 2_147_483_645 => {
+                    self.prompt.hide();
+                    let deferred = Deferred::Nice {
+                        job_ids: self.target_job_ids(),
+                        nice,
+                    };
+                    self.confirm_or_run(self.confirm_policies.nice, deferred);
+                }
+                _ => self
+                    .prompt
+                    .set_error(format!("Not a valid nice value: {}", input)),
+            },
+            PromptKind::Command => match input.as_str() {
+                "" => self.prompt.hide(),
+                "cancel-filtered" => {
+                    self.prompt.hide();
+                    self.request_cancel_filtered();
+                }
+                _ if input.starts_with("node ") => {
+                    self.prompt.hide();
+                    let nodes = expand_hostlist(input["node ".len()..].trim());
+                    self.show_node_detail(nodes);
+                }
+                _ => self.prompt.set_error(trf(
+                    "Unknown command: {command} (available: cancel-filtered, node <name>)",
+                    &[("command", &input)],
+                )),
+            },
+        }
+    }
+
+    /// Ask to cancel every job matching the current filters, selected or not.
+    /// The list is refreshed first so the count matches the queue; the user
+    /// always confirms by typing that count, whatever the cancel policy.
+    fn request_cancel_filtered(&mut self) {
+        if let Err(e) = self.refresh_jobs() {
+            self.set_status_message(format!("Failed to refresh jobs: {}", e), 5);
+            return;
+        }
+        let job_ids: Vec<String> = self
+            .jobs_list
+            .jobs
+            .iter()
+            .map(|job| job.id.clone())
+            .collect();
+        if job_ids.is_empty() {
+            self.set_status_message(tr("No jobs match the filters").to_string(), 3);
+            return;
+        }
+
+        let mut filters = vec![self.get_filter_description()];
+        let quick = self.jobs_list.filter.text();
+        if !quick.is_empty() {
+            filters.push(format!("/{}", quick));
+        }
+        filters.retain(|f| !f.is_empty());
+        let filters = filters.join(", ");
+        let question = trf(
+            "Cancel ALL {count} job(s) matching the filters? Filters: {filters}",
+            &[("count", &job_ids.len().to_string()), ("filters", &filters)],
+        );
+        let pending = PendingAction {
+            action: JobAction::Cancel,
+            job_ids,
+            array: None,
+        };
+        self.ask_confirmation(Deferred::Action(pending), &question, true);
+    }
+
+    /// Show all `scontrol show job` fields of the job under the cursor, or its
+    /// accounting record once slurmctld has forgotten the job
+    fn open_job_detail(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        let job_id = job.id.clone();
+        self.show_job_detail(&job_id);
+    }
+
+    /// Open the node detail popup on the first of these nodes
+    fn show_node_detail(&mut self, nodes: Vec<String>) {
+        if let Some(node) = self.node_panel.show(nodes) {
+            self.load_node_detail(&node);
+        }
+    }
+
+    /// Fetch a node's detail into the node popup
+    fn load_node_detail(&mut self, node: &str) {
+        let detail = self
+            .runtime
+            .block_on(async { get_node_detail(node).await })
+            .map_err(|e| e.to_string());
+        self.node_panel.set_detail(detail);
+    }
+
+    /// Drain, resume or down a node from the node detail, then show its new state
+    fn update_node(&mut self, node: &str, state: NodeState, reason: Option<&str>) {
+        let command = node_update_command(node, state, reason);
+        let title = trf(
+            "Set node {node} to {state}",
+            &[("node", node), ("state", state.as_str())],
+        );
+        match self.run_mutation_output(&title, vec![command]) {
+            None => {}
+            Some(Ok(_)) => {
+                self.node_panel
+                    .set_result(Ok(format!("{} set to {}", node, state.as_str())));
+                self.load_node_detail(node);
+            }
+            Some(Err(e)) => self
+                .node_panel
+                .set_result(Err(e.to_string().trim().to_string())),
+        }
+    }
+
+    /// Show all fields of a job, from slurmctld or else from accounting
+    fn show_job_detail(&mut self, job_id: &str) {
+        let job_id = job_id.to_string();
+        match self
+            .runtime
+            .block_on(async { get_job_record(&job_id).await })
+        {
+            Ok(record) => {
+                let fields = parse_scontrol_fields(&record);
+                let gpu_node = gpu_node(&fields).filter(|_| self.gpu_settings.enabled);
+                let running = fields
+                    .iter()
+                    .any(|(key, value)| key == "JobState" && value == "RUNNING");
+                let pending = fields
+                    .iter()
+                    .any(|(key, value)| key == "JobState" && value == "PENDING");
+                let burst_buffer = fields.iter().any(|(key, value)| {
+                    key == "BurstBuffer" && !value.is_empty() && value != "(null)"
+                });
+                let multi_node = fields.iter().any(|(key, value)| {
+                    key == "NumNodes" && value.parse::<u32>().is_ok_and(|nodes| nodes > 1)
+                });
+                self.job_detail.show(&job_id, "scontrol show job", fields);
+                self.job_detail.set_raw(record);
+                if running && multi_node {
+                    // Without the allocation the pane still lists the node names
+                    if let Ok(nodes) = self
+                        .runtime
+                        .block_on(async { get_node_allocation(&job_id).await })
+                    {
+                        self.job_detail.set_nodes(nodes);
+                    }
+                }
+                if running {
+                    self.job_detail.watch_stats();
+                    let id = job_id.clone();
+                    let poller = Poller::spawn(LIVE_STATS_INTERVAL, move || query_job_stats(&id));
+                    self.live_stats = Some((job_id.clone(), poller));
+                }
+                if pending {
+                    let priority = self
+                        .runtime
+                        .block_on(async { get_priority_factors(&job_id).await });
+                    self.job_detail
+                        .set_priority(priority.map_err(|e| e.to_string()));
+                }
+                if burst_buffer {
+                    let allocations = self
+                        .runtime
+                        .block_on(async { get_burst_buffers().await })
+                        .map(|buffers| {
+                            buffers
+                                .allocations
+                                .into_iter()
+                                .filter(|a| a.job_id.as_deref() == Some(job_id.as_str()))
+                                .collect()
+                        });
+                    self.job_detail
+                        .set_burst_buffer(allocations.map_err(|e| e.to_string()));
+                }
+                if let Some(node) = gpu_node {
+                    self.job_detail.watch_gpus(&node);
+                    self.gpu_monitor = Some(GpuMonitor::spawn(&job_id, &node, &self.gpu_settings));
+                }
+                self.show_attempts(&job_id);
+                self.show_note(&job_id);
+                self.show_template_diff(&job_id);
+            }
+            Err(_) => self.open_accounting_detail(&job_id),
+        }
+    }
+
+    /// List the other attempts of a resubmitted job in the detail pane, with
+    /// how each of them turned out
+    fn show_attempts(&mut self, job_id: &str) {
+        let attempts = self.lineage.attempts(job_id);
+        if attempts.is_empty() {
+            return;
+        }
+        let outcomes = attempts
+            .into_iter()
+            .map(|id| {
+                let queued = self.jobs_list.jobs.iter().find(|job| job.id == id);
+                let state = match queued {
+                    Some(job) => job.state.to_string(),
+                    None => self
+                        .runtime
+                        .block_on(async { get_job_state(&id).await })
+                        .ok()
+                        .flatten()
+                        .unwrap_or_else(|| "UNKNOWN".to_string()),
+                };
+                (id, state)
+            })
+            .collect();
+        self.job_detail.set_attempts(outcomes);
+    }
+
+    /// Show the local note on a job in the detail pane
+    fn show_note(&mut self, job_id: &str) {
+        let note = self.notes.get(&self.cluster_name, job_id);
+        self.job_detail.set_note(note.map(str::to_string));
+    }
+
+    /// Diff the script of a job submitted from a template against the template's
+    /// script or skeleton in the detail pane. The script is the one Slurm kept,
+    /// or else the file that was submitted.
+    fn show_template_diff(&mut self, job_id: &str) {
+        let Some(submission) = self.submissions.submitted(&self.cluster_name, job_id) else {
+            return;
+        };
+        let Some(name) = submission.template.clone() else {
+            return;
+        };
+        let submitted = submission.args.last().cloned().unwrap_or_default();
+        let Some(template) = self.templates.iter().find(|t| t.name == name) else {
+            let diff = Err(trf(
+                "Template {name} is no longer configured",
+                &[("name", &name)],
+            ));
+            self.job_detail.set_template(&name, diff);
+            return;
+        };
+        let base = match (&template.skeleton, &template.script) {
+            (Some(skeleton), _) => Ok(skeleton.clone()),
+            (None, Some(script)) => {
+                let path = expand_home(script);
+                fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))
+            }
+            (None, None) => Err(tr("The template has no script").to_string()),
+        };
+        let script = self
+            .runtime
+            .block_on(async { get_batch_script(job_id).await })
+            .or_else(|_| fs::read_to_string(&submitted))
+            .map_err(|e| format!("{}: {}", submitted, e));
+        let diff = base.and_then(|base| {
+            let script = script?;
+            Ok(if base == script {
+                Vec::new()
+            } else {
+                unified_diff(&base, &script, &format!("template {}", name), &submitted)
+            })
+        });
+        self.job_detail.set_template(&name, diff);
+    }
+
+    /// Set the note on the job under the cursor, or remove it if `note` is empty
+    fn save_note(&mut self, note: &str) {
+        let Some(job_id) = self.jobs_list.selected_job().map(|job| job.id.clone()) else {
+            return;
+        };
+        match self.notes.set(&self.cluster_name, &job_id, note) {
+            Ok(()) => {
+                self.jobs_list.notes = self.notes.for_cluster(&self.cluster_name);
+                let message = if note.is_empty() {
+                    format!("Removed the note on job {}", job_id)
+                } else {
+                    format!("Saved a note on job {}", job_id)
+                };
+                self.set_status_message(message, 3);
+            }
+            Err(e) => self.set_status_message(format!("Failed to save note: {}", e), 5),
+        }
+    }
+
+    /// Show all `sacct` fields of a finished job
+    fn open_accounting_detail(&mut self, job_id: &str) {
+        match self
+            .runtime
+            .block_on(async { get_job_accounting(job_id).await })
+        {
+            Ok((fields, raw)) => {
+                self.job_detail.show(job_id, "sacct", fields);
+                self.job_detail.set_raw(raw);
+                self.show_attempts(job_id);
+                self.show_note(job_id);
+                self.show_template_diff(job_id);
+            }
+            Err(e) => self.set_status_message(format!("Failed to query job {}: {}", job_id, e), 5),
+        }
+    }
+
+    /// Show the environment of a job recorded in accounting
+    fn open_environment(&mut self, job_id: &str) {
+        match self
+            .runtime
+            .block_on(async { get_job_environment(job_id).await })
+        {
+            Ok(vars) => self.job_detail.show_flat(job_id, tr("environment"), vars),
+            Err(e) => self.set_status_message(
+                format!("Failed to get the environment of job {}: {}", job_id, e),
+                5,
+            ),
+        }
+    }
+
+    /// Show the CPU and memory efficiency of jobs from accounting
+    fn open_efficiency_panel(&mut self, job_ids: &[String]) {
+        if job_ids.is_empty() {
+            return;
+        }
+        match self
+            .runtime
+            .block_on(async { get_job_usage(job_ids).await })
+        {
+            Ok(jobs) if jobs.is_empty() => {
+                let message = "No accounting records for the selected jobs yet";
+                self.set_status_message(message.to_string(), 3);
+            }
+            Ok(jobs) => self.efficiency_panel.show(jobs),
+            Err(e) => self.set_status_message(format!("Failed to query accounting: {}", e), 5),
+        }
+    }
+
+    /// Show the timeline of the current user's jobs from accounting
+    fn open_timeline(&mut self) {
+        match self
+            .runtime
+            .block_on(async { get_timeline_jobs(TIMELINE_START).await })
+        {
+            Ok(jobs) => self.timeline.show(jobs),
+            Err(e) => self.set_status_message(format!("Failed to query accounting: {}", e), 5),
+        }
+    }
+
+    /// Show the completion estimate for the selected jobs (or the job under the cursor)
+    fn open_eta_panel(&mut self) {
+        let ids = self.target_job_ids();
+        if ids.is_empty() {
+            return;
+        }
+        let options = SqueueOptions {
+            user: None,
+            job_ids: ids,
+            format: "%i|%j|%T|%M|%l|%C|%S|%e".to_string(),
+            ..Default::default()
+        };
+        match self.runtime.block_on(async { run_squeue(&options).await }) {
+            Ok(jobs) if jobs.is_empty() => {
+                let message = "The selected jobs are no longer in the queue";
+                self.set_status_message(message.to_string(), 3);
+            }
+            Ok(jobs) => self.eta_panel.show(&jobs),
+            Err(e) => self.set_status_message(format!("Failed to query jobs: {}", e), 5),
+        }
+    }
+
+    /// Submit the batch script of a finished job again from its working
+    /// directory, after opening it in the user's editor if `edit` is set
+    fn resubmit_job(&mut self, job_id: &str, edit: bool) {
+        if self.refuse_read_only() {
+            return;
+        }
+        let paths = self
+            .runtime
+            .block_on(async { get_submit_paths(job_id).await });
+        let (script, work_dir) = match paths {
+            Ok(paths) => paths,
+            Err(e) => {
+                self.set_status_message(format!("Cannot resubmit: {}", e), 5);
+                return;
+            }
+        };
+        let command = resubmit_command(job_id, &script, &work_dir);
+        if edit {
+            self.pending_shell = Some(editor_command(&script));
+            self.pending_resubmit = Some(command);
+        } else {
+            self.submit_again(command);
+        }
+    }
+
+    /// Run an sbatch command built by [`resubmit_command`]
+    fn submit_again(&mut self, command: SlurmCommand) {
+        let job_id = command.job_ids.join(",");
+        let title = format!("Resubmit job {}", job_id);
+        match self.run_mutation_output(&title, vec![command]) {
+            None => {}
+            Some(Ok(output)) => match submitted_job_id(&output) {
+                Some(new_id) => {
+                    let message = match self.lineage.record(&new_id, &job_id) {
+                        Ok(()) => format!("Resubmitted job {} as job {}", job_id, new_id),
+                        Err(e) => format!(
+                            "Resubmitted job {} as job {}, but failed to record it: {}",
+                            job_id, new_id, e
+                        ),
+                    };
+                    self.set_status_message(message, 3);
+                }
+                None => {
+                    self.set_status_message(format!("Resubmitted the script of job {}", job_id), 3)
+                }
+            },
+            Some(Err(e)) => {
+                self.set_status_message(format!("Failed to resubmit job {}: {}", job_id, e), 5)
+            }
+        }
+    }
+
+    /// Open the submission form, filled from an existing job when cloning one
+    fn open_submit_form(&mut self, clone_of: Option<&str>) {
+        if self.refuse_read_only() {
+            return;
+        }
+        if self.cluster_limits.is_none() {
+            // Without limits the script is still checked for unknown options and paths
+            let limits = self.runtime.block_on(async { get_cluster_limits().await });
+            self.cluster_limits = Some(limits.unwrap_or_default());
+        }
+        let Some(job_id) = clone_of else {
+            self.submit_form.show();
+            return;
+        };
+        match self
+            .runtime
+            .block_on(async { get_job_request(job_id).await })
+        {
+            Ok(request) => self.submit_form.show_clone(job_id, request),
+            Err(e) => self.set_status_message(format!("Cannot clone job {}: {}", job_id, e), 5),
+        }
+    }
+
+    /// Run the `sbatch` command from the submission form and select the new job
+    fn submit_batch_job(&mut self, command: SlurmCommand) {
+        let args = command.args.clone();
+        self.submit_template = self.submit_form.template().map(str::to_string);
+        let Some(result) = self.run_mutation_output("Submit job", vec![command]) else {
+            self.submit_form.hide();
+            return;
+        };
+        match result {
+            Ok(output) => {
+                self.submit_form.hide();
+                self.show_submitted(&args, &output, "");
+            }
+            Err(e) if self.retries.enabled() && is_transient_failure(&e.to_string()) => {
+                self.submit_form.hide();
+                let error = e.to_string().trim().to_string();
+                let delay = self.retries.push(args, error.clone());
+                let message = format!(
+                    "Submission failed ({}); retrying in {}s, b lists pending submissions",
+                    error,
+                    delay.as_secs()
+                );
+                self.set_status_message(message, 8);
+            }
+            // Keep the form open so the values can be corrected
+            Err(e) => self.set_status_message(format!("Failed to submit job: {}", e), 5),
+        }
+    }
+
+    /// Ask for the command to run with the chosen profile, offering the last one again
+    fn show_wrap_prompt(&mut self) {
+        let profile = self.wrap_profile.as_deref().unwrap_or(tr(DEFAULT_PROFILE));
+        self.prompt.show(
+            PromptKind::Wrap,
+            &trf("Run a command ({profile})", &[("profile", profile)]),
+            tr("Submitted with sbatch --wrap; output goes to slurm-<job>.out"),
+            &self.wrap_command.clone(),
+        );
+    }
+
+    /// Submit a command as a throwaway job with `sbatch --wrap`, using the
+    /// resources of the chosen profile
+    fn submit_wrap(&mut self, command: String) {
+        let template = self
+            .wrap_profile
+            .as_ref()
+            .and_then(|name| self.templates.iter().find(|t| t.name == *name));
+        let mut args = template.map(template_args).unwrap_or_default();
+        if let Some(name) = template.and_then(|t| t.job_name.as_ref()) {
+            args.push(format!("--job-name={}", name));
+        }
+        args.push(format!("--wrap={}", command));
+        self.wrap_command = command;
+        self.submit_batch_job(SlurmCommand::new("sbatch", args, Vec::new()));
+    }
+
+    /// Remember the arguments of a job submitted from the form and select the
+    /// new job; `note` follows the job ID in the status message
+    fn show_submitted(&mut self, args: &[String], output: &str, note: &str) {
+        // Failing to remember the settings only costs the quick resubmit
+        let unsaved = match self.submissions.record(&self.cluster_name, args) {
+            Ok(()) => String::new(),
+            Err(e) => format!(" (settings not saved for resubmit: {})", e),
+        };
+        let Some(job_id) = submitted_job_id(output) else {
+            let message = format!("Submitted the batch script{}{}", note, unsaved);
+            self.set_status_message(message, 3);
+            return;
+        };
+        if let Err(e) = self.refresh_jobs() {
+            let message = format!(
+                "Submitted job {}{}, but refresh failed: {}",
+                job_id, note, e
+            );
+            self.set_status_message(message, 5);
+        } else if self.jobs_list.select_job_by_id(&job_id) {
+            let follow = if self.follow_submitted {
+                self.following = Some(job_id.clone());
+                "; its log opens once it starts"
+            } else {
+                ""
+            };
+            let message = format!("Submitted job {}{}{}{}", job_id, note, follow, unsaved);
+            self.set_status_message(message, 3);
+            // Already running jobs are followed right away
+            self.follow_started_job();
+        } else {
+            let message = format!(
+                "Submitted job {}{}; it is hidden by the filter{}",
+                job_id, note, unsaved
+            );
+            self.set_status_message(message, 5);
+        }
+    }
+
+    /// Try the first pending submission that is due again. Retries wait while
+    /// dry-run mode is on, so they are not turned into previews, and while
+    /// incident mode is on, like the other automatic resubmissions.
+    fn retry_submission(&mut self) {
+        if self.dry_run || self.incident {
+            return;
+        }
+        let Some(entry) = self.retries.due().cloned() else {
+            return;
+        };
+        let command = SlurmCommand::new("sbatch", entry.args.clone(), Vec::new());
+        let Some(result) = self.run_mutation_output("Retry submission", vec![command]) else {
+            return;
+        };
+        match result {
+            Ok(output) => {
+                self.retries.remove(entry.id);
+                let note = format!(" after {} attempts", entry.attempts + 1);
+                self.show_submitted(&entry.args, &output, &note);
+            }
+            Err(e) => {
+                let error = e.to_string().trim().to_string();
+                let message = match self.retries.failed(entry.id, error.clone()) {
+                    Some(delay) => format!(
+                        "Submission attempt {} failed ({}); retrying in {}s",
+                        entry.attempts + 1,
+                        error,
+                        delay.as_secs()
+                    ),
+                    None => format!(
+                        "Gave up submitting after {} attempts: {}; b lists it",
+                        entry.attempts + 1,
+                        error
+                    ),
+                };
+                self.set_status_message(message, 8);
+            }
+        }
+    }
+
+    /// Submit the steps of a pipeline in order, each depending on the jobs of the
+    /// steps it runs after, and show the chain in the dependency graph. A failed
+    /// step stops the pipeline; the steps submitted before it stay queued.
+    fn submit_pipeline(&mut self, name: &str) {
+        let Some(pipeline) = self.pipelines.iter().find(|p| p.name == name).cloned() else {
+            return;
+        };
+        let title = format!("Submit pipeline {}", name);
+        if self.dry_run {
+            // Later steps name the jobs of earlier ones by placeholder
+            let commands: Result<Vec<SlurmCommand>> = pipeline
+                .steps
+                .iter()
+                .map(|step| {
+                    let after: Vec<String> =
+                        step.after.iter().map(|a| format!("<{}>", a)).collect();
+                    step_command(step, &self.templates, &after).map(|planned| planned.command)
+                })
+                .collect();
+            match commands {
+                Ok(commands) => {
+                    self.run_mutation_output(&title, commands);
+                }
+                Err(e) => {
+                    let message = format!("Cannot submit pipeline {}: {}", name, e);
+                    self.set_status_message(message, 5);
+                }
+            }
+            return;
+        }
+
+        let mut job_ids: HashMap<String, String> = HashMap::new();
+        let mut submitted: Vec<String> = Vec::new();
+        for step in &pipeline.steps {
+            let after: Vec<String> = step
+                .after
+                .iter()
+                .filter_map(|a| job_ids.get(a).cloned())
+                .collect();
+            let result = step_command(step, &self.templates, &after).and_then(|planned| {
+                if let Some((file, params)) = &planned.params {
+                    write_params(file, params)
+                        .map_err(|e| eyre!("Failed to write {}: {}", file, e))?;
+                }
+                Ok(planned.command)
+            });
+            let result = match result {
+                Ok(command) => {
+                    self.submit_template = Some(step.template.clone());
+                    self.run_mutation_output(&title, vec![command])
+                }
+                .unwrap_or_else(|| Ok(String::new())),
+                Err(e) => Err(e),
+            };
+            let job_id = result.and_then(|output| {
+                submitted_job_id(&output).ok_or_else(|| eyre!("no job ID in the sbatch output"))
+            });
+            match job_id {
+                Ok(job_id) => {
+                    submitted.push(format!("{} {}", step.name, job_id));
+                    job_ids.insert(step.name.clone(), job_id);
+                }
+                Err(e) => {
+                    let done = if submitted.is_empty() {
+                        String::new()
+                    } else {
+                        format!("; already queued: {}", submitted.join(", "))
+                    };
+                    let message = format!(
+                        "Pipeline {} stopped at step {}: {}{}",
+                        name, step.name, e, done
+                    );
+                    self.set_status_message(message, 8);
+                    let _ = self.refresh_jobs();
+                    return;
+                }
+            }
+        }
+
+        let message = format!("Submitted pipeline {}: {}", name, submitted.join(", "));
+        if let Err(e) = self.refresh_jobs() {
+            self.set_status_message(format!("{}, but refresh failed: {}", message, e), 5);
+            return;
+        }
+        self.set_status_message(message, 5);
+        // The last step's graph shows the whole chain it waits on
+        let last = pipeline
+            .steps
+            .last()
+            .and_then(|step| job_ids.get(&step.name));
+        if last.is_some_and(|job_id| self.jobs_list.select_job_by_id(job_id)) {
+            self.open_dependency_graph();
+        }
+    }
+
+    /// Submit the rows of a manifest, one job each or as the tasks of one array,
+    /// and report the job of every row in the bulk submission panel
+    fn submit_manifest(&mut self, request: &ManifestRequest) {
+        let Some(template) = self
+            .templates
+            .iter()
+            .find(|t| t.name == request.template)
+            .cloned()
+        else {
+            return;
+        };
+        let manifest = match Manifest::read(&request.path) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                self.set_status_message(format!("Cannot submit {}: {}", request.path, e), 5);
+                return;
+            }
+        };
+        let title = format!("Submit {}", request.path);
+        let partitions = self.available_partitions.clone();
+        let planned = if request.array {
+            vec![manifest.plan_array(&template, &partitions)]
+        } else {
+            manifest.plan_jobs(&template, &partitions)
+        };
+        if self.dry_run {
+            let commands = planned
+                .into_iter()
+                .filter_map(|planned| planned.ok().map(|p| p.command))
+                .collect();
+            self.run_mutation_output(&title, commands);
+            return;
+        }
+
+        let mut results: Vec<Result<String, String>> = Vec::new();
+        for planned in planned {
+            let result = planned.and_then(|planned| {
+                if let Some((file, params)) = &planned.params {
+                    write_params(file, params)
+                        .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+                }
+                let output = self
+                    .run_mutation_output(&title, vec![planned.command])
+                    .unwrap_or_else(|| Ok(String::new()))
+                    .map_err(|e| e.to_string().trim().to_string())?;
+                submitted_job_id(&output).ok_or_else(|| "no job ID in the sbatch output".into())
+            });
+            results.push(result);
+        }
+        if request.array {
+            // Row `i` is task `i` of the array
+            let result = results
+                .pop()
+                .unwrap_or(Err("nothing submitted".to_string()));
+            results = (0..manifest.rows.len())
+                .map(|i| result.clone().map(|job_id| format!("{}_{}", job_id, i)))
+                .collect();
+        }
+
+        let submitted = results.iter().filter(|r| r.is_ok()).count();
+        let message = format!(
+            "Submitted {} of {} rows of {}",
+            submitted,
+            results.len(),
+            request.path
+        );
+        self.manifest_panel.set_results(results);
+        if let Err(e) = self.refresh_jobs() {
+            self.set_status_message(format!("{}, but refresh failed: {}", message, e), 5);
+            return;
+        }
+        self.set_status_message(message, 5);
+    }
+
+    /// Show the submissions on this cluster with the accounting state of their jobs
+    fn open_submission_history(&mut self) {
+        let entries = self.submissions.history(&self.cluster_name);
+        let job_ids: Vec<String> = entries.iter().filter_map(|e| e.job_id.clone()).collect();
+        let states = if job_ids.is_empty() {
+            HashMap::new()
+        } else {
+            // Without accounting the jobs are just shown as submitted
+            self.runtime
+                .block_on(async { get_job_states(&job_ids).await })
+                .unwrap_or_default()
+        };
+        self.submissions_panel.show(entries, states);
+    }
+
+    /// Ask to submit the last job from the submission form again, unchanged
+    fn submit_last_again(&mut self) {
+        let Some(args) = self.submissions.last(&self.cluster_name) else {
+            let message = "Nothing submitted from the form yet; press J to submit a job";
+            self.set_status_message(message.to_string(), 3);
+            return;
+        };
+        let deferred = Deferred::Submit(SlurmCommand::new("sbatch", args.to_vec(), Vec::new()));
+        let question = deferred.question();
+        self.ask_confirmation(deferred, &question, false);
+    }
+
+    /// Open the dependency editor for the pending job under the cursor
+    fn open_dependency_editor(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        if job.state != JobState::Pending {
+            let message = format!(
+                "Only pending jobs can change dependencies; job {} is {}",
+                job.id, job.state
+            );
+            self.set_status_message(message, 3);
+            return;
+        }
+        let job_id = job.id.clone();
+        match self.runtime.block_on(async { get_job_info(&job_id).await }) {
+            Ok(info) => {
+                let dependency = info.get("Dependency").cloned().unwrap_or_default();
+                let picked = self.jobs_list.get_selected_jobs();
+                self.dependency_editor.show(job_id, &dependency, picked);
+            }
+            Err(e) => self.set_status_message(format!("Failed to load job {}: {}", job_id, e), 3),
+        }
+    }
+
+    /// Show the dependency graph around the job under the cursor, built from
+    /// the queued jobs of its owner; jobs that already left the queue get their
+    /// state from accounting
+    fn open_dependency_graph(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        let (job_id, user) = (job.id.clone(), job.user.clone());
+        let queued = match self
+            .runtime
+            .block_on(async { get_queue_dependencies(&user).await })
+        {
+            Ok(queued) => queued,
+            Err(e) => {
+                self.set_status_message(format!("Failed to load dependencies: {}", e), 5);
+                return;
+            }
+        };
+        let mut jobs: HashMap<String, GraphJob> = queued
+            .into_iter()
+            .map(|q| (q.id, GraphJob::queued(&q.name, &q.state, &q.dependency)))
+            .collect();
+        let mut missing = missing_targets(&jobs);
+        if !jobs.contains_key(&job_id) {
+            missing.push(job_id.clone());
+        }
+        for target in missing {
+            let base = target.trim_end_matches("_*").to_string();
+            let state = self
+                .runtime
+                .block_on(async { get_job_state(&base).await })
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| "UNKNOWN".to_string());
+            jobs.insert(target, GraphJob::finished(&state));
+        }
+        self.dependency_graph.show(&job_id, &jobs);
+    }
+
+    /// Apply the spec from the dependency editor with `scontrol update`
+    fn save_dependency(&mut self, spec: String) {
+        let Some(job_id) = self.dependency_editor.job_id.clone() else {
+            return;
+        };
+        self.dependency_editor.hide();
+        let command = modify_job_command(&job_id, &[("Dependency".to_string(), spec.clone())]);
+        let title = format!("update dependencies of job {}", job_id);
+        let Some(result) = self.run_mutation(&title, vec![command]) else {
+            return;
+        };
+
+        match result {
+            Ok(()) => {
+                let summary = if spec.is_empty() {
+                    format!("Removed all dependencies of job {}", job_id)
+                } else {
+                    format!("Job {} now depends on {}", job_id, spec)
+                };
+                if let Err(e) = self.refresh_jobs() {
+                    self.set_status_message(format!("Failed to refresh after update: {}", e), 3);
+                } else {
+                    self.set_status_message(summary, 5);
+                }
+            }
+            Err(e) => self.set_status_message(
+                format!("Failed to update dependencies of job {}: {}", job_id, e),
+                5,
+            ),
+        }
+    }
+
+    /// Set the name of the job under the cursor
+    fn rename_job(&mut self, name: String) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        let job_id = job.id.clone();
+        let command = modify_job_command(&job_id, &[("JobName".to_string(), name.clone())]);
+        let Some(result) = self.run_mutation(&format!("rename job {}", job_id), vec![command])
+        else {
+            return;
+        };
+
+        match result {
+            Ok(()) => {
+                if let Err(e) = self.refresh_jobs() {
+                    self.set_status_message(format!("Failed to refresh after rename: {}", e), 3);
+                } else {
+                    self.set_status_message(format!("Renamed job {} to {}", job_id, name), 3);
+                }
+            }
+            Err(e) => {
+                self.set_status_message(format!("Failed to rename job {}: {}", job_id, e), 5);
+            }
+        }
+    }
+
+    /// Offer a longer time limit for the running job under the cursor, prefilled
+    /// with its current limit plus the configured increment
+    fn open_extend_prompt(&mut self) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        if job.state != JobState::Running {
+            let message = "Only running jobs can have their time limit extended";
+            self.set_status_message(message.to_string(), 3);
+            return;
+        }
+        let job_id = job.id.clone();
+        let info = match self.runtime.block_on(async { get_job_info(&job_id).await }) {
+            Ok(info) => info,
+            Err(e) => {
+                self.set_status_message(format!("Failed to query job {}: {}", job_id, e), 5);
+                return;
+            }
+        };
+        let field = |name: &str| info.get(name).and_then(|value| parse_time_limit(value));
+        let (Some(limit), Some(run_time)) = (field("TimeLimit"), field("RunTime")) else {
+            let message = format!("Job {} has no readable time limit", job_id);
+            self.set_status_message(message, 3);
+            return;
+        };
+        if limit == u64::MAX {
+            let message = format!("Job {} has no time limit", job_id);
+            self.set_status_message(message, 3);
+            return;
+        }
+
+        let hint = trf(
+            "Running {run} of {limit} ({left} left). New limit (scontrol update TimeLimit=...):",
+            &[
+                ("run", &format_duration(run_time)),
+                ("limit", &format_duration(limit)),
+                ("left", &format_duration(limit.saturating_sub(run_time))),
+            ],
+        );
+        self.prompt.show(
+            PromptKind::TimeLimit,
+            &trf("Extend time limit of job {id}", &[("id", &job_id)]),
+            &hint,
+            &format_duration(limit + self.extend_by),
+        );
+    }
+
+    /// Set a new time limit on the job under the cursor and report whether the
+    /// scheduler took it: users may only lower their limits on many clusters
+    fn extend_time_limit(&mut self, limit: &str) {
+        let Some(job) = self.jobs_list.selected_job() else {
+            return;
+        };
+        let job_id = job.id.clone();
+        let command = modify_job_command(&job_id, &[("TimeLimit".to_string(), limit.to_string())]);
+        let title = format!("set time limit of job {}", job_id);
+        let Some(result) = self.run_mutation(&title, vec![command]) else {
+            return;
+        };
+        if let Err(e) = result {
+            let message = format!("Time limit of job {} not changed: {}", job_id, e);
+            self.set_status_message(message, 5);
+            return;
+        }
+
+        // Read the limit back, since scontrol can succeed without applying it
+        let info = self.runtime.block_on(async { get_job_info(&job_id).await });
+        let current = info
+            .ok()
+            .and_then(|info| info.get("TimeLimit").cloned())
+            .unwrap_or_default();
+        let message = if parse_time_limit(&current) == parse_time_limit(limit) {
+            format!("Time limit of job {} extended to {}", job_id, current)
+        } else {
+            format!(
+                "The scheduler did not apply the new limit; job {} is limited to {}",
+                job_id, current
+            )
+        };
+        self.set_status_message(message, 5);
+        if let Err(e) = self.refresh_jobs() {
+            self.set_status_message(format!("Failed to refresh jobs: {}", e), 3);
+        }
+    }
+
+    /// Ask which partition to move the selected pending jobs to
+    fn open_partition_move(&mut self) {
+        let ids = self.target_job_ids();
+        self.move_jobs = self
+            .jobs_list
+            .jobs
+            .iter()
+            .filter(|job| ids.contains(&job.id))
+            .cloned()
+            .collect();
+        let pending = self
+            .move_jobs
+            .iter()
+            .filter(|job| job.state == JobState::Pending)
+            .count();
+        if pending == 0 {
+            let message = "Only pending jobs can be moved to another partition";
+            self.set_status_message(message.to_string(), 3);
+            return;
+        }
+        let title = trf(
+            "Move {count} pending job(s) to partition",
+            &[("count", &pending.to_string())],
+        );
+        self.partition_picker
+            .show(&title, self.available_partitions.clone());
+    }
+
+    /// Ask to move the jobs chosen in [`Self::open_partition_move`] to a
+    /// partition, skipping those that are not pending or already there
+    fn request_partition_move(&mut self, partition: String) {
+        let jobs = std::mem::take(&mut self.move_jobs);
+        let mut skipped = Vec::new();
+        let mut job_ids = Vec::new();
+        for job in &jobs {
+            if job.state != JobState::Pending {
+                skipped.push(format!("{} ({})", job.id, job.state));
+            } else if job.partition == partition {
+                skipped.push(format!("{} (already in {})", job.id, partition));
+            } else {
+                job_ids.push(job.id.clone());
+            }
+        }
+        if job_ids.is_empty() {
+            let message = format!("Nothing to move: {}", skipped.join(", "));
+            self.set_status_message(message, 5);
+            return;
+        }
+        let deferred = Deferred::Move {
+            partition,
+            job_ids,
+            skipped,
+        };
+        self.confirm_or_run(self.confirm_policies.partition, deferred);
+    }
+
+    /// Move jobs to a partition, one `scontrol update` per job so each job's
+    /// result is reported separately in the progress panel
+    fn move_to_partition(&mut self, partition: &str, job_ids: &[String], skipped: &[String]) {
+        let parameters = [("Partition".to_string(), partition.to_string())];
+        let commands: Vec<SlurmCommand> = job_ids
+            .iter()
+            .map(|id| modify_job_command(id, &parameters))
+            .collect();
+        let title = format!("move {} job(s) to {}", commands.len(), partition);
+        if self.dry_run {
+            self.run_mutation(&title, commands);
+            return;
+        }
+        let mut note = format!("Moving {} job(s) to {}.", commands.len(), partition);
+        if !skipped.is_empty() {
+            note = format!("{} Skipped: {}", note, skipped.join(", "));
+        }
+        self.run_in_background("move", &title, &note, commands);
+    }
+
+    /// Set the nice value of the selected jobs (or the job under the cursor)
+    fn set_nice(&mut self, job_ids: &[String], nice: i32) {
+        let count = job_ids.len();
+        let commands = job_ids
+            .iter()
+            .map(|id| modify_job_command(id, &[("Nice".to_string(), nice.to_string())]))
+            .collect();
+        let Some(result) = self.run_mutation(&format!("set Nice={}", nice), commands) else {
+            return;
+        };
+
+        match result {
+            Ok(()) => {
+                if let Err(e) = self.refresh_jobs() {
+                    self.set_status_message(format!("Failed to refresh after nice: {}", e), 3);
+                } else {
+                    let message = format!("Set nice value {} on {} job(s)", nice, count);
+                    self.set_status_message(message, 3);
+                }
+            }
+            Err(e) => self.set_status_message(format!("Failed to set nice value: {}", e), 5),
+        }
+    }
+
+    /// Switch to the history view, running the query the first time
+    fn show_history(&mut self) {
+        self.view = View::History;
+        if !self.history.loaded {
+            self.run_history_query();
+        }
+    }
+
+    /// Switch to the partitions view, fetching them anew each time
+    fn show_partitions(&mut self) {
+        self.view = View::Partitions;
+        self.load_partitions();
+    }
+
+    fn load_partitions(&mut self) {
+        let result = self
+            .runtime
+            .block_on(async { get_partition_overview().await });
+        self.partitions_view.set_partitions(result);
+    }
+
+    /// Switch to the GPU view, fetching the inventory anew each time
+    fn show_gpus(&mut self) {
+        self.view = View::Gpus;
+        self.load_gpus();
+    }
+
+    fn load_gpus(&mut self) {
+        let result = self.runtime.block_on(async { get_gpu_inventory().await });
+        self.gpus_view.set_pools(result);
+    }
+
+    /// Switch to the reservations view, fetching them anew each time
+    fn show_reservations(&mut self) {
+        self.view = View::Reservations;
+        self.load_reservations();
+    }
+
+    fn load_reservations(&mut self) {
+        let result = self.runtime.block_on(async { get_reservations().await });
+        self.reservations_view.set_reservations(result);
+    }
+
+    /// Switch to the QoS view, fetching them anew each time
+    fn show_qos(&mut self) {
+        self.view = View::Qos;
+        self.load_qos();
+    }
+
+    fn load_qos(&mut self) {
+        let result = self.runtime.block_on(async { get_qos_overview().await });
+        self.qos_view.set_qos(result);
+    }
+
+    /// Switch to the fair-share view, fetching the tree anew each time
+    fn show_fairshare(&mut self) {
+        self.view = View::Fairshare;
+        self.load_fairshare();
+    }
+
+    fn load_fairshare(&mut self) {
+        let result = self.runtime.block_on(async { get_fairshare_tree().await });
+        self.fairshare_view.set_tree(result);
+    }
+
+    /// Switch to the diagnostics view; it reruns sdiag on every jobs refresh
+    /// interval while shown
+    fn show_diagnostics(&mut self) {
+        self.view = View::Diagnostics;
+        self.load_diagnostics();
+    }
+
+    fn load_diagnostics(&mut self) {
+        let result = self.runtime.block_on(async { get_diagnostics().await });
+        self.diagnostics_view.set_diagnostics(result);
+    }
+
+    /// Switch to the dashboard, counting jobs and nodes anew each time
+    fn show_dashboard(&mut self) {
+        self.view = View::Dashboard;
+        self.load_dashboard();
+    }
+
+    fn load_dashboard(&mut self) {
+        let result = self.runtime.block_on(async { get_cluster_summary().await });
+        self.dashboard_view.set_summary(result);
+    }
+
+    /// Switch to the per-user leaderboard, fetching the queue anew each time
+    fn show_leaderboard(&mut self) {
+        self.view = View::Users;
+        self.load_leaderboard();
+    }
+
+    fn load_leaderboard(&mut self) {
+        let result = self.runtime.block_on(async { get_queued_jobs().await });
+        self.leaderboard.set_jobs(result);
+    }
+
+    /// Switch to the node grid, fetching the nodes anew each time
+    fn show_node_grid(&mut self) {
+        self.view = View::Nodes;
+        self.load_node_grid();
+    }
+
+    fn load_node_grid(&mut self) {
+        let result = self.runtime.block_on(async { get_node_grid().await });
+        self.node_grid.set_nodes(result);
+    }
+
+    /// Switch to the burst buffer view, fetching the status anew each time
+    fn show_burst_buffers(&mut self) {
+        self.view = View::BurstBuffers;
+        self.load_burst_buffers();
+    }
+
+    fn load_burst_buffers(&mut self) {
+        let result = self.runtime.block_on(async { get_burst_buffers().await });
+        self.burst_buffers.set_buffers(result);
+    }
+
+    /// Run the history view's sacct query
+    fn run_history_query(&mut self) {
+        let query = self.history.query.clone();
+        let result = self.runtime.block_on(async { run_sacct(&query).await });
+        self.history.set_results(result);
+    }
+
+    /// Write the history bookmarks to disk
+    fn save_bookmarks(&mut self) {
+        match bookmarks::save(&self.history.bookmarks) {
+            Ok(()) => self.set_status_message("Bookmarks saved".to_string(), 3),
+            Err(e) => self.set_status_message(format!("Failed to save bookmarks: {}", e), 5),
+        }
+    }
+
+    /// Send a signal to the target jobs
+    fn signal_jobs(&mut self, job_ids: &[String], signal: &str, batch_only: bool) {
+        let count = job_ids.len();
+        let commands = signal_commands(job_ids, signal, batch_only);
+        let Some(result) = self.run_mutation(&format!("send SIG{}", signal), commands) else {
+            return;
+        };
+
+        match result {
+            Ok(()) => self.set_status_message(format!("Sent SIG{} to {} job(s)", signal, count), 3),
+            Err(e) => self.set_status_message(format!("Failed to send SIG{}: {}", signal, e), 5),
+        }
+    }
+}
+
+/// Uppercase the first letter of a word
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
     }
 }
+
+/// Sort key ordering job IDs like "9" < "10" < "10_2" < "10_10"
+fn job_id_sort_key(id: &str) -> (u64, u64, String) {
+    let (base, rest) = id.split_once('_').unwrap_or((id, ""));
+    (
+        base.parse().unwrap_or(u64::MAX),
+        rest.parse().unwrap_or(0),
+        id.to_string(),
+    )
+}
@@ -0,0 +1,50 @@
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::slurm::sacct::HistoryQuery;
+
+/// A named, saved history query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    #[serde(flatten)]
+    pub query: HistoryQuery,
+}
+
+/// On-disk format of the bookmarks file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+}
+
+/// Location of the bookmarks file, `~/.config/slurmer/bookmarks.toml`
+fn path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("slurmer").join("bookmarks.toml"))
+}
+
+/// Load saved history queries; a missing file means no bookmarks
+pub fn load() -> Result<Vec<Bookmark>> {
+    let Some(path) = path().filter(|p| p.exists()) else {
+        return Ok(Vec::new());
+    };
+    let text =
+        fs::read_to_string(&path).map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+    let file: BookmarksFile =
+        toml::from_str(&text).map_err(|e| eyre!("Invalid {}: {}", path.display(), e))?;
+    Ok(file.bookmarks)
+}
+
+/// Write all bookmarks back to disk
+pub fn save(bookmarks: &[Bookmark]) -> Result<()> {
+    let path = path().ok_or_else(|| eyre!("No configuration directory found"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = BookmarksFile {
+        bookmarks: bookmarks.to_vec(),
+    };
+    fs::write(&path, toml::to_string_pretty(&file)?)
+        .map_err(|e| eyre!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,64 @@
+use color_eyre::{eyre::eyre, Result};
+
+use crate::utils::deeplink::JobLink;
+
+const USAGE: &str = "\
+Usage: slurmer [OPTIONS] [LINK]
+
+Arguments:
+  [LINK]            Open a shared job link (slurmer://<cluster>/job/<id>) or job ID
+
+Options:
+  -j, --job <ID>    Jump to the given job ID on start
+      --read-only   Disable all actions that change jobs (cancel, hold, update, submit, shells)
+  -h, --help        Print help
+  -V, --version     Print version";
+
+/// Command-line options
+#[derive(Debug, Default)]
+pub struct CliArgs {
+    /// Job to select once the queue has been loaded
+    pub open_job: Option<JobLink>,
+    /// Refuse every action that changes jobs or runs commands on their behalf
+    pub read_only: bool,
+}
+
+impl CliArgs {
+    /// Parse the process arguments.
+    ///
+    /// Returns `Ok(None)` when the process should exit right away (help/version).
+    pub fn parse() -> Result<Option<Self>> {
+        let mut args = CliArgs::default();
+        let mut iter = std::env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    return Ok(None);
+                }
+                "-V" | "--version" => {
+                    println!("slurmer {}", env!("CARGO_PKG_VERSION"));
+                    return Ok(None);
+                }
+                "--read-only" => args.read_only = true,
+                "-j" | "--job" => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| eyre!("{} requires a job ID\n\n{}", arg, USAGE))?;
+                    args.open_job = Some(parse_link(&value)?);
+                }
+                _ if arg.starts_with('-') => {
+                    return Err(eyre!("Unknown option: {}\n\n{}", arg, USAGE));
+                }
+                _ => args.open_job = Some(parse_link(&arg)?),
+            }
+        }
+
+        Ok(Some(args))
+    }
+}
+
+fn parse_link(value: &str) -> Result<JobLink> {
+    JobLink::parse(value).ok_or_else(|| eyre!("Invalid job link or ID: {}\n\n{}", value, USAGE))
+}
@@ -0,0 +1,473 @@
+use color_eyre::{eyre::eyre, Result};
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::{
+    actions::CustomAction,
+    i18n::Language,
+    slurm::{parse_time_limit, JobAction},
+    ui::columns::JobColumn,
+};
+
+/// User configuration read from `~/.config/slurmer/config.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// UI language ("en" or "zh"); taken from the locale if unset
+    pub language: Option<Language>,
+    /// Start in dry-run mode: state-changing actions only show their commands
+    pub dry_run: bool,
+    /// Defaults applied when a partition is chosen in a form, keyed by partition name
+    pub partitions: HashMap<String, PartitionDefaults>,
+    /// Replacement for `squeue` when fetching the job list
+    pub jobs_command: Option<FetchCommand>,
+    /// Pacing of actions on other users' jobs
+    pub admin: AdminSettings,
+    /// Time the extend action adds to a job's limit, e.g. "1:00:00"; one hour if unset
+    pub extend_by: Option<String>,
+    /// Which state-changing actions ask for confirmation
+    pub confirm: ConfirmSettings,
+    /// User-defined commands offered for a job
+    pub actions: Vec<CustomAction>,
+    /// Named resource requests that prefill the submission form
+    pub templates: Vec<JobTemplate>,
+    /// Chains of templates submitted together with their dependencies wired up
+    pub pipelines: Vec<Pipeline>,
+    /// Where the submission form finds the remaining allocation of an account
+    pub balance: BalanceSettings,
+    /// Retries of submissions that failed because the controller was unreachable or busy
+    pub retry: RetrySettings,
+    /// Directory the script picker of the submission form starts in; the
+    /// current directory if unset
+    pub scripts_dir: Option<String>,
+    /// Address the submission form fills in when a mail notification is ticked
+    pub mail_user: Option<String>,
+    /// Remove ANSI color codes from job logs instead of rendering them
+    pub strip_ansi: bool,
+    /// Open the log pane on a job submitted from the form once it starts
+    pub follow_submitted: bool,
+    /// Live GPU readout in the detail pane of running GPU jobs
+    pub gpu: GpuSettings,
+    /// Highlight running jobs whose live MaxRSS reaches this percentage of
+    /// their memory request; 90 if unset, 0 turns the `sstat` check off
+    pub oom_warn_percent: Option<u32>,
+}
+
+/// How the GPU readout reaches a job's node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuAccess {
+    /// `srun --overlap` into the job's allocation
+    Srun,
+    /// `ssh` to the node; lists every GPU of the node
+    Ssh,
+}
+
+/// Live `nvidia-smi` readout for running jobs with GPU gres
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GpuSettings {
+    pub enabled: bool,
+    pub access: GpuAccess,
+    /// Seconds between two readings
+    pub interval_secs: u64,
+}
+
+impl Default for GpuSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            access: GpuAccess::Srun,
+            interval_secs: 5,
+        }
+    }
+}
+
+/// How actions that touch other users' jobs are run: reviewed first, then one
+/// command at a time so a mass hold does not overload slurmctld
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdminSettings {
+    /// Job IDs per scancel/scontrol invocation
+    pub batch_size: usize,
+    /// Pause between two invocations, in milliseconds
+    pub delay_ms: u64,
+    /// Offer drain, resume and down in the node detail; off unless set, for operators
+    pub node_actions: bool,
+}
+
+impl Default for AdminSettings {
+    fn default() -> Self {
+        Self {
+            batch_size: 10,
+            delay_ms: 1000,
+            node_actions: false,
+        }
+    }
+}
+
+/// How submissions that failed for a transient reason (a socket timeout, a busy
+/// controller) are retried: after `delay` seconds, doubling up to `max_delay`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetrySettings {
+    /// Attempts after the first before giving up; 0 turns retrying off
+    pub attempts: u32,
+    /// Seconds before the first retry
+    pub delay: u64,
+    /// Longest wait between two attempts, in seconds
+    pub max_delay: u64,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            attempts: 6,
+            delay: 15,
+            max_delay: 600,
+        }
+    }
+}
+
+/// Whether a state-changing action asks before it runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmPolicy {
+    /// Ask y/n every time
+    Always,
+    /// Run without asking
+    Never,
+    /// Ask y/n, and have the job count typed when more than this many jobs are affected
+    TypedAbove(usize),
+}
+
+/// Confirmation policy per action, e.g. `cancel = { typed_above = 10 }` or
+/// `signal = "never"`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfirmSettings {
+    pub cancel: ConfirmPolicy,
+    pub hold: ConfirmPolicy,
+    pub release: ConfirmPolicy,
+    pub requeue: ConfirmPolicy,
+    pub signal: ConfirmPolicy,
+    pub nice: ConfirmPolicy,
+    /// Moving pending jobs to another partition
+    pub partition: ConfirmPolicy,
+}
+
+impl Default for ConfirmSettings {
+    fn default() -> Self {
+        Self {
+            cancel: ConfirmPolicy::Always,
+            hold: ConfirmPolicy::Always,
+            release: ConfirmPolicy::Always,
+            requeue: ConfirmPolicy::Always,
+            signal: ConfirmPolicy::Never,
+            nice: ConfirmPolicy::Never,
+            partition: ConfirmPolicy::Always,
+        }
+    }
+}
+
+impl ConfirmSettings {
+    /// Policy for a cancel/hold/release/requeue action
+    pub fn for_action(&self, action: JobAction) -> ConfirmPolicy {
+        match action {
+            JobAction::Cancel => self.cancel,
+            JobAction::Hold => self.hold,
+            JobAction::Release => self.release,
+            JobAction::Requeue => self.requeue,
+        }
+    }
+}
+
+/// Settings to prefill when a job is moved to (or submitted on) a partition
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PartitionDefaults {
+    pub qos: Option<String>,
+    pub account: Option<String>,
+    /// GRES specification, e.g. "gpu:1"
+    pub gres: Option<String>,
+    /// Maximum walltime, e.g. "2-00:00:00"; a hint in the edit form and a limit in
+    /// the submission form
+    pub max_time: Option<String>,
+    /// Time limit prefilled in the submission form, e.g. "1:00:00"
+    pub time: Option<String>,
+    /// CPUs per task prefilled in the submission form
+    pub cpus: Option<u32>,
+    /// Memory per node prefilled in the submission form, e.g. "16G"
+    pub memory: Option<String>,
+}
+
+/// Account balance shown in the submission form, from `sshare` (the account's
+/// `GrpTRESMins` limit less its `GrpTRESRaw` usage) or a site command such as `sbank`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BalanceSettings {
+    /// Program and arguments printing the balance; `{account}` is replaced
+    pub command: Option<Vec<String>>,
+    /// Regex whose first group is the remaining balance in the command's output;
+    /// the first number if unset
+    pub pattern: Option<String>,
+    /// Warn when less than this is left, in the command's units
+    pub low: Option<f64>,
+    /// Warn when less than this percentage of the sshare limit is left; 10 if unset
+    pub low_percent: Option<u32>,
+}
+
+/// A named job template, configured as `[[templates]]`; unset fields are left
+/// empty in the submission form
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JobTemplate {
+    pub name: String,
+    pub partition: Option<String>,
+    /// Time limit, e.g. "12:00:00"
+    pub time: Option<String>,
+    pub cpus: Option<u32>,
+    /// Memory per node, e.g. "16G"
+    pub memory: Option<String>,
+    /// GPU count, optionally with a type, e.g. "a100:2"
+    pub gpus: Option<String>,
+    pub account: Option<String>,
+    pub job_name: Option<String>,
+    /// Path of an existing batch script to submit
+    pub script: Option<String>,
+    /// Batch script text written to a new file when the template is picked
+    pub skeleton: Option<String>,
+}
+
+/// A named chain of templates, configured as `[[pipelines]]`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Pipeline {
+    pub name: String,
+    /// Submitted in this order; a step may only run after steps listed before it
+    pub steps: Vec<PipelineStep>,
+}
+
+/// One job of a pipeline
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PipelineStep {
+    pub name: String,
+    /// Template with the script and resources; its `script` must be set
+    pub template: String,
+    /// Steps this one waits for
+    pub after: Vec<String>,
+    /// Dependency type on the `after` steps; "afterok" if unset
+    pub dependency: Option<String>,
+    /// Fan out as an array with one task per value, as in the submission form:
+    /// a list ("0.1, 0.01") or a glob ("data/*.csv")
+    pub sweep: Option<String>,
+}
+
+impl Pipeline {
+    /// Why the pipeline cannot be submitted with these templates, if it cannot
+    fn problem(&self, templates: &[JobTemplate]) -> Option<String> {
+        if self.name.trim().is_empty() {
+            return Some("every pipeline needs a name".to_string());
+        }
+        if self.steps.is_empty() {
+            return Some(format!("pipeline {:?} has no steps", self.name));
+        }
+        for (i, step) in self.steps.iter().enumerate() {
+            let earlier = &self.steps[..i];
+            let problem =
+                if step.name.trim().is_empty() {
+                    Some("every step needs a name".to_string())
+                } else if earlier.iter().any(|s| s.name == step.name) {
+                    Some(format!("step {:?} appears twice", step.name))
+                } else if !templates
+                    .iter()
+                    .any(|t| t.name == step.template && t.script.is_some())
+                {
+                    Some(format!(
+                        "step {:?} needs a template with a script, got {:?}",
+                        step.name, step.template
+                    ))
+                } else if let Some(after) = step
+                    .after
+                    .iter()
+                    .find(|after| !earlier.iter().any(|s| s.name == **after))
+                {
+                    Some(format!(
+                        "step {:?} runs after {:?}, which must be an earlier step",
+                        step.name, after
+                    ))
+                } else if step.dependency.as_deref().is_some_and(|kind| {
+                    !["afterok", "afterany", "afternotok", "after"].contains(&kind)
+                }) {
+                    Some(format!(
+                        "step {:?} has an unknown dependency type",
+                        step.name
+                    ))
+                } else {
+                    None
+                };
+            if let Some(problem) = problem {
+                return Some(format!("pipeline {:?}: {}", self.name, problem));
+            }
+        }
+        None
+    }
+}
+
+/// Command and output layout used instead of the stock Slurm tool, for sites with
+/// patched Slurm or wrapper scripts
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FetchCommand {
+    /// Program and arguments. An argument of exactly `{filters}` expands to the
+    /// squeue filter flags (`--user`, `--states`, ...) for the current filters.
+    pub command: Vec<String>,
+    /// Separator between the fields of an output line
+    #[serde(default = "default_delimiter")]
+    pub delimiter: String,
+    /// Column of each output field, in order: a column title ("ID", "State", ...)
+    /// or a squeue format code ("%i"); "-" ignores the field.
+    /// Titles are replaced by their format codes when the config is loaded.
+    pub fields: Vec<String>,
+}
+
+fn default_delimiter() -> String {
+    "|".to_string()
+}
+
+impl FetchCommand {
+    /// Check the command and turn every field into a squeue format code
+    fn normalize(&mut self) -> Result<()> {
+        if self.command.is_empty() {
+            return Err(eyre!("jobs_command.command must name a program"));
+        }
+        if self.delimiter.is_empty() {
+            return Err(eyre!("jobs_command.delimiter must not be empty"));
+        }
+        let columns = JobColumn::all();
+        for field in &mut self.fields {
+            if field == "-" {
+                continue;
+            }
+            // Columns filled in from sacct cannot be read from the command
+            let column = columns
+                .iter()
+                .filter(|c| !c.format_code().is_empty())
+                .find(|c| {
+                    c.title().eq_ignore_ascii_case(field) || c.format_code() == field.as_str()
+                });
+            match column {
+                Some(column) => *field = column.format_code().to_string(),
+                None => return Err(eyre!("Unknown field {:?} in jobs_command.fields", field)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Location of the configuration file
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("slurmer").join("config.toml"))
+    }
+
+    /// Load the configuration file, falling back to defaults if it does not exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = fs::read_to_string(&path)
+            .map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+        let mut config: Self =
+            toml::from_str(&text).map_err(|e| eyre!("Invalid config {}: {}", path.display(), e))?;
+        if config.admin.batch_size == 0 {
+            return Err(eyre!(
+                "Invalid config {}: admin.batch_size must be at least 1",
+                path.display()
+            ));
+        }
+        if config.oom_warn_percent.is_some_and(|percent| percent > 100) {
+            return Err(eyre!(
+                "Invalid config {}: oom_warn_percent must be between 0 and 100",
+                path.display()
+            ));
+        }
+        if let Some(extend_by) = &config.extend_by {
+            if parse_time_limit(extend_by).is_none_or(|secs| secs == 0 || secs == u64::MAX) {
+                return Err(eyre!(
+                    "Invalid config {}: extend_by must be a time like \"1:00:00\", got {:?}",
+                    path.display(),
+                    extend_by
+                ));
+            }
+        }
+        for (partition, defaults) in &config.partitions {
+            let invalid = [("time", &defaults.time), ("max_time", &defaults.max_time)]
+                .into_iter()
+                .find(|(_, time)| {
+                    time.as_deref()
+                        .is_some_and(|time| parse_time_limit(time).is_none())
+                });
+            if let Some((name, _)) = invalid {
+                return Err(eyre!(
+                    "Invalid config {}: partitions.{}.{} is not a valid time",
+                    path.display(),
+                    partition,
+                    name
+                ));
+            }
+        }
+        for template in &config.templates {
+            let problem = if template.name.trim().is_empty() {
+                Some("every template needs a name".to_string())
+            } else if template.script.is_some() && template.skeleton.is_some() {
+                Some(format!(
+                    "template {:?} sets both script and skeleton",
+                    template.name
+                ))
+            } else if template
+                .time
+                .as_deref()
+                .is_some_and(|time| parse_time_limit(time).is_none())
+            {
+                Some(format!("template {:?} has an invalid time", template.name))
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                return Err(eyre!("Invalid config {}: {}", path.display(), problem));
+            }
+        }
+        let balance = &config.balance;
+        let problem = if balance.command.as_ref().is_some_and(Vec::is_empty) {
+            Some("balance.command must name a program".to_string())
+        } else if let Some(Err(e)) = balance.pattern.as_deref().map(regex::Regex::new) {
+            Some(format!("balance.pattern is not a valid regex: {}", e))
+        } else if balance.low_percent.is_some_and(|percent| percent > 100) {
+            Some("balance.low_percent must be between 0 and 100".to_string())
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            return Err(eyre!("Invalid config {}: {}", path.display(), problem));
+        }
+        if let Some(problem) = config
+            .pipelines
+            .iter()
+            .find_map(|pipeline| pipeline.problem(&config.templates))
+        {
+            return Err(eyre!("Invalid config {}: {}", path.display(), problem));
+        }
+        if let Some(jobs_command) = &mut config.jobs_command {
+            jobs_command
+                .normalize()
+                .map_err(|e| eyre!("Invalid config {}: {}", path.display(), e))?;
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,614 @@
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+/// Language of the user interface, set with `language` in config.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    En,
+    Zh,
+}
+
+impl Language {
+    /// Language from `LC_ALL`/`LC_MESSAGES`/`LANG`, English unless it asks for Chinese
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        if locale.starts_with("zh") {
+            Language::Zh
+        } else {
+            Language::En
+        }
+    }
+}
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Select the UI language; only the first call has an effect
+pub fn set_language(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+/// Translate an English UI string. Strings are looked up by their English text,
+/// so anything without a translation is shown in English.
+pub fn tr(text: &str) -> &str {
+    let table = match LANGUAGE.get() {
+        Some(Language::Zh) => ZH,
+        _ => return text,
+    };
+    table
+        .iter()
+        .find(|(en, _)| *en == text)
+        .map_or(text, |(_, translated)| translated)
+}
+
+/// Translate a template and fill its `{name}` placeholders
+pub fn trf(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(tr(template).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// Simplified Chinese
+const ZH: &[(&str, &str)] = &[
+    // Header and footer
+    ("Slurm Terminal UI", "Slurm 终端界面"),
+    ("No filters applied", "未设置过滤条件"),
+    ("Filters: {filters}", "过滤条件: {filters}"),
+    (
+        "{status} | Refresh: {ago}s ago (auto: {auto})",
+        "{status} | 刷新: {ago} 秒前 (自动: {auto})",
+    ),
+    ("paused", "已暂停"),
+    ("DRY-RUN ", "演练模式 "),
+    ("READ-ONLY ", "只读 "),
+    ("REC ", "录制中 "),
+    (" INCIDENT MODE ", " 事故模式 "),
+    ("Job Stat: ", "作业统计: "),
+    ("Other", "其他"),
+    ("Quit", "退出"),
+    (
+        "Jobs/History/Partitions/GPUs/Reservations/QoS/Fairshare/Diagnostics/Dashboard/Users",
+        "作业/历史/分区/GPU/预留/QoS/公平份额/诊断/概览/用户",
+    ),
+    ("Navigate", "移动"),
+    ("Select", "选择"),
+    ("Script", "脚本"),
+    ("Filter", "过滤"),
+    ("Quick filter", "快速过滤"),
+    ("Columns", "列"),
+    ("Log", "日志"),
+    ("SelectAll", "全选"),
+    ("Refresh", "刷新"),
+    ("Cancel", "取消"),
+    ("Edit", "编辑"),
+    ("Query", "查询"),
+    ("Rerun", "重新运行"),
+    ("Bookmarks", "书签"),
+    ("Details", "详情"),
+    ("Save bookmark", "保存书签"),
+    // Column titles
+    ("ID", "作业号"),
+    ("Name", "名称"),
+    ("User", "用户"),
+    ("State", "状态"),
+    ("Partition", "分区"),
+    ("QoS", "QoS"),
+    ("Nodes", "节点数"),
+    ("Node", "节点"),
+    ("CPUs", "CPU"),
+    ("Time", "时间"),
+    ("Memory", "内存"),
+    ("Account", "账户"),
+    ("Priority", "优先级"),
+    ("WorkDir", "工作目录"),
+    ("Submit", "提交"),
+    ("Start", "开始"),
+    ("End", "结束"),
+    ("Reason", "原因"),
+    ("ExitCode", "退出码"),
+    ("Failure", "失败原因"),
+    ("Notes", "备注"),
+    ("Rank", "排名"),
+    (
+        "No columns selected. Press 'c' to configure columns.",
+        "未选择任何列。按 'c' 配置列。",
+    ),
+    ("Warning", "警告"),
+    // Job actions and confirmation
+    ("cancel", "取消"),
+    ("hold", "挂起"),
+    ("release", "释放"),
+    ("requeue", "重新排队"),
+    ("signal", "发送信号"),
+    ("renice", "修改 nice 值"),
+    ("move", "移动"),
+    ("submit", "提交"),
+    ("Confirm {action}", "确认{action}"),
+    (
+        "Are you sure you want to {action} {count} job(s)?",
+        "确定要{action} {count} 个作业吗?",
+    ),
+    (
+        "{action} the ENTIRE array job {parent} ({count} tasks) with a single `{command}`?",
+        "用一条 `{command}` {action}整个数组作业 {parent} (共 {count} 个任务)?",
+    ),
+    ("Send SIG{signal} to {count} job(s)?", "向 {count} 个作业发送 SIG{signal}?"),
+    (
+        "Send SIG{signal} to the batch shell of {count} job(s)?",
+        "向 {count} 个作业的批处理 shell 发送 SIG{signal}?",
+    ),
+    ("Set nice value {nice} on {count} job(s)?", "将 {count} 个作业的 nice 值设为 {nice}?"),
+    (
+        "Move {count} pending job(s) to partition {partition}?",
+        "将 {count} 个排队作业移动到分区 {partition}?",
+    ),
+    ("Skipped: {jobs}", "跳过: {jobs}"),
+    ("Type {count} to confirm: ", "输入 {count} 以确认: "),
+    (
+        "↑/↓: Scroll | y/Enter: Confirm | n/Esc: Abort",
+        "↑/↓: 滚动 | y/Enter: 确认 | n/Esc: 放弃",
+    ),
+    ("↑/↓: Scroll | Enter: Confirm | Esc: Abort", "↑/↓: 滚动 | Enter: 确认 | Esc: 放弃"),
+    ("No jobs selected to {action}", "没有选中要{action}的作业"),
+    // Command preview
+    (
+        "Nothing was run. These commands would have been executed:",
+        "未执行任何命令。将会执行以下命令:",
+    ),
+    ("Dry run: {title}", "演练: {title}"),
+    ("Review: {title}", "确认: {title}"),
+    ("Running: {title} ({done}/{total})", "执行中: {title} ({done}/{total})"),
+    ("Done: {title}", "完成: {title}"),
+    (
+        "{done}/{total} command(s), {failed} failed",
+        "{done}/{total} 条命令, {failed} 条失败",
+    ),
+    (
+        "↑/↓: Scroll | y/Enter: Run | n/Esc: Abort",
+        "↑/↓: 滚动 | y/Enter: 执行 | n/Esc: 放弃",
+    ),
+    (
+        "↑/↓: Scroll | x: Stop remaining | Enter/Esc/q: Hide",
+        "↑/↓: 滚动 | x: 停止剩余命令 | Enter/Esc/q: 隐藏",
+    ),
+    ("↑/↓: Scroll | Enter/Esc/q: Close", "↑/↓: 滚动 | Enter/Esc/q: 关闭"),
+    // Prompts
+    ("Send signal to {count} job(s)", "向 {count} 个作业发送信号"),
+    (
+        "Signal name or number, e.g. USR1, TERM, 10. Prefix with B: to signal only the batch shell (B:USR1).",
+        "信号名或编号, 如 USR1、TERM、10。加 B: 前缀只发送给批处理 shell (B:USR1)。",
+    ),
+    ("Rename job {id}", "重命名作业 {id}"),
+    ("Note on job {id}", "作业 {id} 的备注"),
+    (
+        "Kept locally and shown in the detail pane; empty removes it",
+        "保存在本地并显示在详情面板中; 留空则删除",
+    ),
+    ("Set nice value of {count} job(s)", "设置 {count} 个作业的 nice 值"),
+    ("Extend time limit of job {id}", "延长作业 {id} 的时间限制"),
+    (
+        "Running {run} of {limit} ({left} left). New limit (scontrol update TimeLimit=...):",
+        "已运行 {run} / {limit} (剩余 {left})。新的时间限制 (scontrol update TimeLimit=...):",
+    ),
+    (
+        "Integer; higher values lower the priority of pending jobs (0 is the default, negative values need admin rights).",
+        "整数; 值越大排队作业的优先级越低 (默认为 0, 负值需要管理员权限)。",
+    ),
+    ("Command", "命令"),
+    (
+        "cancel-filtered: cancel every job matching the current filters | node <name>: node detail",
+        "cancel-filtered: 取消所有符合当前过滤条件的作业 | node <名称>: 节点详情",
+    ),
+    (
+        "Unknown command: {command} (available: cancel-filtered, node <name>)",
+        "未知命令: {command} (可用: cancel-filtered, node <名称>)",
+    ),
+    (
+        "Cancel ALL {count} job(s) matching the filters? Filters: {filters}",
+        "取消符合过滤条件的全部 {count} 个作业? 过滤条件: {filters}",
+    ),
+    ("Type {count} to confirm", "请输入 {count} 以确认"),
+    ("No jobs match the filters", "没有符合过滤条件的作业"),
+    (
+        "New job name (scontrol update JobName=...)",
+        "新的作业名 (scontrol update JobName=...)",
+    ),
+    ("Save Query as Bookmark", "将查询保存为书签"),
+    (
+        "Name for the current history query, e.g. \"lab failures this week\"",
+        "当前历史查询的名称, 如 \"本周实验室失败作业\"",
+    ),
+    // Popups
+    ("Column Management", "列管理"),
+    ("Available Columns", "可选列"),
+    ("Selected Columns", "已选列"),
+    ("Sort Order", "排序"),
+    ("Filter Jobs", "过滤作业"),
+    ("Username", "用户名"),
+    ("Job States", "作业状态"),
+    ("Partitions", "分区"),
+    ("Quality of Service", "服务质量 (QoS)"),
+    ("History Query", "历史查询"),
+    (
+        "↑/↓/Tab: Switch field | Enter: Run | Esc: Cancel",
+        "↑/↓/Tab: 切换字段 | Enter: 运行 | Esc: 取消",
+    ),
+    ("Enter: Run | x: Delete | Esc: Close", "Enter: 运行 | x: 删除 | Esc: 关闭"),
+    (
+        "↑/↓/PgUp/PgDn: Scroll | Esc/q: Close",
+        "↑/↓/PgUp/PgDn: 滚动 | Esc/q: 关闭",
+    ),
+    (
+        "Incident mode: hold pending jobs by partition",
+        "事故模式: 按分区挂起排队作业",
+    ),
+    (
+        "Space: Choose partition | H/Enter: Hold pending jobs | o: End incident mode | Esc/q: Close",
+        "空格: 选择分区 | H/Enter: 挂起排队作业 | o: 结束事故模式 | Esc/q: 关闭",
+    ),
+    ("Actions for job {id}", "作业 {id} 的自定义操作"),
+    (
+        "Enter: Show (last result if any) | r: Run again | Esc/q: Close",
+        "Enter: 查看 (有缓存时显示上次结果) | r: 重新运行 | Esc/q: 关闭",
+    ),
+    ("Dependencies of job {id}", "作业 {id} 的依赖"),
+    ("Dependencies of the new job", "新作业的依赖"),
+    ("Jobs", "作业"),
+    ("Move {count} pending job(s) to partition", "将 {count} 个排队作业移到分区"),
+    ("Enter: Choose | Esc/q: Cancel", "Enter: 选择 | Esc/q: 取消"),
+    ("  ({shown} of {total})", "  (显示 {shown} / 共 {total})"),
+    ("Completion estimate for {count} job(s)", "{count} 个作业的完成预估"),
+    ("↑/↓: Move | Enter/Esc/q: Close", "↑/↓: 移动 | Enter/Esc/q: 关闭"),
+    ("All done by: {time}", "预计全部完成: {time}"),
+    (
+        "Remaining: {hours} CPU-hours (at most, from time limits)",
+        "剩余: {hours} CPU 小时 (按时间限制计算的上限)",
+    ),
+    (
+        "{count} job(s) have no estimate yet and are not included",
+        "{count} 个作业暂无预估, 未计入",
+    ),
+    ("Time left", "剩余时间"),
+    ("Files of job {id}: {dir}", "作业 {id} 的文件: {dir}"),
+    (
+        "↑/↓: Move | Enter/→: Open | Backspace/←: Up | PgUp/PgDn: Scroll preview | r: Reload | Esc/q: Close",
+        "↑/↓: 移动 | Enter/→: 打开 | Backspace/←: 上一级 | PgUp/PgDn: 滚动预览 | r: 刷新 | Esc/q: 关闭",
+    ),
+    ("Choose a batch script: {dir}", "选择批处理脚本: {dir}"),
+    (
+        "↑/↓: Move | Enter: Choose file/Open directory | Backspace/←: Up | PgUp/PgDn: Scroll preview | r: Reload | Esc/q: Cancel",
+        "↑/↓: 移动 | Enter: 选择文件/打开目录 | Backspace/←: 上一级 | PgUp/PgDn: 滚动预览 | r: 刷新 | Esc/q: 取消",
+    ),
+    ("Size", "大小"),
+    ("Modified", "修改时间"),
+    ("Binary file, no preview", "二进制文件, 无法预览"),
+    ("(last 64 KiB)", "(最后 64 KiB)"),
+    ("Submit Job", "提交作业"),
+    (
+        "↑/↓/Tab: Switch field | ←/→: Cycle partition or GPUs | Ctrl+t: Template | Ctrl+o: Choose script | Ctrl+e: Edit script | Ctrl+l: Load modules | Ctrl+d: Dependencies | PgUp/PgDn: Scroll script | Ctrl+r: Test (--test-only) | Enter/Ctrl+a: Submit | Esc: Close",
+        "↑/↓/Tab: 切换字段 | ←/→: 切换分区或 GPU | Ctrl+t: 模板 | Ctrl+o: 选择脚本 | Ctrl+e: 编辑脚本 | Ctrl+l: 加载模块 | Ctrl+d: 依赖 | PgUp/PgDn: 滚动脚本 | Ctrl+r: 测试 (--test-only) | Enter/Ctrl+a: 提交 | Esc: 关闭",
+    ),
+    ("Enter a script path to preview it", "输入脚本路径以预览"),
+    ("Warnings", "警告"),
+    ("Cannot submit", "无法提交"),
+    ("Pending Submissions", "待重试的提交"),
+    ("Modules for {script}", "{script} 的模块"),
+    ("n: Nodes", "n: 节点"),
+    ("Node {node}", "节点 {node}"),
+    ("Allocation", "分配"),
+    ("Loading...", "加载中..."),
+    (
+        " ↑/↓: Navigate | s: Sort by load | g: Node grid | r: Refresh ",
+        " ↑/↓: 移动 | s: 按负载排序 | g: 节点网格 | r: 刷新 ",
+    ),
+    ("least loaded first", "负载最低优先"),
+    ("Jobs holding {gpu}", "占用 {gpu} 的作业"),
+    ("GPUs", "GPU"),
+    ("Reservations", "预留"),
+    ("Maintenance calendar", "维护日历"),
+    ("usable by me", "我可用的"),
+    (
+        " ↑/↓: Navigate | m: Only mine | r: Refresh ",
+        " ↑/↓: 移动 | m: 只看我的 | r: 刷新 ",
+    ),
+    ("Only mine", "只看我的"),
+    ("Fairshare", "公平份额"),
+    (
+        " ↑/↓: Navigate | Enter/←/→: Collapse or expand | r: Refresh ",
+        " ↑/↓: 移动 | Enter/←/→: 折叠或展开 | r: 刷新 ",
+    ),
+    ("Collapse", "折叠"),
+    ("Scheduler diagnostics", "调度器诊断"),
+    (
+        " s: Sort RPCs by count/time | r: Refresh ",
+        " s: RPC 按次数/时间排序 | r: 刷新 ",
+    ),
+    ("Sort RPCs", "RPC 排序"),
+    ("General", "概况"),
+    ("Main schedule statistics (microseconds)", "主调度统计 (微秒)"),
+    ("Backfilling stats", "回填统计"),
+    ("RPCs by message type", "按消息类型的 RPC"),
+    ("RPCs by user", "按用户的 RPC"),
+    ("by count", "按次数"),
+    ("by total time", "按总时间"),
+    ("Dashboard", "概览"),
+    (" r: Refresh ", " r: 刷新 "),
+    ("running", "运行中"),
+    ("pending", "排队中"),
+    ("My jobs", "我的作业"),
+    ("Jobs by state", "各状态作业数"),
+    ("Nodes by state", "各状态节点数"),
+    ("Top pending reasons", "主要排队原因"),
+    ("None configured", "未配置"),
+    ("Sort", "排序"),
+    ("Node grid", "节点网格"),
+    ("Node detail", "节点详情"),
+    (
+        " ←/→/↑/↓: Navigate | Enter: Node detail | t: Group by switch | g: Partitions | r: Refresh ",
+        " ←/→/↑/↓: 移动 | Enter: 节点详情 | t: 按交换机分组 | g: 分区 | r: 刷新 ",
+    ),
+    ("Group by switch", "按交换机分组"),
+    ("shares a job", "有共同作业"),
+    (" b: Burst buffers | r: Refresh ", " b: 突发缓冲 | r: 刷新 "),
+    ("Burst buffers", "突发缓冲"),
+    ("Burst buffer", "突发缓冲"),
+    (
+        " ↑/↓: Navigate | Enter: Job detail | b: Dashboard | r: Refresh ",
+        " ↑/↓: 移动 | Enter: 作业详情 | b: 概览 | r: 刷新 ",
+    ),
+    ("Job detail", "作业详情"),
+    ("Pools", "存储池"),
+    ("drawn by {count} node(s)", "由 {count} 个节点消耗"),
+    ("Power", "功耗"),
+    ("Allocated buffers", "已分配的缓冲"),
+    ("Use per user", "各用户用量"),
+    ("No buffer allocated yet", "尚未分配缓冲"),
+    ("Users in {partition}", "{partition} 中的用户"),
+    ("Users in all partitions", "所有分区中的用户"),
+    (
+        " ↑/↓: Navigate | s: Sort | p: Partition | r: Refresh ",
+        " ↑/↓: 移动 | s: 排序 | p: 分区 | r: 刷新 ",
+    ),
+    (
+        "{allocated}/{total} allocated, {idle} idle, {other} unavailable",
+        "已分配 {allocated}/{total},空闲 {idle},不可用 {other}",
+    ),
+    ("Priority {priority} in {partition}", "{partition} 中的优先级 {priority}"),
+    ("Sort by load", "按负载排序"),
+    ("d: Drain | u: Resume | x: Down", "d: 排空 | u: 恢复 | x: 下线"),
+    ("Set {node} to {state}, reason: ", "将 {node} 设为 {state},原因: "),
+    (
+        "Set {node} to {state}? Enter: Confirm | Backspace: Cancel",
+        "将 {node} 设为 {state}? Enter: 确认 | Backspace: 取消",
+    ),
+    ("Set node {node} to {state}", "将节点 {node} 设为 {state}"),
+    ("Running jobs ({count})", "运行中的作业 ({count})"),
+    (
+        "←/→: Other nodes | ↑/↓/PgUp/PgDn: Scroll | r: Refresh | Esc/q: Close",
+        "←/→: 其他节点 | ↑/↓/PgUp/PgDn: 滚动 | r: 刷新 | Esc/q: 关闭",
+    ),
+    (
+        "↑/↓/PgUp/PgDn: Scroll | r: Refresh | Esc/q: Close",
+        "↑/↓/PgUp/PgDn: 滚动 | r: 刷新 | Esc/q: 关闭",
+    ),
+    ("Search modules", "搜索模块"),
+    (
+        "Enter: Search, then insert | Space: Mark | ↑/↓: Select | Esc: Close",
+        "Enter: 搜索,再次按下插入 | Space: 标记 | ↑/↓: 选择 | Esc: 关闭",
+    ),
+    ("Resource profile", "资源配置"),
+    ("Default resources", "默认资源"),
+    ("Run a command ({profile})", "运行命令({profile})"),
+    (
+        "Submitted with sbatch --wrap; output goes to slurm-<job>.out",
+        "通过 sbatch --wrap 提交;输出写入 slurm-<job>.out",
+    ),
+    (
+        "↑/↓: Select | r: Retry now | d/Del: Cancel | Esc/q: Close",
+        "↑/↓: 选择 | r: 立即重试 | d/Del: 取消 | Esc/q: 关闭",
+    ),
+    (
+        "Pending Submissions - paused during incident mode",
+        "待重试的提交 - 事故模式期间暂停",
+    ),
+    (
+        "No submissions are waiting to be retried",
+        "没有等待重试的提交",
+    ),
+    ("Submit `{command}` again?", "再次提交 `{command}`?"),
+    ("Job templates", "作业模板"),
+    ("Submission History", "提交历史"),
+    ("Interactive Allocation", "交互式分配"),
+    (
+        "↑/↓/Tab: Switch field | ←/→: Cycle partition | Enter: Allocate and open a shell | Esc: Close",
+        "↑/↓/Tab: 切换字段 | ←/→: 切换分区 | Enter: 分配并打开 shell | Esc: 关闭",
+    ),
+    (
+        "↑/↓/PgUp/PgDn: Scroll | Enter/s: Submit again | Esc/q: Close",
+        "↑/↓/PgUp/PgDn: 滚动 | Enter/s: 再次提交 | Esc/q: 关闭",
+    ),
+    (
+        "Nothing has been submitted through slurmer yet",
+        "尚未通过 slurmer 提交任何作业",
+    ),
+    ("Pipelines", "流水线"),
+    ("Submit pipeline {name}?", "提交流水线 {name}?"),
+    ("Bulk Submission", "批量提交"),
+    (
+        "↑/↓/Tab: Switch field | ←/→: Cycle template or mode | Ctrl+o: Choose manifest | PgUp/PgDn: Scroll rows | Enter: Submit | Esc: Close",
+        "↑/↓/Tab: 切换字段 | ←/→: 切换模板或方式 | Ctrl+o: 选择清单 | PgUp/PgDn: 滚动行 | Enter: 提交 | Esc: 关闭",
+    ),
+    (
+        "Submit {count} job(s) from {file} with template {template}?",
+        "使用模板 {template} 从 {file} 提交 {count} 个作业?",
+    ),
+    (
+        "Submit {file} as a job array of {count} tasks from template {template}?",
+        "使用模板 {template} 将 {file} 提交为包含 {count} 个任务的作业数组?",
+    ),
+    ("Timeline of the last {window}, by {grouping}", "最近 {window} 的时间线,按{grouping}分组"),
+    (
+        "g: Group by node/array | +/-: Zoom | ↑/↓/PgUp/PgDn: Scroll | Enter/Esc/q: Close",
+        "g: 按节点/数组分组 | +/-: 缩放 | ↑/↓/PgUp/PgDn: 滚动 | Enter/Esc/q: 关闭",
+    ),
+    ("node", "节点"),
+    ("array", "数组"),
+    ("Not started", "未开始"),
+    ("Array {id}", "数组 {id}"),
+    ("Single jobs", "单个作业"),
+    ("waiting", "等待"),
+    ("running (colored by state)", "运行(按状态着色)"),
+    ("No jobs in this time window", "此时间范围内没有作业"),
+    ("Dependencies around job {id}", "作业 {id} 的依赖关系"),
+    (
+        "↑/↓/PgUp/PgDn: Scroll | Enter/Esc/q: Close",
+        "↑/↓/PgUp/PgDn: 滚动 | Enter/Esc/q: 关闭",
+    ),
+    ("Waits on", "等待"),
+    ("Needed by", "被依赖于"),
+    ("No dependencies", "无依赖"),
+    ("can never be satisfied", "永远无法满足"),
+    ("unknown", "未知"),
+    ("Efficiency of {count} job(s)", "{count} 个作业的资源效率"),
+    ("CPU efficiency overall: ", "总体 CPU 效率: "),
+    (
+        "{count} job(s) used less than half of their requested memory",
+        "{count} 个作业使用的内存不到申请量的一半",
+    ),
+    (
+        "Figures of running jobs are only updated when their steps finish",
+        "运行中作业的数据仅在其作业步结束时更新",
+    ),
+    ("CPU eff.", "CPU 效率"),
+    ("Memory used / requested", "内存 已用 / 申请"),
+    ("Mem eff.", "内存效率"),
+    ("Elapsed", "耗时"),
+    ("Job {id} ({source})", "作业 {id} ({source})"),
+    ("↑/↓/PgUp/PgDn: Scroll | /: Search", "↑/↓/PgUp/PgDn: 滚动 | /: 搜索"),
+    ("Tab/Esc/q: Close", "Tab/Esc/q: 关闭"),
+    ("r: Raw", "r: 原始输出"),
+    ("r: Parsed", "r: 解析视图"),
+    ("y/w: Copy/Save to file", "y/w: 复制/保存到文件"),
+    ("No fields match the search", "没有匹配搜索的字段"),
+    ("Job", "作业"),
+    ("Resources", "资源"),
+    ("Times", "时间"),
+    ("Limits", "限制"),
+    ("Paths", "路径"),
+    ("environment", "环境变量"),
+    ("GPUs on {node}", "{node} 上的 GPU"),
+    ("Nodes ({count})", "节点 ({count})"),
+    ("Requested resources", "请求的资源"),
+    ("Note", "备注"),
+    ("(allocated {amount})", "(已分配 {amount})"),
+    ("Licenses", "许可证"),
+    ("Waiting for nvidia-smi...", "等待 nvidia-smi..."),
+    ("l: Live resources", "l: 实时资源"),
+    ("l: Fields | Tab/Esc/q: Close", "l: 字段 | Tab/Esc/q: 关闭"),
+    ("Waiting for sstat...", "等待 sstat..."),
+    ("Attempt {n} of job originally {id}", "第 {n} 次尝试,原始作业 {id}"),
+    ("[/]: Previous/next attempt", "[/]: 上一次/下一次尝试"),
+    ("Submitted from template {name}", "由模板 {name} 提交"),
+    ("The script matches the template", "脚本与模板一致"),
+    ("Template {name} is no longer configured", "模板 {name} 已不在配置中"),
+    ("The template has no script", "模板没有脚本"),
+    ("Pending: {reason}", "排队原因: {reason}"),
+    ("Other jobs with a higher priority are ahead in the queue.", "队列中有优先级更高的作业排在前面。"),
+    ("Wait; a shorter time limit or fewer resources can let the job backfill sooner.", "请等待;缩短时间限制或减少资源可以让作业更早回填调度。"),
+    ("The job is next in line but the resources it asks for are not free yet.", "作业已排在最前,但所需资源尚未空闲。"),
+    ("Wait, or request fewer nodes, CPUs or memory to fit into what frees up first.", "请等待,或减少节点、CPU 或内存请求以适应最先空出的资源。"),
+    ("The job waits for the jobs it depends on.", "作业正在等待其依赖的作业。"),
+    ("Press G to see which dependencies are still unfulfilled.", "按 G 查看哪些依赖尚未满足。"),
+    ("A job this job depends on ended in a way that can never satisfy the dependency.", "所依赖的某个作业的结束方式使该依赖永远无法满足。"),
+    ("Cancel the job, or remove the failed dependency with o so it can start.", "取消该作业,或按 o 移除失败的依赖以便其启动。"),
+    ("The job was held by its owner.", "作业被其所有者挂起。"),
+    ("Release it with u.", "按 u 释放。"),
+    ("The job was held by an administrator.", "作业被管理员挂起。"),
+    ("Ask the cluster administrators why it was held.", "请询问集群管理员挂起原因。"),
+    ("The job was submitted with a start time that has not come yet.", "作业提交时指定的开始时间尚未到达。"),
+    ("Wait, or start it sooner with scontrol update StartTime=now.", "请等待,或用 scontrol update StartTime=now 提前开始。"),
+    ("Some nodes the job needs are down, drained or reserved.", "作业所需的部分节点已宕机、排空或被预留。"),
+    ("Wait for the nodes to return, or drop --nodelist/--exclude constraints that pin them.", "等待节点恢复,或去掉指定这些节点的 --nodelist/--exclude 约束。"),
+    ("A node the job needs is down.", "作业所需的节点已宕机。"),
+    ("Wait for the node to return, or move the job to another partition with P.", "等待节点恢复,或按 P 将作业移到其他分区。"),
+    ("The partition is down.", "分区已停用。"),
+    ("Move the job to another partition with P, or wait for it to come back.", "按 P 将作业移到其他分区,或等待分区恢复。"),
+    ("The partition is not scheduling jobs.", "该分区当前不调度作业。"),
+    ("Move the job to another partition with P.", "按 P 将作业移到其他分区。"),
+    ("The time limit exceeds the maximum of the partition; the job will never start there.", "时间限制超过分区上限,作业在该分区永远不会启动。"),
+    ("Lower the time limit with e, or move the job with P to a partition with longer limits.", "按 e 降低时间限制,或按 P 移到时间上限更长的分区。"),
+    ("The node count is outside the limits of the partition; the job will never start there.", "节点数超出分区限制,作业在该分区永远不会启动。"),
+    ("Change the node count with e, or move the job to another partition with P.", "按 e 修改节点数,或按 P 移到其他分区。"),
+    ("The request does not fit the configuration of the partition.", "资源请求不符合分区配置。"),
+    ("Adjust the request with e, or move the job to another partition with P.", "按 e 调整请求,或按 P 移到其他分区。"),
+    ("The job waits for its advanced reservation to become available.", "作业正在等待其预留变为可用。"),
+    ("Wait for the reservation to start.", "请等待预留开始。"),
+    ("The licenses the job requests are in use.", "作业请求的许可证正在被占用。"),
+    ("Wait for other jobs to release them.", "请等待其他作业释放许可证。"),
+    ("No node satisfies the constraints of the job; it will never start.", "没有节点满足作业约束,作业永远不会启动。"),
+    ("Cancel and resubmit with different --constraint, GRES or memory requests.", "取消并以不同的 --constraint、GRES 或内存请求重新提交。"),
+    ("The account of the job is invalid.", "作业的账户无效。"),
+    ("Cancel and resubmit with a valid --account.", "取消并使用有效的 --account 重新提交。"),
+    ("The QOS of the job is invalid.", "作业的 QOS 无效。"),
+    ("Change the QOS with e.", "按 e 修改 QOS。"),
+    ("The array has reached its limit of simultaneously running tasks (%N in --array).", "作业数组已达到同时运行任务数上限(--array 中的 %N)。"),
+    ("Wait for running tasks to finish, or raise the limit with scontrol update ArrayTaskThrottle.", "等待运行中的任务结束,或用 scontrol update ArrayTaskThrottle 提高上限。"),
+    ("The job failed to launch, was requeued and is held.", "作业启动失败,已重新排队并被挂起。"),
+    ("Check the node it ran on, then release the job with u.", "检查其运行的节点,然后按 u 释放作业。"),
+    ("The job is being requeued and still cleaning up from its previous run.", "作业正在重新排队,仍在清理上次运行。"),
+    ("Wait a moment.", "请稍候。"),
+    ("Your running jobs already use the most this QOS allows per user.", "您正在运行的作业已用满该 QOS 的每用户上限。"),
+    ("Wait for your other jobs to finish, or move the job to a QOS with a higher limit.", "等待您的其他作业结束,或将作业移到上限更高的 QOS。"),
+    ("The job asks for more than this QOS allows for a single job; it will never start.", "作业请求超过该 QOS 的单作业上限,永远不会启动。"),
+    ("Reduce the request with e, or move the job to a QOS with a higher limit.", "按 e 减少请求,或移到上限更高的 QOS。"),
+    ("All jobs of this QOS together have reached a group limit.", "该 QOS 下所有作业合计已达到组上限。"),
+    ("Wait for jobs in the same QOS to finish, or use another QOS.", "等待同一 QOS 的作业结束,或使用其他 QOS。"),
+    ("Your account (association) has reached one of its limits.", "您的账户(关联)已达到某项上限。"),
+    ("Wait for jobs of the account to finish, or submit under another --account.", "等待该账户的作业结束,或使用其他 --account 提交。"),
+    ("A per-account or QOS limit is reached.", "已达到账户或 QOS 的某项上限。"),
+    ("Wait for other jobs to finish, or reduce the request with e.", "等待其他作业结束,或按 e 减少请求。"),
+    ("{count} sample(s)", "{count} 个采样"),
+    ("CPU-hours", "CPU 小时"),
+    ("Expected end", "预计结束"),
+    (" Watched jobs changed ", " 关注的作业有变化 "),
+    ("↑/↓: Scroll | Enter/Esc/q: Dismiss", "↑/↓: 滚动 | Enter/Esc/q: 知道了"),
+    ("Job {id} ({name}): {from} → {to}", "作业 {id} ({name}): {from} → {to}"),
+    (
+        "Job {id} ({name}) left the queue: {state}",
+        "作业 {id} ({name}) 已离开队列: {state}",
+    ),
+    ("last seen {state}", "最后状态 {state}"),
+    // Status messages
+    ("Incident mode on, auto-refresh paused", "事故模式已开启, 自动刷新已暂停"),
+    ("Incident mode ended, auto-refresh resumed", "事故模式已结束, 自动刷新已恢复"),
+    ("Dry-run mode on", "演练模式已开启"),
+    ("Dry-run mode off", "演练模式已关闭"),
+    (
+        "Read-only mode: actions that change jobs are disabled",
+        "只读模式: 已禁用会更改作业的操作",
+    ),
+    ("read-only mode", "只读模式"),
+    ("Watching job {id}", "已关注作业 {id}"),
+    ("Pinned {count} job(s) to the top", "已将 {count} 个作业置顶"),
+    ("Unpinned {count} job(s)", "已取消置顶 {count} 个作业"),
+    ("Job {id} has no steps yet", "作业 {id} 还没有作业步"),
+    ("Recording macro, press q to stop", "正在录制宏, 按 q 停止"),
+    ("Nothing recorded, the previous macro is kept", "未录制任何按键, 保留之前的宏"),
+    (
+        "Recorded a macro of {count} key(s), replay it with @",
+        "已录制 {count} 个按键的宏, 按 @ 重放",
+    ),
+    ("Stop recording with q before replaying", "请先按 q 停止录制再重放"),
+    ("No macro recorded; press q to record one", "尚未录制宏; 按 q 开始录制"),
+    ("Replayed a macro of {count} key(s)", "已重放 {count} 个按键的宏"),
+    ("Stopped watching job {id}", "已取消关注作业 {id}"),
+    (
+        "Watching {watched} of {count} job(s)",
+        "已关注 {count} 个作业中的 {watched} 个",
+    ),
+];
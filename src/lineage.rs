@@ -0,0 +1,83 @@
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// Jobs resubmitted through slurmer, each with the job it was resubmitted
+/// from, so the attempts of one job can be followed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Lineage {
+    /// New job ID to the job it was resubmitted from
+    #[serde(default)]
+    resubmitted: BTreeMap<String, String>,
+}
+
+/// Location of the lineage file, `~/.local/share/slurmer/lineage.toml`
+fn path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("slurmer").join("lineage.toml"))
+}
+
+impl Lineage {
+    /// Load the recorded resubmissions; a missing file means none
+    pub fn load() -> Result<Self> {
+        let Some(path) = path().filter(|p| p.exists()) else {
+            return Ok(Self::default());
+        };
+        let text = fs::read_to_string(&path)
+            .map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| eyre!("Invalid {}: {}", path.display(), e))
+    }
+
+    /// Record that `job_id` was submitted again as `new_id`, and save
+    pub fn record(&mut self, new_id: &str, job_id: &str) -> Result<()> {
+        self.resubmitted
+            .insert(new_id.to_string(), job_id.to_string());
+        let path = path().ok_or_else(|| eyre!("No data directory found"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)
+            .map_err(|e| eyre!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Every attempt of the job `job_id` belongs to, the original first;
+    /// empty if it was never resubmitted
+    pub fn attempts(&self, job_id: &str) -> Vec<String> {
+        // Walk back to the original submission; the guard stops on a cycle
+        let mut original = job_id;
+        for _ in 0..=self.resubmitted.len() {
+            match self.resubmitted.get(original) {
+                Some(parent) => original = parent,
+                None => break,
+            }
+        }
+        let mut attempts = vec![original.to_string()];
+        let mut i = 0;
+        while i < attempts.len() {
+            let children = self
+                .resubmitted
+                .iter()
+                .filter(|(_, parent)| **parent == attempts[i])
+                .map(|(child, _)| child.clone())
+                .filter(|child| !attempts.contains(child))
+                .collect::<Vec<_>>();
+            attempts.extend(children);
+            i += 1;
+        }
+        if attempts.len() == 1 {
+            return Vec::new();
+        }
+        // Resubmissions in the order they were submitted
+        attempts[1..].sort_by_key(|id| id.parse::<u64>().unwrap_or(u64::MAX));
+        attempts
+    }
+}
+
+/// Job ID from the output of sbatch, e.g. "Submitted batch job 123"
+pub fn submitted_job_id(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .last()
+        .map(|word| word.split(';').next().unwrap_or(word))
+        .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_string)
+}
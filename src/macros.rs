@@ -0,0 +1,124 @@
+use color_eyre::{eyre::eyre, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+/// Modifier prefixes of key names, in the order they are written
+const MODIFIERS: [(KeyModifiers, &str); 3] = [
+    (KeyModifiers::CONTROL, "C-"),
+    (KeyModifiers::ALT, "M-"),
+    (KeyModifiers::SHIFT, "S-"),
+];
+
+/// On-disk format of the macro file: one entry per key, e.g. `["/", "h", "Enter", "C-a"]`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MacroFile {
+    #[serde(default)]
+    keys: Vec<String>,
+}
+
+/// Location of the macro file, `~/.config/slurmer/macro.toml`
+fn path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("slurmer").join("macro.toml"))
+}
+
+/// Load the recorded macro; a missing file means no macro
+pub fn load() -> Result<Vec<KeyEvent>> {
+    let Some(path) = path().filter(|p| p.exists()) else {
+        return Ok(Vec::new());
+    };
+    let text =
+        fs::read_to_string(&path).map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+    let file: MacroFile =
+        toml::from_str(&text).map_err(|e| eyre!("Invalid {}: {}", path.display(), e))?;
+    file.keys
+        .iter()
+        .map(|name| {
+            parse_key(name)
+                .ok_or_else(|| eyre!("Invalid {}: unknown key {:?}", path.display(), name))
+        })
+        .collect()
+}
+
+/// Write the macro to disk
+pub fn save(keys: &[KeyEvent]) -> Result<()> {
+    let path = path().ok_or_else(|| eyre!("No configuration directory found"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = MacroFile {
+        keys: keys.iter().filter_map(|key| key_name(*key)).collect(),
+    };
+    fs::write(&path, toml::to_string_pretty(&file)?)
+        .map_err(|e| eyre!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Name of a key as stored in the macro file; `None` for keys that cannot be recorded
+pub fn key_name(key: KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "BackTab".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        _ => return None,
+    };
+    let mut prefix = String::new();
+    for (modifier, tag) in MODIFIERS {
+        // Shift is already in the case of a character
+        let shifted_char = modifier == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_));
+        if key.modifiers.contains(modifier) && !shifted_char {
+            prefix.push_str(tag);
+        }
+    }
+    Some(prefix + &name)
+}
+
+/// Key for a name written by [`key_name`]
+fn parse_key(name: &str) -> Option<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut name = name;
+    for (modifier, tag) in MODIFIERS {
+        if let Some(rest) = name.strip_prefix(tag).filter(|rest| !rest.is_empty()) {
+            modifiers |= modifier;
+            name = rest;
+        }
+    }
+    let code = match name {
+        "Space" => KeyCode::Char(' '),
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => KeyCode::F(name.strip_prefix('F')?.parse().ok()?),
+            }
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
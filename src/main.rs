@@ -7,14 +7,36 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 
+mod actions;
 mod app;
+mod bookmarks;
+mod cli;
+mod config;
+mod i18n;
+mod lineage;
+mod macros;
+mod manifest;
+mod modules;
+mod notes;
+mod pipeline;
 mod slurm;
+mod submissions;
 mod ui;
 mod utils;
+mod watch;
 
 use app::App;
+use cli::CliArgs;
+use config::Config;
 
 fn main() -> Result<()> {
+    // Parse command-line arguments before touching the terminal
+    let Some(args) = CliArgs::parse()? else {
+        return Ok(());
+    };
+    let config = Config::load()?;
+    i18n::set_language(config.language.unwrap_or_else(i18n::Language::from_env));
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -23,7 +45,7 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run it
-    let mut app = App::new()?;
+    let mut app = App::new(args, config)?;
     let result = app.run(&mut terminal);
 
     // Restore terminal
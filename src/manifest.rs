@@ -0,0 +1,289 @@
+use color_eyre::{eyre::eyre, Result};
+use std::{fs, path::Path};
+
+use crate::{
+    config::JobTemplate,
+    pipeline::PlannedStep,
+    slurm::{
+        command::{shell_quote, SlurmCommand},
+        sweep::{array_range, params_path, PARAMS_VAR},
+    },
+    ui::submitform::{validate_field, SubmitField},
+    utils::expand_home,
+};
+
+/// Manifest columns that set sbatch options instead of environment variables
+const OPTION_COLUMNS: [(&str, SubmitField); 8] = [
+    ("partition", SubmitField::Partition),
+    ("time", SubmitField::TimeLimit),
+    ("cpus", SubmitField::Cpus),
+    ("memory", SubmitField::Memory),
+    ("gpus", SubmitField::Gpus),
+    ("account", SubmitField::Account),
+    ("job_name", SubmitField::JobName),
+    ("workdir", SubmitField::WorkDir),
+];
+
+/// Column naming the batch script, in place of the template's
+const SCRIPT_COLUMN: &str = "script";
+
+/// Jobs to submit from a CSV, TSV or JSON file: one row per job, with a header
+/// naming the columns
+pub struct Manifest {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Manifest {
+    /// Read a manifest; `.json` files hold an array of objects, `.tsv` and `.tab`
+    /// files are tab-separated and anything else comma-separated
+    pub fn read(path: &str) -> Result<Self> {
+        let path = expand_home(path);
+        let text = fs::read_to_string(&path).map_err(|e| eyre!("Cannot read {}: {}", path, e))?;
+        let extension = Path::new(&path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+        let manifest = match extension.as_deref() {
+            Some("json") => Self::from_json(&text)?,
+            Some("tsv") | Some("tab") => Self::from_delimited(&text, b'\t')?,
+            _ => Self::from_delimited(&text, b',')?,
+        };
+        if manifest.rows.is_empty() {
+            return Err(eyre!("{} has no rows", path));
+        }
+        if let Some(column) = manifest.columns.iter().find(|c| !valid_column(c)) {
+            return Err(eyre!(
+                "column {:?} is neither an option nor an environment variable name",
+                column
+            ));
+        }
+        Ok(manifest)
+    }
+
+    fn from_delimited(text: &str, delimiter: u8) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .trim(csv::Trim::All)
+            .from_reader(text.as_bytes());
+        let columns = reader.headers()?.iter().map(str::to_string).collect();
+        let rows = reader
+            .records()
+            .map(|record| Ok(record?.iter().map(str::to_string).collect()))
+            .collect::<Result<Vec<Vec<String>>>>()?;
+        Ok(Self { columns, rows })
+    }
+
+    fn from_json(text: &str) -> Result<Self> {
+        let objects: Vec<serde_json::Map<String, serde_json::Value>> =
+            serde_json::from_str(text).map_err(|e| eyre!("expected an array of objects: {}", e))?;
+        // Columns in the order they first appear
+        let mut columns: Vec<String> = Vec::new();
+        for key in objects.iter().flat_map(|object| object.keys()) {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+        let rows = objects
+            .iter()
+            .map(|object| {
+                columns
+                    .iter()
+                    .map(|column| match object.get(column) {
+                        None | Some(serde_json::Value::Null) => String::new(),
+                        Some(serde_json::Value::String(s)) => s.clone(),
+                        Some(value) => value.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self { columns, rows })
+    }
+
+    /// One `sbatch` per row, or why the row cannot be submitted
+    pub fn plan_jobs(
+        &self,
+        template: &JobTemplate,
+        partitions: &[String],
+    ) -> Vec<Result<PlannedStep, String>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let values = |column: &str| self.value(row, column);
+                let mut args = option_args(template, &values, partitions)?;
+                let variables = self.variables(row);
+                if !variables.is_empty() {
+                    if let Some((name, _)) = variables.iter().find(|(_, v)| v.contains(',')) {
+                        return Err(format!(
+                            "{} contains a comma, which --export cannot pass",
+                            name
+                        ));
+                    }
+                    let exports: Vec<String> = variables
+                        .iter()
+                        .map(|(n, v)| format!("{}={}", n, v))
+                        .collect();
+                    args.push(format!("--export=ALL,{}", exports.join(",")));
+                }
+                args.push(script(template, &values)?);
+                Ok(PlannedStep {
+                    command: SlurmCommand::new("sbatch", args, Vec::new()),
+                    params: None,
+                })
+            })
+            .collect()
+    }
+
+    /// A single job array with one task per row. Task `i` finds the variables of
+    /// row `i` as shell assignments on line `i + 1` of the parameter file; the
+    /// options and script must be the same in every row.
+    pub fn plan_array(
+        &self,
+        template: &JobTemplate,
+        partitions: &[String],
+    ) -> Result<PlannedStep, String> {
+        for (column, _) in OPTION_COLUMNS {
+            if self.index(column).is_some() && !self.uniform(column) {
+                return Err(format!(
+                    "{} differs between rows; submit them as individual jobs",
+                    column
+                ));
+            }
+        }
+        if self.index(SCRIPT_COLUMN).is_some() && !self.uniform(SCRIPT_COLUMN) {
+            return Err("script differs between rows; submit them as individual jobs".to_string());
+        }
+
+        let first = &self.rows[0];
+        let values = |column: &str| self.value(first, column);
+        let mut args = option_args(template, &values, partitions)?;
+        let script = script(template, &values)?;
+        let file = params_path(&script);
+        let absolute = std::path::absolute(&file).unwrap_or(file.clone().into());
+        args.push(format!("--array={}", array_range(self.rows.len())));
+        args.push(format!(
+            "--export=ALL,{}={}",
+            PARAMS_VAR,
+            absolute.display()
+        ));
+        args.push(script);
+
+        let lines = self
+            .rows
+            .iter()
+            .map(|row| {
+                self.variables(row)
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, shell_quote(value)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        Ok(PlannedStep {
+            command: SlurmCommand::new("sbatch", args, Vec::new()),
+            params: Some((file, lines)),
+        })
+    }
+
+    fn index(&self, column: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(column))
+    }
+
+    /// Non-empty value of a column in a row
+    fn value<'a>(&self, row: &'a [String], column: &str) -> Option<&'a str> {
+        self.index(column)
+            .and_then(|i| row.get(i))
+            .map(|v| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn uniform(&self, column: &str) -> bool {
+        let first = self.value(&self.rows[0], column);
+        self.rows.iter().all(|row| self.value(row, column) == first)
+    }
+
+    /// Columns passed to the job as environment variables, with this row's values
+    fn variables<'a>(&'a self, row: &'a [String]) -> Vec<(&'a str, &'a str)> {
+        self.columns
+            .iter()
+            .zip(row)
+            .filter(|(column, _)| !is_option(column))
+            .map(|(column, value)| (column.as_str(), value.as_str()))
+            .collect()
+    }
+}
+
+fn is_option(column: &str) -> bool {
+    column.eq_ignore_ascii_case(SCRIPT_COLUMN)
+        || OPTION_COLUMNS
+            .iter()
+            .any(|(name, _)| column.eq_ignore_ascii_case(name))
+}
+
+/// Option columns, and environment variable names for the rest
+fn valid_column(column: &str) -> bool {
+    let mut chars = column.chars();
+    is_option(column)
+        || chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The template's options, overridden by the row's option columns
+fn option_args<'a>(
+    template: &JobTemplate,
+    values: &impl Fn(&str) -> Option<&'a str>,
+    partitions: &[String],
+) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    for (column, field) in OPTION_COLUMNS {
+        let value = match values(column) {
+            Some(value) => value.to_string(),
+            None => match template_value(template, field) {
+                Some(value) => value,
+                None => continue,
+            },
+        };
+        if let Some(error) = validate_field(field, &value, partitions) {
+            return Err(format!("{}: {}", column, error));
+        }
+        args.extend(field.sbatch_arg(&value));
+    }
+    Ok(args)
+}
+
+fn template_value(template: &JobTemplate, field: SubmitField) -> Option<String> {
+    match field {
+        SubmitField::Partition => template.partition.clone(),
+        SubmitField::TimeLimit => template.time.clone(),
+        SubmitField::Cpus => template.cpus.map(|n| n.to_string()),
+        SubmitField::Memory => template.memory.clone(),
+        SubmitField::Gpus => template.gpus.clone(),
+        SubmitField::Account => template.account.clone(),
+        // Without a name the jobs are told apart by the template they came from
+        SubmitField::JobName => Some(template.job_name.clone().unwrap_or(template.name.clone())),
+        _ => None,
+    }
+}
+
+/// The row's script, or the template's
+fn script<'a>(
+    template: &JobTemplate,
+    values: &impl Fn(&str) -> Option<&'a str>,
+) -> Result<String, String> {
+    let script = values(SCRIPT_COLUMN)
+        .or(template.script.as_deref())
+        .map(expand_home)
+        .ok_or_else(|| {
+            format!(
+                "template {} has no script and there is no script column",
+                template.name
+            )
+        })?;
+    match validate_field(SubmitField::Script, &script, &[]) {
+        Some(error) => Err(format!("{}: {}", script, error)),
+        None => Ok(script),
+    }
+}
@@ -0,0 +1,112 @@
+use color_eyre::{eyre::eyre, Result};
+use std::fs;
+
+use crate::slurm::command::execute_command;
+
+/// Environment modules matching a query: `module -t avail`, then `module -t spider`
+/// for modules hidden behind an Lmod hierarchy. `module` is a shell function, so
+/// both run in a login shell.
+pub async fn search_modules(query: &str) -> Result<Vec<String>> {
+    let (ok, output) = run_module("avail", query).await?;
+    let modules = parse_module_list(&output);
+    if !modules.is_empty() {
+        return Ok(modules);
+    }
+    if !ok {
+        return Err(eyre!("{}", output.trim()));
+    }
+    // Only Lmod knows spider; a failure there just means nothing else was found
+    match run_module("spider", query).await? {
+        (true, output) => Ok(parse_module_list(&output)),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Run `module -t <subcommand> <query>`, returning whether it succeeded and its
+/// output; modules list to stderr
+async fn run_module(subcommand: &str, query: &str) -> Result<(bool, String)> {
+    let script = format!("module -t {} \"$1\" 2>&1", subcommand);
+    let args = vec![
+        "-lc".to_string(),
+        script,
+        "bash".to_string(),
+        query.to_string(),
+    ];
+    let output = execute_command("bash", args).await?;
+    Ok((
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+    ))
+}
+
+/// Module names in terse output: directory headers (`/opt/modulefiles:`),
+/// separators and messages are skipped, default and loaded markers removed
+fn parse_module_list(output: &str) -> Vec<String> {
+    let mut modules: Vec<String> = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if line.is_empty() || line.ends_with(':') || line.starts_with('-') || line.contains(' ') {
+            continue;
+        }
+        let name = ["(default)", "(D)", "<L>", "(L)"]
+            .iter()
+            .fold(line, |name, marker| name.trim_end_matches(marker));
+        // Bare directories of versioned modules end in '/'
+        if name.is_empty() || name.ends_with('/') {
+            continue;
+        }
+        if !modules.iter().any(|m| m == name) {
+            modules.push(name.to_string());
+        }
+    }
+    modules
+}
+
+/// Add a `module load` line per module to a batch script, after its existing
+/// `module load` lines or else after the `#SBATCH` directives, which must come
+/// before any command. Modules the script already loads are skipped; returns
+/// how many lines were added.
+pub fn insert_module_loads(path: &str, modules: &[String]) -> Result<usize> {
+    let text = fs::read_to_string(path).map_err(|e| eyre!("Cannot read {}: {}", path, e))?;
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    let is_load = |line: &str| {
+        let mut words = line.split_whitespace();
+        matches!(words.next(), Some("module") | Some("ml"))
+            && matches!(words.next(), Some("load") | Some("add"))
+    };
+    let loaded = |module: &str| {
+        lines
+            .iter()
+            .any(|line| is_load(line) && line.split_whitespace().skip(2).any(|m| m == module))
+    };
+    let new: Vec<String> = modules
+        .iter()
+        .filter(|module| !loaded(module))
+        .map(|module| format!("module load {}", module))
+        .collect();
+    if new.is_empty() {
+        return Ok(0);
+    }
+
+    let after = lines
+        .iter()
+        .rposition(|line| is_load(line))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| line.trim_start().starts_with("#SBATCH"))
+        })
+        .or_else(|| {
+            lines
+                .first()
+                .filter(|line| line.starts_with("#!"))
+                .map(|_| 0)
+        });
+    let at = after.map_or(0, |i| i + 1);
+    let count = new.len();
+    lines.splice(at..at, new);
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    fs::write(path, text).map_err(|e| eyre!("Cannot write {}: {}", path, e))?;
+    Ok(count)
+}
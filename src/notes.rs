@@ -0,0 +1,65 @@
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::PathBuf,
+};
+
+/// Free-text notes on jobs, kept locally, e.g. "re-ran with fixed input"
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Notes {
+    /// `cluster/job ID` to the note; job IDs are reused across clusters
+    #[serde(default)]
+    notes: BTreeMap<String, String>,
+}
+
+/// Location of the notes file, `~/.local/share/slurmer/notes.toml`
+fn path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("slurmer").join("notes.toml"))
+}
+
+fn key(cluster: &str, job_id: &str) -> String {
+    format!("{}/{}", cluster, job_id)
+}
+
+impl Notes {
+    /// Load the notes; a missing file means none
+    pub fn load() -> Result<Self> {
+        let Some(path) = path().filter(|p| p.exists()) else {
+            return Ok(Self::default());
+        };
+        let text = fs::read_to_string(&path)
+            .map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| eyre!("Invalid {}: {}", path.display(), e))
+    }
+
+    /// Note on a job of a cluster
+    pub fn get(&self, cluster: &str, job_id: &str) -> Option<&str> {
+        self.notes.get(&key(cluster, job_id)).map(String::as_str)
+    }
+
+    /// Notes on the jobs of a cluster, by job ID
+    pub fn for_cluster(&self, cluster: &str) -> HashMap<String, String> {
+        let prefix = format!("{}/", cluster);
+        self.notes
+            .iter()
+            .filter_map(|(key, note)| Some((key.strip_prefix(&prefix)?.to_string(), note.clone())))
+            .collect()
+    }
+
+    /// Set the note on a job, or remove it when `note` is empty, and save
+    pub fn set(&mut self, cluster: &str, job_id: &str, note: &str) -> Result<()> {
+        if note.is_empty() {
+            self.notes.remove(&key(cluster, job_id));
+        } else {
+            self.notes.insert(key(cluster, job_id), note.to_string());
+        }
+        let path = path().ok_or_else(|| eyre!("No data directory found"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)
+            .map_err(|e| eyre!("Failed to write {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,80 @@
+use color_eyre::{eyre::eyre, Result};
+
+use crate::{
+    config::{JobTemplate, PipelineStep},
+    slurm::{
+        command::SlurmCommand,
+        sweep::{array_range, expand_sweep, params_path, PARAMS_VAR},
+    },
+    utils::expand_home,
+};
+
+/// A pipeline step ready to submit
+pub struct PlannedStep {
+    pub command: SlurmCommand,
+    /// File the sweep parameters are written to before the step is submitted
+    pub params: Option<(String, Vec<String>)>,
+}
+
+/// The resource options a template sets, as sbatch arguments
+pub fn template_args(template: &JobTemplate) -> Vec<String> {
+    let options = [
+        ("partition", template.partition.clone()),
+        ("time", template.time.clone()),
+        ("cpus-per-task", template.cpus.map(|n| n.to_string())),
+        ("mem", template.memory.clone()),
+        ("gres", template.gpus.as_ref().map(|g| format!("gpu:{}", g))),
+        ("account", template.account.clone()),
+    ];
+    options
+        .into_iter()
+        .filter_map(|(name, value)| Some(format!("--{}={}", name, value?)))
+        .collect()
+}
+
+/// The `sbatch` command of a pipeline step, depending on the jobs in `after`
+/// (the IDs of the steps it runs after, or placeholders when only previewing)
+pub fn step_command(
+    step: &PipelineStep,
+    templates: &[JobTemplate],
+    after: &[String],
+) -> Result<PlannedStep> {
+    let template = templates
+        .iter()
+        .find(|t| t.name == step.template)
+        .ok_or_else(|| eyre!("no template named {}", step.template))?;
+    let script = template
+        .script
+        .as_deref()
+        .map(expand_home)
+        .ok_or_else(|| eyre!("template {} has no script", template.name))?;
+
+    let mut args = template_args(template);
+    // Step names keep the jobs of a pipeline apart in the list and the graph
+    let job_name = template.job_name.as_deref().unwrap_or(&step.name);
+    args.push(format!("--job-name={}", job_name));
+    if !after.is_empty() {
+        let kind = step.dependency.as_deref().unwrap_or("afterok");
+        args.push(format!("--dependency={}:{}", kind, after.join(":")));
+    }
+
+    let mut params = None;
+    if let Some(spec) = &step.sweep {
+        let values = expand_sweep(spec).map_err(|e| eyre!("step {}: {}", step.name, e))?;
+        let file = params_path(&script);
+        let absolute = std::path::absolute(&file).unwrap_or(file.clone().into());
+        args.push(format!("--array={}", array_range(values.len())));
+        args.push(format!(
+            "--export=ALL,{}={}",
+            PARAMS_VAR,
+            absolute.display()
+        ));
+        params = Some((file, values));
+    }
+    args.push(script);
+
+    Ok(PlannedStep {
+        command: SlurmCommand::new("sbatch", args, Vec::new()),
+        params,
+    })
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Local};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use super::command::{CommandStatus, SlurmCommand};
+
+/// A state-changing command slurmer has executed
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub time: DateTime<Local>,
+    pub command: String,
+    pub job_ids: Vec<String>,
+    pub status: CommandStatus,
+}
+
+impl AuditEntry {
+    pub fn new(command: &SlurmCommand, status: CommandStatus) -> Self {
+        Self {
+            time: Local::now(),
+            command: command.to_string(),
+            job_ids: command.job_ids.clone(),
+            status,
+        }
+    }
+
+    /// Tab separated line written to the on-disk log
+    fn to_log_line(&self) -> String {
+        format!(
+            "{}\t{}\tjobs={}\t{}",
+            self.time.to_rfc3339(),
+            self.status.to_string().replace(['\n', '\t'], " "),
+            self.job_ids.join(","),
+            self.command
+        )
+    }
+}
+
+/// Every state-changing command run in this session, mirrored to
+/// `~/.local/share/slurmer/audit.log`
+pub struct AuditLog {
+    pub entries: Vec<AuditEntry>,
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            path: dirs::data_local_dir().map(|dir| dir.join("slurmer").join("audit.log")),
+        }
+    }
+
+    /// Location of the on-disk log
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Keep the entry in memory and append it to the on-disk log
+    pub fn record(&mut self, entry: AuditEntry) -> io::Result<()> {
+        let line = entry.to_log_line();
+        self.entries.push(entry);
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)
+    }
+}
@@ -0,0 +1,127 @@
+use color_eyre::{eyre::eyre, Result};
+use regex::Regex;
+
+use super::command::execute_command;
+use crate::config::BalanceSettings;
+
+/// What is left of an account's allocation
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountBalance {
+    /// Shown next to the account, e.g. "81200 cpu-min left of 100000"
+    pub summary: String,
+    /// Set when the balance is low or exhausted
+    pub warning: Option<String>,
+}
+
+/// Balance of an account from the configured command, or from sshare
+pub async fn get_balance(account: &str, settings: &BalanceSettings) -> Result<AccountBalance> {
+    match &settings.command {
+        Some(command) => command_balance(account, command, settings).await,
+        None => sshare_balance(account, settings).await,
+    }
+}
+
+/// Remaining balance printed by a site command such as `sbank balance statement`
+async fn command_balance(
+    account: &str,
+    command: &[String],
+    settings: &BalanceSettings,
+) -> Result<AccountBalance> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| eyre!("balance.command is empty"))?;
+    let args = args
+        .iter()
+        .map(|a| a.replace("{account}", account))
+        .collect();
+    let output = execute_command(program, args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{} failed: {}", program, stderr.trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let pattern = settings
+        .pattern
+        .as_deref()
+        .unwrap_or(r"(-?[\d,]+(?:\.\d+)?)");
+    let regex = Regex::new(pattern)?;
+    let remaining = regex
+        .captures(&stdout)
+        .and_then(|c| c.get(1))
+        .and_then(|m| m.as_str().replace(',', "").parse::<f64>().ok())
+        .ok_or_else(|| eyre!("no balance in the output of {}", program))?;
+
+    let warning = if remaining <= 0.0 {
+        Some(format!("Account {} has no balance left", account))
+    } else if settings.low.is_some_and(|low| remaining < low) {
+        Some(format!("Account {} is low: {} left", account, remaining))
+    } else {
+        None
+    };
+    Ok(AccountBalance {
+        summary: format!("{} left", remaining),
+        warning,
+    })
+}
+
+/// The account's `GrpTRESMins` limit less its `GrpTRESRaw` usage, for the first
+/// TRES the limit names
+async fn sshare_balance(account: &str, settings: &BalanceSettings) -> Result<AccountBalance> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        format!("--accounts={}", account),
+        "--format=Account,User,GrpTRESMins,GrpTRESRaw".to_string(),
+    ];
+    let output = execute_command("sshare", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("sshare failed: {}", stderr.trim()));
+    }
+    // The account's own line has no user
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fields: Vec<&str> = stdout
+        .lines()
+        .map(|line| line.split('|').collect::<Vec<_>>())
+        .find(|f| f.len() == 4 && f[0].trim() == account && f[1].is_empty())
+        .ok_or_else(|| eyre!("sshare does not know account {}", account))?;
+
+    let tres = |list: &str| -> Vec<(String, f64)> {
+        list.split(',')
+            .filter_map(|item| {
+                let (name, value) = item.split_once('=')?;
+                Some((name.to_string(), value.parse().ok()?))
+            })
+            .collect()
+    };
+    let Some((name, limit)) = tres(fields[2]).into_iter().next() else {
+        return Ok(AccountBalance {
+            summary: "no GrpTRESMins limit".to_string(),
+            warning: None,
+        });
+    };
+    let used = tres(fields[3])
+        .into_iter()
+        .find(|(n, _)| *n == name)
+        .map_or(0.0, |(_, used)| used);
+    let remaining = (limit - used).max(0.0);
+
+    let low_percent = settings.low_percent.unwrap_or(10) as f64;
+    let warning = if remaining <= 0.0 {
+        Some(format!(
+            "Account {} has used all of its {} {}-minutes",
+            account, limit, name
+        ))
+    } else if remaining < limit * low_percent / 100.0 {
+        Some(format!(
+            "Account {} is low: {:.0} of {} {}-minutes left",
+            account, remaining, limit, name
+        ))
+    } else {
+        None
+    };
+    Ok(AccountBalance {
+        summary: format!("{:.0} {}-min left of {}", remaining, name, limit),
+        warning,
+    })
+}
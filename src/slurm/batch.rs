@@ -0,0 +1,94 @@
+use crossbeam::channel::{unbounded, Receiver, TryRecvError};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::runtime::Runtime;
+
+use super::command::{run_command, CommandStatus, SlurmCommand};
+
+/// State-changing commands run one at a time in the background, pausing between
+/// them so that large admin actions do not flood the controller.
+pub struct BatchRun {
+    pub commands: Vec<SlurmCommand>,
+    receiver: Receiver<(usize, CommandStatus)>,
+    cancel: Arc<AtomicBool>,
+    /// Number of commands that have finished
+    pub finished: usize,
+    pub failed: usize,
+    /// Set once the background task has stopped, normally or after a cancel
+    done: bool,
+}
+
+impl BatchRun {
+    /// Start running `commands` on `runtime`, waiting `delay` between them
+    pub fn spawn(runtime: &Runtime, commands: Vec<SlurmCommand>, delay: Duration) -> Self {
+        let (sender, receiver) = unbounded();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let task_commands = commands.clone();
+        let task_cancel = Arc::clone(&cancel);
+        runtime.spawn(async move {
+            for (i, command) in task_commands.iter().enumerate() {
+                if i > 0 {
+                    tokio::time::sleep(delay).await;
+                }
+                if task_cancel.load(Ordering::Acquire) {
+                    break;
+                }
+                let status = run_command(command).await;
+                if sender.send((i, status)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            commands,
+            receiver,
+            cancel,
+            finished: 0,
+            failed: 0,
+            done: false,
+        }
+    }
+
+    /// Skip the commands that have not started yet
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Acquire)
+    }
+
+    /// Results that arrived since the last call, as (command index, status)
+    pub fn poll(&mut self) -> Vec<(usize, CommandStatus)> {
+        let mut results = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok((i, status)) => {
+                    self.finished += 1;
+                    if status != CommandStatus::Success {
+                        self.failed += 1;
+                    }
+                    results.push((i, status));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        results
+    }
+
+    /// Whether the background task has stopped
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
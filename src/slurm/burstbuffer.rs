@@ -0,0 +1,164 @@
+use color_eyre::{eyre::eyre, Result};
+
+use super::{
+    command::{execute_command, parse_scontrol_fields},
+    sacct::parse_size,
+};
+
+/// Space of one burst buffer pool
+#[derive(Debug, Clone, Default)]
+pub struct BufferPool {
+    /// Burst buffer plugin, e.g. "datawarp" or "lua"
+    pub plugin: String,
+    pub name: String,
+    /// Sizes as reported, e.g. "5800GiB"
+    pub granularity: String,
+    pub total: String,
+    pub used: String,
+    pub free: String,
+}
+
+impl BufferPool {
+    /// Used share of the pool, when its sizes parse
+    pub fn usage(&self) -> Option<f64> {
+        let total = buffer_size(&self.total).filter(|t| *t > 0)?;
+        Some(buffer_size(&self.used)? as f64 / total as f64)
+    }
+}
+
+/// A buffer allocated to a job, or a persistent one created by name
+#[derive(Debug, Clone, Default)]
+pub struct BufferAllocation {
+    pub job_id: Option<String>,
+    /// Name of a persistent buffer
+    pub name: Option<String>,
+    pub pool: String,
+    pub size: String,
+    /// e.g. "allocated", "staging-in", "staged-out"
+    pub state: String,
+    pub user: String,
+    pub created: String,
+}
+
+/// Burst buffer pools, allocations and usage per user from
+/// `scontrol show burstbuffer`
+#[derive(Debug, Clone, Default)]
+pub struct BurstBuffers {
+    pub pools: Vec<BufferPool>,
+    pub allocations: Vec<BufferAllocation>,
+    /// Space used by each user
+    pub users: Vec<(String, String)>,
+}
+
+/// Fetch the burst buffer status; a cluster without a burst buffer plugin
+/// reports none
+pub async fn get_burst_buffers() -> Result<BurstBuffers> {
+    let args = vec!["show".to_string(), "burstbuffer".to_string()];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(parse_burst_buffers(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parse `scontrol show burstbuffer` output, one block per plugin:
+///
+/// ```text
+/// Name=datawarp DefaultPool=wlm_pool Granularity=200GiB TotalSpace=5800GiB FreeSpace=4600GiB UsedSpace=1200GiB
+///   AltPoolName[0]=ssd Granularity=16MiB TotalSpace=1TiB FreeSpace=1TiB UsedSpace=0
+///   Allocated Buffers:
+///     JobID=169509 CreateTime=2024-08-19T09:10:00 Pool=wlm_pool Size=1200GiB State=allocated UserID=alan(1000)
+///   Per User Buffer Use:
+///     UserID=alan(1000) Used=1200GiB
+/// ```
+fn parse_burst_buffers(output: &str) -> BurstBuffers {
+    enum Section {
+        Config,
+        Allocations,
+        Users,
+    }
+    let mut buffers = BurstBuffers::default();
+    let mut plugin = String::new();
+    let mut section = Section::Config;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        match trimmed {
+            "" => continue,
+            "Allocated Buffers:" => {
+                section = Section::Allocations;
+                continue;
+            }
+            "Per User Buffer Use:" => {
+                section = Section::Users;
+                continue;
+            }
+            _ => {}
+        }
+        let fields = parse_scontrol_fields(trimmed);
+        let field = |key: &str| {
+            fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone())
+                .filter(|v| !v.is_empty() && v != "(null)")
+        };
+        let pool = |name: String, plugin: &str| BufferPool {
+            plugin: plugin.to_string(),
+            name,
+            granularity: field("Granularity").unwrap_or_default(),
+            total: field("TotalSpace").unwrap_or_default(),
+            used: field("UsedSpace").unwrap_or_default(),
+            free: field("FreeSpace").unwrap_or_default(),
+        };
+
+        if let Some(name) = field("Name").filter(|_| !line.starts_with(char::is_whitespace)) {
+            // A new plugin; its own line describes the default pool
+            plugin = name;
+            section = Section::Config;
+            let name = field("DefaultPool").unwrap_or_else(|| plugin.clone());
+            buffers.pools.push(pool(name, &plugin));
+            continue;
+        }
+        let user = || {
+            field("UserID")
+                .map(|u| u.split('(').next().unwrap_or(&u).to_string())
+                .unwrap_or_default()
+        };
+        match section {
+            Section::Config => {
+                // "PoolName[0]=ssd" or, with datawarp, "AltPoolName[0]=ssd"
+                let name = fields
+                    .iter()
+                    .find(|(k, _)| k.starts_with("PoolName[") || k.starts_with("AltPoolName["))
+                    .map(|(_, v)| v.clone());
+                if let Some(name) = name {
+                    buffers.pools.push(pool(name, &plugin));
+                }
+            }
+            Section::Allocations => buffers.allocations.push(BufferAllocation {
+                job_id: field("JobID"),
+                name: field("Name"),
+                pool: field("Pool").unwrap_or_default(),
+                size: field("Size").unwrap_or_default(),
+                state: field("State").unwrap_or_default(),
+                user: user(),
+                created: field("CreateTime").unwrap_or_default(),
+            }),
+            Section::Users => {
+                if let Some(used) = field("Used") {
+                    buffers.users.push((user(), used));
+                }
+            }
+        }
+    }
+    buffers
+}
+
+/// Bytes of a burst buffer size like "1200GiB", "16MiB" or a plain count
+fn buffer_size(size: &str) -> Option<u64> {
+    let size = size.trim().trim_end_matches("iB").trim_end_matches('B');
+    parse_size(size, 1)
+}
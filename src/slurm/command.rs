@@ -1,6 +1,14 @@
 use async_process::{Command, Output};
-use color_eyre::Result;
-use std::collections::HashMap;
+use color_eyre::{eyre::eyre, Result};
+use std::{collections::HashMap, fmt, path::Path};
+
+use super::{
+    expand_hostlist,
+    limits::{
+        parse_node_gres, parse_node_memory, parse_partition_limits, parse_qos_limits, ClusterLimits,
+    },
+    parse_time_limit, JobAction,
+};
 
 /// Execute a Slurm command asynchronously and return the output
 pub async fn execute_command(cmd: &str, args: Vec<String>) -> Result<Output> {
@@ -17,42 +25,560 @@ pub async fn _execute_squeue(args: Vec<String>) -> Result<String> {
 }
 
 /// Execute the scontrol command to get detailed job information
-pub async fn _execute_scontrol(job_id: &str) -> Result<String> {
-    let args = vec!["show".to_string(), "job".to_string(), job_id.to_string()];
+pub async fn execute_scontrol(job_id: &str) -> Result<String> {
+    let args = vec![
+        "show".to_string(),
+        "job".to_string(),
+        job_id.to_string(),
+        "-o".to_string(),
+    ];
     let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!(
+            "scontrol show job {} failed: {}",
+            job_id,
+            stderr.trim()
+        ));
+    }
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
     Ok(stdout)
 }
 
-/// Execute the scancel command to cancel jobs
-pub async fn execute_scancel(job_ids: Vec<String>) -> Result<()> {
-    if job_ids.is_empty() {
-        return Ok(());
+/// Get the full multi-line `scontrol show job` record of a job
+pub async fn get_job_record(job_id: &str) -> Result<String> {
+    let args = vec!["show".to_string(), "job".to_string(), job_id.to_string()];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("job {}: {}", job_id, stderr.trim()));
     }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
 
-    // if jobs >= 200, split into chunks for avoiding command line length issues
-    let chunk_size = 200;
-    let chunks: Vec<Vec<String>> = job_ids
-        .chunks(chunk_size)
-        .map(|chunk| chunk.to_vec())
-        .collect();
-    for chunk in chunks {
-        let _ = execute_command("scancel", chunk).await?;
+/// Get the batch script of a job with `scontrol write batch_script <id> -`
+pub async fn get_batch_script(job_id: &str) -> Result<String> {
+    let args = vec![
+        "write".to_string(),
+        "batch_script".to_string(),
+        job_id.to_string(),
+        "-".to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("job {}: {}", job_id, stderr.trim()));
     }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
 
-    Ok(())
+/// Get the key=value fields of `scontrol show job` for a single job
+pub async fn get_job_info(job_id: &str) -> Result<HashMap<String, String>> {
+    let output = execute_scontrol(job_id).await?;
+    Ok(parse_scontrol_output(&output))
 }
 
-/// Execute a command to modify a job (scontrol update)
-pub async fn _modify_job(job_id: &str, parameters: HashMap<String, String>) -> Result<()> {
-    let mut args = vec!["update".to_string(), format!("JobId={}", job_id)];
+/// Resources a running job holds on one of its nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeAllocation {
+    pub node: String,
+    pub cpus: u32,
+    pub gpus: u32,
+    /// Allocated memory in bytes, if reported
+    pub memory: Option<u64>,
+}
+
+/// Per-node allocation of a job, from the detail lines of `scontrol -d show job`
+pub async fn get_node_allocation(job_id: &str) -> Result<Vec<NodeAllocation>> {
+    let args = vec![
+        "-d".to_string(),
+        "show".to_string(),
+        "job".to_string(),
+        job_id.to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("job {}: {}", job_id, stderr.trim()));
+    }
+    Ok(parse_node_allocation(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parse lines like `Nodes=node[01-02] CPU_IDs=0-15 Mem=64000 GRES=gpu:a100:2(IDX:0-1)`,
+/// one allocation per node of the hostlist
+fn parse_node_allocation(output: &str) -> Vec<NodeAllocation> {
+    // Number of IDs in a list like "0-15,32-47"
+    let count_ids = |ids: &str| -> u32 {
+        ids.split(',')
+            .filter_map(|range| {
+                let (first, last) = range.split_once('-').unwrap_or((range, range));
+                (last.parse::<u32>().ok()? + 1).checked_sub(first.parse().ok()?)
+            })
+            .sum()
+    };
+    // GPUs in a GRES list like "gpu:a100:2(IDX:0-1),shard:0"
+    let count_gpus = |gres: &str| -> u32 {
+        gres.split(',')
+            .filter(|item| item.starts_with("gpu"))
+            .filter_map(|item| {
+                item.split('(')
+                    .next()?
+                    .rsplit(':')
+                    .next()?
+                    .parse::<u32>()
+                    .ok()
+            })
+            .sum()
+    };
+
+    let mut allocations = Vec::new();
+    for line in output.lines() {
+        let fields = parse_scontrol_output(line);
+        let (Some(nodes), Some(cpu_ids)) = (fields.get("Nodes"), fields.get("CPU_IDs")) else {
+            continue;
+        };
+        let cpus = count_ids(cpu_ids);
+        let gpus = fields.get("GRES").map_or(0, |gres| count_gpus(gres));
+        let memory = fields
+            .get("Mem")
+            .and_then(|mem| mem.parse::<u64>().ok())
+            .map(|mb| mb * 1024 * 1024);
+        allocations.extend(
+            expand_hostlist(nodes)
+                .into_iter()
+                .map(|node| NodeAllocation {
+                    node,
+                    cpus,
+                    gpus,
+                    memory,
+                }),
+        );
+    }
+    allocations
+}
+
+/// Working directory of a job, from scontrol or else from accounting
+pub async fn get_work_dir(job_id: &str) -> Result<String> {
+    let work_dir = match get_job_info(job_id).await {
+        Ok(info) => info.get("WorkDir").cloned().unwrap_or_default(),
+        Err(_) => {
+            let args = vec![
+                "--noheader".to_string(),
+                "--parsable2".to_string(),
+                "--allocations".to_string(),
+                "--format=WorkDir".to_string(),
+                format!("--jobs={}", job_id),
+            ];
+            let output = execute_command("sacct", args).await?;
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        }
+    };
+    if work_dir.is_empty() || work_dir == "(null)" {
+        return Err(eyre!("No working directory is known for job {}", job_id));
+    }
+    Ok(work_dir)
+}
+
+/// Batch script path and working directory of a job: from scontrol while
+/// slurmctld still knows the job, then from the `SubmitLine` kept by accounting
+pub async fn get_submit_paths(job_id: &str) -> Result<(String, String)> {
+    let (script, work_dir) = match get_job_info(job_id).await {
+        Ok(info) => (
+            info.get("Command").cloned().unwrap_or_default(),
+            info.get("WorkDir").cloned().unwrap_or_default(),
+        ),
+        Err(_) => {
+            let args = vec![
+                "--noheader".to_string(),
+                "--parsable2".to_string(),
+                "--allocations".to_string(),
+                "--format=WorkDir,SubmitLine".to_string(),
+                format!("--jobs={}", job_id),
+            ];
+            let output = execute_command("sacct", args).await?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let (work_dir, submit_line) = stdout
+                .lines()
+                .next()
+                .and_then(|line| line.split_once('|'))
+                .ok_or_else(|| eyre!("Job {} is not known to scontrol or sacct", job_id))?;
+            // The script is the first argument of sbatch that names a file;
+            // option values cannot be told apart from it otherwise
+            let script = submit_line
+                .split_whitespace()
+                .skip(1)
+                .filter(|arg| !arg.starts_with('-'))
+                .find(|arg| Path::new(work_dir).join(arg).is_file())
+                .unwrap_or_default();
+            (script.to_string(), work_dir.to_string())
+        }
+    };
+
+    if script.is_empty() || script == "(null)" || work_dir.is_empty() {
+        return Err(eyre!("No batch script is recorded for job {}", job_id));
+    }
+    let script = Path::new(&work_dir).join(script);
+    if !script.is_file() {
+        return Err(eyre!("Batch script {} no longer exists", script.display()));
+    }
+    Ok((script.to_string_lossy().into_owned(), work_dir))
+}
 
+/// What an existing job asked for, to submit a copy of it; empty fields were not
+/// requested or are not known
+#[derive(Debug, Clone, Default)]
+pub struct JobRequest {
+    pub script: String,
+    pub work_dir: String,
+    pub partition: String,
+    pub time_limit: String,
+    pub cpus_per_task: String,
+    /// Memory per node, e.g. "16G"
+    pub memory: String,
+    /// GPU count, optionally with a type, e.g. "a100:2"
+    pub gpus: String,
+    pub account: String,
+    pub name: String,
+}
+
+/// Script, working directory and resources of a job, from scontrol while
+/// slurmctld still knows the job, then from accounting
+pub async fn get_job_request(job_id: &str) -> Result<JobRequest> {
+    let (script, work_dir) = get_submit_paths(job_id).await?;
+    let known = |value: Option<&String>| {
+        value
+            .filter(|v| !v.is_empty() && *v != "(null)" && *v != "N/A")
+            .cloned()
+            .unwrap_or_default()
+    };
+    let mut request = JobRequest {
+        script,
+        work_dir,
+        ..JobRequest::default()
+    };
+
+    if let Ok(info) = get_job_info(job_id).await {
+        request.partition = known(info.get("Partition"));
+        request.time_limit = known(info.get("TimeLimit"));
+        request.cpus_per_task = known(info.get("CPUs/Task"));
+        // Memory requested per CPU has no equivalent in the form
+        request.memory = known(info.get("MinMemoryNode"));
+        request.account = known(info.get("Account"));
+        request.name = known(info.get("JobName"));
+        // "gres/gpu:2" on newer Slurm, "gpu:a100:2" on older
+        request.gpus = known(info.get("TresPerNode").or_else(|| info.get("Gres")))
+            .split(',')
+            .map(|g| g.trim_start_matches("gres/").trim_start_matches("gres:"))
+            .find_map(|g| g.strip_prefix("gpu:"))
+            .unwrap_or_default()
+            .to_string();
+        return Ok(request);
+    }
+
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        "--allocations".to_string(),
+        "--format=Partition,Timelimit,ReqMem,ReqTRES,Account,JobName".to_string(),
+        format!("--jobs={}", job_id),
+    ];
+    let output = execute_command("sacct", args).await?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fields: Vec<String> = stdout
+        .lines()
+        .next()
+        .map(|line| line.split('|').map(str::to_string).collect())
+        .unwrap_or_default();
+    let field = |i: usize| known(fields.get(i));
+    request.partition = field(0);
+    // "Partition_Limit" means no limit of its own was asked for
+    request.time_limit = Some(field(1))
+        .filter(|time| parse_time_limit(time).is_some())
+        .unwrap_or_default();
+    // Older Slurm marks memory per node with 'n' and per CPU with 'c'
+    let memory = field(2);
+    request.memory = match memory.strip_suffix('n') {
+        Some(per_node) => per_node.to_string(),
+        None if memory.ends_with('c') => String::new(),
+        None => memory,
+    };
+    // The total CPU count in ReqTRES is not the per-task count, so CPUs are left
+    // to the script; "gres/gpu=2" or "gres/gpu:a100=2"
+    request.gpus = field(3)
+        .split(',')
+        .find_map(|tres| {
+            let (name, count) = tres.split_once('=')?;
+            match name.strip_prefix("gres/gpu")? {
+                "" => Some(count.to_string()),
+                kind => Some(format!("{}:{}", kind.strip_prefix(':')?, count)),
+            }
+        })
+        .unwrap_or_default();
+    request.account = field(4);
+    request.name = field(5);
+    Ok(request)
+}
+
+/// Ask the scheduler when a job would start without submitting it, with
+/// `sbatch --test-only`, e.g. "Job 123 to start at 2024-05-01T10:00:00 using 4
+/// processors on nodes n01 in partition cpu"
+pub async fn test_submit(command: &SlurmCommand) -> Result<String> {
+    let mut args = command.args.clone();
+    args.insert(0, "--test-only".to_string());
+    let output = execute_command(&command.program, args).await?;
+    // The estimate and any error are both printed on stderr
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .map(|line| line.trim_start_matches("sbatch: ").trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("; ");
+    if output.status.success() {
+        Ok(message)
+    } else {
+        Err(eyre!("{}", message))
+    }
+}
+
+/// Parse whitespace separated key=value pairs as printed by scontrol
+pub fn parse_scontrol_output(output: &str) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+
+    for part in output.split_whitespace() {
+        if let Some(index) = part.find('=') {
+            let key = &part[0..index];
+            let value = &part[(index + 1)..];
+            result.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    result
+}
+
+/// Key=value pairs of a (multi-line) `scontrol show` record, in output order.
+/// Values may contain spaces, e.g. a `Command` with arguments: a word that does
+/// not start with `Key=` is appended to the previous value.
+pub fn parse_scontrol_fields(output: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for word in output.split_whitespace() {
+        let pair = word
+            .split_once('=')
+            .filter(|(key, _)| key.starts_with(|c: char| c.is_ascii_alphabetic()));
+        match (pair, fields.last_mut()) {
+            (Some((key, value)), _) => fields.push((key.to_string(), value.to_string())),
+            (None, Some((_, value))) => {
+                value.push(' ');
+                value.push_str(word);
+            }
+            (None, None) => {}
+        }
+    }
+    fields
+}
+
+/// Maximum number of job IDs passed to a single command, to keep command lines short
+const MAX_IDS_PER_COMMAND: usize = 200;
+
+/// A state-changing Slurm command, kept as data so it can be previewed (dry-run)
+/// before it is run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlurmCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Jobs affected by the command, for the audit log
+    pub job_ids: Vec<String>,
+}
+
+impl SlurmCommand {
+    pub fn new(program: &str, args: Vec<String>, job_ids: Vec<String>) -> Self {
+        Self {
+            program: program.to_string(),
+            args,
+            job_ids,
+        }
+    }
+}
+
+/// Outcome of running a [`SlurmCommand`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandStatus {
+    Success,
+    /// Exited with a non-zero code (`None` if killed by a signal)
+    Failed {
+        code: Option<i32>,
+        stderr: String,
+    },
+    /// The command could not be started
+    NotRun(String),
+}
+
+impl fmt::Display for CommandStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandStatus::Success => write!(f, "ok"),
+            CommandStatus::Failed {
+                code: Some(code),
+                stderr,
+            } => write!(f, "exit {}: {}", code, stderr),
+            CommandStatus::Failed { code: None, stderr } => write!(f, "killed: {}", stderr),
+            CommandStatus::NotRun(e) => write!(f, "not run: {}", e),
+        }
+    }
+}
+
+impl fmt::Display for SlurmCommand {
+    /// Format as a copy-pasteable shell command line
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", shell_quote(arg))?;
+        }
+        Ok(())
+    }
+}
+
+/// Quote an argument for display if the shell would otherwise split or expand it
+pub fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-+=:,./@%".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Run a state-changing command and report how it went
+pub async fn run_command(command: &SlurmCommand) -> CommandStatus {
+    run_command_output(command).await.0
+}
+
+/// Run a state-changing command and report how it went, with what it printed
+/// on success (e.g. the ID of a job submitted with sbatch)
+pub async fn run_command_output(command: &SlurmCommand) -> (CommandStatus, String) {
+    match execute_command(&command.program, command.args.clone()).await {
+        Ok(output) if output.status.success() => (
+            CommandStatus::Success,
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        Ok(output) => (
+            CommandStatus::Failed {
+                code: output.status.code(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            },
+            String::new(),
+        ),
+        Err(e) => (CommandStatus::NotRun(e.to_string()), String::new()),
+    }
+}
+
+/// Command submitting a job's batch script again from its working directory
+pub fn resubmit_command(job_id: &str, script: &str, work_dir: &str) -> SlurmCommand {
+    SlurmCommand::new(
+        "sbatch",
+        vec![format!("--chdir={}", work_dir), script.to_string()],
+        vec![job_id.to_string()],
+    )
+}
+
+/// Command opening a file in the user's editor (`$VISUAL`, `$EDITOR`, then `vi`)
+pub fn editor_command(path: &str) -> SlurmCommand {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    // The editor may come with arguments, e.g. "code --wait"
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let mut args: Vec<String> = words.map(str::to_string).collect();
+    args.push(path.to_string());
+    SlurmCommand::new(program, args, Vec::new())
+}
+
+/// Commands applying a bulk action to jobs: `scancel` or `scontrol hold|release|requeue`
+pub fn job_action_commands(action: JobAction, job_ids: &[String]) -> Vec<SlurmCommand> {
+    job_ids
+        .chunks(MAX_IDS_PER_COMMAND)
+        .map(|chunk| match action {
+            JobAction::Cancel => SlurmCommand::new("scancel", chunk.to_vec(), chunk.to_vec()),
+            _ => SlurmCommand::new(
+                "scontrol",
+                vec![action.verb().to_string(), chunk.join(",")],
+                chunk.to_vec(),
+            ),
+        })
+        .collect()
+}
+
+/// Commands sending a signal to jobs with `scancel --signal`
+///
+/// With `batch_only` the signal goes to the batch shell only (`--batch`), which is
+/// what scripts trapping e.g. USR1 for checkpointing usually expect.
+pub fn signal_commands(job_ids: &[String], signal: &str, batch_only: bool) -> Vec<SlurmCommand> {
+    job_ids
+        .chunks(MAX_IDS_PER_COMMAND)
+        .map(|chunk| {
+            let mut args = vec![format!("--signal={}", signal)];
+            if batch_only {
+                args.push("--batch".to_string());
+            }
+            args.extend(chunk.iter().cloned());
+            SlurmCommand::new("scancel", args, chunk.to_vec())
+        })
+        .collect()
+}
+
+/// Command modifying a job with `scontrol update`
+///
+/// Parameters are passed in order as `Key=Value` pairs.
+pub fn modify_job_command(job_id: &str, parameters: &[(String, String)]) -> SlurmCommand {
+    let mut args = vec!["update".to_string(), format!("JobId={}", job_id)];
     for (key, value) in parameters {
         args.push(format!("{}={}", key, value));
     }
+    SlurmCommand::new("scontrol", args, vec![job_id.to_string()])
+}
+
+/// Command opening an interactive shell inside a running job's allocation
+///
+/// `--overlap` lets the step share resources already used by the job's own steps.
+pub fn attach_shell_command(job_id: &str) -> SlurmCommand {
+    let args = vec![
+        format!("--jobid={}", job_id),
+        "--overlap".to_string(),
+        "--pty".to_string(),
+        "bash".to_string(),
+    ];
+    SlurmCommand::new("srun", args, vec![job_id.to_string()])
+}
+
+/// Command starting the user's `$SHELL` in a directory
+pub fn work_dir_shell_command(work_dir: &str, job_id: &str) -> SlurmCommand {
+    let args = vec![
+        "-c".to_string(),
+        "cd -- \"$1\" && exec \"${SHELL:-/bin/sh}\"".to_string(),
+        "sh".to_string(),
+        work_dir.to_string(),
+    ];
+    SlurmCommand::new("sh", args, vec![job_id.to_string()])
+}
 
-    let _ = execute_command("scontrol", args).await?;
-    Ok(())
+/// Command opening an SSH session to a node of a job
+pub fn ssh_command(node: &str, job_id: &str) -> SlurmCommand {
+    SlurmCommand::new("ssh", vec![node.to_string()], vec![job_id.to_string()])
 }
 
 /// Get available partitions
@@ -73,6 +599,82 @@ pub async fn get_partitions() -> Result<Vec<String>> {
     Ok(partitions)
 }
 
+/// Get the name of the cluster from the Slurm configuration
+pub async fn get_cluster_name() -> Result<String> {
+    let output =
+        execute_command("scontrol", vec!["show".to_string(), "config".to_string()]).await?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let name = stdout
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "ClusterName")
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    name.ok_or_else(|| eyre!("ClusterName not found in scontrol show config"))
+}
+
+/// Fetch partition limits (`scontrol show partition`) and per-job QoS limits
+/// (`sacctmgr show qos`) used to validate forms
+pub async fn get_cluster_limits() -> Result<ClusterLimits> {
+    let args = vec![
+        "show".to_string(),
+        "partition".to_string(),
+        "-o".to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let mut partitions = parse_partition_limits(&String::from_utf8_lossy(&output.stdout));
+
+    // Node memory only refines the memory check, so a failing sinfo is not an error
+    let args = ["-h", "-N", "-o", "%P|%m"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    if let Ok(output) = execute_command("sinfo", args).await {
+        if output.status.success() {
+            for (name, memory) in parse_node_memory(&String::from_utf8_lossy(&output.stdout)) {
+                if let Some(limits) = partitions.get_mut(&name) {
+                    limits.max_node_memory = Some(memory);
+                }
+            }
+        }
+    }
+
+    // The GPU inventory only offers choices in the form, so it is optional too
+    let args = ["-h", "-o", "%P|%G"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    if let Ok(output) = execute_command("sinfo", args).await {
+        if output.status.success() {
+            for (name, gpus) in parse_node_gres(&String::from_utf8_lossy(&output.stdout)) {
+                if let Some(limits) = partitions.get_mut(&name) {
+                    limits.gpus = gpus;
+                }
+            }
+        }
+    }
+
+    // QoS limits are optional: sacctmgr needs slurmdbd, which not every cluster runs
+    let args = ["-n", "-P", "show", "qos", "format=Name,MaxWall,MaxTRES"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let qos = match execute_command("sacctmgr", args).await {
+        Ok(output) if output.status.success() => {
+            parse_qos_limits(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => HashMap::new(),
+    };
+
+    Ok(ClusterLimits { partitions, qos })
+}
+
 /// Get available QOS options
 pub async fn get_qos() -> Result<Vec<String>> {
     let output = execute_command(
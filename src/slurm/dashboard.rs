@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::{
+    command::{execute_command, parse_scontrol_output},
+    gpu::{is_unavailable, parse_node_gpus},
+    node::watts,
+    partition::StateCounts,
+};
+use crate::utils::get_username;
+
+/// Pending reasons listed on the dashboard
+const TOP_REASONS: usize = 5;
+
+/// Headline numbers of the cluster, for the dashboard
+#[derive(Debug, Clone, Default)]
+pub struct ClusterSummary {
+    /// Jobs per state, most first
+    pub jobs: Vec<(String, u32)>,
+    /// Nodes per state, most first
+    pub nodes: Vec<(String, u32)>,
+    pub cpus: StateCounts,
+    pub gpus: StateCounts,
+    pub my_running: u32,
+    pub my_pending: u32,
+    /// The most common reasons jobs are pending, most first
+    pub pending_reasons: Vec<(String, u32)>,
+    /// Power draw of the nodes that report it, where acct_gather_energy is configured
+    pub power: Option<PowerDraw>,
+}
+
+/// Power draw added up over the nodes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PowerDraw {
+    pub current_watts: u64,
+    pub average_watts: u64,
+    /// Nodes reporting a draw
+    pub nodes: u32,
+}
+
+/// Count the jobs of every user (`squeue`) and the nodes, CPUs and GPUs of
+/// every node (`scontrol -d show node`)
+pub async fn get_cluster_summary() -> Result<ClusterSummary> {
+    let args = ["-h", "-o", "%T|%u|%r"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let output = execute_command("squeue", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let mut summary = ClusterSummary::default();
+    count_jobs(
+        &mut summary,
+        &String::from_utf8_lossy(&output.stdout),
+        &get_username(),
+    );
+
+    let args = vec![
+        "-d".to_string(),
+        "show".to_string(),
+        "node".to_string(),
+        "-o".to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    count_nodes(&mut summary, &String::from_utf8_lossy(&output.stdout));
+    Ok(summary)
+}
+
+/// Count `squeue -h -o %T|%u|%r` lines by state, and the pending ones by reason
+fn count_jobs(summary: &mut ClusterSummary, output: &str, user: &str) {
+    let mut states: HashMap<&str, u32> = HashMap::new();
+    let mut reasons: HashMap<&str, u32> = HashMap::new();
+    for line in output.lines() {
+        let cols: Vec<&str> = line.split('|').map(str::trim).collect();
+        let [state, owner, reason] = cols[..] else {
+            continue;
+        };
+        *states.entry(state).or_default() += 1;
+        match state {
+            "PENDING" => {
+                *reasons.entry(reason).or_default() += 1;
+                if owner == user {
+                    summary.my_pending += 1;
+                }
+            }
+            "RUNNING" if owner == user => summary.my_running += 1,
+            _ => {}
+        }
+    }
+    summary.jobs = ranked(states);
+    summary.pending_reasons = ranked(reasons);
+    summary.pending_reasons.truncate(TOP_REASONS);
+}
+
+/// Count `scontrol -d show node -o` lines by state, and add up their CPUs,
+/// GPUs and power draw
+fn count_nodes(summary: &mut ClusterSummary, output: &str) {
+    let mut states: HashMap<String, u32> = HashMap::new();
+    let mut power = PowerDraw::default();
+    for line in output.lines() {
+        let fields = parse_scontrol_output(line);
+        if !fields.contains_key("NodeName") {
+            continue;
+        }
+        let state = fields.get("State").map_or("", String::as_str);
+        *states.entry(node_state(state)).or_default() += 1;
+
+        let number = |name: &str| fields.get(name).and_then(|n| n.parse::<u32>().ok());
+        let total = number("CPUEfctv").or(number("CPUTot")).unwrap_or(0);
+        let allocated = number("CPUAlloc").unwrap_or(0).min(total);
+        summary.cpus.total += total;
+        summary.cpus.allocated += allocated;
+        if is_unavailable(state) {
+            summary.cpus.other += total - allocated;
+        } else {
+            summary.cpus.idle += total - allocated;
+        }
+
+        if let Some(current) = fields.get("CurrentWatts").and_then(|w| watts(w)) {
+            power.current_watts += current as u64;
+            power.average_watts += fields
+                .get("AveWatts")
+                .and_then(|w| watts(w))
+                .unwrap_or(current) as u64;
+            power.nodes += 1;
+        }
+    }
+    summary.nodes = ranked(states);
+    summary.power = (power.nodes > 0).then_some(power);
+
+    let (pools, _) = parse_node_gpus(output);
+    for pool in pools {
+        summary.gpus.allocated += pool.counts.allocated;
+        summary.gpus.idle += pool.counts.idle;
+        summary.gpus.other += pool.counts.other;
+        summary.gpus.total += pool.counts.total;
+    }
+}
+
+/// State a node is counted under: its base state without flags and suffixes,
+/// except that draining nodes count as DRAINING or DRAINED and unresponsive
+/// ones as NOT_RESPONDING
+fn node_state(state: &str) -> String {
+    let mut flags = state.split('+');
+    let base = flags.next().unwrap_or_default();
+    let bare = base.trim_end_matches(['*', '~', '#', '%', '$', '@', '!', '^', '-']);
+    if base.ends_with('*') && bare != "DOWN" {
+        return "NOT_RESPONDING".to_string();
+    }
+    if flags.any(|flag| flag.starts_with("DRAIN")) {
+        let drained = matches!(bare, "IDLE" | "DOWN");
+        return if drained { "DRAINED" } else { "DRAINING" }.to_string();
+    }
+    if bare.is_empty() {
+        "UNKNOWN".to_string()
+    } else {
+        bare.to_string()
+    }
+}
+
+/// Counts as a list, most first and then by name
+fn ranked<K: ToString>(counts: HashMap<K, u32>) -> Vec<(String, u32)> {
+    let mut ranked: Vec<(String, u32)> = counts
+        .into_iter()
+        .map(|(key, count)| (key.to_string(), count))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked
+}
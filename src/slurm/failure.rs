@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// Why a finished job did not succeed, derived from its accounting state and
+/// exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    OutOfMemory,
+    Timeout,
+    NodeFail,
+    /// Killed by this signal
+    Signal(u32),
+    /// Exited with this non-zero code
+    Exit(u32),
+}
+
+impl fmt::Display for FailureClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureClass::OutOfMemory => write!(f, "OOM"),
+            FailureClass::Timeout => write!(f, "timeout"),
+            FailureClass::NodeFail => write!(f, "node fail"),
+            FailureClass::Signal(signal) => write!(f, "signal {}", signal),
+            FailureClass::Exit(code) => write!(f, "exit {}", code),
+        }
+    }
+}
+
+/// Classify a job from its sacct `State` and `ExitCode` (`code:signal`);
+/// `None` for jobs that succeeded or have not finished
+pub fn classify_failure(state: &str, exit_code: &str) -> Option<FailureClass> {
+    // "CANCELLED by 1234" -> "CANCELLED"
+    match state.split_whitespace().next().unwrap_or("") {
+        "OUT_OF_MEMORY" | "OOM" => return Some(FailureClass::OutOfMemory),
+        "TIMEOUT" | "TO" | "DEADLINE" | "DL" => return Some(FailureClass::Timeout),
+        "NODE_FAIL" | "NF" | "BOOT_FAIL" | "BF" => return Some(FailureClass::NodeFail),
+        _ => {}
+    }
+    let (code, signal) = exit_code.split_once(':')?;
+    match (code.parse::<u32>().ok()?, signal.parse::<u32>().ok()?) {
+        (_, signal) if signal > 0 => Some(FailureClass::Signal(signal)),
+        (code, _) if code > 0 => Some(FailureClass::Exit(code)),
+        _ => None,
+    }
+}
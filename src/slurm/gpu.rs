@@ -0,0 +1,337 @@
+use std::{collections::HashMap, process::Command, time::Duration};
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::{
+    command::{execute_command, parse_scontrol_output},
+    expand_hostlist,
+    limits::gpu_entries,
+    partition::StateCounts,
+};
+use crate::{
+    config::{GpuAccess, GpuSettings},
+    utils::poller::Poller,
+};
+
+/// Fields queried from `nvidia-smi`, in the order they are parsed
+const QUERY: &str = "--query-gpu=index,name,utilization.gpu,memory.used,memory.total";
+
+/// One GPU as reported by `nvidia-smi`
+#[derive(Debug, Clone)]
+pub struct GpuReading {
+    pub index: String,
+    pub name: String,
+    /// Utilization in percent
+    pub utilization: u32,
+    /// Memory in MiB
+    pub memory_used: u64,
+    pub memory_total: u64,
+}
+
+/// Node to query for a running job that was allocated GPUs: its batch host
+pub fn gpu_node(fields: &[(String, String)]) -> Option<String> {
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let has_gpus = fields.iter().any(|(key, value)| {
+        (key.contains("TRES") || key.starts_with("TresPer") || key == "Gres")
+            && value.contains("gpu")
+    });
+    (has_gpus && field("JobState") == Some("RUNNING"))
+        .then(|| field("BatchHost").map(str::to_string))
+        .flatten()
+}
+
+/// Runs `nvidia-smi` on a job's node every few seconds on a background
+/// thread, until the monitor is dropped
+pub struct GpuMonitor {
+    pub job_id: String,
+    poller: Poller<Result<Vec<GpuReading>, String>>,
+}
+
+impl GpuMonitor {
+    /// Start reading out the GPUs of `job_id` on `node`
+    pub fn spawn(job_id: &str, node: &str, settings: &GpuSettings) -> Self {
+        let interval = Duration::from_secs(settings.interval_secs.max(1));
+        let command = query_command(job_id, node, settings.access);
+        Self {
+            job_id: job_id.to_string(),
+            poller: Poller::spawn(interval, move || run_query(&command)),
+        }
+    }
+
+    /// Newest reading since the last call, if any
+    pub fn latest(&self) -> Option<Result<Vec<GpuReading>, String>> {
+        self.poller.drain().pop()
+    }
+}
+
+/// Program and arguments that run `nvidia-smi` on the node. Through `srun`
+/// the query runs inside the job's allocation, so only its GPUs are listed.
+fn query_command(job_id: &str, node: &str, access: GpuAccess) -> Vec<String> {
+    let mut command: Vec<String> = match access {
+        GpuAccess::Srun => vec![
+            "srun".to_string(),
+            format!("--jobid={}", job_id),
+            "--overlap".to_string(),
+            format!("--nodelist={}", node),
+            "--ntasks=1".to_string(),
+        ],
+        GpuAccess::Ssh => vec!["ssh".to_string(), node.to_string()],
+    };
+    command.extend([
+        "nvidia-smi".to_string(),
+        QUERY.to_string(),
+        "--format=csv,noheader,nounits".to_string(),
+    ]);
+    command
+}
+
+fn run_query(command: &[String]) -> Result<Vec<GpuReading>, String> {
+    let output = Command::new(&command[0])
+        .args(&command[1..])
+        .output()
+        .map_err(|e| format!("failed to run {}: {}", command[0], e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .next()
+            .unwrap_or("nvidia-smi failed")
+            .to_string());
+    }
+    Ok(parse_readings(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `nvidia-smi --format=csv,noheader,nounits` lines in [`QUERY`] order
+fn parse_readings(output: &str) -> Vec<GpuReading> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split(',').map(str::trim).collect();
+            if f.len() < 5 {
+                return None;
+            }
+            Some(GpuReading {
+                index: f[0].to_string(),
+                name: f[1].to_string(),
+                utilization: f[2].parse().unwrap_or(0),
+                memory_used: f[3].parse().unwrap_or(0),
+                memory_total: f[4].parse().unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// GPUs of one type across the cluster, for the GPU view
+#[derive(Debug, Clone, Default)]
+pub struct GpuPool {
+    /// Type such as "a100", "gpu" for untyped gres
+    pub name: String,
+    /// Nodes with GPUs of this type
+    pub nodes: u32,
+    /// GPUs by state; the free GPUs of down, drained and failing nodes count as other
+    pub counts: StateCounts,
+    /// Running jobs holding GPUs of this type, most GPUs first
+    pub holders: Vec<GpuHolder>,
+}
+
+/// A running job and the GPUs of one type it holds
+#[derive(Debug, Clone)]
+pub struct GpuHolder {
+    pub job_id: String,
+    pub user: String,
+    /// Hostlist of the job, e.g. "gpu[01-02]"
+    pub nodes: String,
+    pub count: u32,
+}
+
+/// Node states whose free GPUs cannot be allocated
+const UNAVAILABLE_STATES: [&str; 8] = [
+    "DOWN",
+    "DRAIN",
+    "DRAINED",
+    "DRAINING",
+    "FAIL",
+    "FAILING",
+    "NOT_RESPONDING",
+    "INVAL",
+];
+
+/// Aggregate the gres of every node (`scontrol -d show node`) into GPUs per
+/// type, with the running jobs holding them (`scontrol show job`)
+pub async fn get_gpu_inventory() -> Result<Vec<GpuPool>> {
+    let args = vec![
+        "-d".to_string(),
+        "show".to_string(),
+        "node".to_string(),
+        "-o".to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let (mut pools, node_types) = parse_node_gpus(&String::from_utf8_lossy(&output.stdout));
+
+    // The holders are a nicety: a failing scontrol leaves them empty
+    let args = vec!["show".to_string(), "job".to_string(), "-o".to_string()];
+    if let Ok(output) = execute_command("scontrol", args).await {
+        if output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            for (name, holder) in parse_gpu_holders(&stdout, &node_types) {
+                match pools.iter_mut().find(|pool| pool.name == name) {
+                    Some(pool) => pool.holders.push(holder),
+                    None => pools.push(GpuPool {
+                        name,
+                        holders: vec![holder],
+                        ..GpuPool::default()
+                    }),
+                }
+            }
+        }
+    }
+
+    pools.sort_by(|a, b| a.name.cmp(&b.name));
+    for pool in &mut pools {
+        pool.holders
+            .sort_by(|a, b| b.count.cmp(&a.count).then(a.job_id.cmp(&b.job_id)));
+    }
+    Ok(pools)
+}
+
+/// Whether a node in `state` cannot take jobs: "IDLE+DRAIN", "MIXED+DRAIN"
+/// or "DOWN*", where a trailing * means not responding
+pub(super) fn is_unavailable(state: &str) -> bool {
+    state.split('+').any(|flag| {
+        flag.ends_with('*')
+            || UNAVAILABLE_STATES
+                .contains(&flag.trim_end_matches(['~', '#', '%', '$', '@', '!', '^', '-']))
+    })
+}
+
+/// Parse `scontrol -d show node -o` output into GPU pools, along with the GPU
+/// types of every node
+pub(super) fn parse_node_gpus(output: &str) -> (Vec<GpuPool>, HashMap<String, Vec<String>>) {
+    let mut pools: Vec<GpuPool> = Vec::new();
+    let mut node_types: HashMap<String, Vec<String>> = HashMap::new();
+    for line in output.lines() {
+        let fields = parse_scontrol_output(line);
+        let Some(node) = fields.get("NodeName") else {
+            continue;
+        };
+        let used = fields
+            .get("GresUsed")
+            .map(|g| gpu_entries(g))
+            .unwrap_or_default();
+        let unavailable = is_unavailable(fields.get("State").map_or("", String::as_str));
+
+        for (name, count) in gpu_entries(fields.get("Gres").map_or("", String::as_str)) {
+            let allocated = used
+                .iter()
+                .filter(|(used_name, _)| *used_name == name)
+                .map(|(_, n)| *n)
+                .sum::<u32>()
+                .min(count);
+            let name = name.unwrap_or_else(|| "gpu".to_string());
+            node_types
+                .entry(node.clone())
+                .or_default()
+                .push(name.clone());
+
+            let pool = match pools.iter().position(|pool| pool.name == name) {
+                Some(i) => &mut pools[i],
+                None => {
+                    pools.push(GpuPool {
+                        name,
+                        ..GpuPool::default()
+                    });
+                    pools.last_mut().unwrap()
+                }
+            };
+            pool.nodes += 1;
+            pool.counts.total += count;
+            pool.counts.allocated += allocated;
+            if unavailable {
+                pool.counts.other += count - allocated;
+            } else {
+                pool.counts.idle += count - allocated;
+            }
+        }
+    }
+    (pools, node_types)
+}
+
+/// Parse `scontrol show job -o` output into the GPUs each running job holds,
+/// per type. Untyped allocations ("gres/gpu=2") go to the GPU type of the
+/// job's nodes when they have only one.
+fn parse_gpu_holders(
+    output: &str,
+    node_types: &HashMap<String, Vec<String>>,
+) -> Vec<(String, GpuHolder)> {
+    let mut holders = Vec::new();
+    for line in output.lines() {
+        let fields = parse_scontrol_output(line);
+        if fields.get("JobState").map(String::as_str) != Some("RUNNING") {
+            continue;
+        }
+        let Some(tres) = fields.get("AllocTRES").or_else(|| fields.get("TRES")) else {
+            continue;
+        };
+        let mut typed: Vec<(String, u32)> = Vec::new();
+        let mut untyped = 0;
+        for entry in tres.split(',') {
+            let Some((key, value)) = entry.split_once('=') else {
+                continue;
+            };
+            let Ok(count) = value.parse::<u32>() else {
+                continue;
+            };
+            match key.strip_prefix("gres/gpu") {
+                Some("") => untyped = count,
+                Some(name) if name.starts_with(':') => typed.push((name[1..].to_string(), count)),
+                _ => {}
+            }
+        }
+        let nodes = fields.get("NodeList").cloned().unwrap_or_default();
+        if typed.is_empty() && untyped > 0 {
+            let mut types: Vec<&String> = expand_hostlist(&nodes)
+                .iter()
+                .filter_map(|node| node_types.get(node))
+                .flatten()
+                .collect();
+            types.sort();
+            types.dedup();
+            let name = match types[..] {
+                [only] => only.clone(),
+                _ => "gpu".to_string(),
+            };
+            typed.push((name, untyped));
+        }
+
+        let job_id = match (fields.get("ArrayJobId"), fields.get("ArrayTaskId")) {
+            (Some(array), Some(task)) => format!("{}_{}", array, task),
+            _ => fields.get("JobId").cloned().unwrap_or_default(),
+        };
+        // "alice(1001)"
+        let user = fields
+            .get("UserId")
+            .map(|u| u.split('(').next().unwrap_or(u).to_string())
+            .unwrap_or_default();
+        for (name, count) in typed {
+            holders.push((
+                name,
+                GpuHolder {
+                    job_id: job_id.clone(),
+                    user: user.clone(),
+                    nodes: nodes.clone(),
+                    count,
+                },
+            ));
+        }
+    }
+    holders
+}
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use super::{command::parse_scontrol_output, format_size, parse_time_limit};
+
+/// Limits of a partition as reported by `scontrol show partition`
+#[derive(Debug, Clone, Default)]
+pub struct PartitionLimits {
+    /// Maximum walltime, e.g. "2-00:00:00" or "UNLIMITED"
+    pub max_time: Option<String>,
+    /// Maximum nodes per job, `None` if unlimited
+    pub max_nodes: Option<u32>,
+    pub total_nodes: Option<u32>,
+    pub total_cpus: Option<u32>,
+    /// QoS allowed on the partition, `None` meaning all
+    pub allow_qos: Option<Vec<String>>,
+    /// Accounts allowed on the partition, `None` meaning all
+    pub allow_accounts: Option<Vec<String>>,
+    pub deny_accounts: Vec<String>,
+    /// GRES names configured on the partition's nodes (e.g. "gpu")
+    pub gres: Vec<String>,
+    /// Memory of the partition's largest node in bytes, from `sinfo`
+    pub max_node_memory: Option<u64>,
+    /// GPU types on the partition's nodes, from `sinfo`
+    pub gpus: Vec<GpuType>,
+}
+
+/// GPUs of one type configured on a partition's nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuType {
+    /// Type such as "a100", `None` for untyped `gpu:4` gres
+    pub name: Option<String>,
+    /// Most GPUs of this type on a single node
+    pub per_node: u32,
+}
+
+impl PartitionLimits {
+    /// Every valid value of the form's GPU field (`--gres=gpu:<value>`), e.g.
+    /// "a100:1" to "a100:4" for nodes with four A100s
+    pub fn gpu_choices(&self) -> Vec<String> {
+        self.gpus
+            .iter()
+            .flat_map(|gpu| {
+                (1..=gpu.per_node).map(move |count| match &gpu.name {
+                    Some(name) => format!("{}:{}", name, count),
+                    None => count.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// GPU types and per-node counts for display, e.g. "a100 ×4, v100 ×2"
+    pub fn gpu_summary(&self) -> String {
+        self.gpus
+            .iter()
+            .map(|gpu| format!("{} ×{}", gpu.name.as_deref().unwrap_or("gpu"), gpu.per_node))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Per-job limits of a QoS as reported by `sacctmgr show qos`
+#[derive(Debug, Clone, Default)]
+pub struct QosLimits {
+    pub max_wall: Option<String>,
+    pub max_cpus: Option<u32>,
+    pub max_nodes: Option<u32>,
+}
+
+/// Partition and QoS limits used to validate forms before Slurm rejects a request
+#[derive(Debug, Clone, Default)]
+pub struct ClusterLimits {
+    pub partitions: HashMap<String, PartitionLimits>,
+    pub qos: HashMap<String, QosLimits>,
+}
+
+/// Requested resources to check against the limits; `None` fields are not checked
+#[derive(Debug, Clone, Default)]
+pub struct ResourceRequest<'a> {
+    pub partition: &'a str,
+    pub qos: Option<&'a str>,
+    pub account: Option<&'a str>,
+    pub time_limit: Option<&'a str>,
+    pub cpus: Option<u32>,
+    pub nodes: Option<u32>,
+    pub gres: Option<&'a str>,
+    /// Memory per node in bytes
+    pub memory: Option<u64>,
+}
+
+/// Resource a limit violation refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitField {
+    TimeLimit,
+    QoS,
+    Account,
+    Cpus,
+    Nodes,
+    Gres,
+    Memory,
+}
+
+impl ClusterLimits {
+    /// Check a request, returning a short message for every violated limit
+    pub fn check(&self, req: &ResourceRequest) -> Vec<(LimitField, String)> {
+        let mut errors = Vec::new();
+        let part = self.partitions.get(req.partition);
+        let qos = req.qos.and_then(|q| self.qos.get(q));
+
+        if let Some(time) = req.time_limit.and_then(parse_time_limit) {
+            let max_part = part.and_then(|p| p.max_time.as_deref());
+            let max_qos = qos.and_then(|q| q.max_wall.as_deref());
+            for (max, owner) in [(max_part, "partition"), (max_qos, "QoS")] {
+                if let Some(max) = max {
+                    if parse_time_limit(max).is_some_and(|m| time > m) {
+                        errors.push((LimitField::TimeLimit, format!("{} max is {}", owner, max)));
+                    }
+                }
+            }
+        }
+
+        if let Some(cpus) = req.cpus {
+            if let Some(total) = part.and_then(|p| p.total_cpus).filter(|&t| cpus > t) {
+                errors.push((LimitField::Cpus, format!("partition has {} CPUs", total)));
+            }
+            if let Some(max) = qos.and_then(|q| q.max_cpus).filter(|&m| cpus > m) {
+                errors.push((LimitField::Cpus, format!("QoS max is {} CPUs", max)));
+            }
+        }
+
+        if let Some(nodes) = req.nodes {
+            if let Some(max) = part.and_then(|p| p.max_nodes).filter(|&m| nodes > m) {
+                errors.push((LimitField::Nodes, format!("partition max is {} nodes", max)));
+            }
+            if let Some(total) = part.and_then(|p| p.total_nodes).filter(|&t| nodes > t) {
+                errors.push((LimitField::Nodes, format!("partition has {} nodes", total)));
+            }
+            if let Some(max) = qos.and_then(|q| q.max_nodes).filter(|&m| nodes > m) {
+                errors.push((LimitField::Nodes, format!("QoS max is {} nodes", max)));
+            }
+        }
+
+        if let Some(memory) = req.memory {
+            if let Some(max) = part.and_then(|p| p.max_node_memory).filter(|&m| memory > m) {
+                let message = format!("largest node has {}", format_size(max));
+                errors.push((LimitField::Memory, message));
+            }
+        }
+
+        let Some(part) = part else {
+            return errors;
+        };
+
+        if let (Some(q), Some(allowed)) = (req.qos, &part.allow_qos) {
+            if !q.is_empty() && !allowed.iter().any(|a| a == q) {
+                errors.push((LimitField::QoS, "not allowed on this partition".to_string()));
+            }
+        }
+
+        if let Some(account) = req.account.filter(|a| !a.is_empty()) {
+            let allowed = part
+                .allow_accounts
+                .as_ref()
+                .is_none_or(|list| list.iter().any(|a| a == account));
+            if !allowed || part.deny_accounts.iter().any(|a| a == account) {
+                errors.push((
+                    LimitField::Account,
+                    "not allowed on this partition".to_string(),
+                ));
+            }
+        }
+
+        if let Some(gres) = req.gres.filter(|g| !g.is_empty()) {
+            for entry in gres.split(',') {
+                let name = entry.split(':').next().unwrap_or(entry);
+                if !part.gres.iter().any(|g| g == name) {
+                    errors.push((LimitField::Gres, format!("no {} on this partition", name)));
+                } else if name == "gpu" {
+                    errors.extend(check_gpus(part, entry).map(|e| (LimitField::Gres, e)));
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+/// Parse `scontrol show partition -o` output (one partition per line)
+pub fn parse_partition_limits(output: &str) -> HashMap<String, PartitionLimits> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields = parse_scontrol_output(line);
+            let name = fields.get("PartitionName")?.clone();
+            let limits = PartitionLimits {
+                max_time: fields.get("MaxTime").cloned(),
+                max_nodes: fields.get("MaxNodes").and_then(|v| v.parse().ok()),
+                total_nodes: fields.get("TotalNodes").and_then(|v| v.parse().ok()),
+                total_cpus: fields.get("TotalCPUs").and_then(|v| v.parse().ok()),
+                allow_qos: fields.get("AllowQos").and_then(|v| parse_list(v)),
+                allow_accounts: fields.get("AllowAccounts").and_then(|v| parse_list(v)),
+                deny_accounts: fields
+                    .get("DenyAccounts")
+                    .and_then(|v| parse_list(v))
+                    .unwrap_or_default(),
+                gres: fields
+                    .get("TRES")
+                    .map(|tres| {
+                        tres.split(',')
+                            .filter_map(|t| t.strip_prefix("gres/"))
+                            .filter_map(|t| t.split(['=', ':']).next())
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                max_node_memory: None,
+                gpus: Vec::new(),
+            };
+            Some((name, limits))
+        })
+        .collect()
+}
+
+/// Check a `gpu[:type][:count]` request against the GPUs the partition's nodes have
+fn check_gpus(part: &PartitionLimits, request: &str) -> Option<String> {
+    if part.gpus.is_empty() {
+        return None;
+    }
+    let mut parts: Vec<&str> = request.split(':').skip(1).collect();
+    let count = match parts.last().map(|c| c.parse::<u32>()) {
+        Some(Ok(count)) => {
+            parts.pop();
+            count
+        }
+        _ => 1,
+    };
+    let kind = parts.first().copied();
+    let per_node = match kind {
+        Some(kind) => match part.gpus.iter().find(|g| g.name.as_deref() == Some(kind)) {
+            Some(gpu) => gpu.per_node,
+            None => return Some(format!("no {} GPUs (has {})", kind, part.gpu_summary())),
+        },
+        None => part.gpus.iter().map(|g| g.per_node).max().unwrap_or(0),
+    };
+    (count > per_node).then(|| format!("nodes have at most {} GPUs", per_node))
+}
+
+/// Parse `sinfo -h -o %P|%G` output into the GPU types of each partition, e.g.
+/// `gpu*|gpu:a100:4(S:0-1),gpu:v100:2` or `cpu|(null)`
+pub fn parse_node_gres(output: &str) -> HashMap<String, Vec<GpuType>> {
+    let mut partitions: HashMap<String, Vec<GpuType>> = HashMap::new();
+    for line in output.lines() {
+        let Some((partition, gres)) = line.split_once('|') else {
+            continue;
+        };
+        let partition = partition.trim().trim_end_matches('*');
+        let gpus = partitions.entry(partition.to_string()).or_default();
+        for (name, count) in gpu_entries(gres) {
+            match gpus.iter_mut().find(|g| g.name == name) {
+                Some(gpu) => gpu.per_node = gpu.per_node.max(count),
+                None => gpus.push(GpuType {
+                    name,
+                    per_node: count,
+                }),
+            }
+        }
+    }
+    for gpus in partitions.values_mut() {
+        gpus.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    partitions.retain(|_, gpus| !gpus.is_empty());
+    partitions
+}
+
+/// GPU type and count of every gpu entry in a node's `Gres` or `GresUsed`,
+/// e.g. `(Some("a100"), 2)` for "gpu:a100:2(IDX:0-1)"
+pub fn gpu_entries(gres: &str) -> Vec<(Option<String>, u32)> {
+    split_gres(gres)
+        .into_iter()
+        .filter_map(|entry| {
+            // Drop the socket binding or indexes, e.g. "(S:0-1)"
+            let entry = entry.split('(').next().unwrap_or(entry).trim();
+            let mut fields: Vec<&str> = entry.split(':').collect();
+            if fields.first() != Some(&"gpu") {
+                return None;
+            }
+            let count = fields.pop()?.parse::<u32>().ok()?;
+            // Anything between the name and the count is the type ("no_consume" is a flag)
+            let name = fields
+                .get(1)
+                .filter(|t| **t != "no_consume")
+                .map(|t| t.to_string());
+            Some((name, count))
+        })
+        .collect()
+}
+
+/// Split a gres list on the commas outside the socket bindings, which may hold
+/// commas of their own
+fn split_gres(gres: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in gres.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(&gres[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(&gres[start..]);
+    entries
+}
+
+/// Parse `sacctmgr -n -P show qos format=Name,MaxWall,MaxTRES` output
+pub fn parse_qos_limits(output: &str) -> HashMap<String, QosLimits> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut cols = line.split('|');
+            let name = cols.next()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            let max_wall = cols.next().map(str::trim).filter(|s| !s.is_empty());
+            let tres = parse_tres(cols.next().unwrap_or(""));
+            let limits = QosLimits {
+                max_wall: max_wall.map(str::to_string),
+                max_cpus: tres.get("cpu").and_then(|v| v.parse().ok()),
+                max_nodes: tres.get("node").and_then(|v| v.parse().ok()),
+            };
+            Some((name.to_string(), limits))
+        })
+        .collect()
+}
+
+/// Parse `sinfo -h -N -o %P|%m` output (one line per node and partition) into the
+/// memory of each partition's largest node, in bytes
+pub fn parse_node_memory(output: &str) -> HashMap<String, u64> {
+    let mut largest: HashMap<String, u64> = HashMap::new();
+    for line in output.lines() {
+        let Some((partition, memory)) = line.split_once('|') else {
+            continue;
+        };
+        // The default partition is marked with a trailing '*'; memory is in megabytes
+        let partition = partition.trim().trim_end_matches('*');
+        let Ok(megabytes) = memory.trim().parse::<u64>() else {
+            continue;
+        };
+        let entry = largest.entry(partition.to_string()).or_default();
+        *entry = (*entry).max(megabytes * 1024 * 1024);
+    }
+    largest
+}
+
+/// Parse a comma separated list where "ALL" means no restriction
+fn parse_list(value: &str) -> Option<Vec<String>> {
+    if value == "ALL" {
+        None
+    } else {
+        Some(value.split(',').map(str::to_string).collect())
+    }
+}
+
+/// Parse a TRES string like "cpu=64,node=2,gres/gpu=4"
+fn parse_tres(value: &str) -> HashMap<&str, &str> {
+    value
+        .split(',')
+        .filter_map(|kv| kv.trim().split_once('='))
+        .collect()
+}
@@ -0,0 +1,396 @@
+use std::{
+    fs::{self, Metadata, OpenOptions},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    limits::{ClusterLimits, LimitField, ResourceRequest},
+    sacct::{parse_size, MIB},
+};
+
+/// Long options understood by sbatch, without the leading `--`
+const LONG_OPTIONS: &[&str] = &[
+    "account",
+    "acctg-freq",
+    "array",
+    "batch",
+    "bb",
+    "bbf",
+    "begin",
+    "chdir",
+    "cluster-constraint",
+    "clusters",
+    "comment",
+    "constraint",
+    "container",
+    "container-id",
+    "contiguous",
+    "core-spec",
+    "cores-per-socket",
+    "cpu-freq",
+    "cpus-per-gpu",
+    "cpus-per-task",
+    "deadline",
+    "delay-boot",
+    "dependency",
+    "distribution",
+    "error",
+    "exclude",
+    "exclusive",
+    "export",
+    "export-file",
+    "extra",
+    "extra-node-info",
+    "get-user-env",
+    "gid",
+    "gpu-bind",
+    "gpu-freq",
+    "gpus",
+    "gpus-per-node",
+    "gpus-per-socket",
+    "gpus-per-task",
+    "gres",
+    "gres-flags",
+    "hint",
+    "hold",
+    "ignore-pbs",
+    "input",
+    "job-name",
+    "kill-on-invalid-dep",
+    "licenses",
+    "mail-type",
+    "mail-user",
+    "mcs-label",
+    "mem",
+    "mem-bind",
+    "mem-per-cpu",
+    "mem-per-gpu",
+    "mincpus",
+    "network",
+    "nice",
+    "no-kill",
+    "no-requeue",
+    "nodefile",
+    "nodelist",
+    "nodes",
+    "ntasks",
+    "ntasks-per-core",
+    "ntasks-per-gpu",
+    "ntasks-per-node",
+    "ntasks-per-socket",
+    "oom-kill-step",
+    "open-mode",
+    "output",
+    "overcommit",
+    "oversubscribe",
+    "parsable",
+    "partition",
+    "power",
+    "prefer",
+    "priority",
+    "profile",
+    "propagate",
+    "qos",
+    "quiet",
+    "reboot",
+    "requeue",
+    "reservation",
+    "resv-ports",
+    "segment",
+    "signal",
+    "sockets-per-node",
+    "spread-job",
+    "stepmgr",
+    "switches",
+    "test-only",
+    "thread-spec",
+    "threads-per-core",
+    "time",
+    "time-min",
+    "tmp",
+    "tres-bind",
+    "tres-per-task",
+    "uid",
+    "use-min-nodes",
+    "verbose",
+    "wait",
+    "wait-all-nodes",
+    "wckey",
+];
+
+/// Short options of sbatch and the long option each stands for
+const SHORT_OPTIONS: &[(char, &str)] = &[
+    ('A', "account"),
+    ('a', "array"),
+    ('b', "begin"),
+    ('B', "extra-node-info"),
+    ('C', "constraint"),
+    ('c', "cpus-per-task"),
+    ('D', "chdir"),
+    ('d', "dependency"),
+    ('e', "error"),
+    ('F', "nodefile"),
+    ('G', "gpus"),
+    ('H', "hold"),
+    ('i', "input"),
+    ('J', "job-name"),
+    ('k', "no-kill"),
+    ('L', "licenses"),
+    ('M', "clusters"),
+    ('m', "distribution"),
+    ('N', "nodes"),
+    ('n', "ntasks"),
+    ('O', "overcommit"),
+    ('o', "output"),
+    ('p', "partition"),
+    ('Q', "quiet"),
+    ('q', "qos"),
+    ('S', "core-spec"),
+    ('s', "oversubscribe"),
+    ('t', "time"),
+    ('v', "verbose"),
+    ('W', "wait"),
+    ('w', "nodelist"),
+    ('x', "exclude"),
+];
+
+/// One option of an `#SBATCH` line or the sbatch command line
+struct SbatchOption {
+    /// Long name, or the option as written when it is unknown
+    name: String,
+    known: bool,
+    value: Option<String>,
+}
+
+/// Split words into options; a value is either attached (`--mem=4G`, `-p4`) or
+/// the next word when that is not an option itself
+fn parse_options(words: &[&str]) -> Vec<SbatchOption> {
+    let mut options = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        i += 1;
+        let (name, known, attached) = if let Some(long) = word.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            (name.to_string(), LONG_OPTIONS.contains(&name), value)
+        } else if let Some(short) = word.strip_prefix('-').filter(|s| !s.is_empty()) {
+            let mut chars = short.chars();
+            let flag = chars.next().unwrap_or_default();
+            let rest = chars.as_str();
+            let attached = (!rest.is_empty()).then(|| rest.to_string());
+            match SHORT_OPTIONS.iter().find(|(c, _)| *c == flag) {
+                Some((_, long)) => (long.to_string(), true, attached),
+                None => (word.to_string(), false, attached),
+            }
+        } else {
+            continue;
+        };
+        let value = attached.or_else(|| {
+            let next = words.get(i).filter(|w| !w.starts_with('-'))?;
+            i += 1;
+            Some(next.to_string())
+        });
+        options.push(SbatchOption { name, known, value });
+    }
+    options
+}
+
+/// Options of the `#SBATCH` lines in the script header, with their line numbers;
+/// sbatch stops reading directives at the first command
+fn directives(script: &str) -> Vec<(usize, SbatchOption)> {
+    let mut found = Vec::new();
+    for (i, line) in script.lines().enumerate() {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            break;
+        }
+        let Some(body) = trimmed.strip_prefix("#SBATCH") else {
+            continue;
+        };
+        // A `#` starts a comment after the options
+        let words: Vec<&str> = body
+            .split_whitespace()
+            .take_while(|w| !w.starts_with('#'))
+            .collect();
+        found.extend(parse_options(&words).into_iter().map(|o| (i + 1, o)));
+    }
+    found
+}
+
+/// Value of an option, where the command line wins over the script and a later
+/// directive over an earlier one
+fn lookup<'a>(
+    cli: &'a [SbatchOption],
+    directives: &'a [(usize, SbatchOption)],
+    name: &str,
+) -> Option<&'a str> {
+    cli.iter()
+        .rev()
+        .chain(directives.iter().rev().map(|(_, o)| o))
+        .find(|o| o.name == name)
+        .and_then(|o| o.value.as_deref())
+}
+
+/// Label of a limit in a warning
+fn limit_label(field: LimitField) -> &'static str {
+    match field {
+        LimitField::TimeLimit => "Time limit",
+        LimitField::QoS => "QoS",
+        LimitField::Account => "Account",
+        LimitField::Cpus => "CPUs",
+        LimitField::Nodes => "Nodes",
+        LimitField::Gres => "GPUs",
+        LimitField::Memory => "Memory",
+    }
+}
+
+/// Check a batch script before it is submitted with the extra sbatch `args`, which
+/// override its `#SBATCH` lines. Warns about unknown directives, requests beyond the
+/// partition's limits or node memory, and an interpreter that cannot run.
+pub fn lint_script(
+    script_path: &str,
+    script: &str,
+    args: &[String],
+    limits: &ClusterLimits,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let directives = directives(script);
+    for (line, option) in directives.iter().filter(|(_, o)| !o.known) {
+        warnings.push(format!(
+            "Line {}: unknown #SBATCH option {}",
+            line, option.name
+        ));
+    }
+
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+    let cli = parse_options(&words);
+    let value = |name: &str| lookup(&cli, &directives, name);
+
+    let gres = value("gres").or_else(|| value("gpus").map(|_| "gpu"));
+    let request = ResourceRequest {
+        partition: value("partition").unwrap_or(""),
+        qos: value("qos"),
+        account: value("account"),
+        time_limit: value("time"),
+        cpus: value("cpus-per-task").and_then(|v| v.parse().ok()),
+        nodes: value("nodes")
+            .and_then(|v| v.split('-').next())
+            .and_then(|v| v.parse().ok()),
+        gres,
+        // --mem=0 asks for all the memory of a node
+        memory: value("mem")
+            .and_then(|v| parse_size(v, MIB))
+            .filter(|&m| m > 0),
+    };
+    for (field, message) in limits.check(&request) {
+        warnings.push(format!("{}: {}", limit_label(field), message));
+    }
+
+    // The interpreter of the shebang line must be runnable on the nodes
+    if let Some(interpreter) = script
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("#!"))
+        .and_then(|rest| rest.split_whitespace().next())
+    {
+        let path = Path::new(interpreter);
+        let path = if path.is_relative() {
+            let script_dir = Path::new(script_path).parent().unwrap_or(Path::new(""));
+            script_dir.join(path)
+        } else {
+            path.to_path_buf()
+        };
+        if !path.metadata().is_ok_and(|m| is_executable(&m)) {
+            warnings.push(format!("Interpreter {} is not executable", interpreter));
+        }
+    } else {
+        warnings.push("The script has no #! line; sbatch requires one".to_string());
+    }
+
+    warnings
+}
+
+/// Paths the job needs that would make it fail right away: the working directory,
+/// the directories `--output` and `--error` write to (the working directory when
+/// unset) and the `--input` file. Unlike [`lint_script`] warnings these block the
+/// submission.
+pub fn path_problems(script: &str, args: &[String]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let directives = directives(script);
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+    let cli = parse_options(&words);
+    let value = |name: &str| lookup(&cli, &directives, name);
+
+    // Paths are resolved against the job's working directory
+    let work_dir = PathBuf::from(value("chdir").unwrap_or("."));
+    if let Some(problem) = directory_problem(&work_dir) {
+        problems.push(format!(
+            "Working directory {}: {}",
+            work_dir.display(),
+            problem
+        ));
+        return problems;
+    }
+    let mut checked = Vec::new();
+    for name in ["output", "error"] {
+        // Without --output the log is slurm-%j.out in the working directory
+        let pattern = value(name).unwrap_or("slurm-%j.out");
+        let path = work_dir.join(pattern);
+        // Directories named with `%j`-style patterns are only known once the job runs
+        let Some(dir) = path.parent().filter(|d| !d.to_string_lossy().contains('%')) else {
+            continue;
+        };
+        if checked.contains(&dir.to_path_buf()) {
+            continue;
+        }
+        checked.push(dir.to_path_buf());
+        if let Some(problem) = directory_problem(dir) {
+            problems.push(format!("--{}: {} {}", name, dir.display(), problem));
+        }
+    }
+    if let Some(input) = value("input").filter(|i| !i.contains('%')) {
+        if let Err(e) = fs::File::open(work_dir.join(input)) {
+            problems.push(format!("--input: cannot read {}: {}", input, e));
+        }
+    }
+    problems
+}
+
+/// Why the job could not write to `dir`, if it cannot
+fn directory_problem(dir: &Path) -> Option<String> {
+    match fs::metadata(dir) {
+        Err(e) if e.kind() == ErrorKind::NotFound => Some("does not exist".to_string()),
+        Err(e) => Some(format!("cannot be accessed: {}", e)),
+        Ok(metadata) if !metadata.is_dir() => Some("is not a directory".to_string()),
+        Ok(_) if !is_writable(dir) => Some("is not writable".to_string()),
+        Ok(_) => None,
+    }
+}
+
+/// Whether files can be created in `dir`; permission bits miss ACLs and read-only
+/// mounts, so a file is created and removed again
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".slurmer-write-check-{}", std::process::id()));
+    match OpenOptions::new().write(true).create_new(true).open(&probe) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            true
+        }
+        Err(e) => e.kind() == ErrorKind::AlreadyExists,
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(metadata: &Metadata) -> bool {
+    metadata.is_file()
+}
@@ -1,5 +1,27 @@
+pub mod audit;
+pub mod balance;
+pub mod batch;
+pub mod burstbuffer;
 pub mod command;
+pub mod dashboard;
+pub mod failure;
+pub mod gpu;
+pub mod limits;
+pub mod lint;
+pub mod node;
+pub mod partition;
+pub mod qos;
+pub mod reasons;
+pub mod reservation;
+pub mod retry;
+pub mod sacct;
+pub mod sdiag;
+pub mod sprio;
 pub mod squeue;
+pub mod sshare;
+pub mod sstat;
+pub mod sweep;
+pub mod users;
 
 use std::fmt;
 use std::str::FromStr;
@@ -71,6 +93,264 @@ impl FromStr for JobState {
     }
 }
 
+/// Parse a Slurm time limit into seconds.
+///
+/// Accepts the formats understood by sbatch/scontrol: "minutes", "minutes:seconds",
+/// "hours:minutes:seconds", "days-hours", "days-hours:minutes" and
+/// "days-hours:minutes:seconds". `UNLIMITED`/`INFINITE` map to `u64::MAX`;
+/// values too large for a u64 of seconds are rejected.
+pub fn parse_time_limit(s: &str) -> Option<u64> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("UNLIMITED") || s.eq_ignore_ascii_case("INFINITE") {
+        return Some(u64::MAX);
+    }
+
+    let parse_parts = |s: &str| -> Option<Vec<u64>> {
+        s.split(':')
+            .map(|p| {
+                if p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()) {
+                    None
+                } else {
+                    p.parse::<u64>().ok()
+                }
+            })
+            .collect()
+    };
+
+    if let Some((days, rest)) = s.split_once('-') {
+        let days = parse_parts(days).filter(|d| d.len() == 1)?[0];
+        let parts = parse_parts(rest)?;
+        let (h, m, sec) = match parts.as_slice() {
+            [h] => (*h, 0, 0),
+            [h, m] => (*h, *m, 0),
+            [h, m, sec] => (*h, *m, *sec),
+            _ => return None,
+        };
+        seconds(days, h, m, sec)
+    } else {
+        let parts = parse_parts(s)?;
+        match parts.as_slice() {
+            [m] => seconds(0, 0, *m, 0),
+            [m, sec] => seconds(0, 0, *m, *sec),
+            [h, m, sec] => seconds(0, *h, *m, *sec),
+            _ => None,
+        }
+    }
+}
+
+/// Days, hours, minutes and seconds added up, or `None` on overflow; a sum of
+/// `u64::MAX` would read as unlimited, so it is rejected too
+fn seconds(days: u64, hours: u64, minutes: u64, secs: u64) -> Option<u64> {
+    days.checked_mul(86400)?
+        .checked_add(hours.checked_mul(3600)?)?
+        .checked_add(minutes.checked_mul(60)?)?
+        .checked_add(secs)
+        .filter(|&total| total != u64::MAX)
+}
+
+/// Seconds as Slurm-style `D-HH:MM:SS` (or `HH:MM:SS` under a day)
+pub fn format_duration(secs: u64) -> String {
+    let (days, rest) = (secs / 86400, secs % 86400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    );
+    if days > 0 {
+        format!("{}-{}", days, time)
+    } else {
+        time
+    }
+}
+
+/// Bytes in the largest fitting binary unit, e.g. "3.2G"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// Joules in the largest fitting decimal unit, e.g. "4.1MJ"
+pub fn format_energy(joules: u64) -> String {
+    format_decimal(joules, "J")
+}
+
+/// Watts in the largest fitting decimal unit, e.g. "12.3kW"
+pub fn format_power(watts: u64) -> String {
+    format_decimal(watts, "W")
+}
+
+fn format_decimal(value: u64, unit: &str) -> String {
+    const PREFIXES: [&str; 5] = ["", "k", "M", "G", "T"];
+    let mut scaled = value as f64;
+    let mut prefix = 0;
+    while scaled >= 1000.0 && prefix + 1 < PREFIXES.len() {
+        scaled /= 1000.0;
+        prefix += 1;
+    }
+    if prefix == 0 {
+        format!("{}{}", value, unit)
+    } else {
+        format!("{:.1}{}{}", scaled, PREFIXES[prefix], unit)
+    }
+}
+
+/// First host of a Slurm hostlist expression, e.g. `node01` for `node[01-04,07],gpu1`
+pub fn first_host(node_list: &str) -> Option<String> {
+    // Only the first comma-separated entry outside brackets matters
+    let mut depth = 0;
+    let end = node_list
+        .char_indices()
+        .find(|&(_, c)| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                ',' if depth == 0 => return true,
+                _ => {}
+            }
+            false
+        })
+        .map_or(node_list.len(), |(i, _)| i);
+    let mut rest = node_list[..end].trim();
+
+    // Replace every bracketed range with its first value
+    let mut host = String::new();
+    while let Some(open) = rest.find('[') {
+        let close = rest[open..].find(']')? + open;
+        host.push_str(&rest[..open]);
+        let first = rest[open + 1..close].split([',', '-']).next()?;
+        host.push_str(first);
+        rest = &rest[close + 1..];
+    }
+    host.push_str(rest);
+    (!host.is_empty()).then_some(host)
+}
+
+/// Every host of a Slurm hostlist expression, e.g. `node01 node02 node07 gpu1`
+/// for `node[01-02,07],gpu1`; zero padding of the ranges is kept
+pub fn expand_hostlist(node_list: &str) -> Vec<String> {
+    // Split on commas outside brackets
+    let mut entries = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in node_list.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(&node_list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(&node_list[start..]);
+    entries
+        .into_iter()
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty() && *entry != "(null)" && *entry != "None")
+        .flat_map(expand_host)
+        .collect()
+}
+
+/// Hosts of one hostlist entry, which may hold several bracketed ranges
+fn expand_host(entry: &str) -> Vec<String> {
+    let Some(open) = entry.find('[') else {
+        return vec![entry.to_string()];
+    };
+    let Some(close) = entry[open..].find(']').map(|i| i + open) else {
+        return vec![entry.to_string()];
+    };
+    let (prefix, ranges) = (&entry[..open], &entry[open + 1..close]);
+    let suffixes = expand_host(&entry[close + 1..]);
+    let mut hosts = Vec::new();
+    for range in ranges.split(',') {
+        let (first, last) = range.split_once('-').unwrap_or((range, range));
+        let values = match (first.parse::<u64>(), last.parse::<u64>()) {
+            (Ok(from), Ok(to)) => (from..=to)
+                .map(|n| format!("{:0width$}", n, width = first.len()))
+                .collect(),
+            _ => vec![range.to_string()],
+        };
+        for value in values {
+            hosts.extend(
+                suffixes
+                    .iter()
+                    .map(|suffix| format!("{}{}{}", prefix, value, suffix)),
+            );
+        }
+    }
+    hosts
+}
+
+/// Bulk actions that can be applied to a set of jobs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobAction {
+    Cancel,
+    Hold,
+    Release,
+    Requeue,
+}
+
+impl JobAction {
+    /// Verb used in confirmation prompts
+    pub fn verb(&self) -> &'static str {
+        match self {
+            JobAction::Cancel => "cancel",
+            JobAction::Hold => "hold",
+            JobAction::Release => "release",
+            JobAction::Requeue => "requeue",
+        }
+    }
+
+    /// Past tense used in status messages
+    pub fn past_tense(&self) -> &'static str {
+        match self {
+            JobAction::Cancel => "Cancelled",
+            JobAction::Hold => "Held",
+            JobAction::Release => "Released",
+            JobAction::Requeue => "Requeued",
+        }
+    }
+
+    /// The Slurm command that performs this action
+    pub fn command(&self) -> &'static str {
+        match self {
+            JobAction::Cancel => "scancel",
+            JobAction::Hold => "scontrol hold",
+            JobAction::Release => "scontrol release",
+            JobAction::Requeue => "scontrol requeue",
+        }
+    }
+}
+
+/// Signal names accepted by `scancel --signal`
+const SIGNAL_NAMES: &[&str] = &[
+    "HUP", "INT", "QUIT", "ABRT", "KILL", "USR1", "USR2", "PIPE", "ALRM", "TERM", "CONT", "STOP",
+    "TSTP", "TTIN", "TTOU", "URG", "XCPU", "XFSZ", "VTALRM", "PROF", "WINCH",
+];
+
+/// Normalize a signal given by name (`USR1`, `SIGUSR1`, case-insensitive) or number
+/// into the form passed to `scancel --signal`.
+pub fn normalize_signal(s: &str) -> Option<String> {
+    let s = s.trim();
+    if let Ok(n) = s.parse::<u8>() {
+        return (1..=64).contains(&n).then(|| n.to_string());
+    }
+    let upper = s.to_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+    SIGNAL_NAMES.contains(&name).then(|| name.to_string())
+}
+
 #[derive(Debug, Clone)]
 pub struct Job {
     pub id: String,
@@ -78,6 +358,7 @@ pub struct Job {
     pub user: String,
     pub state: JobState,
     pub time: String,
+    pub time_limit: Option<String>,
     pub nodes: u32,
     pub node: Option<String>,
     pub cpus: u32,
@@ -101,6 +382,7 @@ impl Default for Job {
             user: String::new(),
             state: JobState::Other,
             time: String::new(),
+            time_limit: None,
             nodes: 0,
             node: None,
             cpus: 0,
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::{
+    command::{execute_command, parse_scontrol_fields, SlurmCommand},
+    expand_hostlist,
+};
+
+/// Node states an operator can set with `scontrol update`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeState {
+    /// Let running jobs finish but start no new ones
+    Drain,
+    /// Return a drained or down node to service
+    Resume,
+    /// Take the node out of service now
+    Down,
+}
+
+impl NodeState {
+    /// Value of `State=` in `scontrol update`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeState::Drain => "DRAIN",
+            NodeState::Resume => "RESUME",
+            NodeState::Down => "DOWN",
+        }
+    }
+
+    /// slurmctld refuses to drain or down a node without a reason
+    pub fn needs_reason(&self) -> bool {
+        !matches!(self, NodeState::Resume)
+    }
+}
+
+/// Command setting a node's state, with the reason shown by `sinfo -R`
+pub fn node_update_command(node: &str, state: NodeState, reason: Option<&str>) -> SlurmCommand {
+    let mut args = vec![
+        "update".to_string(),
+        format!("NodeName={}", node),
+        format!("State={}", state.as_str()),
+    ];
+    if let Some(reason) = reason {
+        args.push(format!("Reason={}", reason));
+    }
+    SlurmCommand::new("scontrol", args, Vec::new())
+}
+
+/// A node as reported by `scontrol -d show node`, with the jobs running on it
+#[derive(Debug, Clone, Default)]
+pub struct NodeDetail {
+    /// Every field in output order; `Reason` keeps its spaces
+    pub fields: Vec<(String, String)>,
+    /// Running and completing jobs, from squeue
+    pub jobs: Vec<String>,
+}
+
+impl NodeDetail {
+    /// Value of a field, unless Slurm reports it as unset
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .filter(|v| !v.is_empty() && *v != "(null)" && *v != "None" && *v != "N/A")
+    }
+}
+
+/// Fetch a node's configuration, allocation and drain reason, and the jobs on it
+pub async fn get_node_detail(name: &str) -> Result<NodeDetail> {
+    let args = vec![
+        "-d".to_string(),
+        "show".to_string(),
+        "node".to_string(),
+        name.to_string(),
+        "-o".to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!(
+            "scontrol show node {} failed: {}",
+            name,
+            stderr.trim()
+        ));
+    }
+    let fields = parse_scontrol_fields(&String::from_utf8_lossy(&output.stdout));
+    if fields.is_empty() {
+        return Err(eyre!("Node {} not found", name));
+    }
+
+    // The job list is a nicety: a failing squeue leaves it empty
+    let args = ["-h", "-t", "RUNNING,COMPLETING", "-o", "%i", "-w", name]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let jobs = match execute_command("squeue", args).await {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(NodeDetail { fields, jobs })
+}
+
+/// What a node on the node grid is colored by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeCategory {
+    Idle,
+    Mixed,
+    Allocated,
+    /// Draining or drained
+    Drain,
+    /// Down, failing or not responding
+    Down,
+    /// Reserved, in maintenance, powered down or unknown
+    Other,
+}
+
+impl NodeCategory {
+    /// Category of a node in `state`, e.g. "MIXED+DRAIN" or "IDLE*"; a node that
+    /// is down counts as down whatever else it is
+    pub fn of(state: &str) -> Self {
+        let flags: Vec<&str> = state
+            .split('+')
+            .map(|flag| flag.trim_end_matches(['*', '~', '#', '%', '$', '@', '!', '^', '-']))
+            .collect();
+        let not_responding = state
+            .split('+')
+            .next()
+            .is_some_and(|base| base.ends_with('*'));
+        if not_responding
+            || flags
+                .iter()
+                .any(|flag| matches!(*flag, "DOWN" | "FAIL" | "FAILING" | "NOT_RESPONDING"))
+        {
+            return NodeCategory::Down;
+        }
+        if flags.iter().any(|flag| flag.starts_with("DRAIN")) {
+            return NodeCategory::Drain;
+        }
+        match flags[0] {
+            "IDLE" => NodeCategory::Idle,
+            "MIXED" => NodeCategory::Mixed,
+            "ALLOCATED" | "COMPLETING" => NodeCategory::Allocated,
+            _ => NodeCategory::Other,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeCategory::Idle => "idle",
+            NodeCategory::Mixed => "mixed",
+            NodeCategory::Allocated => "alloc",
+            NodeCategory::Drain => "drain",
+            NodeCategory::Down => "down",
+            NodeCategory::Other => "other",
+        }
+    }
+}
+
+/// One node of the node grid
+#[derive(Debug, Clone)]
+pub struct NodeCell {
+    pub name: String,
+    /// As reported, e.g. "MIXED+DRAIN"
+    pub state: String,
+    pub category: NodeCategory,
+    pub partitions: String,
+    pub cpus_allocated: u32,
+    pub cpus_total: u32,
+    /// Why the node is drained or down
+    pub reason: Option<String>,
+    /// Leaf switch of the node, when the cluster has a network topology
+    pub switch: Option<String>,
+    /// Running jobs on the node
+    pub jobs: Vec<String>,
+    /// Current and average power draw in watts, where acct_gather_energy is
+    /// configured
+    pub current_watts: Option<u32>,
+    pub average_watts: Option<u32>,
+}
+
+/// Fetch every node with its state and CPU allocation, in slurm.conf order,
+/// with its leaf switch (`scontrol show topology`) and running jobs (`squeue`)
+pub async fn get_node_grid() -> Result<Vec<NodeCell>> {
+    let args = vec!["show".to_string(), "node".to_string(), "-o".to_string()];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut nodes: Vec<NodeCell> = stdout.lines().filter_map(parse_node_cell).collect();
+
+    // Without a topology plugin scontrol fails or lists no switches, and the
+    // grid stays flat
+    let args = vec!["show".to_string(), "topology".to_string()];
+    if let Ok(output) = execute_command("scontrol", args).await {
+        if output.status.success() {
+            let switches = leaf_switches(&String::from_utf8_lossy(&output.stdout));
+            for node in &mut nodes {
+                node.switch = switches.get(&node.name).cloned();
+            }
+        }
+    }
+
+    // The jobs only mark where multi-node jobs are placed
+    let args = ["-h", "-t", "RUNNING", "-o", "%i|%N"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    if let Ok(output) = execute_command("squeue", args).await {
+        if output.status.success() {
+            let mut jobs: HashMap<String, Vec<String>> = HashMap::new();
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                let Some((job_id, node_list)) = line.split_once('|') else {
+                    continue;
+                };
+                for node in expand_hostlist(node_list) {
+                    jobs.entry(node)
+                        .or_default()
+                        .push(job_id.trim().to_string());
+                }
+            }
+            for node in &mut nodes {
+                node.jobs = jobs.remove(&node.name).unwrap_or_default();
+            }
+        }
+    }
+    Ok(nodes)
+}
+
+/// Leaf switch of every node from `scontrol show topology` lines like
+/// "SwitchName=s0 Level=0 LinkSpeed=1 Nodes=tux[0-3]": the lowest-level
+/// switch listing the node
+fn leaf_switches(output: &str) -> HashMap<String, String> {
+    let mut leaves: HashMap<String, (u32, String)> = HashMap::new();
+    for line in output.lines() {
+        let fields = parse_scontrol_fields(line);
+        let field = |key: &str| {
+            fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        let (Some(name), Some(nodes)) = (field("SwitchName"), field("Nodes")) else {
+            continue;
+        };
+        let level = field("Level").and_then(|l| l.parse().ok()).unwrap_or(0);
+        for node in expand_hostlist(nodes) {
+            match leaves.get(&node) {
+                Some((lowest, _)) if *lowest <= level => {}
+                _ => {
+                    leaves.insert(node, (level, name.to_string()));
+                }
+            }
+        }
+    }
+    leaves
+        .into_iter()
+        .map(|(node, (_, switch))| (node, switch))
+        .collect()
+}
+
+/// Parse one line of `scontrol show node -o`
+fn parse_node_cell(line: &str) -> Option<NodeCell> {
+    let fields = parse_scontrol_fields(line);
+    let field = |key: &str| {
+        fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .filter(|v| !v.is_empty() && *v != "(null)" && *v != "None")
+    };
+    let name = field("NodeName")?.to_string();
+    let state = field("State").unwrap_or("UNKNOWN").to_string();
+    let number = |key: &str| field(key).and_then(|n| n.parse::<u32>().ok());
+    Some(NodeCell {
+        name,
+        category: NodeCategory::of(&state),
+        state,
+        partitions: field("Partitions").unwrap_or_default().to_string(),
+        cpus_allocated: number("CPUAlloc").unwrap_or(0),
+        cpus_total: number("CPUEfctv").or(number("CPUTot")).unwrap_or(0),
+        reason: field("Reason").map(str::to_string),
+        switch: None,
+        jobs: Vec::new(),
+        current_watts: field("CurrentWatts").and_then(watts),
+        average_watts: field("AveWatts").and_then(watts),
+    })
+}
+
+/// Watts of a node's CurrentWatts or AveWatts; without energy accounting
+/// scontrol reports 0 or "n/s"
+pub(super) fn watts(value: &str) -> Option<u32> {
+    value.parse().ok().filter(|&w| w > 0)
+}
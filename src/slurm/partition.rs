@@ -0,0 +1,219 @@
+use color_eyre::{eyre::eyre, Result};
+use std::collections::HashMap;
+
+use super::command::{execute_command, parse_scontrol_output};
+
+/// Flags of `scontrol show partition` worth pointing out when set to YES
+const YES_FLAGS: [&str; 6] = [
+    "Hidden",
+    "RootOnly",
+    "ExclusiveUser",
+    "ReqResv",
+    "LLN",
+    "DisableRootJobs",
+];
+
+/// Nodes or CPUs of a partition by state, as counted by `sinfo %F` and `%C`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StateCounts {
+    pub allocated: u32,
+    pub idle: u32,
+    /// Down, drained, failing and otherwise unavailable nodes or CPUs
+    pub other: u32,
+    pub total: u32,
+}
+
+impl StateCounts {
+    /// Allocated share of what is in service, 1.0 when nothing is
+    pub fn load(&self) -> f64 {
+        let usable = self.total.saturating_sub(self.other);
+        if usable == 0 {
+            1.0
+        } else {
+            self.allocated as f64 / usable as f64
+        }
+    }
+}
+
+/// A partition with its limits, defaults and nodes, for the partitions view
+#[derive(Debug, Clone, Default)]
+pub struct PartitionInfo {
+    pub name: String,
+    /// UP, DOWN, DRAIN or INACTIVE
+    pub state: String,
+    /// Jobs that name no partition go here
+    pub default: bool,
+    pub max_time: Option<String>,
+    /// Maximum nodes per job, `None` if unlimited
+    pub max_nodes: Option<String>,
+    /// Time limit of jobs that ask for none
+    pub default_time: Option<String>,
+    /// Memory of jobs that ask for none, e.g. "4000M/CPU"
+    pub default_memory: Option<String>,
+    /// QoS allowed on the partition, `None` meaning all
+    pub allow_qos: Option<String>,
+    /// Flags that restrict or change scheduling, e.g. "Hidden" or "OverSubscribe=FORCE"
+    pub flags: Vec<String>,
+    pub nodes: StateCounts,
+    pub cpus: StateCounts,
+    /// Pending jobs that could start here, counting jobs submitted to
+    /// several partitions in each of them
+    pub pending: u32,
+}
+
+/// Fetch every partition from `scontrol show partition`, with node and CPU
+/// counts by state from `sinfo` and the pending backlog from `squeue`
+pub async fn get_partition_overview() -> Result<Vec<PartitionInfo>> {
+    let args = vec![
+        "show".to_string(),
+        "partition".to_string(),
+        "-o".to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let mut partitions = parse_partitions(&String::from_utf8_lossy(&output.stdout));
+
+    // Without sinfo the counts fall back to the partition's TotalNodes and TotalCPUs
+    let args = ["-h", "-o", "%R|%F|%C"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    if let Ok(output) = execute_command("sinfo", args).await {
+        if output.status.success() {
+            let counts = parse_state_counts(&String::from_utf8_lossy(&output.stdout));
+            for partition in &mut partitions {
+                if let Some((nodes, cpus)) = counts.get(&partition.name) {
+                    partition.nodes = *nodes;
+                    partition.cpus = *cpus;
+                }
+            }
+        }
+    }
+
+    let args = ["-h", "-t", "PENDING", "-o", "%P"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    if let Ok(output) = execute_command("squeue", args).await {
+        if output.status.success() {
+            let pending = count_pending(&String::from_utf8_lossy(&output.stdout));
+            for partition in &mut partitions {
+                partition.pending = pending.get(&partition.name).copied().unwrap_or(0);
+            }
+        }
+    }
+    Ok(partitions)
+}
+
+/// Parse `scontrol show partition -o` output (one partition per line)
+fn parse_partitions(output: &str) -> Vec<PartitionInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields = parse_scontrol_output(line);
+            let get = |key: &str| {
+                fields
+                    .get(key)
+                    .filter(|v| !v.is_empty() && *v != "(null)" && *v != "NONE")
+                    .cloned()
+            };
+            let yes = |key: &str| fields.get(key).is_some_and(|v| v == "YES");
+            let number = |key: &str| get(key).and_then(|n| n.parse().ok()).unwrap_or(0);
+
+            let mut flags: Vec<String> = YES_FLAGS
+                .iter()
+                .filter(|flag| yes(flag))
+                .map(|flag| flag.to_string())
+                .collect();
+            if let Some(mode) = get("OverSubscribe").filter(|m| m != "NO") {
+                flags.push(format!("OverSubscribe={}", mode));
+            }
+            if let Some(mode) = get("PreemptMode").filter(|m| m != "OFF") {
+                flags.push(format!("Preempt={}", mode));
+            }
+            let default_memory = get("DefMemPerCPU")
+                .filter(|m| m != "UNLIMITED")
+                .map(|m| format!("{}M/CPU", m))
+                .or_else(|| {
+                    get("DefMemPerNode")
+                        .filter(|m| m != "UNLIMITED")
+                        .map(|m| format!("{}M/node", m))
+                });
+
+            Some(PartitionInfo {
+                name: get("PartitionName")?,
+                state: get("State").unwrap_or_default(),
+                default: yes("Default"),
+                max_time: get("MaxTime"),
+                max_nodes: get("MaxNodes"),
+                default_time: get("DefaultTime"),
+                default_memory,
+                allow_qos: get("AllowQos").filter(|q| q != "ALL"),
+                flags,
+                nodes: StateCounts {
+                    total: number("TotalNodes"),
+                    ..StateCounts::default()
+                },
+                cpus: StateCounts {
+                    total: number("TotalCPUs"),
+                    ..StateCounts::default()
+                },
+                pending: 0,
+            })
+        })
+        .collect()
+}
+
+/// Parse `sinfo -h -o %R|%F|%C` output: allocated/idle/other/total nodes and
+/// CPUs per partition
+fn parse_state_counts(output: &str) -> HashMap<String, (StateCounts, StateCounts)> {
+    let parse = |states: &str| {
+        let numbers: Vec<u32> = states
+            .trim()
+            .split('/')
+            .filter_map(|n| n.parse().ok())
+            .collect();
+        match numbers[..] {
+            [allocated, idle, other, total] => Some(StateCounts {
+                allocated,
+                idle,
+                other,
+                total,
+            }),
+            _ => None,
+        }
+    };
+    let mut counts: HashMap<String, (StateCounts, StateCounts)> = HashMap::new();
+    for line in output.lines() {
+        let mut parts = line.split('|');
+        let (Some(partition), Some(nodes), Some(cpus)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let (Some(nodes), Some(cpus)) = (parse(nodes), parse(cpus)) else {
+            continue;
+        };
+        // sinfo splits a partition over several lines when its nodes differ
+        let entry = counts.entry(partition.trim().to_string()).or_default();
+        for (sum, add) in [(&mut entry.0, nodes), (&mut entry.1, cpus)] {
+            sum.allocated += add.allocated;
+            sum.idle += add.idle;
+            sum.other += add.other;
+            sum.total += add.total;
+        }
+    }
+    counts
+}
+
+/// Count `squeue -h -t PENDING -o %P` output per partition; a job submitted
+/// to "short,long" is counted in both
+fn count_pending(output: &str) -> HashMap<String, u32> {
+    let mut pending = HashMap::new();
+    for partition in output.split_whitespace().flat_map(|line| line.split(',')) {
+        *pending.entry(partition.to_string()).or_insert(0) += 1;
+    }
+    pending
+}
@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::command::execute_command;
+use crate::utils::get_username;
+
+/// Fields queried from `sacctmgr show qos`, in the order they are parsed
+const QOS_FORMAT: &str =
+    "format=Name,Priority,MaxWall,MaxTRES,MaxTRESPU,MaxJobsPU,MaxSubmitPU,GrpTRES,Preempt,Flags";
+
+/// A QoS and the limits behind most QOS* pending reasons
+#[derive(Debug, Clone, Default)]
+pub struct QosInfo {
+    pub name: String,
+    pub priority: u32,
+    pub max_wall: Option<String>,
+    /// Per job, e.g. "cpu=256,gres/gpu=8"
+    pub max_tres: Option<String>,
+    /// Summed over a user's running jobs
+    pub max_tres_per_user: Option<String>,
+    pub max_jobs_per_user: Option<String>,
+    pub max_submit_per_user: Option<String>,
+    /// Summed over every job in the QoS
+    pub grp_tres: Option<String>,
+    /// QoS this one may preempt
+    pub preempt: Option<String>,
+    pub flags: Option<String>,
+    /// One of my associations lists it; `None` if the associations are unknown
+    pub allowed: Option<bool>,
+    /// Default QoS of one of my associations
+    pub default: bool,
+}
+
+/// Fetch every QoS with its limits, and which ones my associations may use
+pub async fn get_qos_overview() -> Result<Vec<QosInfo>> {
+    let args = vec![
+        "-n".to_string(),
+        "-P".to_string(),
+        "show".to_string(),
+        "qos".to_string(),
+        QOS_FORMAT.to_string(),
+    ];
+    let output = execute_command("sacctmgr", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let mut qos = parse_qos(&String::from_utf8_lossy(&output.stdout));
+
+    // Without my associations nothing is marked as usable or not
+    let args = vec![
+        "-n".to_string(),
+        "-P".to_string(),
+        "show".to_string(),
+        "assoc".to_string(),
+        format!("user={}", get_username()),
+        "format=QOS,DefaultQOS".to_string(),
+    ];
+    if let Ok(output) = execute_command("sacctmgr", args).await {
+        if output.status.success() {
+            let (allowed, defaults) = parse_associations(&String::from_utf8_lossy(&output.stdout));
+            for q in &mut qos {
+                q.allowed = Some(allowed.contains(&q.name));
+                q.default = defaults.contains(&q.name);
+            }
+        }
+    }
+    Ok(qos)
+}
+
+/// Parse `sacctmgr -n -P show qos` output in [`QOS_FORMAT`] order
+fn parse_qos(output: &str) -> Vec<QosInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let cols: Vec<&str> = line.split('|').map(str::trim).collect();
+            let col = |i: usize| cols.get(i).filter(|v| !v.is_empty()).map(|v| v.to_string());
+            Some(QosInfo {
+                name: col(0)?,
+                priority: col(1).and_then(|p| p.parse().ok()).unwrap_or(0),
+                max_wall: col(2),
+                max_tres: col(3),
+                max_tres_per_user: col(4),
+                max_jobs_per_user: col(5),
+                max_submit_per_user: col(6),
+                grp_tres: col(7),
+                preempt: col(8),
+                flags: col(9),
+                allowed: None,
+                default: false,
+            })
+        })
+        .collect()
+}
+
+/// QoS usable by my associations and their default QoS, from
+/// `sacctmgr -n -P show assoc format=QOS,DefaultQOS`
+fn parse_associations(output: &str) -> (HashSet<String>, HashSet<String>) {
+    let mut allowed = HashSet::new();
+    let mut defaults = HashSet::new();
+    for line in output.lines() {
+        let (qos, default) = line.split_once('|').unwrap_or((line, ""));
+        allowed.extend(
+            qos.split(',')
+                .map(str::trim)
+                .filter(|q| !q.is_empty())
+                .map(str::to_string),
+        );
+        let default = default.trim();
+        if !default.is_empty() {
+            // The default is usable even when the QOS column leaves it out
+            allowed.insert(default.to_string());
+            defaults.insert(default.to_string());
+        }
+    }
+    (allowed, defaults)
+}
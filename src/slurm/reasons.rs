@@ -0,0 +1,174 @@
+/// Plain-language explanation of a pending reason and what can be done about it.
+/// Both are English texts meant to go through `tr`.
+#[derive(Debug, Clone, Copy)]
+pub struct ReasonHelp {
+    pub explanation: &'static str,
+    pub remedy: &'static str,
+}
+
+/// Pending reasons with their explanation and remedy
+const REASONS: &[(&str, &str, &str)] = &[
+    (
+        "Priority",
+        "Other jobs with a higher priority are ahead in the queue.",
+        "Wait; a shorter time limit or fewer resources can let the job backfill sooner.",
+    ),
+    (
+        "Resources",
+        "The job is next in line but the resources it asks for are not free yet.",
+        "Wait, or request fewer nodes, CPUs or memory to fit into what frees up first.",
+    ),
+    (
+        "Dependency",
+        "The job waits for the jobs it depends on.",
+        "Press G to see which dependencies are still unfulfilled.",
+    ),
+    (
+        "DependencyNeverSatisfied",
+        "A job this job depends on ended in a way that can never satisfy the dependency.",
+        "Cancel the job, or remove the failed dependency with o so it can start.",
+    ),
+    (
+        "JobHeldUser",
+        "The job was held by its owner.",
+        "Release it with u.",
+    ),
+    (
+        "JobHeldAdmin",
+        "The job was held by an administrator.",
+        "Ask the cluster administrators why it was held.",
+    ),
+    (
+        "BeginTime",
+        "The job was submitted with a start time that has not come yet.",
+        "Wait, or start it sooner with scontrol update StartTime=now.",
+    ),
+    (
+        "ReqNodeNotAvail",
+        "Some nodes the job needs are down, drained or reserved.",
+        "Wait for the nodes to return, or drop --nodelist/--exclude constraints that pin them.",
+    ),
+    (
+        "NodeDown",
+        "A node the job needs is down.",
+        "Wait for the node to return, or move the job to another partition with P.",
+    ),
+    (
+        "PartitionDown",
+        "The partition is down.",
+        "Move the job to another partition with P, or wait for it to come back.",
+    ),
+    (
+        "PartitionInactive",
+        "The partition is not scheduling jobs.",
+        "Move the job to another partition with P.",
+    ),
+    (
+        "PartitionTimeLimit",
+        "The time limit exceeds the maximum of the partition; the job will never start there.",
+        "Lower the time limit with e, or move the job with P to a partition with longer limits.",
+    ),
+    (
+        "PartitionNodeLimit",
+        "The node count is outside the limits of the partition; the job will never start there.",
+        "Change the node count with e, or move the job to another partition with P.",
+    ),
+    (
+        "PartitionConfig",
+        "The request does not fit the configuration of the partition.",
+        "Adjust the request with e, or move the job to another partition with P.",
+    ),
+    (
+        "Reservation",
+        "The job waits for its advanced reservation to become available.",
+        "Wait for the reservation to start.",
+    ),
+    (
+        "Licenses",
+        "The licenses the job requests are in use.",
+        "Wait for other jobs to release them.",
+    ),
+    (
+        "BadConstraints",
+        "No node satisfies the constraints of the job; it will never start.",
+        "Cancel and resubmit with different --constraint, GRES or memory requests.",
+    ),
+    (
+        "InvalidAccount",
+        "The account of the job is invalid.",
+        "Cancel and resubmit with a valid --account.",
+    ),
+    (
+        "InvalidQOS",
+        "The QOS of the job is invalid.",
+        "Change the QOS with e.",
+    ),
+    (
+        "JobArrayTaskLimit",
+        "The array has reached its limit of simultaneously running tasks (%N in --array).",
+        "Wait for running tasks to finish, or raise the limit with scontrol update ArrayTaskThrottle.",
+    ),
+    (
+        "launch failed requeued held",
+        "The job failed to launch, was requeued and is held.",
+        "Check the node it ran on, then release the job with u.",
+    ),
+    (
+        "Cleaning",
+        "The job is being requeued and still cleaning up from its previous run.",
+        "Wait a moment.",
+    ),
+];
+
+/// Explanation of a pending reason as printed by squeue (`(Priority)`) or
+/// scontrol (`ReqNodeNotAvail,_UnavailableNodes:n[1-2]`); `None` for reasons
+/// that are not known or mean the job is not waiting
+pub fn explain_reason(reason: &str) -> Option<ReasonHelp> {
+    let reason = reason.trim().trim_start_matches('(').trim_end_matches(')');
+    // Details like the unavailable nodes follow a comma
+    let code = reason.split(',').next().unwrap_or_default().trim();
+    if let Some(&(_, explanation, remedy)) = REASONS.iter().find(|(name, ..)| *name == code) {
+        return Some(ReasonHelp {
+            explanation,
+            remedy,
+        });
+    }
+
+    // Limits of QOS and associations, e.g. QOSMaxCpuPerUserLimit or AssocGrpGRES
+    let help = |explanation, remedy| {
+        Some(ReasonHelp {
+            explanation,
+            remedy,
+        })
+    };
+    if code.starts_with("QOSMax") && code.contains("PerUser") {
+        help(
+            "Your running jobs already use the most this QOS allows per user.",
+            "Wait for your other jobs to finish, or move the job to a QOS with a higher limit.",
+        )
+    } else if code.starts_with("QOSMaxWall")
+        || code.starts_with("QOSMax") && code.contains("PerJob")
+    {
+        help(
+            "The job asks for more than this QOS allows for a single job; it will never start.",
+            "Reduce the request with e, or move the job to a QOS with a higher limit.",
+        )
+    } else if code.starts_with("QOSGrp") {
+        help(
+            "All jobs of this QOS together have reached a group limit.",
+            "Wait for jobs in the same QOS to finish, or use another QOS.",
+        )
+    } else if code.starts_with("AssocGrp") || code.starts_with("AssocMax") {
+        help(
+            "Your account (association) has reached one of its limits.",
+            "Wait for jobs of the account to finish, or submit under another --account.",
+        )
+    } else if code.starts_with("MaxCpuPerAccount") || code.starts_with("QOSMax") {
+        help(
+            "A per-account or QOS limit is reached.",
+            "Wait for other jobs to finish, or reduce the request with e.",
+        )
+    } else {
+        None
+    }
+}
@@ -0,0 +1,185 @@
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use color_eyre::{eyre::eyre, Result};
+
+use super::{
+    command::{execute_command, parse_scontrol_output},
+    parse_time_limit,
+};
+use crate::utils::get_username;
+
+/// Pending reason of jobs held back by a maintenance reservation
+const MAINT_REASON: &str = "Reserved for maintenance";
+
+/// Time format of the reservation times, once the 'T' is replaced
+const RESERVATION_TIME: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Time format of squeue's expected start times
+const SQUEUE_TIME: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// One of my pending jobs that may still be running when a maintenance
+/// reservation starts
+#[derive(Debug, Clone)]
+pub struct Clash {
+    pub job_id: String,
+    pub name: String,
+    /// Expected start, or now when the scheduler has not planned the job yet
+    pub start: NaiveDateTime,
+    /// Start plus the time limit; `None` without a (finite) limit
+    pub end: Option<NaiveDateTime>,
+}
+
+/// A reservation from `scontrol show reservation`
+#[derive(Debug, Clone, Default)]
+pub struct Reservation {
+    pub name: String,
+    /// ACTIVE or INACTIVE (not started yet)
+    pub state: String,
+    pub start: String,
+    pub end: String,
+    /// Hostlist, e.g. "node[01-04]"
+    pub nodes: String,
+    pub node_count: u32,
+    pub partition: Option<String>,
+    pub users: Option<String>,
+    pub accounts: Option<String>,
+    /// e.g. MAINT, IGNORE_JOBS, SPEC_NODES
+    pub flags: Vec<String>,
+    /// My pending jobs that ask for the reservation or wait on it as maintenance
+    pub affected: Vec<String>,
+    /// My pending jobs whose estimated run overlaps the maintenance window
+    pub clashes: Vec<Clash>,
+}
+
+impl Reservation {
+    pub fn is_maintenance(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "MAINT")
+    }
+
+    /// Start and end, when both parse; an open-ended reservation has no window
+    pub fn window(&self) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        let start = NaiveDateTime::parse_from_str(&self.start, RESERVATION_TIME).ok()?;
+        let end = NaiveDateTime::parse_from_str(&self.end, RESERVATION_TIME).ok()?;
+        Some((start, end))
+    }
+}
+
+/// Fetch every reservation, marking the ones that hold back my pending jobs
+pub async fn get_reservations() -> Result<Vec<Reservation>> {
+    let args = vec![
+        "show".to_string(),
+        "reservation".to_string(),
+        "-o".to_string(),
+    ];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let mut reservations = parse_reservations(&String::from_utf8_lossy(&output.stdout));
+
+    // Without squeue no reservation is highlighted
+    let args = vec![
+        "-h".to_string(),
+        "-u".to_string(),
+        get_username(),
+        "-t".to_string(),
+        "PENDING".to_string(),
+        "-o".to_string(),
+        "%i|%v|%P|%r|%S|%l|%j".to_string(),
+    ];
+    if let Ok(output) = execute_command("squeue", args).await {
+        if output.status.success() {
+            let now = Local::now().naive_local();
+            mark_affected(
+                &mut reservations,
+                &String::from_utf8_lossy(&output.stdout),
+                now,
+            );
+        }
+    }
+    Ok(reservations)
+}
+
+/// Parse `scontrol show reservation -o` output (one reservation per line);
+/// "No reservations in the system" yields none
+fn parse_reservations(output: &str) -> Vec<Reservation> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields = parse_scontrol_output(line);
+            let get = |key: &str| {
+                fields
+                    .get(key)
+                    .filter(|v| !v.is_empty() && *v != "(null)")
+                    .cloned()
+            };
+            Some(Reservation {
+                name: get("ReservationName")?,
+                state: get("State").unwrap_or_default(),
+                start: get("StartTime").unwrap_or_default().replace('T', " "),
+                end: get("EndTime").unwrap_or_default().replace('T', " "),
+                nodes: get("Nodes").unwrap_or_default(),
+                node_count: get("NodeCnt").and_then(|n| n.parse().ok()).unwrap_or(0),
+                partition: get("PartitionName"),
+                users: get("Users"),
+                accounts: get("Accounts"),
+                flags: get("Flags")
+                    .map(|f| f.split(',').map(str::to_string).collect())
+                    .unwrap_or_default(),
+                affected: Vec::new(),
+                clashes: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Match `squeue -h -t PENDING -o %i|%v|%P|%r|%S|%l|%j` lines to the
+/// reservations: a job is affected by the reservation it asks for, and by
+/// maintenance reservations on its partition while it waits for "Reserved for
+/// maintenance". It clashes with a maintenance reservation on its partition
+/// that has not ended yet when its expected start plus its time limit
+/// overlaps the window
+fn mark_affected(reservations: &mut [Reservation], output: &str, now: NaiveDateTime) {
+    for line in output.lines() {
+        let mut cols = line.splitn(7, '|').map(str::trim);
+        let (Some(job), Some(reservation), Some(partitions), Some(reason)) =
+            (cols.next(), cols.next(), cols.next(), cols.next())
+        else {
+            continue;
+        };
+        let start = cols
+            .next()
+            .and_then(|start| NaiveDateTime::parse_from_str(start, SQUEUE_TIME).ok())
+            .map_or(now, |start| start.max(now));
+        let end = cols
+            .next()
+            .and_then(parse_time_limit)
+            .filter(|&limit| limit != u64::MAX)
+            .map(|limit| start + TimeDelta::seconds(limit as i64));
+        let name = cols.next().unwrap_or_default();
+
+        let partitions: Vec<&str> = partitions.split(',').collect();
+        for res in reservations.iter_mut() {
+            let on_partition = res
+                .partition
+                .as_deref()
+                .is_none_or(|p| partitions.contains(&p));
+            let maintenance = res.is_maintenance() && on_partition;
+            if res.name == reservation || (maintenance && reason.contains(MAINT_REASON)) {
+                res.affected.push(job.to_string());
+            }
+            let overlaps = res.window().is_some_and(|(res_start, res_end)| {
+                res_end > now && start < res_end && end.is_none_or(|end| end > res_start)
+            });
+            // A job running in the reservation itself is no clash
+            if maintenance && overlaps && res.name != reservation {
+                res.clashes.push(Clash {
+                    job_id: job.to_string(),
+                    name: name.to_string(),
+                    start,
+                    end,
+                });
+            }
+        }
+    }
+}
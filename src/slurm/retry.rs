@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+
+use crate::config::RetrySettings;
+
+/// Parts of sbatch errors that mean the controller could not take the job right
+/// now, rather than that the job itself is wrong
+const TRANSIENT_ERRORS: &[&str] = &[
+    "socket timed out",
+    "unable to contact slurm controller",
+    "temporarily unable to accept job",
+    "resource temporarily unavailable",
+    "connection refused",
+    "connection timed out",
+    "zero bytes were transmitted or received",
+    "slurmctld is busy",
+    "communication connection failure",
+];
+
+/// Whether an sbatch error is worth retrying
+pub fn is_transient_failure(error: &str) -> bool {
+    let error = error.to_lowercase();
+    TRANSIENT_ERRORS.iter().any(|marker| error.contains(marker))
+}
+
+/// A submission waiting to be tried again
+#[derive(Debug, Clone)]
+pub struct PendingSubmission {
+    /// Stays the same while other entries come and go
+    pub id: u64,
+    pub args: Vec<String>,
+    /// Attempts made so far, the first one included
+    pub attempts: u32,
+    /// When the next attempt is due; none once retrying gave up
+    pub next_attempt: Option<Instant>,
+    /// Error of the last attempt
+    pub error: String,
+}
+
+/// Submissions that failed transiently, retried with exponential backoff until
+/// they succeed, give up or are cancelled. Only kept for the session.
+pub struct RetryQueue {
+    settings: RetrySettings,
+    pub entries: Vec<PendingSubmission>,
+    next_id: u64,
+}
+
+impl RetryQueue {
+    pub fn new(settings: RetrySettings) -> Self {
+        Self {
+            settings,
+            entries: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.settings.attempts > 0
+    }
+
+    /// Queue a submission whose first attempt failed; returns the wait before the retry
+    pub fn push(&mut self, args: Vec<String>, error: String) -> Duration {
+        let delay = self.backoff(1);
+        self.next_id += 1;
+        self.entries.push(PendingSubmission {
+            id: self.next_id,
+            args,
+            attempts: 1,
+            next_attempt: Some(Instant::now() + delay),
+            error,
+        });
+        delay
+    }
+
+    /// The first submission whose next attempt is due
+    pub fn due(&self) -> Option<&PendingSubmission> {
+        let now = Instant::now();
+        self.entries
+            .iter()
+            .find(|entry| entry.next_attempt.is_some_and(|at| at <= now))
+    }
+
+    /// Record another failed attempt. Returns the wait before the next one, or
+    /// none when the error is not transient or the attempts are used up.
+    pub fn failed(&mut self, id: u64, error: String) -> Option<Duration> {
+        let max_attempts = self.settings.attempts + 1;
+        let transient = is_transient_failure(&error);
+        let delay = self.entries.iter().find(|e| e.id == id).and_then(|entry| {
+            (transient && entry.attempts + 1 < max_attempts)
+                .then(|| self.backoff(entry.attempts + 1))
+        });
+        let entry = self.entries.iter_mut().find(|e| e.id == id)?;
+        entry.attempts += 1;
+        entry.error = error;
+        entry.next_attempt = delay.map(|delay| Instant::now() + delay);
+        delay
+    }
+
+    /// Try a submission again on the next tick, also after retrying gave up
+    pub fn retry_now(&mut self, id: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+            entry.next_attempt = Some(Instant::now());
+        }
+    }
+
+    /// Drop a submission, once it went through or was cancelled
+    pub fn remove(&mut self, id: u64) -> Option<PendingSubmission> {
+        let index = self.entries.iter().position(|e| e.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Wait after the given number of failed attempts: the configured delay,
+    /// doubled for every attempt after the first, up to the maximum
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempts.saturating_sub(1));
+        let secs = self.settings.delay.saturating_mul(factor);
+        Duration::from_secs(secs.min(self.settings.max_delay.max(self.settings.delay)))
+    }
+}
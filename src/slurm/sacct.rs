@@ -0,0 +1,617 @@
+use chrono::NaiveDateTime;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{
+    command::execute_command,
+    failure::{classify_failure, FailureClass},
+    parse_time_limit,
+};
+
+/// Fields requested from sacct, in the order they are parsed
+const SACCT_FORMAT: &str =
+    "JobID,JobName,User,State,Partition,Elapsed,Start,End,ExitCode,NodeList,ConsumedEnergyRaw";
+
+/// Bytes in a MiB, the unit of memory requests without a suffix
+pub const MIB: u64 = 1024 * 1024;
+
+/// A historical accounting query for the history view
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryQuery {
+    /// Users to include; empty means the current user, "ALL" means everyone
+    pub users: Vec<String>,
+    /// Job states, e.g. FAILED, TIMEOUT; empty means any state
+    pub states: Vec<String>,
+    /// Start of the time range in any sacct time format, e.g. "now-7days"
+    pub start: String,
+    /// End of the time range; empty means now
+    pub end: String,
+    pub partitions: Vec<String>,
+}
+
+impl Default for HistoryQuery {
+    fn default() -> Self {
+        Self {
+            users: Vec::new(),
+            states: Vec::new(),
+            start: "now-1days".to_string(),
+            end: String::new(),
+            partitions: Vec::new(),
+        }
+    }
+}
+
+impl HistoryQuery {
+    /// Arguments for `sacct`
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--noheader".to_string(),
+            "--parsable2".to_string(),
+            "--allocations".to_string(),
+            format!("--format={}", SACCT_FORMAT),
+            format!("--starttime={}", self.start),
+        ];
+        if !self.end.is_empty() {
+            args.push(format!("--endtime={}", self.end));
+        }
+        if self.users.iter().any(|u| u.eq_ignore_ascii_case("ALL")) {
+            args.push("--allusers".to_string());
+        } else if !self.users.is_empty() {
+            args.push(format!("--user={}", self.users.join(",")));
+        }
+        if !self.states.is_empty() {
+            args.push(format!("--state={}", self.states.join(",")));
+        }
+        if !self.partitions.is_empty() {
+            args.push(format!("--partition={}", self.partitions.join(",")));
+        }
+        args
+    }
+
+    /// Short human-readable summary of the query
+    pub fn describe(&self) -> String {
+        let users = if self.users.is_empty() {
+            "me".to_string()
+        } else {
+            self.users.join(",")
+        };
+        let mut parts = vec![format!("users={}", users)];
+        if !self.states.is_empty() {
+            parts.push(format!("states={}", self.states.join(",")));
+        }
+        if !self.partitions.is_empty() {
+            parts.push(format!("partitions={}", self.partitions.join(",")));
+        }
+        let end = if self.end.is_empty() {
+            "now"
+        } else {
+            &self.end
+        };
+        parts.push(format!("{} → {}", self.start, end));
+        parts.join(" ")
+    }
+}
+
+/// A job record from `sacct`
+#[derive(Debug, Clone)]
+pub struct HistoryJob {
+    pub id: String,
+    pub name: String,
+    pub user: String,
+    /// State without the "by <uid>" suffix sacct adds to CANCELLED
+    pub state: String,
+    pub partition: String,
+    pub elapsed: String,
+    pub start: String,
+    pub end: String,
+    pub exit_code: String,
+    pub node_list: String,
+    /// Joules the job used; `None` unless acct_gather_energy is configured
+    pub energy: Option<u64>,
+}
+
+/// Run sacct for a history query
+pub async fn run_sacct(query: &HistoryQuery) -> Result<Vec<HistoryJob>> {
+    let output = execute_command("sacct", query.to_args()).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(parse_sacct_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `sacct --parsable2` output in [`SACCT_FORMAT`] order
+fn parse_sacct_output(output: &str) -> Vec<HistoryJob> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split('|').collect();
+            if f.len() < 10 {
+                return None;
+            }
+            Some(HistoryJob {
+                id: f[0].to_string(),
+                name: f[1].to_string(),
+                user: f[2].to_string(),
+                state: f[3].split_whitespace().next().unwrap_or("").to_string(),
+                partition: f[4].to_string(),
+                elapsed: f[5].to_string(),
+                start: f[6].to_string(),
+                end: f[7].to_string(),
+                exit_code: f[8].to_string(),
+                node_list: f[9].to_string(),
+                // 0 without energy accounting, NO_VAL64 when a node had no reading
+                energy: f
+                    .get(10)
+                    .and_then(|e| e.parse::<u64>().ok())
+                    .filter(|&e| e > 0 && e < u64::MAX - 1),
+            })
+        })
+        .collect()
+}
+
+/// State of a job as recorded by accounting, e.g. "COMPLETED" or "FAILED"
+pub async fn get_job_state(job_id: &str) -> Result<Option<String>> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        "--allocations".to_string(),
+        "--format=State".to_string(),
+        format!("--jobs={}", job_id),
+    ];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().next())
+        .map(str::to_string))
+}
+
+/// States of several jobs as recorded by accounting, by job ID
+pub async fn get_job_states(job_ids: &[String]) -> Result<HashMap<String, String>> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        "--allocations".to_string(),
+        "--format=JobID,State".to_string(),
+        format!("--jobs={}", job_ids.join(",")),
+    ];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let mut states: HashMap<String, String> = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((id, state)) = line.split_once('|') else {
+            continue;
+        };
+        // "CANCELLED by 1000" is just CANCELLED here
+        let state = state.split_whitespace().next().unwrap_or_default();
+        // The tasks of an array job count under its ID; "MIXED" when they differ
+        let id = id.split('_').next().unwrap_or(id);
+        states
+            .entry(id.to_string())
+            .and_modify(|s| {
+                if s != state {
+                    *s = "MIXED".to_string();
+                }
+            })
+            .or_insert_with(|| state.to_string());
+    }
+    Ok(states)
+}
+
+/// How a finished job ended, for the ExitCode and Failure columns
+#[derive(Debug, Clone)]
+pub struct JobExit {
+    /// `code:signal` as reported by sacct
+    pub exit_code: String,
+    pub failure: Option<FailureClass>,
+}
+
+/// Exit codes of finished jobs from accounting, by job ID
+pub async fn get_job_exits(job_ids: &[String]) -> Result<HashMap<String, JobExit>> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        "--allocations".to_string(),
+        "--format=JobID,State,ExitCode".to_string(),
+        format!("--jobs={}", job_ids.join(",")),
+    ];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split('|').collect();
+            let [id, state, exit_code] = f[..] else {
+                return None;
+            };
+            let exit = JobExit {
+                exit_code: exit_code.to_string(),
+                failure: classify_failure(state, exit_code),
+            };
+            Some((id.to_string(), exit))
+        })
+        .collect())
+}
+
+/// Fields requested for the timeline, in the order they are parsed
+const TIMELINE_FORMAT: &str = "JobID,JobName,State,Submit,Start,End,NodeList";
+
+/// Time format of sacct's Submit, Start and End
+const SACCT_TIME: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// When a job was submitted, started and ended, for the timeline
+#[derive(Debug, Clone)]
+pub struct TimelineJob {
+    pub id: String,
+    pub name: String,
+    pub state: String,
+    pub submit: Option<NaiveDateTime>,
+    /// `None` while pending
+    pub start: Option<NaiveDateTime>,
+    /// `None` while pending or running
+    pub end: Option<NaiveDateTime>,
+    pub node_list: String,
+}
+
+/// The current user's jobs that were queued or running since `start`
+/// (any sacct time, e.g. "now-7days")
+pub async fn get_timeline_jobs(start: &str) -> Result<Vec<TimelineJob>> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        "--allocations".to_string(),
+        format!("--format={}", TIMELINE_FORMAT),
+        format!("--starttime={}", start),
+    ];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    // "Unknown" and "None" mark times that have not come yet
+    let time = |s: &str| NaiveDateTime::parse_from_str(s, SACCT_TIME).ok();
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split('|').collect();
+            (f.len() >= 7).then(|| TimelineJob {
+                id: f[0].to_string(),
+                name: f[1].to_string(),
+                state: f[2].split_whitespace().next().unwrap_or("").to_string(),
+                submit: time(f[3]),
+                start: time(f[4]),
+                end: time(f[5]),
+                node_list: f[6].to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Step fields added to the accounting detail, since they are only recorded per step
+const STEP_FIELDS: &[&str] = &[
+    "State",
+    "ExitCode",
+    "Elapsed",
+    "TotalCPU",
+    "MaxRSS",
+    "MaxVMSize",
+    "NodeList",
+];
+
+/// Every accounting field of a job (`sacct --format=ALL`), for jobs slurmctld
+/// has forgotten: the non-empty fields of the allocation, then [`STEP_FIELDS`]
+/// of each step keyed like "MaxRSS (batch)", with the output they were parsed from
+pub async fn get_job_accounting(job_id: &str) -> Result<(Vec<(String, String)>, String)> {
+    let args = vec![
+        "--parsable2".to_string(),
+        "--format=ALL".to_string(),
+        format!("--jobs={}", job_id),
+    ];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let raw = String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string();
+    let fields = parse_accounting(&raw);
+    if fields.is_empty() {
+        return Err(eyre!("job {} is not in the accounting database", job_id));
+    }
+    Ok((fields, raw))
+}
+
+/// Parse `sacct --parsable2` output with a header line into detail fields
+fn parse_accounting(output: &str) -> Vec<(String, String)> {
+    let mut lines = output.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let names: Vec<&str> = header.split('|').collect();
+    let mut fields = Vec::new();
+    for line in lines {
+        let row = names.iter().zip(line.split('|'));
+        let id = line.split('|').next().unwrap_or_default();
+        match id.split_once('.') {
+            None => fields.extend(
+                row.filter(|(_, value)| !value.is_empty())
+                    .map(|(name, value)| (name.to_string(), value.to_string())),
+            ),
+            Some((_, step)) => fields.extend(
+                row.filter(|(name, value)| STEP_FIELDS.contains(name) && !value.is_empty())
+                    .map(|(name, value)| (format!("{} ({})", name, step), value.to_string())),
+            ),
+        }
+    }
+    fields
+}
+
+/// Environment the batch job was started with, as (name, value) pairs, from
+/// `sacct --env-vars`. Only recorded on sites with `AccountingStoreFlags=job_env`.
+pub async fn get_job_environment(job_id: &str) -> Result<Vec<(String, String)>> {
+    let args = vec!["--env-vars".to_string(), format!("--jobs={}", job_id)];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let vars = parse_environment(&String::from_utf8_lossy(&output.stdout));
+    if vars.is_empty() {
+        return Err(eyre!(
+            "no environment recorded for job {} (needs AccountingStoreFlags=job_env)",
+            job_id
+        ));
+    }
+    Ok(vars)
+}
+
+/// Parse `NAME=value` lines after the "Batch Job Environment for ..." header;
+/// lines that do not start a variable continue the previous (multi-line) value
+fn parse_environment(output: &str) -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for line in output.lines() {
+        // Exported shell functions are named like "BASH_FUNC_module%%"
+        let var = line.split_once('=').filter(|(name, _)| {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '%')
+        });
+        match (var, vars.last_mut()) {
+            (Some((name, value)), _) => vars.push((name.to_string(), value.to_string())),
+            (None, Some((_, value))) => {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+            (None, None) => {}
+        }
+    }
+    vars
+}
+
+/// Fields requested for [`get_job_steps`], in the order they are parsed
+const STEP_FORMAT: &str = "JobID,JobName,State,Elapsed,MaxRSS";
+
+/// A step of a job: the batch script, the extern step or an srun step
+#[derive(Debug, Clone)]
+pub struct JobStep {
+    /// Step ID, e.g. "12345.batch" or "12345.0"
+    pub id: String,
+    pub name: String,
+    pub state: String,
+    pub elapsed: String,
+    /// Peak resident memory in bytes
+    pub max_rss: Option<u64>,
+}
+
+/// Steps of a job from accounting. Accounting records MaxRSS when a step
+/// ends, so for running steps it is taken from `sstat`.
+pub async fn get_job_steps(job_id: &str) -> Result<Vec<JobStep>> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        format!("--format={}", STEP_FORMAT),
+        format!("--jobs={}", job_id),
+    ];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    let mut steps = parse_steps(&String::from_utf8_lossy(&output.stdout));
+
+    if steps.iter().any(|step| step.max_rss.is_none()) {
+        let args = vec![
+            "--noheader".to_string(),
+            "--parsable2".to_string(),
+            "--allsteps".to_string(),
+            "--format=JobID,MaxRSS".to_string(),
+            format!("--jobs={}", job_id),
+        ];
+        // sstat fails once the job has ended; the accounting figures stay
+        if let Ok(output) = execute_command("sstat", args).await {
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                let Some((id, max_rss)) = line.split_once('|') else {
+                    continue;
+                };
+                if let Some(step) = steps.iter_mut().find(|step| step.id == id) {
+                    step.max_rss = step.max_rss.or(parse_size(max_rss, 1));
+                }
+            }
+        }
+    }
+    Ok(steps)
+}
+
+/// Live peak memory of running jobs from `sstat`: the largest MaxRSS of any
+/// of their steps, in bytes, by job ID
+pub async fn get_live_max_rss(job_ids: &[String]) -> Result<HashMap<String, u64>> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        "--allsteps".to_string(),
+        "--format=JobID,MaxRSS".to_string(),
+        format!("--jobs={}", job_ids.join(",")),
+    ];
+    // sstat reports jobs it cannot query on stderr and goes on with the rest
+    let output = execute_command("sstat", args).await?;
+    let mut max_rss: HashMap<String, u64> = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((step_id, rss)) = line.split_once('|') else {
+            continue;
+        };
+        let job_id = step_id.split('.').next().unwrap_or(step_id);
+        if let Some(rss) = parse_size(rss, 1) {
+            let peak = max_rss.entry(job_id.to_string()).or_default();
+            *peak = (*peak).max(rss);
+        }
+    }
+    Ok(max_rss)
+}
+
+/// Parse `sacct --parsable2` output in [`STEP_FORMAT`] order, skipping the allocation row
+fn parse_steps(output: &str) -> Vec<JobStep> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split('|').collect();
+            if f.len() < 5 || !f[0].contains('.') {
+                return None;
+            }
+            Some(JobStep {
+                id: f[0].to_string(),
+                name: f[1].to_string(),
+                // "CANCELLED by 1234" -> "CANCELLED"
+                state: f[2].split_whitespace().next().unwrap_or("").to_string(),
+                elapsed: f[3].to_string(),
+                max_rss: parse_size(f[4], 1),
+            })
+        })
+        .collect()
+}
+
+/// Fields requested for [`get_job_usage`], in the order they are parsed
+const USAGE_FORMAT: &str = "JobID,State,ElapsedRaw,AllocCPUS,TotalCPU,ReqMem,ReqTRES,MaxRSS,NNodes";
+
+/// Resources a job was given and what it used, as recorded by accounting
+#[derive(Debug, Clone)]
+pub struct JobUsage {
+    pub id: String,
+    pub state: String,
+    /// Wall time in seconds
+    pub elapsed: u64,
+    pub cpus: u64,
+    /// CPU time used by all steps, in seconds
+    pub total_cpu: u64,
+    /// Requested memory for the whole job, in bytes
+    pub requested_mem: Option<u64>,
+    /// Highest resident memory of any step, in bytes
+    pub max_rss: Option<u64>,
+}
+
+impl JobUsage {
+    /// CPU time used over CPU time allocated (elapsed × CPUs)
+    pub fn cpu_efficiency(&self) -> Option<f64> {
+        let allocated = self.elapsed * self.cpus;
+        (allocated > 0).then(|| self.total_cpu as f64 / allocated as f64)
+    }
+
+    /// Peak memory over requested memory
+    pub fn mem_efficiency(&self) -> Option<f64> {
+        match (self.max_rss, self.requested_mem) {
+            (Some(used), Some(requested)) if requested > 0 => Some(used as f64 / requested as f64),
+            _ => None,
+        }
+    }
+}
+
+/// CPU and memory use of jobs, for the efficiency panel
+pub async fn get_job_usage(job_ids: &[String]) -> Result<Vec<JobUsage>> {
+    let args = vec![
+        "--noheader".to_string(),
+        "--parsable2".to_string(),
+        format!("--format={}", USAGE_FORMAT),
+        format!("--jobs={}", job_ids.join(",")),
+    ];
+    let output = execute_command("sacct", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(parse_usage(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `sacct --parsable2` output in [`USAGE_FORMAT`] order. Allocation rows
+/// start a job; its steps only contribute their peak memory.
+fn parse_usage(output: &str) -> Vec<JobUsage> {
+    let mut jobs: Vec<JobUsage> = Vec::new();
+    for line in output.lines() {
+        let f: Vec<&str> = line.split('|').collect();
+        if f.len() < 9 {
+            continue;
+        }
+        let max_rss = parse_size(f[7], 1);
+        if let Some((id, _)) = f[0].split_once('.') {
+            if let Some(job) = jobs.iter_mut().find(|job| job.id == id) {
+                job.max_rss = job.max_rss.max(max_rss);
+            }
+            continue;
+        }
+
+        let cpus = f[3].parse().unwrap_or(0);
+        let nodes: u64 = f[8].parse().unwrap_or(1);
+        // ReqTRES has the job total; ReqMem may be per node ("n") or per CPU ("c")
+        let tres_mem = f[6]
+            .split(',')
+            .find_map(|tres| tres.strip_prefix("mem="))
+            .and_then(|mem| parse_size(mem, MIB));
+        let requested_mem = tres_mem.or_else(|| match f[5].strip_suffix('n') {
+            Some(per_node) => parse_size(per_node, MIB).map(|mem| mem * nodes),
+            None => match f[5].strip_suffix('c') {
+                Some(per_cpu) => parse_size(per_cpu, MIB).map(|mem| mem * cpus),
+                None => parse_size(f[5], MIB),
+            },
+        });
+        jobs.push(JobUsage {
+            id: f[0].to_string(),
+            state: f[1].split_whitespace().next().unwrap_or("").to_string(),
+            elapsed: f[2].parse().unwrap_or(0),
+            cpus,
+            // TotalCPU is [D-][HH:]MM:SS[.mmm]
+            total_cpu: parse_time_limit(f[4].split('.').next().unwrap_or("")).unwrap_or(0),
+            requested_mem,
+            max_rss,
+        });
+    }
+    jobs
+}
+
+/// Parse a Slurm memory size like "1234K" or "4G"; `unit` is the multiplier of
+/// a bare number (bytes for MaxRSS, MiB for requests)
+pub fn parse_size(s: &str, unit: u64) -> Option<u64> {
+    let s = s.trim();
+    let (number, multiplier) = match s.chars().last()? {
+        'K' | 'k' => (&s[..s.len() - 1], 1024),
+        'M' | 'm' => (&s[..s.len() - 1], MIB),
+        'G' | 'g' => (&s[..s.len() - 1], 1024 * MIB),
+        'T' | 't' => (&s[..s.len() - 1], 1024 * 1024 * MIB),
+        _ => (s, unit),
+    };
+    let value: f64 = number.parse().ok()?;
+    Some((value * multiplier as f64) as u64)
+}
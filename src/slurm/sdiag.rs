@@ -0,0 +1,124 @@
+use color_eyre::{eyre::eyre, Result};
+
+use super::command::execute_command;
+
+/// Calls of one RPC type or by one user since the statistics were reset
+#[derive(Debug, Clone, Default)]
+pub struct RpcStat {
+    pub name: String,
+    pub count: u64,
+    /// Microseconds
+    pub ave_time: u64,
+    /// Microseconds
+    pub total_time: u64,
+}
+
+/// Scheduler statistics from `sdiag`
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    /// Sections of `key: value` lines in output order, e.g. "Main schedule
+    /// statistics (microseconds)"; the unindented lines form the first, "General"
+    pub sections: Vec<(String, Vec<(String, String)>)>,
+    pub rpc_types: Vec<RpcStat>,
+    pub rpc_users: Vec<RpcStat>,
+}
+
+/// Run `sdiag`
+pub async fn get_diagnostics() -> Result<Diagnostics> {
+    let output = execute_command("sdiag", Vec::new()).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(parse_sdiag(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `sdiag` output. An unindented line followed by indented ones opens a
+/// section they belong to; the RPC sections are parsed into [`RpcStat`]s
+fn parse_sdiag(output: &str) -> Diagnostics {
+    let mut diagnostics = Diagnostics {
+        sections: vec![("General".to_string(), Vec::new())],
+        ..Diagnostics::default()
+    };
+    // Index of the section indented lines go to
+    let mut current = 0;
+    let mut lines = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .peekable();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('*') {
+            continue;
+        }
+        let indented = line.starts_with(char::is_whitespace);
+
+        // "sdiag output at <time>" and "Data since <time>" have no colon
+        if let Some(prefix) = ["sdiag output at", "Data since"]
+            .into_iter()
+            .find(|prefix| trimmed.starts_with(prefix))
+        {
+            let time = trimmed[prefix.len()..].trim().to_string();
+            diagnostics.sections[0].1.push((prefix.to_string(), time));
+            continue;
+        }
+
+        if indented {
+            let title = diagnostics.sections[current].0.as_str();
+            let rpc = if title.contains("by message type") {
+                Some(&mut diagnostics.rpc_types)
+            } else if title.contains("by user") {
+                Some(&mut diagnostics.rpc_users)
+            } else {
+                None
+            };
+            match (rpc, parse_rpc(trimmed)) {
+                (Some(stats), Some(stat)) => stats.push(stat),
+                _ => {
+                    if let Some((key, value)) = trimmed.split_once(':') {
+                        diagnostics.sections[current]
+                            .1
+                            .push((key.trim().to_string(), value.trim().to_string()));
+                    }
+                }
+            }
+            continue;
+        }
+
+        // e.g. "Backfilling stats (WARNING: data obtained in the middle of ...)"
+        let heading = lines
+            .peek()
+            .is_some_and(|next| next.starts_with(char::is_whitespace));
+        match trimmed.split_once(": ") {
+            Some((key, value)) if !heading => {
+                current = 0;
+                diagnostics.sections[0]
+                    .1
+                    .push((key.trim().to_string(), value.trim().to_string()));
+            }
+            _ => {
+                let title = trimmed.trim_end_matches(':').to_string();
+                diagnostics.sections.push((title, Vec::new()));
+                current = diagnostics.sections.len() - 1;
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Parse an RPC line like
+/// "REQUEST_JOB_INFO ( 2003) count:500 ave_time:300 total_time:150000"
+fn parse_rpc(line: &str) -> Option<RpcStat> {
+    let name = line.split_whitespace().next()?;
+    let field = |key: &str| {
+        line.split_whitespace()
+            .find_map(|word| word.strip_prefix(key))
+            .and_then(|n| n.parse::<u64>().ok())
+    };
+    Some(RpcStat {
+        name: name.to_string(),
+        count: field("count:")?,
+        ave_time: field("ave_time:").unwrap_or(0),
+        total_time: field("total_time:").unwrap_or(0),
+    })
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::command::execute_command;
+
+/// Weighted factors queried from sprio, in the order they are parsed, with
+/// their sprio format codes
+const FACTORS: [(&str, &str); 7] = [
+    ("Age", "%A"),
+    ("Fairshare", "%F"),
+    ("JobSize", "%J"),
+    ("Partition", "%P"),
+    ("QoS", "%Q"),
+    ("TRES", "%T"),
+    ("Site", "%S"),
+];
+
+/// Priority of a pending job in one of its partitions, split into the
+/// weighted factors it is the sum of
+#[derive(Debug, Clone)]
+pub struct PriorityFactors {
+    pub partition: String,
+    pub priority: u64,
+    /// Weighted factors in [`FACTORS`] order
+    pub factors: Vec<(&'static str, u64)>,
+    /// Subtracted from the sum (`--nice`)
+    pub nice: i64,
+}
+
+/// Fetch the weighted priority factors of a pending job, one entry per
+/// partition it was submitted to
+pub async fn get_priority_factors(job_id: &str) -> Result<Vec<PriorityFactors>> {
+    let codes: Vec<&str> = FACTORS.iter().map(|(_, code)| *code).collect();
+    let format = format!("%r|%Y|{}|%N", codes.join("|"));
+    let args = vec![
+        "-h".to_string(),
+        "-j".to_string(),
+        job_id.to_string(),
+        "-o".to_string(),
+        format,
+    ];
+    let output = execute_command("sprio", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(parse_factors(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `sprio -h -o %r|%Y|<factors>|%N` output
+fn parse_factors(output: &str) -> Vec<PriorityFactors> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let cols: Vec<&str> = line.split('|').map(str::trim).collect();
+            if cols.len() != FACTORS.len() + 3 {
+                return None;
+            }
+            let factors = FACTORS
+                .iter()
+                .zip(&cols[2..])
+                .map(|((name, _), value)| (*name, weighted(value)))
+                .collect();
+            Some(PriorityFactors {
+                partition: cols[0].to_string(),
+                priority: weighted(cols[1]),
+                factors,
+                nice: cols[cols.len() - 1].parse().unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// A weighted factor; the TRES factor is a list like "cpu=12,mem=3" and counts
+/// with its sum
+fn weighted(value: &str) -> u64 {
+    value
+        .split(',')
+        .map(|item| item.rsplit('=').next().unwrap_or(item))
+        .filter_map(|n| n.parse::<f64>().ok())
+        .sum::<f64>()
+        .round() as u64
+}
+
+/// Rank of every pending job among the pending jobs of its partition, highest
+/// priority first, as (rank, pending in the partition); a job submitted to
+/// several partitions gets its best rank
+pub async fn get_priority_ranks() -> Result<HashMap<String, (usize, usize)>> {
+    let args = ["-h", "-o", "%i|%r|%Y"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let output = execute_command("sprio", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(rank_jobs(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Rank `sprio -h -o %i|%r|%Y` lines within each partition
+fn rank_jobs(output: &str) -> HashMap<String, (usize, usize)> {
+    let mut partitions: HashMap<&str, Vec<(&str, u64)>> = HashMap::new();
+    for line in output.lines() {
+        let cols: Vec<&str> = line.split('|').map(str::trim).collect();
+        let [job, partition, priority] = cols[..] else {
+            continue;
+        };
+        partitions
+            .entry(partition)
+            .or_default()
+            .push((job, weighted(priority)));
+    }
+    let mut ranks: HashMap<String, (usize, usize)> = HashMap::new();
+    for jobs in partitions.values_mut() {
+        jobs.sort_by_key(|(_, priority)| std::cmp::Reverse(*priority));
+        let total = jobs.len();
+        for (i, (job, _)) in jobs.iter().enumerate() {
+            let rank = (i + 1, total);
+            ranks
+                .entry(job.to_string())
+                .and_modify(|best| {
+                    if rank.0 < best.0 {
+                        *best = rank;
+                    }
+                })
+                .or_insert(rank);
+        }
+    }
+    ranks
+}
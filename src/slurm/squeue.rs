@@ -1,11 +1,12 @@
 use async_process::{Command, Output};
-use color_eyre::eyre::Error;
+use color_eyre::eyre::{eyre, Error};
 use color_eyre::Result;
 use std::collections::HashMap;
 use std::str::FromStr;
 
 use super::Job;
 use super::JobState;
+use crate::config::FetchCommand;
 
 #[derive(Debug, Clone)]
 pub struct SqueueOptions {
@@ -15,8 +16,12 @@ pub struct SqueueOptions {
     pub qos: Vec<String>,
     pub name_filter: Option<String>,
     pub node_filter: Option<String>,
+    /// Only list these job IDs
+    pub job_ids: Vec<String>,
     pub format: String,
     pub sorts: HashMap<String, bool>, // Map of field to sort direction (true for ascending, false for descending)
+    /// Site-specific command replacing squeue, from the config file
+    pub command: Option<FetchCommand>,
 }
 
 impl Default for SqueueOptions {
@@ -35,8 +40,10 @@ impl Default for SqueueOptions {
             qos: Vec::new(),
             name_filter: None,
             node_filter: None,
+            job_ids: Vec::new(),
             format: "%i|%j|%u|%T|%M|%N|%C|%m|%P|%q".to_string(), // JobID|Name|User|State|Time|Nodes|CPUs|Memory|Partition|QOS
             sorts,
+            command: None,
         }
     }
 }
@@ -56,6 +63,40 @@ impl SqueueOptions {
 
 impl SqueueOptions {
     pub fn to_args(&self) -> Vec<String> {
+        let mut args = self.filter_args();
+
+        // Name filter is now handled internally by the application
+        // so we don't pass it to squeue
+
+        // Format specification
+        args.push("--format".to_string());
+        args.push(self.format.clone());
+
+        // Sort options
+        if !self.sorts.is_empty() {
+            // Create a sort string from the sorts map
+            let sort_string = self
+                .sorts
+                .iter()
+                .map(|(field, ascending)| {
+                    let prefix = if *ascending { "" } else { "-" };
+                    format!("{}{}", prefix, field)
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+
+            args.push("--sort".to_string());
+            args.push(sort_string);
+        }
+
+        // No header flag to make parsing easier
+        args.push("--noheader".to_string());
+
+        args
+    }
+
+    /// Filter flags for the user, state, partition, QoS and job ID filters
+    pub fn filter_args(&self) -> Vec<String> {
         let mut args = Vec::new();
 
         // User filter
@@ -90,38 +131,21 @@ impl SqueueOptions {
             args.push(qos);
         }
 
-        // Name filter is now handled internally by the application
-        // so we don't pass it to squeue
-
-        // Format specification
-        args.push("--format".to_string());
-        args.push(self.format.clone());
-
-        // Sort options
-        if !self.sorts.is_empty() {
-            // Create a sort string from the sorts map
-            let sort_string = self
-                .sorts
-                .iter()
-                .map(|(field, ascending)| {
-                    let prefix = if *ascending { "" } else { "-" };
-                    format!("{}{}", prefix, field)
-                })
-                .collect::<Vec<_>>()
-                .join(",");
-
-            args.push("--sort".to_string());
-            args.push(sort_string);
+        // Job ID filter
+        if !self.job_ids.is_empty() {
+            args.push("--jobs".to_string());
+            args.push(self.job_ids.join(","));
         }
 
-        // No header flag to make parsing easier
-        args.push("--noheader".to_string());
-
         args
     }
 }
 
 pub async fn run_squeue(options: &SqueueOptions) -> Result<Vec<Job>> {
+    if let Some(command) = &options.command {
+        return run_fetch_command(command, options).await;
+    }
+
     let args = options.to_args();
     // eprintln!("Running squeue with args: {:?}", args);
 
@@ -151,11 +175,74 @@ pub async fn run_squeue(options: &SqueueOptions) -> Result<Vec<Job>> {
     // }
 
     // Pass the format options with the output to ensure correct parsing
-    parse_squeue_output(&output, &options.format)
+    parse_squeue_output(&output, &options.format_codes(), "|")
+}
+
+/// A queued job with its dependency spec, for the dependency graph
+#[derive(Debug, Clone)]
+pub struct QueuedDependency {
+    pub id: String,
+    pub name: String,
+    pub state: String,
+    /// As printed by squeue, e.g. `afterok:12(unfulfilled)`; "(null)" for none
+    pub dependency: String,
+}
+
+/// Queued jobs of a user with their dependencies (`squeue --format=%i|%j|%T|%E`)
+pub async fn get_queue_dependencies(user: &str) -> Result<Vec<QueuedDependency>> {
+    let output = Command::new("squeue")
+        .args(["--noheader", "--format=%i|%j|%T|%E", "--user", user])
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("squeue failed: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let f: Vec<&str> = line.splitn(4, '|').collect();
+            (f.len() == 4).then(|| QueuedDependency {
+                id: f[0].trim().to_string(),
+                name: f[1].to_string(),
+                state: f[2].to_string(),
+                dependency: f[3].trim().to_string(),
+            })
+        })
+        .collect())
 }
 
-/// Dynamic parsing of squeue output based on the provided format string
-fn parse_squeue_output(output: &Output, format: &str) -> Result<Vec<Job>> {
+/// Fetch jobs with a site-specific command from the config file
+async fn run_fetch_command(command: &FetchCommand, options: &SqueueOptions) -> Result<Vec<Job>> {
+    let (program, rest) = command
+        .command
+        .split_first()
+        .ok_or_else(|| eyre!("jobs_command has no program"))?;
+    let mut args = Vec::new();
+    for arg in rest {
+        if arg == "{filters}" {
+            args.extend(options.filter_args());
+        } else {
+            args.push(arg.clone());
+        }
+    }
+
+    let output = Command::new(program).args(&args).output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{} failed: {}", program, stderr.trim()));
+    }
+
+    let codes: Vec<&str> = command.fields.iter().map(String::as_str).collect();
+    parse_squeue_output(&output, &codes, &command.delimiter)
+}
+
+/// Dynamic parsing of squeue output based on the format codes of its fields
+fn parse_squeue_output(
+    output: &Output,
+    format_codes: &[&str],
+    delimiter: &str,
+) -> Result<Vec<Job>> {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let lines: Vec<&str> = stdout.lines().collect();
 
@@ -169,8 +256,6 @@ fn parse_squeue_output(output: &Output, format: &str) -> Result<Vec<Job>> {
         return Ok(jobs);
     }
 
-    let format_codes: Vec<&str> = format.split('|').collect();
-
     if format_codes.is_empty() {
         // eprintln!("Warning: Empty format codes, using default format");
         return Ok(jobs);
@@ -183,7 +268,7 @@ fn parse_squeue_output(output: &Output, format: &str) -> Result<Vec<Job>> {
             continue;
         }
 
-        let parts: Vec<&str> = line.split('|').collect();
+        let parts: Vec<&str> = line.split(delimiter).collect();
         if parts.is_empty() || parts.len() < format_codes.len() / 2 {
             // eprintln!("Skipping invalid line: {}", line);
             continue;
@@ -213,40 +298,22 @@ fn parse_squeue_output(output: &Output, format: &str) -> Result<Vec<Job>> {
                 "%i" | "%A" => job.id = value,
                 "%j" => job.name = value,
                 "%u" => job.user = value,
-                "%T" => {
-                    job.state = JobState::from_str(&value).unwrap_or_else(|_| {
-                        // eprintln!("Failed to parse job state: {}", value);
-                        JobState::Other
-                    })
-                }
+                "%T" => job.state = JobState::from_str(&value).unwrap_or(JobState::Other),
                 "%M" => job.time = value,
-                "%D" => {
-                    job.nodes = value.parse::<u32>().unwrap_or_else(|_| {
-                        // eprintln!("Failed to parse node count: {}", value);
-                        0
-                    })
-                }
+                "%l" => job.time_limit = Some(value),
+                "%D" => job.nodes = value.parse::<u32>().unwrap_or(0),
                 "%N" => job.node = Some(value),
-                "%C" => {
-                    job.cpus = value.parse::<u32>().unwrap_or_else(|_| {
-                        // eprintln!("Failed to parse CPU count: {}", value);
-                        0
-                    })
-                }
+                "%C" => job.cpus = value.parse::<u32>().unwrap_or(0),
                 "%m" => job.memory = value,
                 "%P" => job.partition = value,
                 "%q" => job.qos = value,
                 "%a" => job.account = Some(value),
-                "%Q" => {
-                    job.priority = value.parse::<u32>().ok().or_else(|| {
-                        // eprintln!("Failed to parse priority: {}", value);
-                        None
-                    })
-                }
+                "%Q" => job.priority = value.parse::<u32>().ok(),
                 "%Z" => job.work_dir = Some(value),
                 "%V" => job.submit_time = Some(value),
                 "%S" => job.start_time = Some(value),
                 "%e" => job.end_time = Some(value),
+                "%R" | "%r" => job.pending_reason = Some(value),
                 _ => {
                     // eprintln!("Unknown format code: {}", format_codes[i]);
                 }
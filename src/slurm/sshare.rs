@@ -0,0 +1,111 @@
+use color_eyre::{eyre::eyre, Result};
+
+use super::command::execute_command;
+
+/// One account or user association from `sshare -l`
+#[derive(Debug, Clone, Default)]
+pub struct ShareNode {
+    /// Levels below root, from the indentation of the Account column
+    pub depth: usize,
+    pub account: String,
+    /// Set on user associations, the leaves of the tree
+    pub user: Option<String>,
+    /// Unique within the tree, e.g. "root/physics/alice@"; kept across refreshes
+    pub path: String,
+    /// Shares as configured, or "parent"
+    pub raw_shares: String,
+    pub norm_shares: String,
+    pub raw_usage: String,
+    pub effective_usage: String,
+    /// Fair-share factor of a user, 0.0 to 1.0
+    pub fairshare: Option<f64>,
+    /// Share over usage at this level of the tree (fair tree algorithm)
+    pub level_fs: String,
+    pub has_children: bool,
+}
+
+impl ShareNode {
+    /// Name shown in the tree
+    pub fn label(&self) -> &str {
+        self.user.as_deref().unwrap_or(&self.account)
+    }
+}
+
+/// Fetch the fair-share tree of my associations and their parent accounts
+pub async fn get_fairshare_tree() -> Result<Vec<ShareNode>> {
+    let args = vec!["-l".to_string(), "-P".to_string()];
+    let output = execute_command("sshare", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(parse_sshare(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `sshare -l -P` output; the columns are found by their header, as
+/// they differ between Slurm versions
+fn parse_sshare(output: &str) -> Vec<ShareNode> {
+    let mut lines = output.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let columns: Vec<&str> = header.split('|').map(str::trim).collect();
+    let index = |name: &str| columns.iter().position(|c| *c == name);
+    let (Some(account_col), user_col) = (index("Account"), index("User")) else {
+        return Vec::new();
+    };
+
+    let mut nodes: Vec<ShareNode> = Vec::new();
+    // Path of the last node at each depth
+    let mut ancestors: Vec<String> = Vec::new();
+    for line in lines {
+        let cols: Vec<&str> = line.split('|').collect();
+        let Some(account_field) = cols.get(account_col) else {
+            continue;
+        };
+        let account = account_field.trim();
+        if account.is_empty() {
+            continue;
+        }
+        let depth = account_field.len() - account_field.trim_start().len();
+        let get = |name: &str| {
+            index(name)
+                .and_then(|i| cols.get(i))
+                .map(|v| v.trim().to_string())
+                .unwrap_or_default()
+        };
+        let user = user_col
+            .and_then(|i| cols.get(i))
+            .map(|u| u.trim())
+            .filter(|u| !u.is_empty())
+            .map(str::to_string);
+
+        ancestors.truncate(depth);
+        let parent = ancestors.last().cloned().unwrap_or_default();
+        let path = match &user {
+            Some(user) => format!("{}/{}@", parent, user),
+            None => format!("{}/{}", parent, account),
+        };
+        if depth > 0 {
+            if let Some(parent) = nodes.iter_mut().rev().find(|n| n.depth == depth - 1) {
+                parent.has_children = true;
+            }
+        }
+        ancestors.push(path.clone());
+
+        nodes.push(ShareNode {
+            depth,
+            account: account.to_string(),
+            user,
+            path,
+            raw_shares: get("RawShares"),
+            norm_shares: get("NormShares"),
+            raw_usage: get("RawUsage"),
+            effective_usage: get("EffectvUsage"),
+            fairshare: get("FairShare").parse().ok(),
+            level_fs: get("LevelFS"),
+            has_children: false,
+        });
+    }
+    nodes
+}
@@ -0,0 +1,57 @@
+use std::process::Command;
+
+use super::{parse_time_limit, sacct::parse_size};
+
+/// Fields requested from sstat, in the order they are parsed
+const STATS_FORMAT: &str = "JobID,AveCPU,MaxRSS,MaxDiskRead,MaxDiskWrite";
+
+/// Live resource use of a running job: for each figure, the largest over its steps
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JobStats {
+    /// Average CPU time of the tasks, in seconds
+    pub ave_cpu: u64,
+    /// Peak resident memory, in bytes
+    pub max_rss: u64,
+    /// Bytes read from and written to disk by the busiest task
+    pub max_disk_read: u64,
+    pub max_disk_write: u64,
+}
+
+/// Query `sstat` for the running steps of a job. Runs the command directly,
+/// for use on a polling thread.
+pub fn query_job_stats(job_id: &str) -> Result<JobStats, String> {
+    let output = Command::new("sstat")
+        .args(["--noheader", "--parsable2", "--allsteps"])
+        .arg(format!("--format={}", STATS_FORMAT))
+        .arg(format!("--jobs={}", job_id))
+        .output()
+        .map_err(|e| format!("failed to run sstat: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || stdout.trim().is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .next()
+            .unwrap_or("sstat reported no running steps")
+            .to_string());
+    }
+    Ok(parse_stats(&stdout))
+}
+
+/// Parse `sstat --parsable2` output in [`STATS_FORMAT`] order
+fn parse_stats(output: &str) -> JobStats {
+    let mut stats = JobStats::default();
+    for line in output.lines() {
+        let f: Vec<&str> = line.split('|').collect();
+        if f.len() < 5 {
+            continue;
+        }
+        // AveCPU is [D-][HH:]MM:SS[.mmm]
+        let ave_cpu = parse_time_limit(f[1].split('.').next().unwrap_or("")).unwrap_or(0);
+        stats.ave_cpu = stats.ave_cpu.max(ave_cpu);
+        stats.max_rss = stats.max_rss.max(parse_size(f[2], 1).unwrap_or(0));
+        stats.max_disk_read = stats.max_disk_read.max(parse_size(f[3], 1).unwrap_or(0));
+        stats.max_disk_write = stats.max_disk_write.max(parse_size(f[4], 1).unwrap_or(0));
+    }
+    stats
+}
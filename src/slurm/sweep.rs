@@ -0,0 +1,100 @@
+use regex::Regex;
+use std::{fs, io, path::Path};
+
+use crate::utils::expand_home;
+
+/// Environment variable holding the path of the parameter file in every array task
+pub const PARAMS_VAR: &str = "SLURMER_PARAMS";
+
+/// Parameters of a sweep: the files matching a glob such as `data/*.csv`, or the
+/// values of a comma-separated list such as `0.1,0.01,0.001`
+pub fn expand_sweep(spec: &str) -> Result<Vec<String>, String> {
+    let spec = spec.trim();
+    if !spec.contains(['*', '?', '[']) {
+        let values: Vec<String> = spec
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .collect();
+        if values.is_empty() {
+            return Err("no values given".to_string());
+        }
+        return Ok(values);
+    }
+
+    // Wildcards are matched in the file name only, like most shell globs in practice
+    let (dir, pattern) = match spec.rsplit_once('/') {
+        Some(("", pattern)) => ("/", pattern),
+        Some((dir, pattern)) => (dir, pattern),
+        None => ("", spec),
+    };
+    if dir.contains(['*', '?', '[']) {
+        return Err("wildcards are only supported in the file name".to_string());
+    }
+    let regex = glob_regex(pattern).ok_or("invalid pattern")?;
+    let read_dir = if dir.is_empty() { "." } else { dir };
+    let entries = fs::read_dir(expand_home(read_dir)).map_err(|e| e.to_string())?;
+    let mut matches: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        // Like the shell, `*` does not match hidden files
+        .filter(|name| !name.starts_with('.') || pattern.starts_with('.'))
+        .filter(|name| regex.is_match(name))
+        .map(|name| match dir {
+            "" => name,
+            "/" => format!("/{}", name),
+            _ => format!("{}/{}", dir, name),
+        })
+        .collect();
+    matches.sort();
+    if matches.is_empty() {
+        return Err("no files match".to_string());
+    }
+    Ok(matches)
+}
+
+/// Regex matching a whole file name against a glob with `*`, `?` and `[...]`
+fn glob_regex(pattern: &str) -> Option<Regex> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            '[' => {
+                // `[!abc]` negates the class, as in the shell
+                let class: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                match class.strip_prefix('!') {
+                    Some(rest) => regex.push_str(&format!("[^{}]", rest)),
+                    None => regex.push_str(&format!("[{}]", class)),
+                }
+            }
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).ok()
+}
+
+/// `--array` range for a sweep of `count` tasks, indexed from 0 like the lines
+/// of the parameter file
+pub fn array_range(count: usize) -> String {
+    match count {
+        0 | 1 => "0".to_string(),
+        _ => format!("0-{}", count - 1),
+    }
+}
+
+/// Parameter file next to the script: `run.sh` gets `run.params`
+pub fn params_path(script: &str) -> String {
+    Path::new(script)
+        .with_extension("params")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Write one parameter per line; array task `i` reads line `i + 1`
+pub fn write_params(path: &str, params: &[String]) -> io::Result<()> {
+    fs::write(path, params.join("\n") + "\n")
+}
@@ -0,0 +1,146 @@
+use std::collections::{HashMap, HashSet};
+
+use color_eyre::{eyre::eyre, Result};
+
+use super::{
+    command::{execute_command, parse_scontrol_output},
+    expand_hostlist,
+};
+
+/// A running or pending job of the queue, as far as the user leaderboard needs it
+#[derive(Debug, Clone)]
+pub struct QueuedJob {
+    pub user: String,
+    /// Pending jobs may name several partitions
+    pub partitions: Vec<String>,
+    pub running: bool,
+    pub cpus: u32,
+    pub gpus: u32,
+    /// Nodes a running job is on
+    pub nodes: Vec<String>,
+}
+
+/// What one user holds and waits for, for the leaderboard
+#[derive(Debug, Clone, Default)]
+pub struct UserUsage {
+    pub user: String,
+    pub running_jobs: u32,
+    /// CPUs and GPUs of the running jobs
+    pub cpus: u32,
+    pub gpus: u32,
+    /// Distinct nodes the running jobs are on
+    pub nodes: u32,
+    pub pending_jobs: u32,
+    /// CPUs the pending jobs ask for
+    pub pending_cpus: u32,
+}
+
+/// Fetch the running and pending jobs of every user from `scontrol show job`
+pub async fn get_queued_jobs() -> Result<Vec<QueuedJob>> {
+    let args = vec!["show".to_string(), "job".to_string(), "-o".to_string()];
+    let output = execute_command("scontrol", args).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("{}", stderr.trim()));
+    }
+    Ok(parse_queued_jobs(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `scontrol show job -o` output, keeping running and pending jobs
+fn parse_queued_jobs(output: &str) -> Vec<QueuedJob> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields = parse_scontrol_output(line);
+            let running = match fields.get("JobState").map(String::as_str) {
+                Some("RUNNING") => true,
+                Some("PENDING") => false,
+                _ => return None,
+            };
+            // "alice(1001)"
+            let user = fields.get("UserId")?;
+            let user = user.split('(').next().unwrap_or(user).to_string();
+            let partitions = fields
+                .get("Partition")
+                .map(|p| p.split(',').map(str::to_string).collect())
+                .unwrap_or_default();
+            let cpus = fields
+                .get("NumCPUs")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+            let (gpus, nodes) = if running {
+                let tres = fields.get("AllocTRES").map_or("", String::as_str);
+                let nodes = fields
+                    .get("NodeList")
+                    .map(|n| expand_hostlist(n))
+                    .unwrap_or_default();
+                (tres_gpus(tres), nodes)
+            } else {
+                (0, Vec::new())
+            };
+            Some(QueuedJob {
+                user,
+                partitions,
+                running,
+                cpus,
+                gpus,
+                nodes,
+            })
+        })
+        .collect()
+}
+
+/// GPUs of a TRES list like "cpu=8,gres/gpu=2,gres/gpu:a100=2": the untyped
+/// count, or the sum of the typed ones when it is missing
+fn tres_gpus(tres: &str) -> u32 {
+    let mut untyped = None;
+    let mut typed = 0;
+    for entry in tres.split(',') {
+        let Some((key, value)) = entry.split_once('=') else {
+            continue;
+        };
+        let Ok(count) = value.parse::<u32>() else {
+            continue;
+        };
+        match key.strip_prefix("gres/gpu") {
+            Some("") => untyped = Some(count),
+            Some(name) if name.starts_with(':') => typed += count,
+            _ => {}
+        }
+    }
+    untyped.unwrap_or(typed)
+}
+
+/// Add up the jobs per user, those of one partition only if given; a pending
+/// job submitted to several partitions counts in each of them
+pub fn leaderboard(jobs: &[QueuedJob], partition: Option<&str>) -> Vec<UserUsage> {
+    let mut users: HashMap<&str, (UserUsage, HashSet<&str>)> = HashMap::new();
+    for job in jobs {
+        if partition.is_some_and(|p| !job.partitions.iter().any(|j| j == p)) {
+            continue;
+        }
+        let (usage, nodes) = users.entry(job.user.as_str()).or_insert_with(|| {
+            let usage = UserUsage {
+                user: job.user.clone(),
+                ..UserUsage::default()
+            };
+            (usage, HashSet::new())
+        });
+        if job.running {
+            usage.running_jobs += 1;
+            usage.cpus += job.cpus;
+            usage.gpus += job.gpus;
+            nodes.extend(job.nodes.iter().map(String::as_str));
+        } else {
+            usage.pending_jobs += 1;
+            usage.pending_cpus += job.cpus;
+        }
+    }
+    users
+        .into_values()
+        .map(|(mut usage, nodes)| {
+            usage.nodes = nodes.len() as u32;
+            usage
+        })
+        .collect()
+}
@@ -0,0 +1,136 @@
+use chrono::Local;
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// Submissions kept in the history; older ones are dropped
+const HISTORY_LIMIT: usize = 500;
+
+/// Jobs submitted through slurmer: the sbatch arguments of the last job submitted
+/// from the form, per cluster, so it can be submitted again with one key, and a
+/// history of every sbatch run
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Submissions {
+    #[serde(default)]
+    last: BTreeMap<String, Vec<String>>,
+    /// Oldest first
+    #[serde(default)]
+    history: Vec<Submission>,
+}
+
+/// One sbatch run and how it went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub cluster: String,
+    /// Local time, e.g. "2024-05-01 10:00:00"
+    pub time: String,
+    pub args: Vec<String>,
+    /// Hash of the script as submitted; empty if it could not be read
+    #[serde(default)]
+    pub script_hash: String,
+    /// ID of the new job; none if sbatch failed
+    pub job_id: Option<String>,
+    /// Why sbatch failed
+    pub error: Option<String>,
+    /// Template the job was filled from, if any
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+impl Submission {
+    /// A submission made now with these arguments; the script is the last one
+    pub fn new(cluster: &str, args: &[String], result: Result<Option<String>, String>) -> Self {
+        let (job_id, error) = match result {
+            Ok(job_id) => (job_id, None),
+            Err(e) => (None, Some(e)),
+        };
+        Self {
+            cluster: cluster.to_string(),
+            time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            args: args.to_vec(),
+            script_hash: args.last().and_then(|s| script_hash(s)).unwrap_or_default(),
+            job_id,
+            error,
+            template: None,
+        }
+    }
+
+    /// Whether the script differs from the one submitted, or is gone
+    pub fn script_changed(&self) -> bool {
+        let current = self.args.last().and_then(|s| script_hash(s));
+        !self.script_hash.is_empty() && current.as_deref() != Some(self.script_hash.as_str())
+    }
+}
+
+/// FNV-1a hash of a script's contents, in hex; stable across runs and builds
+fn script_hash(path: &str) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    let hash = content.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+    Some(format!("{:016x}", hash))
+}
+
+/// Location of the submissions file, `~/.local/share/slurmer/submissions.toml`
+fn path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("slurmer").join("submissions.toml"))
+}
+
+impl Submissions {
+    /// Load the last submissions; a missing file means none
+    pub fn load() -> Result<Self> {
+        let Some(path) = path().filter(|p| p.exists()) else {
+            return Ok(Self::default());
+        };
+        let text = fs::read_to_string(&path)
+            .map_err(|e| eyre!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| eyre!("Invalid {}: {}", path.display(), e))
+    }
+
+    /// Arguments of the last submission on a cluster
+    pub fn last(&self, cluster: &str) -> Option<&[String]> {
+        self.last.get(cluster).map(Vec::as_slice)
+    }
+
+    /// Submissions on a cluster, newest first
+    pub fn history(&self, cluster: &str) -> Vec<Submission> {
+        self.history
+            .iter()
+            .rev()
+            .filter(|s| s.cluster == cluster)
+            .cloned()
+            .collect()
+    }
+
+    /// The submission that queued a job; an array task is found by its array's ID
+    pub fn submitted(&self, cluster: &str, job_id: &str) -> Option<&Submission> {
+        let array_id = job_id.split('_').next().unwrap_or(job_id);
+        self.history
+            .iter()
+            .rev()
+            .find(|s| s.cluster == cluster && s.job_id.as_deref().is_some_and(|id| id == array_id))
+    }
+
+    /// Remember the arguments of a submission and save
+    pub fn record(&mut self, cluster: &str, args: &[String]) -> Result<()> {
+        self.last.insert(cluster.to_string(), args.to_vec());
+        self.save()
+    }
+
+    /// Add a submission to the history and save
+    pub fn log(&mut self, submission: Submission) -> Result<()> {
+        self.history.push(submission);
+        let excess = self.history.len().saturating_sub(HISTORY_LIMIT);
+        self.history.drain(..excess);
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = path().ok_or_else(|| eyre!("No data directory found"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)
+            .map_err(|e| eyre!("Failed to write {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,195 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    actions::{ActionCache, ActionResult, CustomAction},
+    i18n::{tr, trf},
+    slurm::Job,
+};
+
+/// Result of a key press in the actions popup
+pub enum ActionsPopupAction {
+    None,
+    /// Run (or re-run) the action at this index for the job; see [`ActionsPopup::target`]
+    Run(usize),
+}
+
+/// Popup listing the user-defined actions for a job and showing their last output
+pub struct ActionsPopup {
+    pub visible: bool,
+    actions: Vec<CustomAction>,
+    job: Option<Job>,
+    cache: ActionCache,
+    state: TableState,
+    /// Index of the action whose output is shown, if any
+    output: Option<usize>,
+    scroll: u16,
+}
+
+impl ActionsPopup {
+    pub fn new(actions: Vec<CustomAction>) -> Self {
+        Self {
+            visible: false,
+            actions,
+            job: None,
+            cache: ActionCache::default(),
+            state: TableState::default(),
+            output: None,
+            scroll: 0,
+        }
+    }
+
+    pub fn has_actions(&self) -> bool {
+        !self.actions.is_empty()
+    }
+
+    /// Show the action list for a job
+    pub fn show(&mut self, job: Job) {
+        self.job = Some(job);
+        self.output = None;
+        self.state.select(Some(0));
+        self.visible = true;
+    }
+
+    /// Hide the popup
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// The action at `index` and the job it is shown for
+    pub fn target(&self, index: usize) -> Option<(CustomAction, Job)> {
+        Some((self.actions.get(index)?.clone(), self.job.clone()?))
+    }
+
+    /// Store the result of an action and show it
+    pub fn set_result(&mut self, job_id: &str, action: &str, result: ActionResult) {
+        self.cache.insert(job_id, action, result);
+        self.output = self.actions.iter().position(|a| a.name == action);
+        self.scroll = 0;
+    }
+
+    /// Handle key events for the popup
+    pub fn handle_key(&mut self, key: KeyEvent) -> ActionsPopupAction {
+        let Some(job) = &self.job else {
+            return ActionsPopupAction::None;
+        };
+
+        if let Some(index) = self.output {
+            match key.code {
+                KeyCode::Char('r') => {
+                    return ActionsPopupAction::Run(index);
+                }
+                KeyCode::Char('q') | KeyCode::Backspace => self.output = None,
+                KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+                KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+                _ => {}
+            }
+            return ActionsPopupAction::None;
+        }
+
+        let last = self.actions.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Enter | KeyCode::Char('r') => {
+                let Some(action) = self.actions.get(selected) else {
+                    return ActionsPopupAction::None;
+                };
+                // Show the cached result instantly; r always runs the action again
+                if key.code == KeyCode::Enter && self.cache.get(&job.id, &action.name).is_some() {
+                    self.output = Some(selected);
+                    self.scroll = 0;
+                } else {
+                    return ActionsPopupAction::Run(selected);
+                }
+            }
+            _ => {}
+        }
+        ActionsPopupAction::None
+    }
+
+    /// Render the popup
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+        let Some(job) = &self.job else {
+            return;
+        };
+
+        let shown = self
+            .output
+            .and_then(|i| self.actions.get(i))
+            .and_then(|action| Some((action, self.cache.get(&job.id, &action.name)?)));
+        if let Some((action, result)) = shown {
+            let (state, color) = if result.success {
+                ("ok", Color::Green)
+            } else {
+                ("failed", Color::Red)
+            };
+            let block = Block::default()
+                .title(
+                    Line::from(format!(
+                        "{} - job {} - {} ({})",
+                        action.name,
+                        job.id,
+                        result.time.format("%H:%M:%S"),
+                        state
+                    ))
+                    .centered(),
+                )
+                .title_bottom(
+                    Line::from(format!(
+                        "$ {} | r: Refresh | q: Back | Esc: Close",
+                        result.command
+                    ))
+                    .centered(),
+                )
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color))
+                .style(Style::default().bg(Color::Black));
+            let output = Paragraph::new(result.output.as_str())
+                .block(block)
+                .scroll((self.scroll, 0));
+            frame.render_widget(output, area);
+            return;
+        }
+
+        let block = Block::default()
+            .title(Line::from(trf("Actions for job {id}", &[("id", &job.id)])).centered())
+            .title_bottom(
+                Line::from(tr(
+                    "Enter: Show (last result if any) | r: Run again | Esc/q: Close",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+
+        let rows = self.actions.iter().map(|action| {
+            let last_run = match self.cache.get(&job.id, &action.name) {
+                Some(result) if result.success => {
+                    Cell::from(format!("ran {}", result.time.format("%H:%M:%S")))
+                }
+                Some(result) => Cell::from(format!("failed {}", result.time.format("%H:%M:%S")))
+                    .style(Style::default().fg(Color::Red)),
+                None => Cell::from("not run").style(Style::default().fg(Color::DarkGray)),
+            };
+            Row::new(vec![Cell::from(action.name.as_str()), last_run])
+        });
+        let table = Table::new(rows, [Constraint::Min(20), Constraint::Length(16)])
+            .block(block)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::i18n::tr;
+
+/// Alert listing changes of watched jobs; stays open until acknowledged
+pub struct WatchAlert {
+    pub visible: bool,
+    messages: Vec<(DateTime<Local>, String)>,
+    scroll: u16,
+}
+
+impl WatchAlert {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            messages: Vec::new(),
+            scroll: 0,
+        }
+    }
+
+    /// Add messages and show the alert; unacknowledged messages are kept
+    pub fn push(&mut self, messages: Vec<String>) {
+        let now = Local::now();
+        self.messages
+            .extend(messages.into_iter().map(|message| (now, message)));
+        self.visible = true;
+    }
+
+    /// Acknowledge all messages and hide the alert
+    pub fn hide(&mut self) {
+        self.messages.clear();
+        self.scroll = 0;
+        self.visible = false;
+    }
+
+    /// Handle key events for the alert
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down if (self.scroll as usize) + 1 < self.messages.len() => {
+                self.scroll += 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Render the alert
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(tr(" Watched jobs changed ")).centered())
+            .title_bottom(Line::from(tr("↑/↓: Scroll | Enter/Esc/q: Dismiss")).centered())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            .style(Style::default().bg(Color::Black));
+
+        let lines: Vec<Line> = self
+            .messages
+            .iter()
+            .map(|(time, message)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", time.format("%H:%M:%S")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        message.as_str(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                ])
+            })
+            .collect();
+        let alert = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(alert, area);
+    }
+}
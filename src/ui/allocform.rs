@@ -0,0 +1,236 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    i18n::tr,
+    slurm::command::SlurmCommand,
+    ui::{
+        jobedit::cycle,
+        submitform::{validate_field, SubmitField},
+    },
+};
+
+/// Job name given to allocations when none is entered
+const DEFAULT_NAME: &str = "interactive";
+
+/// Action to take after handling a key in the allocation form
+#[derive(Debug, Clone)]
+pub enum AllocAction {
+    /// Do nothing
+    None,
+    /// Close the form
+    Close,
+    /// Run this `salloc --no-shell`, then open a shell in the allocation named `name`
+    Allocate { command: SlurmCommand, name: String },
+}
+
+/// A single input of the allocation form
+struct AllocInput {
+    field: SubmitField,
+    value: String,
+    error: Option<String>,
+}
+
+/// Popup form for an interactive allocation with `salloc`
+pub struct AllocForm {
+    pub visible: bool,
+    fields: Vec<AllocInput>,
+    focus: usize,
+    /// Feedback shown above the help text (e.g. "Fix the highlighted fields")
+    message: Option<String>,
+}
+
+impl AllocForm {
+    pub fn new() -> Self {
+        let fields = [
+            SubmitField::Partition,
+            SubmitField::TimeLimit,
+            SubmitField::Cpus,
+            SubmitField::Memory,
+            SubmitField::Gpus,
+            SubmitField::Account,
+            SubmitField::JobName,
+        ]
+        .into_iter()
+        .map(|field| AllocInput {
+            field,
+            value: if field == SubmitField::JobName {
+                DEFAULT_NAME.to_string()
+            } else {
+                String::new()
+            },
+            error: None,
+        })
+        .collect();
+        Self {
+            visible: false,
+            fields,
+            focus: 0,
+            message: None,
+        }
+    }
+
+    /// Show the form; values entered last time are kept
+    pub fn show(&mut self) {
+        self.focus = 0;
+        self.message = None;
+        self.visible = true;
+    }
+
+    /// Hide the form
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Job name of the allocation; the name finds it in the list once granted
+    fn name(&self) -> String {
+        self.fields
+            .iter()
+            .find(|f| f.field == SubmitField::JobName)
+            .map(|f| f.value.trim())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(DEFAULT_NAME)
+            .to_string()
+    }
+
+    /// The `salloc` command for the entered values. `--no-shell` keeps the
+    /// allocation after the shell opened in it exits.
+    fn command(&self) -> SlurmCommand {
+        let mut args = vec!["--no-shell".to_string()];
+        args.extend(
+            self.fields
+                .iter()
+                .filter(|f| f.field != SubmitField::JobName && !f.value.trim().is_empty())
+                .filter_map(|f| f.field.sbatch_arg(f.value.trim())),
+        );
+        args.push(format!("--job-name={}", self.name()));
+        SlurmCommand::new("salloc", args, Vec::new())
+    }
+
+    /// Handle key events for the allocation form
+    pub fn handle_key(&mut self, key: KeyEvent, partitions: &[String]) -> AllocAction {
+        match key.code {
+            KeyCode::Esc => AllocAction::Close,
+            KeyCode::Up | KeyCode::BackTab => {
+                self.focus = (self.focus + self.fields.len() - 1) % self.fields.len();
+                AllocAction::None
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.focus = (self.focus + 1) % self.fields.len();
+                AllocAction::None
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let f = &mut self.fields[self.focus];
+                if f.field == SubmitField::Partition {
+                    cycle(&mut f.value, partitions, key.code == KeyCode::Right);
+                    f.error = None;
+                }
+                AllocAction::None
+            }
+            KeyCode::Enter => {
+                for f in &mut self.fields {
+                    f.error = validate_field(f.field, &f.value, partitions);
+                }
+                if self.fields.iter().any(|f| f.error.is_some()) {
+                    self.message = Some("Fix the highlighted fields first".to_string());
+                    return AllocAction::None;
+                }
+                AllocAction::Allocate {
+                    command: self.command(),
+                    name: self.name(),
+                }
+            }
+            KeyCode::Char(c) => {
+                let f = &mut self.fields[self.focus];
+                f.value.push(c);
+                f.error = None;
+                AllocAction::None
+            }
+            KeyCode::Backspace => {
+                let f = &mut self.fields[self.focus];
+                f.value.pop();
+                f.error = None;
+                AllocAction::None
+            }
+            _ => AllocAction::None,
+        }
+    }
+
+    /// Render the allocation form
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(tr("Interactive Allocation")).centered())
+            .borders(Borders::NONE)
+            .style(Style::default().bg(Color::Black));
+        frame.render_widget(block, area);
+
+        let mut constraints: Vec<Constraint> =
+            self.fields.iter().map(|_| Constraint::Length(3)).collect();
+        constraints.push(Constraint::Length(3)); // Command
+        constraints.push(Constraint::Min(0)); // Message
+        constraints.push(Constraint::Length(3)); // Help text
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(constraints)
+            .split(area);
+
+        for (i, f) in self.fields.iter().enumerate() {
+            let focused = i == self.focus;
+            let title = match &f.error {
+                Some(err) => format!("{} ✗ {}", f.field.title(), err),
+                None if f.field.optional() => format!("{} (optional)", f.field.title()),
+                None => f.field.title().to_string(),
+            };
+            let style = match (focused, f.error.is_some()) {
+                (true, _) => Style::default().fg(Color::Cyan),
+                (false, true) => Style::default().fg(Color::Red),
+                (false, false) => Style::default(),
+            };
+            let input = Paragraph::new(f.value.clone()).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .style(style),
+            );
+            frame.render_widget(input, chunks[i]);
+
+            if focused {
+                frame.set_cursor_position(Position {
+                    x: chunks[i].x + 1 + f.value.chars().count() as u16,
+                    y: chunks[i].y + 1,
+                });
+            }
+        }
+
+        let n = self.fields.len();
+        let command = Paragraph::new(self.command().to_string())
+            .style(Style::default().fg(Color::Gray))
+            .block(Block::default().title(tr("Command")).borders(Borders::ALL));
+        frame.render_widget(command, chunks[n]);
+
+        if let Some(message) = &self.message {
+            let msg = Paragraph::new(message.as_str())
+                .style(Style::default().fg(Color::Yellow))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(msg, chunks[n + 1]);
+        }
+
+        let help_text =
+            "↑/↓/Tab: Switch field | ←/→: Cycle partition | Enter: Allocate and open a shell | Esc: Close";
+        let help = Paragraph::new(tr(help_text))
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[n + 2]);
+    }
+}
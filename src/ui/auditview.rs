@@ -0,0 +1,112 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    i18n::tr,
+    slurm::{audit::AuditLog, command::CommandStatus},
+};
+
+/// Scrollable panel listing the commands recorded in the audit log
+pub struct AuditPanel {
+    pub visible: bool,
+    state: TableState,
+}
+
+impl AuditPanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            state: TableState::default(),
+        }
+    }
+
+    /// Show the panel with the newest entry selected
+    pub fn show(&mut self, log: &AuditLog) {
+        self.state
+            .select(log.entries.len().checked_sub(1).or(Some(0)));
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent, log: &AuditLog) {
+        let last = log.entries.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::PageUp => self.state.select(Some(selected.saturating_sub(10))),
+            KeyCode::PageDown => self.state.select(Some((selected + 10).min(last))),
+            KeyCode::Home => self.state.select(Some(0)),
+            KeyCode::End => self.state.select(Some(last)),
+            _ => {}
+        }
+    }
+
+    /// Render the panel
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, log: &AuditLog) {
+        frame.render_widget(Clear, area);
+
+        let location = log
+            .path()
+            .map(|p| format!(" - {}", p.display()))
+            .unwrap_or_default();
+        let block = Block::default()
+            .title(Line::from(format!("Audit Log{}", location)).centered())
+            .title_bottom(Line::from(tr("↑/↓/PgUp/PgDn: Scroll | Esc/q: Close")).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+
+        if log.entries.is_empty() {
+            let empty = Table::new(
+                vec![Row::new(vec!["No commands have been run in this session"])],
+                [Constraint::Percentage(100)],
+            )
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let rows = log.entries.iter().map(|entry| {
+            let status_style = match entry.status {
+                CommandStatus::Success => Style::default().fg(Color::Green),
+                _ => Style::default().fg(Color::Red),
+            };
+            Row::new(vec![
+                Cell::from(entry.time.format("%m-%d %H:%M:%S").to_string()),
+                Cell::from(entry.status.to_string()).style(status_style),
+                Cell::from(entry.job_ids.join(",")),
+                Cell::from(entry.command.clone()),
+            ])
+        });
+
+        let header = Row::new(vec!["Time", "Status", "Jobs", "Command"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Percentage(25),
+                Constraint::Percentage(20),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
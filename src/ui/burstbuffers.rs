@@ -0,0 +1,270 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    i18n::tr,
+    slurm::burstbuffer::{BufferPool, BurstBuffers},
+    utils::get_username,
+};
+
+/// Cells of the pool usage bar
+const BAR_WIDTH: usize = 10;
+
+/// Result of a key press in the burst buffer view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BurstBuffersAction {
+    None,
+    /// Fetch the status again
+    Refresh,
+    /// Show the job holding the buffer under the cursor
+    Open(String),
+    /// Back to the dashboard
+    Close,
+}
+
+/// Burst buffer pools with their space, and the buffers allocated to jobs
+pub struct BurstBuffersView {
+    buffers: BurstBuffers,
+    /// Error from the last scontrol
+    error: Option<String>,
+    pub loaded: bool,
+    /// Cursor in the allocations
+    state: TableState,
+    user: String,
+}
+
+impl BurstBuffersView {
+    pub fn new() -> Self {
+        Self {
+            buffers: BurstBuffers::default(),
+            error: None,
+            loaded: false,
+            state: TableState::default(),
+            user: get_username(),
+        }
+    }
+
+    /// Show a freshly fetched status, keeping the cursor where it was
+    pub fn set_buffers(&mut self, result: color_eyre::Result<BurstBuffers>) {
+        match result {
+            Ok(buffers) => {
+                let count = buffers.allocations.len();
+                let selected = self
+                    .state
+                    .selected()
+                    .unwrap_or(0)
+                    .min(count.saturating_sub(1));
+                self.state.select((count > 0).then_some(selected));
+                self.buffers = buffers;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> BurstBuffersAction {
+        let last = self.buffers.allocations.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Enter => {
+                let job_id = self
+                    .state
+                    .selected()
+                    .and_then(|i| self.buffers.allocations.get(i))
+                    .and_then(|allocation| allocation.job_id.clone());
+                if let Some(job_id) = job_id {
+                    return BurstBuffersAction::Open(job_id);
+                }
+            }
+            KeyCode::Char('b') => return BurstBuffersAction::Close,
+            KeyCode::Char('r') => return BurstBuffersAction::Refresh,
+            _ => {}
+        }
+        BurstBuffersAction::None
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(tr("Burst buffers"))
+            .title_bottom(
+                Line::from(tr(
+                    " ↑/↓: Navigate | Enter: Job detail | b: Dashboard | r: Refresh ",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("scontrol failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            (None, true) if self.buffers.pools.is_empty() => Some((
+                "No burst buffer plugin is configured".to_string(),
+                Color::Gray,
+            )),
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(self.buffers.pools.len() as u16 + 2), // Pools
+                Constraint::Min(0),                                      // Allocations
+            ])
+            .split(inner);
+        let header_style = Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD);
+
+        let header = Row::new(vec![
+            "Plugin",
+            "Pool",
+            "Usage",
+            "Used",
+            "Free",
+            "Total",
+            "Granularity",
+        ])
+        .style(header_style);
+        let rows = self.buffers.pools.iter().map(|pool| {
+            Row::new(vec![
+                Cell::from(pool.plugin.clone()),
+                Cell::from(pool.name.clone()),
+                Cell::from(usage_bar(pool)),
+                Cell::from(pool.used.clone()),
+                Cell::from(pool.free.clone()),
+                Cell::from(pool.total.clone()),
+                Cell::from(pool.granularity.clone()),
+            ])
+        });
+        let pools = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Fill(1),
+                Constraint::Length(BAR_WIDTH as u16 + 6),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(12),
+            ],
+        )
+        .header(header)
+        .block(Block::default().title(tr("Pools")).borders(Borders::TOP));
+        frame.render_widget(pools, chunks[0]);
+
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(3), Constraint::Fill(1)])
+            .split(chunks[1]);
+        let mine = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let header = Row::new(vec![
+            "Job / Name",
+            "User",
+            "Pool",
+            "Size",
+            "State",
+            "Created",
+        ])
+        .style(header_style);
+        let rows = self.buffers.allocations.iter().map(|allocation| {
+            let owner = allocation
+                .job_id
+                .clone()
+                .or_else(|| allocation.name.clone())
+                .unwrap_or_default();
+            let row = Row::new(vec![
+                owner,
+                allocation.user.clone(),
+                allocation.pool.clone(),
+                allocation.size.clone(),
+                allocation.state.clone(),
+                allocation.created.clone(),
+            ]);
+            if allocation.user == self.user {
+                row.style(mine)
+            } else {
+                row
+            }
+        });
+        let allocations = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Length(12),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(20),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(tr("Allocated buffers"))
+                .borders(Borders::TOP),
+        )
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(allocations, halves[0], &mut self.state);
+
+        let header = Row::new(vec!["User", "Used"]).style(header_style);
+        let rows = self.buffers.users.iter().map(|(user, used)| {
+            let row = Row::new(vec![user.clone(), used.clone()]);
+            if *user == self.user {
+                row.style(mine)
+            } else {
+                row
+            }
+        });
+        let users = Table::new(rows, [Constraint::Fill(1), Constraint::Length(10)])
+            .header(header)
+            .block(
+                Block::default()
+                    .title(tr("Use per user"))
+                    .borders(Borders::TOP | Borders::LEFT),
+            );
+        frame.render_widget(users, halves[1]);
+    }
+}
+
+/// Bar of the used share of a pool, or nothing when its sizes do not parse
+fn usage_bar(pool: &BufferPool) -> Line<'static> {
+    let Some(usage) = pool.usage() else {
+        return Line::default();
+    };
+    let filled = ((usage.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let color = match usage {
+        u if u >= 0.9 => Color::Red,
+        u if u >= 0.5 => Color::Yellow,
+        _ => Color::Green,
+    };
+    Line::from(vec![
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "░".repeat(BAR_WIDTH - filled),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(format!(" {:>3.0}%", usage * 100.0)),
+    ])
+}
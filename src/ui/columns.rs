@@ -7,6 +7,8 @@ use ratatui::{
     Frame,
 };
 
+use crate::i18n::tr;
+
 /// Available columns for display in job list
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum JobColumn {
@@ -28,6 +30,13 @@ pub enum JobColumn {
     StartTime,
     EndTime,
     PReason,
+    ExitCode,
+    /// Why a finished job failed, derived from its state and exit code
+    Failure,
+    /// Local note on the job
+    Notes,
+    /// Place of a pending job in its partition's queue, from sprio
+    PriorityRank,
 }
 
 impl JobColumn {
@@ -52,6 +61,10 @@ impl JobColumn {
             JobColumn::StartTime => "Start",
             JobColumn::EndTime => "End",
             JobColumn::PReason => "Reason", // Pending reason
+            JobColumn::ExitCode => "ExitCode",
+            JobColumn::Failure => "Failure",
+            JobColumn::Notes => "Notes",
+            JobColumn::PriorityRank => "Rank",
         }
     }
 
@@ -76,6 +89,12 @@ impl JobColumn {
             JobColumn::StartTime => "%S",  // Start time
             JobColumn::EndTime => "%e",    // End time
             JobColumn::PReason => "%R",    // Pending reason
+            // Not known to squeue; filled in from sacct for finished jobs
+            JobColumn::ExitCode | JobColumn::Failure => "",
+            // Kept by slurmer itself
+            JobColumn::Notes => "",
+            // Computed from sprio
+            JobColumn::PriorityRank => "",
         }
     }
 
@@ -101,6 +120,10 @@ impl JobColumn {
             JobColumn::StartTime => Constraint::Length(19),
             JobColumn::EndTime => Constraint::Length(19),
             JobColumn::PReason => Constraint::Percentage(20), // Pending reason can be long
+            JobColumn::ExitCode => Constraint::Length(8),
+            JobColumn::Failure => Constraint::Length(10),
+            JobColumn::Notes => Constraint::Percentage(15),
+            JobColumn::PriorityRank => Constraint::Length(9),
         }
     }
 
@@ -125,6 +148,10 @@ impl JobColumn {
             JobColumn::StartTime,
             JobColumn::EndTime,
             JobColumn::PReason,
+            JobColumn::ExitCode,
+            JobColumn::Failure,
+            JobColumn::Notes,
+            JobColumn::PriorityRank,
         ]
     }
 
@@ -249,7 +276,7 @@ impl ColumnsPopup {
         frame.render_widget(Clear, area);
         // Create a block for the popup
         let block = Block::default()
-            .title(Line::from("Column Management").centered())
+            .title(Line::from(tr("Column Management")).centered())
             .borders(Borders::NONE)
             .style(Style::default().bg(Color::Black));
 
@@ -286,7 +313,7 @@ impl ColumnsPopup {
 
         // Available columns list
         let available_block = Block::default()
-            .title("Available Columns")
+            .title(tr("Available Columns"))
             .borders(Borders::ALL)
             .style(if self.focus == ColumnsFocus::AvailableColumns {
                 Style::default().fg(Color::Cyan)
@@ -297,7 +324,7 @@ impl ColumnsPopup {
         let available_items: Vec<ListItem> = self
             .available_columns
             .iter()
-            .map(|col| ListItem::new(col.title()))
+            .map(|col| ListItem::new(tr(col.title())))
             .collect();
 
         let available_list = List::new(available_items)
@@ -312,7 +339,7 @@ impl ColumnsPopup {
 
         // Selected columns list
         let selected_block = Block::default()
-            .title("Selected Columns")
+            .title(tr("Selected Columns"))
             .borders(Borders::ALL)
             .style(if self.focus == ColumnsFocus::SelectedColumns {
                 Style::default().fg(Color::Cyan)
@@ -323,7 +350,7 @@ impl ColumnsPopup {
         let selected_items: Vec<ListItem> = self
             .selected_columns
             .iter()
-            .map(|col| ListItem::new(col.title()))
+            .map(|col| ListItem::new(tr(col.title())))
             .collect();
 
         let selected_list = List::new(selected_items)
@@ -334,7 +361,7 @@ impl ColumnsPopup {
 
         // Sort columns list
         let sort_block = Block::default()
-            .title("Sort Order")
+            .title(tr("Sort Order"))
             .borders(Borders::ALL)
             .style(if self.focus == ColumnsFocus::SortColumns {
                 Style::default().fg(Color::Cyan)
@@ -0,0 +1,177 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    ui::jobslist::state_color,
+};
+
+/// Result of a key press in the confirmation dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    None,
+    /// Run the action
+    Confirm,
+    /// Drop the action
+    Abort,
+}
+
+/// Confirmation for a state-changing action, listing the affected jobs and
+/// their states. Large actions can require typing the job count instead of y.
+pub struct ConfirmDialog {
+    pub visible: bool,
+    title: String,
+    question: String,
+    /// Affected jobs as (ID, state)
+    jobs: Vec<(String, String)>,
+    /// Job count the user has to type, if typed confirmation is required
+    typed: Option<usize>,
+    input: String,
+    error: Option<String>,
+    scroll: u16,
+}
+
+impl ConfirmDialog {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            title: String::new(),
+            question: String::new(),
+            jobs: Vec::new(),
+            typed: None,
+            input: String::new(),
+            error: None,
+            scroll: 0,
+        }
+    }
+
+    /// Ask about an action on `jobs`; with `typed` set the user has to type
+    /// the number of jobs
+    pub fn show(&mut self, title: &str, question: &str, jobs: Vec<(String, String)>, typed: bool) {
+        self.title = title.to_string();
+        self.question = question.to_string();
+        self.typed = typed.then_some(jobs.len());
+        self.jobs = jobs;
+        self.input.clear();
+        self.error = None;
+        self.scroll = 0;
+        self.visible = true;
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the dialog
+    pub fn handle_key(&mut self, key: KeyEvent) -> ConfirmAction {
+        match (self.typed, key.code) {
+            (_, KeyCode::Up) => self.scroll = self.scroll.saturating_sub(1),
+            (_, KeyCode::Down) if (self.scroll as usize) + 1 < self.jobs.len() => {
+                self.scroll += 1;
+            }
+            (None, KeyCode::Char('y') | KeyCode::Enter) => {
+                self.hide();
+                return ConfirmAction::Confirm;
+            }
+            (None, KeyCode::Char('n') | KeyCode::Char('q')) => {
+                self.hide();
+                return ConfirmAction::Abort;
+            }
+            (Some(count), KeyCode::Enter) => {
+                if self.input.trim() == count.to_string() {
+                    self.hide();
+                    return ConfirmAction::Confirm;
+                }
+                let count = count.to_string();
+                self.error = Some(trf("Type {count} to confirm", &[("count", &count)]));
+            }
+            (Some(_), KeyCode::Backspace) => {
+                self.input.pop();
+            }
+            (Some(_), KeyCode::Char(c)) if c.is_ascii_digit() => self.input.push(c),
+            _ => {}
+        }
+        ConfirmAction::None
+    }
+
+    /// Render the dialog
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let color = if self.typed.is_some() {
+            Color::Yellow
+        } else {
+            Color::Cyan
+        };
+        let block = Block::default()
+            .title(Line::from(self.title.as_str()).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Question
+                Constraint::Min(1),    // Affected jobs
+                Constraint::Length(1), // Typed count or error
+                Constraint::Length(1), // Help
+            ])
+            .split(inner);
+
+        let question = Paragraph::new(self.question.as_str())
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(question, chunks[0]);
+
+        let lines: Vec<Line> = self
+            .jobs
+            .iter()
+            .map(|(id, state)| {
+                let state_style = match state.parse() {
+                    Ok(state) => Style::default().fg(state_color(state)),
+                    Err(_) => Style::default(),
+                };
+                Line::from(vec![
+                    Span::raw(format!("{:<16}", id)),
+                    Span::styled(state.as_str(), state_style),
+                ])
+            })
+            .collect();
+        let jobs = Paragraph::new(lines).scroll((self.scroll, 0));
+        frame.render_widget(jobs, chunks[1]);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red));
+            frame.render_widget(error, chunks[2]);
+        } else if let Some(count) = self.typed {
+            let prompt = trf(
+                "Type {count} to confirm: ",
+                &[("count", &count.to_string())],
+            );
+            let width = prompt.chars().count() as u16;
+            let line = Line::from(vec![Span::raw(prompt), Span::raw(self.input.as_str())]);
+            frame.render_widget(Paragraph::new(line), chunks[2]);
+            frame.set_cursor_position(Position::new(
+                chunks[2].x + width + self.input.len() as u16,
+                chunks[2].y,
+            ));
+        }
+
+        let help = match self.typed {
+            Some(_) => "↑/↓: Scroll | Enter: Confirm | Esc: Abort",
+            None => "↑/↓: Scroll | y/Enter: Confirm | n/Esc: Abort",
+        };
+        let help = Paragraph::new(tr(help)).style(Style::default().fg(Color::Gray));
+        frame.render_widget(help, chunks[3]);
+    }
+}
@@ -0,0 +1,272 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, Paragraph},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::{dashboard::ClusterSummary, format_power, partition::StateCounts},
+};
+
+/// Widest label of a bar chart; longer pending reasons are cut
+const MAX_LABEL: usize = 28;
+
+/// Result of a key press in the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardAction {
+    None,
+    /// Count everything again
+    Refresh,
+    /// Switch to the burst buffer view
+    BurstBuffers,
+}
+
+/// Headline numbers of the cluster: CPU and GPU gauges, my jobs, and bar
+/// charts of jobs and nodes by state and of the top pending reasons
+pub struct DashboardView {
+    summary: Option<ClusterSummary>,
+    /// Error from the last squeue or scontrol
+    error: Option<String>,
+    pub loaded: bool,
+}
+
+impl DashboardView {
+    pub fn new() -> Self {
+        Self {
+            summary: None,
+            error: None,
+            loaded: false,
+        }
+    }
+
+    /// Show fresh numbers; an error keeps the last ones on screen
+    pub fn set_summary(&mut self, result: color_eyre::Result<ClusterSummary>) {
+        match result {
+            Ok(summary) => {
+                self.summary = Some(summary);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> DashboardAction {
+        match key.code {
+            KeyCode::Char('b') => DashboardAction::BurstBuffers,
+            KeyCode::Char('r') => DashboardAction::Refresh,
+            _ => DashboardAction::None,
+        }
+    }
+
+    /// Render the view
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(tr("Dashboard"))
+            .title_bottom(Line::from(tr(" b: Burst buffers | r: Refresh ")).centered())
+            .borders(Borders::ALL);
+
+        let summary = match (&self.summary, &self.error) {
+            (Some(summary), _) => summary,
+            (None, error) => {
+                let (text, color) = match error {
+                    Some(e) => (format!("Failed to count jobs and nodes: {}", e), Color::Red),
+                    None => ("Loading...".to_string(), Color::Gray),
+                };
+                let paragraph = Paragraph::new(text)
+                    .style(Style::default().fg(color))
+                    .block(block);
+                frame.render_widget(paragraph, area);
+                return;
+            }
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let error_height = if self.error.is_some() { 1 } else { 0 };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(error_height), // Last error
+                Constraint::Length(3),            // Gauges
+                Constraint::Min(0),               // Bar charts
+            ])
+            .split(inner);
+        if let Some(e) = &self.error {
+            let line = Paragraph::new(format!("Refresh failed, showing the last figures: {}", e))
+                .style(Style::default().fg(Color::Red));
+            frame.render_widget(line, rows[0]);
+        }
+
+        // A power panel only where acct_gather_energy is configured
+        let power_width = if summary.power.is_some() { 1 } else { 0 };
+        let gauges = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Fill(power_width),
+            ])
+            .split(rows[1]);
+        render_gauge(frame, gauges[0], tr("CPUs"), &summary.cpus);
+        render_gauge(frame, gauges[1], tr("GPUs"), &summary.gpus);
+        let mine = Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("{} ", summary.my_running),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(tr("running")),
+            Span::raw("  "),
+            Span::styled(
+                format!("{} ", summary.my_pending),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(tr("pending")),
+        ]))
+        .block(Block::default().title(tr("My jobs")).borders(Borders::ALL));
+        frame.render_widget(mine, gauges[2]);
+        if let Some(power) = &summary.power {
+            let draw = Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("{} ", format_power(power.current_watts)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(format!(
+                    "(avg {}, {} node(s))",
+                    format_power(power.average_watts),
+                    power.nodes
+                )),
+            ]))
+            .block(Block::default().title(tr("Power")).borders(Borders::ALL));
+            frame.render_widget(draw, gauges[3]);
+        }
+
+        let charts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ])
+            .split(rows[2]);
+        render_bars(
+            frame,
+            charts[0],
+            tr("Jobs by state"),
+            &summary.jobs,
+            job_color,
+        );
+        render_bars(
+            frame,
+            charts[1],
+            tr("Nodes by state"),
+            &summary.nodes,
+            node_color,
+        );
+        render_bars(
+            frame,
+            charts[2],
+            tr("Top pending reasons"),
+            &summary.pending_reasons,
+            |_| Color::Yellow,
+        );
+    }
+}
+
+/// Allocated share of CPUs or GPUs, colored like the partition usage bars
+fn render_gauge(frame: &mut Frame, area: Rect, title: &str, counts: &StateCounts) {
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL);
+    if counts.total == 0 {
+        let none = Paragraph::new(tr("None configured"))
+            .style(Style::default().fg(Color::Gray))
+            .block(block);
+        frame.render_widget(none, area);
+        return;
+    }
+    let color = match counts.load() {
+        load if load >= 0.9 => Color::Red,
+        load if load >= 0.5 => Color::Yellow,
+        _ => Color::Green,
+    };
+    let label = trf(
+        "{allocated}/{total} allocated, {idle} idle, {other} unavailable",
+        &[
+            ("allocated", &counts.allocated.to_string()),
+            ("total", &counts.total.to_string()),
+            ("idle", &counts.idle.to_string()),
+            ("other", &counts.other.to_string()),
+        ],
+    );
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color).bg(Color::DarkGray))
+        .ratio((counts.allocated as f64 / counts.total as f64).min(1.0))
+        .label(label)
+        .block(block);
+    frame.render_widget(gauge, area);
+}
+
+/// Horizontal bar chart of counts, scaled to the largest
+fn render_bars(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    counts: &[(String, u32)],
+    color: fn(&str) -> Color,
+) {
+    let label_width = counts
+        .iter()
+        .map(|(label, _)| label.chars().count().min(MAX_LABEL))
+        .max()
+        .unwrap_or(0);
+    let max = counts.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+    let count_width = max.to_string().len();
+    // Two borders and the spaces around the bar
+    let bar_width = (area.width as usize)
+        .saturating_sub(label_width + count_width + 4)
+        .max(1);
+    let lines: Vec<Line> = counts
+        .iter()
+        .map(|(label, count)| {
+            let label: String = label.chars().take(MAX_LABEL).collect();
+            let filled = ((*count as f64 / max as f64 * bar_width as f64).round() as usize)
+                .max(usize::from(*count > 0));
+            Line::from(vec![
+                Span::raw(format!("{:<label_width$} ", label)),
+                Span::styled("█".repeat(filled), Style::default().fg(color(&label))),
+                Span::raw(format!(" {}", count)),
+            ])
+        })
+        .collect();
+    let chart = Paragraph::new(lines).block(
+        Block::default()
+            .title(title.to_string())
+            .borders(Borders::ALL),
+    );
+    frame.render_widget(chart, area);
+}
+
+fn job_color(state: &str) -> Color {
+    match state {
+        "RUNNING" => Color::Green,
+        "PENDING" => Color::Yellow,
+        "COMPLETING" | "CONFIGURING" => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+fn node_color(state: &str) -> Color {
+    match state {
+        "IDLE" => Color::Green,
+        "MIXED" | "ALLOCATED" | "COMPLETING" => Color::Cyan,
+        "DOWN" | "DRAINED" | "DRAINING" | "NOT_RESPONDING" | "FAIL" | "FAILING" => Color::Red,
+        _ => Color::Gray,
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::i18n::{tr, trf};
+
+/// Dependency types offered for new entries, in the order Tab cycles through them
+const KINDS: [&str; 3] = ["afterok", "afterany", "afternotok"];
+
+/// The type after `kind` in [`KINDS`]; other types are left alone
+fn next_kind(kind: &str) -> Option<&'static str> {
+    let i = KINDS.iter().position(|k| *k == kind)?;
+    Some(KINDS[(i + 1) % KINDS.len()])
+}
+
+/// One condition of a job's dependency spec, e.g. `afterok:1234`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyEntry {
+    /// afterok, afterany, afternotok, after, aftercorr, singleton, ...
+    pub kind: String,
+    /// Job the condition refers to; `None` for `singleton`
+    pub target: Option<String>,
+    /// State reported by scontrol, e.g. "unfulfilled"
+    pub state: Option<String>,
+}
+
+impl DependencyEntry {
+    fn spec(&self) -> String {
+        match &self.target {
+            Some(target) => format!("{}:{}", self.kind, target),
+            None => self.kind.clone(),
+        }
+    }
+}
+
+/// Result of a key press in the dependency editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyAction {
+    None,
+    /// Set the job's dependency to this spec (empty removes all dependencies)
+    Save(String),
+}
+
+/// Editor for the `Dependency` of a pending job
+pub struct DependencyEditor {
+    pub visible: bool,
+    /// Job being edited; `None` for a job about to be submitted
+    pub job_id: Option<String>,
+    entries: Vec<DependencyEntry>,
+    /// `,` (all must hold) or `?` (any may hold), as in the original spec
+    separator: char,
+    state: ListState,
+    /// Job IDs typed for new entries
+    input: String,
+    /// Dependency type used for new entries
+    add_kind: &'static str,
+    /// Jobs selected in the list view, offered with Ctrl+p
+    picked: Vec<String>,
+    /// Jobs of the list to pick from for a job about to be submitted: ID and label
+    candidates: Vec<(String, String)>,
+    candidate_state: ListState,
+    /// Candidates marked with Space
+    marked: HashSet<String>,
+    /// Keys move through the candidates rather than the conditions
+    candidates_focused: bool,
+    error: Option<String>,
+}
+
+impl DependencyEditor {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            job_id: None,
+            entries: Vec::new(),
+            separator: ',',
+            state: ListState::default(),
+            input: String::new(),
+            add_kind: "afterok",
+            picked: Vec::new(),
+            candidates: Vec::new(),
+            candidate_state: ListState::default(),
+            marked: HashSet::new(),
+            candidates_focused: false,
+            error: None,
+        }
+    }
+
+    /// Open the editor for a job with its current `Dependency` value from scontrol
+    pub fn show(&mut self, job_id: String, dependency: &str, picked: Vec<String>) {
+        let (entries, separator) = parse_dependency(dependency);
+        self.entries = entries;
+        self.separator = separator;
+        self.picked = picked.into_iter().filter(|id| *id != job_id).collect();
+        self.job_id = Some(job_id);
+        self.state.select((!self.entries.is_empty()).then_some(0));
+        self.input.clear();
+        self.add_kind = "afterok";
+        self.candidates.clear();
+        self.candidates_focused = false;
+        self.error = None;
+        self.visible = true;
+    }
+
+    /// Open the editor for the `--dependency` of a job about to be submitted, with
+    /// the jobs of the list (ID and label) to pick from
+    pub fn show_for_submission(
+        &mut self,
+        dependency: &str,
+        candidates: Vec<(String, String)>,
+        picked: Vec<String>,
+    ) {
+        self.show(String::new(), dependency, picked);
+        self.job_id = None;
+        self.candidate_state
+            .select((!candidates.is_empty()).then_some(0));
+        self.candidates_focused = !candidates.is_empty();
+        self.candidates = candidates;
+        self.marked.clear();
+    }
+
+    /// Hide the editor
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn add(&mut self, targets: &[String]) {
+        for target in targets {
+            let entry = DependencyEntry {
+                kind: self.add_kind.to_string(),
+                target: Some(target.clone()),
+                state: None,
+            };
+            if !self.entries.iter().any(|e| e.spec() == entry.spec()) {
+                self.entries.push(entry);
+            }
+        }
+        self.state.select(self.entries.len().checked_sub(1));
+    }
+
+    /// Handle key events for the editor
+    pub fn handle_key(&mut self, key: KeyEvent) -> DependencyAction {
+        let selected = self.state.selected();
+        self.error = None;
+        match (key.modifiers, key.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('s')) => {
+                let spec = self
+                    .entries
+                    .iter()
+                    .map(DependencyEntry::spec)
+                    .collect::<Vec<_>>()
+                    .join(&self.separator.to_string());
+                return DependencyAction::Save(spec);
+            }
+            (_, KeyCode::Left | KeyCode::Right) if !self.candidates.is_empty() => {
+                self.candidates_focused = !self.candidates_focused;
+            }
+            (_, KeyCode::Up) if self.candidates_focused => {
+                let i = self.candidate_state.selected().unwrap_or(0);
+                self.candidate_state.select(Some(i.saturating_sub(1)));
+            }
+            (_, KeyCode::Down) if self.candidates_focused => {
+                let i = self.candidate_state.selected().map_or(0, |i| i + 1);
+                self.candidate_state
+                    .select(Some(i.min(self.candidates.len() - 1)));
+            }
+            (_, KeyCode::Char(' ')) if self.candidates_focused => {
+                if let Some((id, _)) = self
+                    .candidate_state
+                    .selected()
+                    .and_then(|i| self.candidates.get(i))
+                {
+                    if !self.marked.remove(id) {
+                        self.marked.insert(id.clone());
+                    }
+                }
+            }
+            (_, KeyCode::Enter) if self.candidates_focused && self.input.is_empty() => {
+                // The marked jobs, or the highlighted one when none is marked
+                let targets: Vec<String> = if self.marked.is_empty() {
+                    self.candidate_state
+                        .selected()
+                        .and_then(|i| self.candidates.get(i))
+                        .map(|(id, _)| vec![id.clone()])
+                        .unwrap_or_default()
+                } else {
+                    self.candidates
+                        .iter()
+                        .filter(|(id, _)| self.marked.contains(id))
+                        .map(|(id, _)| id.clone())
+                        .collect()
+                };
+                self.add(&targets);
+                self.marked.clear();
+            }
+            (_, KeyCode::Up) => {
+                self.state
+                    .select(selected.map(|i| i.saturating_sub(1)).or(Some(0)));
+            }
+            (_, KeyCode::Down) if !self.entries.is_empty() => {
+                let last = self.entries.len() - 1;
+                self.state
+                    .select(Some(selected.map_or(0, |i| (i + 1).min(last))));
+            }
+            (_, KeyCode::Delete) => {
+                if let Some(i) = selected.filter(|&i| i < self.entries.len()) {
+                    self.entries.remove(i);
+                    let len = self.entries.len();
+                    self.state.select((len > 0).then(|| i.min(len - 1)));
+                }
+            }
+            (_, KeyCode::Tab) => {
+                self.add_kind = next_kind(self.add_kind).unwrap_or(KINDS[0]);
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('t')) => {
+                // Switch the selected entry between afterok, afterany and afternotok
+                if let Some(entry) = selected.and_then(|i| self.entries.get_mut(i)) {
+                    if let Some(kind) = next_kind(&entry.kind) {
+                        entry.kind = kind.to_string();
+                        entry.state = None;
+                    }
+                }
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('p')) => {
+                if self.picked.is_empty() {
+                    self.error = Some("No other jobs are selected in the job list".to_string());
+                } else {
+                    let picked = self.picked.clone();
+                    self.add(&picked);
+                }
+            }
+            (_, KeyCode::Enter) => {
+                let targets: Vec<String> = self
+                    .input
+                    .split([',', ' ', ':'])
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                let valid = |id: &String| {
+                    id.chars()
+                        .all(|c| c.is_ascii_digit() || c == '_' || c == '*')
+                };
+                if targets.is_empty() {
+                    self.error = Some("Type one or more job IDs to add".to_string());
+                } else if let Some(bad) = targets.iter().find(|id| !valid(id)) {
+                    self.error = Some(format!("Not a job ID: {}", bad));
+                } else {
+                    self.add(&targets);
+                    self.input.clear();
+                }
+            }
+            (_, KeyCode::Backspace) => {
+                self.input.pop();
+            }
+            (_, KeyCode::Char(c)) => self.input.push(c),
+            _ => {}
+        }
+        DependencyAction::None
+    }
+
+    /// Render the editor
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let title = match &self.job_id {
+            Some(id) => trf("Dependencies of job {id}", &[("id", id)]),
+            None => tr("Dependencies of the new job").to_string(),
+        };
+        let block = Block::default()
+            .title(Line::from(title).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // Entries
+                Constraint::Length(3), // Input
+                Constraint::Length(1), // Error
+                Constraint::Length(2), // Help
+            ])
+            .split(inner);
+
+        let items: Vec<ListItem> = if self.entries.is_empty() {
+            vec![ListItem::new("No dependencies").style(Style::default().fg(Color::DarkGray))]
+        } else {
+            self.entries
+                .iter()
+                .map(|entry| {
+                    let mut spans = vec![Span::raw(entry.spec())];
+                    if let Some(state) = &entry.state {
+                        let color = match state.as_str() {
+                            "unfulfilled" => Color::Yellow,
+                            "failed" => Color::Red,
+                            _ => Color::Green,
+                        };
+                        spans.push(Span::styled(
+                            format!("  ({})", state),
+                            Style::default().fg(color),
+                        ));
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect()
+        };
+        let joined = if self.separator == '?' {
+            "any may hold"
+        } else {
+            "all must hold"
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(format!("Conditions ({})", joined))
+                    .borders(Borders::ALL),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let entries_area = if self.candidates.is_empty() {
+            chunks[0]
+        } else {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .split(chunks[0]);
+            let items: Vec<ListItem> = self
+                .candidates
+                .iter()
+                .map(|(id, label)| {
+                    let mark = if self.marked.contains(id) {
+                        "[x]"
+                    } else {
+                        "[ ]"
+                    };
+                    ListItem::new(format!("{} {}", mark, label))
+                })
+                .collect();
+            let border = if self.candidates_focused {
+                Color::Yellow
+            } else {
+                Color::Gray
+            };
+            let jobs = List::new(items)
+                .block(
+                    Block::default()
+                        .title(tr("Jobs"))
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(border)),
+                )
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            frame.render_stateful_widget(jobs, columns[1], &mut self.candidate_state);
+            columns[0]
+        };
+        frame.render_stateful_widget(list, entries_area, &mut self.state);
+
+        let input = Paragraph::new(self.input.as_str()).block(
+            Block::default()
+                .title(format!("Add {} on job ID(s)", self.add_kind))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        frame.render_widget(input, chunks[1]);
+        frame.set_cursor_position(Position::new(
+            chunks[1].x + 1 + self.input.len() as u16,
+            chunks[1].y + 1,
+        ));
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red));
+            frame.render_widget(error, chunks[2]);
+        }
+
+        let picked = if self.picked.is_empty() {
+            String::new()
+        } else {
+            format!(" | Ctrl+p: Add {} selected job(s)", self.picked.len())
+        };
+        let enter = if self.candidates.is_empty() {
+            "Enter: Add typed IDs"
+        } else {
+            "←/→: Conditions/jobs | Space: Mark job | Enter: Add marked jobs or typed IDs"
+        };
+        let help = Paragraph::new(vec![
+            Line::from(format!("{} | Tab: Type of new entries{}", enter, picked)),
+            Line::from(
+                "↑/↓: Move | Del: Remove | Ctrl+t: Change type | Ctrl+s: Save | Esc: Cancel",
+            ),
+        ])
+        .style(Style::default().fg(Color::Gray));
+        frame.render_widget(help, chunks[3]);
+    }
+}
+
+/// Parse a `Dependency` value as printed by `scontrol show job`, e.g.
+/// `afterok:12(unfulfilled),afterany:13_*(failed)`. Returns the entries and the
+/// separator between them.
+pub fn parse_dependency(spec: &str) -> (Vec<DependencyEntry>, char) {
+    let spec = spec.trim();
+    if spec.is_empty() || spec == "(null)" {
+        return (Vec::new(), ',');
+    }
+    let separator = if spec.contains('?') { '?' } else { ',' };
+
+    let mut entries = Vec::new();
+    for item in spec.split(separator).filter(|s| !s.is_empty()) {
+        let (condition, state) = match item.split_once('(') {
+            Some((condition, rest)) => (condition, Some(rest.trim_end_matches(')').to_string())),
+            None => (item, None),
+        };
+        let mut parts = condition.split(':');
+        let kind = parts.next().unwrap_or_default().to_string();
+        let targets: Vec<&str> = parts.collect();
+        if targets.is_empty() {
+            entries.push(DependencyEntry {
+                kind,
+                target: None,
+                state,
+            });
+            continue;
+        }
+        for target in targets {
+            entries.push(DependencyEntry {
+                kind: kind.clone(),
+                target: Some(target.to_string()),
+                state: state.clone(),
+            });
+        }
+    }
+    (entries, separator)
+}
@@ -0,0 +1,375 @@
+use std::collections::{HashMap, HashSet};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::{
+    dependency::{parse_dependency, DependencyEntry},
+    jobslist::state_color,
+};
+use crate::{
+    i18n::{tr, trf},
+    slurm::JobState,
+};
+
+/// Levels of dependencies followed in each direction
+const MAX_DEPTH: usize = 8;
+
+/// A job in the dependency graph
+#[derive(Debug, Clone)]
+pub struct GraphJob {
+    pub name: String,
+    pub state: String,
+    /// Conditions it waits on; empty once the job has left the queue
+    pub dependencies: Vec<DependencyEntry>,
+}
+
+impl GraphJob {
+    /// A queued job with its dependency spec as printed by squeue or scontrol
+    pub fn queued(name: &str, state: &str, dependency: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: state.to_string(),
+            dependencies: parse_dependency(dependency).0,
+        }
+    }
+
+    /// A job known only from accounting
+    pub fn finished(state: &str) -> Self {
+        Self {
+            name: String::new(),
+            state: state.to_string(),
+            dependencies: Vec::new(),
+        }
+    }
+}
+
+/// Jobs a dependency target refers to: the job itself, or every task of an
+/// array for `123_*` or a bare array job ID
+fn lookup<'a>(jobs: &'a HashMap<String, GraphJob>, target: &str) -> Vec<(&'a str, &'a GraphJob)> {
+    if let Some((id, job)) = jobs.get_key_value(target) {
+        return vec![(id.as_str(), job)];
+    }
+    let base = target.trim_end_matches("_*");
+    let prefix = format!("{}_", base);
+    let mut tasks: Vec<(&str, &GraphJob)> = jobs
+        .iter()
+        .filter(|(id, _)| id.starts_with(&prefix))
+        .map(|(id, job)| (id.as_str(), job))
+        .collect();
+    tasks.sort_by(|a, b| a.0.cmp(b.0));
+    tasks
+}
+
+/// Targets of the queued jobs' dependencies that are not in `jobs`, i.e. jobs
+/// that have left the queue and whose state has to come from accounting
+pub fn missing_targets(jobs: &HashMap<String, GraphJob>) -> Vec<String> {
+    let mut missing: Vec<String> = jobs
+        .values()
+        .flat_map(|job| &job.dependencies)
+        .filter_map(|entry| entry.target.clone())
+        .filter(|target| lookup(jobs, target).is_empty())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    missing.sort();
+    missing
+}
+
+/// Whether a dependency target names this job, or the array it belongs to
+fn names_job(target: &str, id: &str) -> bool {
+    if target == id {
+        return true;
+    }
+    // A bare array job ID or `123_*` covers every task
+    let base = target.trim_end_matches("_*");
+    (target.ends_with("_*") || !target.contains('_')) && id.split('_').next() == Some(base)
+}
+
+/// Tree view of the jobs a job waits on (afterok, afterany, singleton, ...)
+/// and of the jobs waiting on it, with the current state of each, to see why
+/// a pipeline stage is stuck
+pub struct DependencyGraph {
+    pub visible: bool,
+    job_id: String,
+    lines: Vec<Line<'static>>,
+    scroll: u16,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            job_id: String::new(),
+            lines: Vec::new(),
+            scroll: 0,
+        }
+    }
+
+    /// Show the graph around `job_id`, which must be in `jobs`
+    pub fn show(&mut self, job_id: &str, jobs: &HashMap<String, GraphJob>) {
+        self.job_id = job_id.to_string();
+        self.lines = graph_lines(job_id, jobs);
+        self.scroll = 0;
+        self.visible = true;
+    }
+
+    /// Hide the view
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let last = self.lines.len().saturating_sub(1) as u16;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Enter => self.hide(),
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(last),
+            KeyCode::Home => self.scroll = 0,
+            _ => {}
+        }
+    }
+
+    /// Render the view
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(
+                Line::from(trf("Dependencies around job {id}", &[("id", &self.job_id)])).centered(),
+            )
+            .title_bottom(Line::from(tr("↑/↓/PgUp/PgDn: Scroll | Enter/Esc/q: Close")).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let max_scroll = self.lines.len().saturating_sub(inner.height as usize) as u16;
+        frame.render_widget(
+            Paragraph::new(self.lines.clone()).scroll((self.scroll.min(max_scroll), 0)),
+            inner,
+        );
+    }
+}
+
+/// The job, the tree of jobs it waits on and the tree of jobs waiting on it
+fn graph_lines(job_id: &str, jobs: &HashMap<String, GraphJob>) -> Vec<Line<'static>> {
+    let heading = |text: &str| {
+        Line::styled(
+            text.to_string(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let mut lines = vec![Line::from(job_spans(job_id, &lookup(jobs, job_id)))];
+
+    lines.push(Line::default());
+    lines.push(heading(tr("Waits on")));
+    let before = lines.len();
+    let mut seen = HashSet::from([job_id.to_string()]);
+    upstream(job_id, jobs, "", 0, &mut seen, &mut lines);
+    if lines.len() == before {
+        lines.push(none_line());
+    }
+
+    lines.push(Line::default());
+    lines.push(heading(tr("Needed by")));
+    let before = lines.len();
+    let mut seen = HashSet::from([job_id.to_string()]);
+    downstream(job_id, jobs, "", 0, &mut seen, &mut lines);
+    if lines.len() == before {
+        lines.push(none_line());
+    }
+    lines
+}
+
+fn none_line() -> Line<'static> {
+    Line::styled(
+        format!("  {}", tr("No dependencies")),
+        Style::default().fg(Color::DarkGray),
+    )
+}
+
+/// Branch lines for the conditions `job_id` waits on, then for theirs
+fn upstream(
+    job_id: &str,
+    jobs: &HashMap<String, GraphJob>,
+    indent: &str,
+    depth: usize,
+    seen: &mut HashSet<String>,
+    lines: &mut Vec<Line<'static>>,
+) {
+    let Some((_, job)) = lookup(jobs, job_id).first().copied() else {
+        return;
+    };
+    let count = job.dependencies.len();
+    for (i, entry) in job.dependencies.iter().enumerate() {
+        let last = i + 1 == count;
+        let branch = format!("  {}{}", indent, if last { "└─ " } else { "├─ " });
+        let child_indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+
+        let mut spans = vec![
+            Span::styled(branch, Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{} ", entry.kind),
+                Style::default().fg(Color::Yellow),
+            ),
+        ];
+        // Singleton waits on the running jobs of the user with the same name
+        let targets: Vec<String> = match &entry.target {
+            Some(target) => vec![target.clone()],
+            None => {
+                spans.push(Span::raw(format!("\"{}\" ", job.name)));
+                let mut same_name: Vec<String> = jobs
+                    .iter()
+                    .filter(|(id, other)| {
+                        other.name == job.name && id.as_str() != job_id && other.state != "PENDING"
+                    })
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                same_name.sort();
+                same_name
+            }
+        };
+        if let Some(target) = &entry.target {
+            spans.extend(job_spans(target, &lookup(jobs, target)));
+        }
+        if let Some(state) = &entry.state {
+            let color = match state.as_str() {
+                "unfulfilled" => Color::Yellow,
+                "failed" => Color::Red,
+                _ => Color::Green,
+            };
+            spans.push(Span::styled(
+                format!(" ({})", state),
+                Style::default().fg(color),
+            ));
+            if state == "failed" {
+                spans.push(Span::styled(
+                    format!(" {}", tr("can never be satisfied")),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        lines.push(Line::from(spans));
+
+        if entry.target.is_none() {
+            // List the jobs a singleton waits on one level below
+            for (j, id) in targets.iter().enumerate() {
+                let branch = if j + 1 == targets.len() {
+                    "└─ "
+                } else {
+                    "├─ "
+                };
+                let mut spans = vec![Span::styled(
+                    format!("  {}{}", child_indent, branch),
+                    Style::default().fg(Color::DarkGray),
+                )];
+                spans.extend(job_spans(id, &lookup(jobs, id)));
+                lines.push(Line::from(spans));
+            }
+            continue;
+        }
+        for target in targets {
+            if depth + 1 >= MAX_DEPTH || !seen.insert(target.clone()) {
+                continue;
+            }
+            upstream(&target, jobs, &child_indent, depth + 1, seen, lines);
+        }
+    }
+}
+
+/// Branch lines for the jobs whose conditions name `job_id`, then for theirs
+fn downstream(
+    job_id: &str,
+    jobs: &HashMap<String, GraphJob>,
+    indent: &str,
+    depth: usize,
+    seen: &mut HashSet<String>,
+    lines: &mut Vec<Line<'static>>,
+) {
+    let mut waiting: Vec<(&String, &DependencyEntry)> = jobs
+        .iter()
+        .filter_map(|(id, job)| {
+            job.dependencies
+                .iter()
+                .find(|entry| {
+                    entry
+                        .target
+                        .as_deref()
+                        .is_some_and(|t| names_job(t, job_id))
+                })
+                .map(|entry| (id, entry))
+        })
+        .filter(|(id, _)| id.as_str() != job_id)
+        .collect();
+    waiting.sort_by(|a, b| a.0.cmp(b.0));
+
+    let count = waiting.len();
+    for (i, (id, entry)) in waiting.into_iter().enumerate() {
+        let last = i + 1 == count;
+        let mut spans = vec![
+            Span::styled(
+                format!("  {}{}", indent, if last { "└─ " } else { "├─ " }),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("{} ", entry.kind),
+                Style::default().fg(Color::Yellow),
+            ),
+        ];
+        spans.extend(job_spans(id, &lookup(jobs, id)));
+        lines.push(Line::from(spans));
+        if depth + 1 < MAX_DEPTH && seen.insert(id.clone()) {
+            let child_indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+            downstream(id, jobs, &child_indent, depth + 1, seen, lines);
+        }
+    }
+}
+
+/// "ID name STATE", with the states of every task when the ID is an array
+fn job_spans(id: &str, found: &[(&str, &GraphJob)]) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::styled(
+        id.to_string(),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    let Some((_, first)) = found.first() else {
+        spans.push(Span::styled(
+            format!("  {}", tr("unknown")),
+            Style::default().fg(Color::DarkGray),
+        ));
+        return spans;
+    };
+    if !first.name.is_empty() {
+        spans.push(Span::raw(format!("  {}", first.name)));
+    }
+    // Tasks per state, in order of first appearance
+    let mut states: Vec<(&str, usize)> = Vec::new();
+    for (_, job) in found {
+        match states.iter_mut().find(|(state, _)| *state == job.state) {
+            Some((_, count)) => *count += 1,
+            None => states.push((&job.state, 1)),
+        }
+    }
+    for (state, count) in states {
+        let color = state_color(state.parse().unwrap_or(JobState::Other));
+        let text = if found.len() > 1 {
+            format!("  {}×{}", state, count)
+        } else {
+            format!("  {}", state)
+        };
+        spans.push(Span::styled(text, Style::default().fg(color)));
+    }
+    spans
+}
@@ -0,0 +1,239 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::{
+    i18n::tr,
+    slurm::sdiag::{Diagnostics, RpcStat},
+};
+
+/// Result of a key press in the diagnostics view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticsAction {
+    None,
+    /// Run sdiag again
+    Refresh,
+}
+
+/// Scheduler statistics from `sdiag`: server and job counters, main and
+/// backfill scheduler cycles, and the busiest RPCs by type and user
+pub struct DiagnosticsView {
+    diagnostics: Option<Diagnostics>,
+    /// Error from the last sdiag
+    error: Option<String>,
+    pub loaded: bool,
+    /// When sdiag last ran, for the periodic refresh
+    fetched: Option<Instant>,
+    /// Rank the RPCs by call count instead of total time
+    rpc_by_count: bool,
+}
+
+impl DiagnosticsView {
+    pub fn new() -> Self {
+        Self {
+            diagnostics: None,
+            error: None,
+            loaded: false,
+            fetched: None,
+            rpc_by_count: false,
+        }
+    }
+
+    /// Show fresh statistics; an error keeps the last ones on screen
+    pub fn set_diagnostics(&mut self, result: color_eyre::Result<Diagnostics>) {
+        match result {
+            Ok(diagnostics) => {
+                self.diagnostics = Some(diagnostics);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.fetched = Some(Instant::now());
+        self.loaded = true;
+    }
+
+    /// sdiag last ran at least `interval` ago
+    pub fn is_stale(&self, interval: Duration) -> bool {
+        self.fetched
+            .is_none_or(|fetched| fetched.elapsed() >= interval)
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> DiagnosticsAction {
+        match key.code {
+            KeyCode::Char('s') => self.rpc_by_count = !self.rpc_by_count,
+            KeyCode::Char('r') => return DiagnosticsAction::Refresh,
+            _ => {}
+        }
+        DiagnosticsAction::None
+    }
+
+    /// Render the view
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let mut title = tr("Scheduler diagnostics").to_string();
+        if let Some(fetched) = self.fetched {
+            title = format!("{} ({}s)", title, fetched.elapsed().as_secs());
+        }
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(tr(" s: Sort RPCs by count/time | r: Refresh ")).centered())
+            .borders(Borders::ALL);
+
+        let diagnostics = match (&self.diagnostics, &self.error) {
+            (Some(diagnostics), _) => diagnostics,
+            (None, error) => {
+                let (text, color) = match error {
+                    Some(e) => (format!("sdiag failed: {}", e), Color::Red),
+                    None => ("Loading...".to_string(), Color::Gray),
+                };
+                let paragraph = Paragraph::new(text)
+                    .style(Style::default().fg(color))
+                    .block(block);
+                frame.render_widget(paragraph, area);
+                return;
+            }
+        };
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let error_height = if self.error.is_some() { 1 } else { 0 };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(error_height), // Last error
+                Constraint::Percentage(55),       // Sections
+                Constraint::Min(4),               // RPCs
+            ])
+            .split(inner);
+        if let Some(e) = &self.error {
+            let line = Paragraph::new(format!("sdiag failed, showing the last figures: {}", e))
+                .style(Style::default().fg(Color::Red));
+            frame.render_widget(line, rows[0]);
+        }
+
+        let sections: Vec<&(String, Vec<(String, String)>)> = diagnostics
+            .sections
+            .iter()
+            .filter(|(_, fields)| !fields.is_empty())
+            .collect();
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Fill(1); sections.len()])
+            .split(rows[1]);
+        for ((title, fields), &column) in sections.into_iter().zip(columns.iter()) {
+            // Cycle times of the schedulers are in microseconds
+            let timed = title.contains("microseconds") || title.starts_with("Backfilling");
+            let width = fields.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+            let lines: Vec<Line> = fields
+                .iter()
+                .map(|(key, value)| {
+                    let mut spans = vec![
+                        Span::styled(
+                            format!("{:<width$} ", key),
+                            Style::default().fg(Color::Yellow),
+                        ),
+                        Span::raw(value.as_str()),
+                    ];
+                    let micros = value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|_| timed && is_cycle_time(key));
+                    if let Some(micros) = micros {
+                        spans.push(Span::styled(
+                            format!(" ({})", format_micros(micros)),
+                            Style::default().fg(Color::Cyan),
+                        ));
+                    }
+                    Line::from(spans)
+                })
+                .collect();
+            let section = Paragraph::new(lines)
+                .block(Block::default().title(tr(title)).borders(Borders::TOP));
+            frame.render_widget(section, column);
+        }
+
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Fill(3), Constraint::Fill(2)])
+            .split(rows[2]);
+        let by = if self.rpc_by_count {
+            tr("by count")
+        } else {
+            tr("by total time")
+        };
+        self.render_rpcs(
+            frame,
+            halves[0],
+            &format!("{} {}", tr("RPCs by message type"), by),
+            &diagnostics.rpc_types,
+        );
+        self.render_rpcs(
+            frame,
+            halves[1],
+            &format!("{} {}", tr("RPCs by user"), by),
+            &diagnostics.rpc_users,
+        );
+    }
+
+    /// Table of the busiest RPCs, as many as fit
+    fn render_rpcs(&self, frame: &mut Frame, area: Rect, title: &str, stats: &[RpcStat]) {
+        let mut stats: Vec<&RpcStat> = stats.iter().collect();
+        if self.rpc_by_count {
+            stats.sort_by_key(|stat| std::cmp::Reverse(stat.count));
+        } else {
+            stats.sort_by_key(|stat| std::cmp::Reverse(stat.total_time));
+        }
+        let rows = stats.into_iter().map(|stat| {
+            Row::new(vec![
+                stat.name.clone(),
+                stat.count.to_string(),
+                format_micros(stat.ave_time),
+                format_micros(stat.total_time),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(
+            Row::new(vec!["", "Count", "Average", "Total"]).style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(
+            Block::default()
+                .title(title.to_string())
+                .borders(Borders::TOP),
+        );
+        frame.render_widget(table, area);
+    }
+}
+
+/// Fields of the scheduler sections that hold a cycle time, like "Last cycle"
+/// or "Mean cycle", as opposed to counts like "Total cycles" or "Depth Mean"
+fn is_cycle_time(key: &str) -> bool {
+    key.ends_with("cycle") && !key.contains("depth")
+}
+
+/// Microseconds in the largest unit that keeps them readable
+fn format_micros(micros: u64) -> String {
+    match micros {
+        0..1_000 => format!("{} µs", micros),
+        1_000..1_000_000 => format!("{:.1} ms", micros as f64 / 1e3),
+        _ => format!("{:.2} s", micros as f64 / 1e6),
+    }
+}
@@ -0,0 +1,172 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::{format_duration, format_size, sacct::JobUsage},
+};
+
+/// seff-style view of how much of the requested CPU and memory jobs used,
+/// so over-requested resources stand out
+pub struct EfficiencyPanel {
+    pub visible: bool,
+    jobs: Vec<JobUsage>,
+    state: TableState,
+}
+
+impl EfficiencyPanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            jobs: Vec::new(),
+            state: TableState::default(),
+        }
+    }
+
+    /// Show the usage of these jobs
+    pub fn show(&mut self, jobs: Vec<JobUsage>) {
+        self.jobs = jobs;
+        self.state.select(Some(0));
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let last = self.jobs.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Enter => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            _ => {}
+        }
+    }
+
+    /// Render the panel
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(
+                Line::from(trf(
+                    "Efficiency of {count} job(s)",
+                    &[("count", &self.jobs.len().to_string())],
+                ))
+                .centered(),
+            )
+            .title_bottom(Line::from(tr("↑/↓: Move | Enter/Esc/q: Close")).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Summary
+                Constraint::Min(1),    // Per-job list
+            ])
+            .split(inner);
+
+        // Overall CPU efficiency weights jobs by their allocated CPU time
+        let used: u64 = self.jobs.iter().map(|job| job.total_cpu).sum();
+        let allocated: u64 = self.jobs.iter().map(|job| job.elapsed * job.cpus).sum();
+        let overall = (allocated > 0).then(|| used as f64 / allocated as f64);
+        let low_memory = self
+            .jobs
+            .iter()
+            .filter(|job| job.mem_efficiency().is_some_and(|eff| eff < 0.5))
+            .count();
+        let summary = vec![
+            Line::from(vec![
+                tr("CPU efficiency overall: ").into(),
+                percent_span(overall),
+            ])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+            Line::from(trf(
+                "{count} job(s) used less than half of their requested memory",
+                &[("count", &low_memory.to_string())],
+            )),
+            Line::from(tr(
+                "Figures of running jobs are only updated when their steps finish",
+            ))
+            .style(Style::default().fg(Color::DarkGray)),
+        ];
+        frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+        let header = Row::new(vec![
+            tr("ID"),
+            tr("State"),
+            tr("Elapsed"),
+            tr("CPUs"),
+            tr("CPU eff."),
+            tr("Memory used / requested"),
+            tr("Mem eff."),
+        ])
+        .style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows = self.jobs.iter().map(|job| {
+            let memory = format!(
+                "{} / {}",
+                job.max_rss.map_or("-".to_string(), format_size),
+                job.requested_mem.map_or("-".to_string(), format_size)
+            );
+            Row::new(vec![
+                Cell::from(job.id.as_str()),
+                Cell::from(job.state.as_str()),
+                Cell::from(format_duration(job.elapsed)),
+                Cell::from(job.cpus.to_string()),
+                Cell::from(percent_span(job.cpu_efficiency())),
+                Cell::from(memory),
+                Cell::from(percent_span(job.mem_efficiency())),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Length(11),
+                Constraint::Length(12),
+                Constraint::Length(5),
+                Constraint::Length(9),
+                Constraint::Fill(1),
+                Constraint::Length(9),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, chunks[1], &mut self.state);
+    }
+}
+
+/// An efficiency as a percentage, red under 25%, yellow under 50%, else green
+fn percent_span(efficiency: Option<f64>) -> Span<'static> {
+    match efficiency {
+        Some(eff) => {
+            let color = if eff < 0.25 {
+                Color::Red
+            } else if eff < 0.5 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            Span::styled(format!("{:.0}%", eff * 100.0), Style::default().fg(color))
+        }
+        None => Span::raw("-"),
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::{format_duration, parse_time_limit, Job, JobState},
+    ui::jobslist::state_color,
+};
+
+/// Time format of squeue's start and end times
+const SQUEUE_TIME: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Expected finish of one job
+struct EtaRow {
+    id: String,
+    name: String,
+    state: JobState,
+    cpus: u32,
+    /// Walltime left in seconds; `None` without a (finite) time limit
+    remaining: Option<u64>,
+    /// Expected end; `None` if the scheduler has no estimate
+    end: Option<NaiveDateTime>,
+}
+
+impl EtaRow {
+    fn new(job: &Job, now: NaiveDateTime) -> Self {
+        let limit = job
+            .time_limit
+            .as_deref()
+            .and_then(parse_time_limit)
+            .filter(|&limit| limit != u64::MAX);
+        let elapsed = parse_time_limit(&job.time).unwrap_or(0);
+        let remaining = match job.state {
+            JobState::Running => limit.map(|limit| limit.saturating_sub(elapsed)),
+            JobState::Pending => limit,
+            _ => Some(0),
+        };
+
+        // squeue knows the end of running jobs, and of pending jobs once the
+        // scheduler has planned their start
+        let end = job
+            .end_time
+            .as_deref()
+            .and_then(|end| NaiveDateTime::parse_from_str(end, SQUEUE_TIME).ok())
+            .or_else(|| match job.state {
+                JobState::Running => remaining.map(|secs| now + TimeDelta::seconds(secs as i64)),
+                _ => None,
+            });
+
+        Self {
+            id: job.id.clone(),
+            name: job.name.clone(),
+            state: job.state,
+            cpus: job.cpus,
+            remaining,
+            end,
+        }
+    }
+
+    fn cpu_hours(&self) -> Option<f64> {
+        self.remaining
+            .map(|secs| secs as f64 * self.cpus as f64 / 3600.0)
+    }
+}
+
+/// Aggregate finish estimate for a set of jobs: when the last one should be
+/// done, the CPU-hours still to run, and the expected end of each job
+pub struct EtaPanel {
+    pub visible: bool,
+    rows: Vec<EtaRow>,
+    state: TableState,
+}
+
+impl EtaPanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            rows: Vec::new(),
+            state: TableState::default(),
+        }
+    }
+
+    /// Show the estimate for these jobs, as listed by squeue with `%l`, `%S` and `%e`
+    pub fn show(&mut self, jobs: &[Job]) {
+        let now = Local::now().naive_local();
+        self.rows = jobs.iter().map(|job| EtaRow::new(job, now)).collect();
+        // Soonest first, jobs without an estimate last
+        self.rows
+            .sort_by_key(|row| (row.end.is_none(), row.end, row.id.clone()));
+        self.state.select(Some(0));
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let last = self.rows.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Enter => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            _ => {}
+        }
+    }
+
+    /// Render the panel
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(
+                Line::from(trf(
+                    "Completion estimate for {count} job(s)",
+                    &[("count", &self.rows.len().to_string())],
+                ))
+                .centered(),
+            )
+            .title_bottom(Line::from(tr("↑/↓: Move | Enter/Esc/q: Close")).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Summary
+                Constraint::Min(1),    // Per-job list
+            ])
+            .split(inner);
+
+        let unknown = self.rows.iter().filter(|row| row.end.is_none()).count();
+        let latest = match self.rows.iter().filter_map(|row| row.end).max() {
+            Some(end) => end.format("%Y-%m-%d %H:%M").to_string(),
+            None => "-".to_string(),
+        };
+        let cpu_hours: f64 = self.rows.iter().filter_map(EtaRow::cpu_hours).sum();
+        let mut summary = vec![
+            Line::from(trf("All done by: {time}", &[("time", &latest)]))
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+            Line::from(trf(
+                "Remaining: {hours} CPU-hours (at most, from time limits)",
+                &[("hours", &format!("{:.1}", cpu_hours))],
+            )),
+        ];
+        if unknown > 0 {
+            summary.push(
+                Line::from(trf(
+                    "{count} job(s) have no estimate yet and are not included",
+                    &[("count", &unknown.to_string())],
+                ))
+                .style(Style::default().fg(Color::Yellow)),
+            );
+        }
+        frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+        let header = Row::new(vec![
+            tr("ID"),
+            tr("Name"),
+            tr("State"),
+            tr("CPUs"),
+            tr("Time left"),
+            tr("CPU-hours"),
+            tr("Expected end"),
+        ])
+        .style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows = self.rows.iter().map(|row| {
+            let remaining = row.remaining.map_or("-".to_string(), format_duration);
+            let cpu_hours = row
+                .cpu_hours()
+                .map_or("-".to_string(), |hours| format!("{:.1}", hours));
+            let end = row.end.map_or("-".to_string(), |end| {
+                end.format("%Y-%m-%d %H:%M").to_string()
+            });
+            Row::new(vec![
+                Cell::from(row.id.as_str()),
+                Cell::from(row.name.as_str()),
+                Cell::from(row.state.to_string())
+                    .style(Style::default().fg(state_color(row.state))),
+                Cell::from(row.cpus.to_string()),
+                Cell::from(remaining),
+                Cell::from(cpu_hours),
+                Cell::from(end),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(5),
+                Constraint::Length(12),
+                Constraint::Length(10),
+                Constraint::Length(17),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, chunks[1], &mut self.state);
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{i18n::tr, slurm::sshare::ShareNode, utils::get_username};
+
+/// Cells of the fair-share factor bar
+const BAR_WIDTH: usize = 10;
+
+/// Result of a key press in the fair-share view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FairshareAction {
+    None,
+    /// Fetch the tree again
+    Refresh,
+}
+
+/// Collapsible tree of accounts and users from `sshare -l`, opened along the
+/// path to my own associations
+pub struct FairshareView {
+    nodes: Vec<ShareNode>,
+    /// Paths of the collapsed accounts
+    collapsed: HashSet<String>,
+    /// Error from the last sshare
+    error: Option<String>,
+    pub loaded: bool,
+    state: TableState,
+    user: String,
+}
+
+impl FairshareView {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            collapsed: HashSet::new(),
+            error: None,
+            loaded: false,
+            state: TableState::default(),
+            user: get_username(),
+        }
+    }
+
+    /// Show a freshly fetched tree; the first one starts with every account
+    /// collapsed except those above my associations
+    pub fn set_tree(&mut self, result: color_eyre::Result<Vec<ShareNode>>) {
+        match result {
+            Ok(nodes) => {
+                if !self.loaded {
+                    let mine: Vec<&str> = nodes
+                        .iter()
+                        .filter(|n| n.user.as_deref() == Some(self.user.as_str()))
+                        .map(|n| n.path.as_str())
+                        .collect();
+                    self.collapsed = nodes
+                        .iter()
+                        .filter(|n| n.has_children)
+                        .filter(|n| !mine.iter().any(|m| m.starts_with(&format!("{}/", n.path))))
+                        .map(|n| n.path.clone())
+                        .collect();
+                }
+                self.nodes = nodes;
+                self.error = None;
+                let last = self.visible().len().saturating_sub(1);
+                let selected = self.state.selected().unwrap_or(0).min(last);
+                self.state
+                    .select((!self.nodes.is_empty()).then_some(selected));
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    /// Indexes of the nodes outside collapsed accounts
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = Vec::new();
+        // Depth of the collapsed account whose subtree is being skipped
+        let mut hidden_below: Option<usize> = None;
+        for (i, node) in self.nodes.iter().enumerate() {
+            if let Some(depth) = hidden_below {
+                if node.depth > depth {
+                    continue;
+                }
+                hidden_below = None;
+            }
+            visible.push(i);
+            if node.has_children && self.collapsed.contains(&node.path) {
+                hidden_below = Some(node.depth);
+            }
+        }
+        visible
+    }
+
+    fn selected_node(&self) -> Option<&ShareNode> {
+        let index = *self.visible().get(self.state.selected()?)?;
+        self.nodes.get(index)
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> FairshareAction {
+        let last = self.visible().len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Home => self.state.select(Some(0)),
+            KeyCode::End => self.state.select(Some(last)),
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right => {
+                let Some(node) = self.selected_node().filter(|n| n.has_children) else {
+                    return FairshareAction::None;
+                };
+                let path = node.path.clone();
+                let collapse = match key.code {
+                    KeyCode::Left => true,
+                    KeyCode::Right => false,
+                    _ => !self.collapsed.contains(&path),
+                };
+                if collapse {
+                    self.collapsed.insert(path);
+                } else {
+                    self.collapsed.remove(&path);
+                }
+            }
+            KeyCode::Char('r') => return FairshareAction::Refresh,
+            _ => {}
+        }
+        FairshareAction::None
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(tr("Fairshare"))
+            .title_bottom(
+                Line::from(tr(
+                    " ↑/↓: Navigate | Enter/←/→: Collapse or expand | r: Refresh ",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("sshare failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let header = Row::new(vec![
+            "Account / User",
+            "RawShares",
+            "NormShares",
+            "RawUsage",
+            "EffectvUsage",
+            "FairShare",
+            "LevelFS",
+        ])
+        .style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows: Vec<Row> = self
+            .visible()
+            .into_iter()
+            .map(|i| &self.nodes[i])
+            .map(|node| {
+                let marker = match (node.has_children, self.collapsed.contains(&node.path)) {
+                    (true, true) => "▸ ",
+                    (true, false) => "▾ ",
+                    _ => "  ",
+                };
+                let mine = node.user.as_deref() == Some(self.user.as_str());
+                let name_style = if mine {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else if node.user.is_none() {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                let name = format!("{}{}{}", "  ".repeat(node.depth), marker, node.label());
+                Row::new(vec![
+                    Cell::from(name).style(name_style),
+                    Cell::from(node.raw_shares.clone()),
+                    Cell::from(node.norm_shares.clone()),
+                    Cell::from(node.raw_usage.clone()),
+                    Cell::from(node.effective_usage.clone()),
+                    Cell::from(node.fairshare.map(fairshare_bar).unwrap_or_default()),
+                    Cell::from(node.level_fs.clone()),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(11),
+                Constraint::Length(12),
+                Constraint::Length(13),
+                Constraint::Length(BAR_WIDTH as u16 + 7),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
+
+/// Bar of a fair-share factor: red below 0.2, yellow below 0.5
+fn fairshare_bar(factor: f64) -> Line<'static> {
+    let filled = ((factor.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let color = match factor {
+        f if f < 0.2 => Color::Red,
+        f if f < 0.5 => Color::Yellow,
+        _ => Color::Green,
+    };
+    Line::from(vec![
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "░".repeat(BAR_WIDTH - filled),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(format!(" {:.3}", factor)),
+    ])
+}
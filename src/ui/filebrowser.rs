@@ -0,0 +1,354 @@
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::{
+    fs::{self, File},
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::format_size,
+};
+
+/// Bytes of a file read for the preview; larger files show their end
+const PREVIEW_BYTES: u64 = 64 * 1024;
+
+/// A file or directory in the browser
+struct Entry {
+    name: String,
+    is_dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Text preview of the selected file
+enum Preview {
+    Text { lines: Vec<String>, truncated: bool },
+    Binary,
+    Error(String),
+}
+
+/// Result of a key press in the browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserAction {
+    None,
+    /// A file was chosen in picker mode
+    Chosen(PathBuf),
+}
+
+/// File browser rooted at a job's working directory, with sizes, modification
+/// times and a preview of text files, to check on a job's outputs. In picker
+/// mode it chooses a file anywhere on the filesystem instead.
+pub struct FileBrowser {
+    pub visible: bool,
+    job_id: String,
+    /// Choosing a file rather than browsing a job's files
+    picking: bool,
+    root: PathBuf,
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    error: Option<String>,
+    state: TableState,
+    preview: Option<Preview>,
+    preview_scroll: u16,
+}
+
+impl FileBrowser {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            job_id: String::new(),
+            picking: false,
+            root: PathBuf::new(),
+            dir: PathBuf::new(),
+            entries: Vec::new(),
+            error: None,
+            state: TableState::default(),
+            preview: None,
+            preview_scroll: 0,
+        }
+    }
+
+    /// Browse the working directory of a job
+    pub fn show(&mut self, job_id: &str, work_dir: &str) {
+        self.job_id = job_id.to_string();
+        self.picking = false;
+        self.root = PathBuf::from(work_dir);
+        self.enter(self.root.clone());
+        self.visible = true;
+    }
+
+    /// Choose a file, starting in `dir`; any directory up to the filesystem root
+    /// can be reached
+    pub fn show_picker(&mut self, dir: PathBuf) {
+        let dir = fs::canonicalize(&dir).unwrap_or(dir);
+        self.picking = true;
+        self.root = dir
+            .ancestors()
+            .last()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        self.enter(dir);
+        self.visible = true;
+    }
+
+    /// Hide the browser
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// List a directory, selecting its first entry
+    fn enter(&mut self, dir: PathBuf) {
+        self.dir = dir;
+        self.reload();
+        self.state.select((!self.entries.is_empty()).then_some(0));
+        self.load_preview();
+    }
+
+    /// Read the current directory again, keeping the selected name if it is still there
+    fn reload(&mut self) {
+        let selected = self.selected().map(|entry| entry.name.clone());
+        match read_entries(&self.dir) {
+            Ok(entries) => {
+                self.entries = entries;
+                self.error = None;
+            }
+            Err(e) => {
+                self.entries.clear();
+                self.error = Some(e.to_string());
+            }
+        }
+        let index = selected
+            .and_then(|name| self.entries.iter().position(|entry| entry.name == name))
+            .or((!self.entries.is_empty()).then_some(0));
+        self.state.select(index);
+    }
+
+    fn selected(&self) -> Option<&Entry> {
+        self.entries.get(self.state.selected()?)
+    }
+
+    /// Preview the selected file
+    fn load_preview(&mut self) {
+        self.preview_scroll = 0;
+        self.preview = self
+            .selected()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| read_preview(&self.dir.join(&entry.name)));
+    }
+
+    /// Handle key events for the browser
+    pub fn handle_key(&mut self, key: KeyEvent) -> BrowserAction {
+        let last = self.entries.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => {
+                self.state.select(Some(selected.saturating_sub(1)));
+                self.load_preview();
+            }
+            KeyCode::Down => {
+                self.state.select(Some((selected + 1).min(last)));
+                self.load_preview();
+            }
+            KeyCode::Enter | KeyCode::Right => match self.selected() {
+                Some(entry) if entry.is_dir => {
+                    let dir = self.dir.join(&entry.name);
+                    self.enter(dir);
+                }
+                Some(entry) if self.picking && key.code == KeyCode::Enter => {
+                    let path = self.dir.join(&entry.name);
+                    self.hide();
+                    return BrowserAction::Chosen(path);
+                }
+                _ => {}
+            },
+            // Up one level, but not above the working directory
+            KeyCode::Backspace | KeyCode::Left if self.dir != self.root => {
+                let name = self
+                    .dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned());
+                if let Some(parent) = self.dir.parent().map(Path::to_path_buf) {
+                    self.enter(parent);
+                    if let Some(index) = name
+                        .and_then(|name| self.entries.iter().position(|entry| entry.name == name))
+                    {
+                        self.state.select(Some(index));
+                        self.load_preview();
+                    }
+                }
+            }
+            KeyCode::Char('r') => {
+                self.reload();
+                self.load_preview();
+            }
+            KeyCode::PageUp => self.preview_scroll = self.preview_scroll.saturating_sub(10),
+            KeyCode::PageDown => self.preview_scroll = self.preview_scroll.saturating_add(10),
+            _ => {}
+        }
+        BrowserAction::None
+    }
+
+    /// Render the browser
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let dir = self.dir.to_string_lossy();
+        let (title, help) = if self.picking {
+            (
+                trf("Choose a batch script: {dir}", &[("dir", &dir)]),
+                "↑/↓: Move | Enter: Choose file/Open directory | Backspace/←: Up | PgUp/PgDn: Scroll preview | r: Reload | Esc/q: Cancel",
+            )
+        } else {
+            (
+                trf(
+                    "Files of job {id}: {dir}",
+                    &[("id", &self.job_id), ("dir", &dir)],
+                ),
+                "↑/↓: Move | Enter/→: Open | Backspace/←: Up | PgUp/PgDn: Scroll preview | r: Reload | Esc/q: Close",
+            )
+        };
+        let block = Block::default()
+            .title(Line::from(title).centered())
+            .title_bottom(Line::from(tr(help)).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+
+        if let Some(error) = &self.error {
+            let error = Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red));
+            frame.render_widget(error, chunks[0]);
+        } else {
+            let header = Row::new(vec![tr("Name"), tr("Size"), tr("Modified")]).style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let rows = self.entries.iter().map(|entry| {
+                let (name, size, style) = if entry.is_dir {
+                    (
+                        format!("{}/", entry.name),
+                        String::new(),
+                        Style::default().fg(Color::Blue),
+                    )
+                } else {
+                    (
+                        entry.name.clone(),
+                        format_size(entry.size),
+                        Style::default(),
+                    )
+                };
+                let modified = entry.modified.map_or("-".to_string(), |time| {
+                    DateTime::<Local>::from(time)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                });
+                Row::new(vec![
+                    Cell::from(name),
+                    Cell::from(size),
+                    Cell::from(modified),
+                ])
+                .style(style)
+            });
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Fill(1),
+                    Constraint::Length(8),
+                    Constraint::Length(16),
+                ],
+            )
+            .header(header)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            frame.render_stateful_widget(table, chunks[0], &mut self.state);
+        }
+
+        let preview_block = Block::default()
+            .borders(Borders::LEFT)
+            .border_style(Style::default().fg(Color::DarkGray));
+        let preview = match &self.preview {
+            None => Paragraph::new(""),
+            Some(Preview::Binary) => Paragraph::new(tr("Binary file, no preview"))
+                .style(Style::default().fg(Color::DarkGray)),
+            Some(Preview::Error(e)) => {
+                Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red))
+            }
+            Some(Preview::Text { lines, truncated }) => {
+                let mut text: Vec<Line> = Vec::new();
+                if *truncated {
+                    text.push(Line::styled(
+                        tr("(last 64 KiB)"),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                text.extend(lines.iter().map(|line| Line::from(line.as_str())));
+                let max_scroll = text.len().saturating_sub(chunks[1].height as usize) as u16;
+                Paragraph::new(text).scroll((self.preview_scroll.min(max_scroll), 0))
+            }
+        };
+        frame.render_widget(preview.block(preview_block), chunks[1]);
+    }
+}
+
+/// Entries of a directory, directories first, each group by name
+fn read_entries(dir: &Path) -> std::io::Result<Vec<Entry>> {
+    let mut entries: Vec<Entry> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| {
+            let metadata = entry.metadata().ok();
+            Entry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                is_dir: metadata.as_ref().is_some_and(|m| m.is_dir()),
+                size: metadata.as_ref().map_or(0, |m| m.len()),
+                modified: metadata.and_then(|m| m.modified().ok()),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// The start of a file, or its end if it is larger than [`PREVIEW_BYTES`]
+fn read_preview(path: &Path) -> Preview {
+    let read = || -> std::io::Result<(Vec<u8>, bool)> {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let truncated = len > PREVIEW_BYTES;
+        if truncated {
+            file.seek(SeekFrom::End(-(PREVIEW_BYTES as i64)))?;
+        }
+        let mut bytes = Vec::new();
+        file.take(PREVIEW_BYTES).read_to_end(&mut bytes)?;
+        Ok((bytes, truncated))
+    };
+    match read() {
+        Ok((bytes, _)) if bytes.contains(&0) => Preview::Binary,
+        Ok((bytes, truncated)) => {
+            let text = String::from_utf8_lossy(&bytes);
+            let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+            // The first line of a tail is usually cut in the middle
+            if truncated && !lines.is_empty() {
+                lines.remove(0);
+            }
+            Preview::Text { lines, truncated }
+        }
+        Err(e) => Preview::Error(e.to_string()),
+    }
+}
@@ -8,6 +8,7 @@ use ratatui::{
 };
 use regex::Regex;
 
+use crate::i18n::tr;
 use crate::slurm::{squeue::SqueueOptions, JobState};
 
 /// Filter popup state
@@ -137,7 +138,7 @@ impl FilterPopup {
         frame.render_widget(Clear, area);
         // Create a block for the popup
         let block = Block::default()
-            .title(Line::from("Filter Jobs").centered())
+            .title(Line::from(tr("Filter Jobs")).centered())
             .borders(Borders::NONE)
             .style(Style::default().bg(Color::Black));
 
@@ -196,7 +197,7 @@ impl FilterPopup {
 
         // Username field
         let username_block = Block::default()
-            .title("Username")
+            .title(tr("Username"))
             .borders(Borders::ALL)
             .style(if self.focus == FilterFocus::Username {
                 Style::default().fg(Color::Cyan)
@@ -296,7 +297,7 @@ impl FilterPopup {
         all_states: &[JobState],
     ) {
         let state_block = Block::default()
-            .title("Job States")
+            .title(tr("Job States"))
             .borders(Borders::ALL)
             .style(if self.focus == FilterFocus::States {
                 Style::default().fg(Color::Cyan)
@@ -335,7 +336,7 @@ impl FilterPopup {
         all_partitions: &[String],
     ) {
         let partition_block = Block::default()
-            .title("Partitions")
+            .title(tr("Partitions"))
             .borders(Borders::ALL)
             .style(if self.focus == FilterFocus::Partitions {
                 Style::default().fg(Color::Cyan)
@@ -374,7 +375,7 @@ impl FilterPopup {
         all_qos: &[String],
     ) {
         let qos_block = Block::default()
-            .title("Quality of Service")
+            .title(tr("Quality of Service"))
             .borders(Borders::ALL)
             .style(if self.focus == FilterFocus::QoS {
                 Style::default().fg(Color::Cyan)
@@ -564,13 +565,13 @@ impl FilterPopup {
                 if self.tab_index > 0 {
                     self.tab_index -= 1;
                     self.update_focus_for_tab();
-                    return FilterAction::None;
+                    FilterAction::None
                 } else if self.tab_index == 0 {
                     self.tab_index = 5; // Wrap around to last tab
                     self.update_focus_for_tab();
-                    return FilterAction::None;
+                    FilterAction::None
                 } else {
-                    return FilterAction::None; // No change if already at first tab
+                    FilterAction::None // No change if already at first tab
                 }
             }
             KeyCode::Right => {
@@ -578,13 +579,13 @@ impl FilterPopup {
                 if self.tab_index < 5 {
                     self.tab_index += 1;
                     self.update_focus_for_tab();
-                    return FilterAction::None;
+                    FilterAction::None
                 } else if self.tab_index == 5 {
                     self.tab_index = 0; // Wrap around to first tab
                     self.update_focus_for_tab();
-                    return FilterAction::None;
+                    FilterAction::None
                 } else {
-                    return FilterAction::None; // No change if already at last tab
+                    FilterAction::None // No change if already at last tab
                 }
             }
             _ => FilterAction::None,
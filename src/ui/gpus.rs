@@ -0,0 +1,182 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use super::partitions::usage_bar;
+use crate::{
+    i18n::{tr, trf},
+    slurm::gpu::GpuPool,
+};
+
+/// Result of a key press in the GPU view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpusAction {
+    None,
+    /// Fetch the inventory again
+    Refresh,
+}
+
+/// GPUs of every type across the cluster, and the jobs holding the selected type
+pub struct GpusView {
+    pools: Vec<GpuPool>,
+    /// Error from the last `scontrol show node`
+    error: Option<String>,
+    pub loaded: bool,
+    state: TableState,
+}
+
+impl GpusView {
+    pub fn new() -> Self {
+        Self {
+            pools: Vec::new(),
+            error: None,
+            loaded: false,
+            state: TableState::default(),
+        }
+    }
+
+    /// Show a freshly fetched inventory, keeping the cursor where it was
+    pub fn set_pools(&mut self, result: color_eyre::Result<Vec<GpuPool>>) {
+        match result {
+            Ok(pools) => {
+                let last = pools.len().saturating_sub(1);
+                let selected = self.state.selected().unwrap_or(0).min(last);
+                self.state.select((!pools.is_empty()).then_some(selected));
+                self.pools = pools;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> GpusAction {
+        let last = self.pools.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Char('r') => return GpusAction::Refresh,
+            _ => {}
+        }
+        GpusAction::None
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(tr("GPUs"))
+            .title_bottom(Line::from(tr(" ↑/↓: Navigate | r: Refresh ")).centered())
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("scontrol failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            (None, true) if self.pools.is_empty() => {
+                Some(("No node has GPUs configured".to_string(), Color::Gray))
+            }
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(self.pools.len() as u16 + 2), // Types
+                Constraint::Min(0),                              // Holders
+            ])
+            .split(inner);
+
+        let header_style = Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD);
+        let header = Row::new(vec![
+            "Type",
+            "Nodes",
+            "Usage",
+            "Total",
+            "Allocated",
+            "Idle",
+            "Unavailable",
+            "Jobs",
+        ])
+        .style(header_style);
+        let rows = self.pools.iter().map(|pool| {
+            let percent = pool.counts.allocated as f64 * 100.0 / pool.counts.total.max(1) as f64;
+            Row::new(vec![
+                Cell::from(pool.name.clone()),
+                Cell::from(pool.nodes.to_string()),
+                Cell::from(usage_bar(&pool.counts, format!("{:>3.0}%", percent))),
+                Cell::from(pool.counts.total.to_string()),
+                Cell::from(pool.counts.allocated.to_string()),
+                Cell::from(pool.counts.idle.to_string()).style(Style::default().fg(
+                    if pool.counts.idle == 0 {
+                        Color::Red
+                    } else {
+                        Color::Green
+                    },
+                )),
+                Cell::from(pool.counts.other.to_string()),
+                Cell::from(pool.holders.len().to_string()),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Length(6),
+                Constraint::Length(16),
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(12),
+                Constraint::Length(6),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(table, chunks[0], &mut self.state);
+
+        let Some(pool) = self.state.selected().and_then(|i| self.pools.get(i)) else {
+            return;
+        };
+        let holders = pool.holders.iter().map(|holder| {
+            Row::new(vec![
+                holder.job_id.clone(),
+                holder.user.clone(),
+                holder.count.to_string(),
+                holder.nodes.clone(),
+            ])
+        });
+        let table = Table::new(
+            holders,
+            [
+                Constraint::Length(14),
+                Constraint::Length(12),
+                Constraint::Length(6),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(vec!["Job", "User", "GPUs", "Nodes"]).style(header_style))
+        .block(
+            Block::default()
+                .title(trf("Jobs holding {gpu}", &[("gpu", &pool.name)]))
+                .borders(Borders::TOP),
+        );
+        frame.render_widget(table, chunks[1]);
+    }
+}
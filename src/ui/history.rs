@@ -0,0 +1,480 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::TableState,
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table},
+    Frame,
+};
+use std::str::FromStr;
+
+use crate::{
+    bookmarks::Bookmark,
+    i18n::tr,
+    slurm::{
+        failure::classify_failure,
+        format_energy,
+        sacct::{HistoryJob, HistoryQuery},
+        JobState,
+    },
+    ui::{jobslist::state_color, layout::centered_popup_area, quickfilter::QuickFilter},
+};
+
+/// Action the app should take after a key press in the history view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    /// Do nothing
+    None,
+    /// Run sacct with the current query
+    RunQuery,
+    /// Ask for a name and save the current query as a bookmark
+    SaveBookmark,
+    /// Bookmarks were edited and should be written to disk
+    BookmarksChanged,
+    /// Submit the batch script of the job under the cursor again, after
+    /// opening it in an editor if `edit` is set
+    Resubmit { edit: bool },
+    /// Open the submission form filled from the job under the cursor
+    Clone,
+    /// Show all accounting fields of the job under the cursor
+    Details,
+    /// Show the CPU and memory efficiency of the job under the cursor
+    Efficiency,
+    /// Show the environment of the job under the cursor
+    Environment,
+}
+
+/// Form for editing the history query
+struct QueryForm {
+    /// Field values, in [`QueryForm::TITLES`] order
+    values: Vec<String>,
+    focus: usize,
+}
+
+impl QueryForm {
+    const TITLES: [&'static str; 5] = [
+        "Users (comma separated, empty = me, ALL = everyone)",
+        "States (e.g. FAILED,TIMEOUT,OUT_OF_MEMORY)",
+        "Start (e.g. now-7days, 2024-05-01, today)",
+        "End (empty = now)",
+        "Partitions (comma separated)",
+    ];
+
+    fn from_query(query: &HistoryQuery) -> Self {
+        Self {
+            values: vec![
+                query.users.join(","),
+                query.states.join(","),
+                query.start.clone(),
+                query.end.clone(),
+                query.partitions.join(","),
+            ],
+            focus: 0,
+        }
+    }
+
+    fn to_query(&self) -> HistoryQuery {
+        let list = |s: &str| -> Vec<String> {
+            s.split(',')
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        };
+        let start = self.values[2].trim();
+        HistoryQuery {
+            users: list(&self.values[0]),
+            states: list(&self.values[1].to_uppercase()),
+            start: if start.is_empty() {
+                HistoryQuery::default().start
+            } else {
+                start.to_string()
+            },
+            end: self.values[3].trim().to_string(),
+            partitions: list(&self.values[4]),
+        }
+    }
+}
+
+/// History view: finished jobs from sacct, with saved queries (bookmarks)
+pub struct HistoryView {
+    pub query: HistoryQuery,
+    /// Jobs shown: the query results that pass the quick filter
+    pub jobs: Vec<HistoryJob>,
+    /// Jobs returned by the last query
+    results: Vec<HistoryJob>,
+    /// The `/` filter bar
+    pub filter: QuickFilter,
+    pub bookmarks: Vec<Bookmark>,
+    /// Name of the bookmark the current query was loaded from
+    active_bookmark: Option<String>,
+    /// Whether the query has been run at least once
+    pub loaded: bool,
+    /// Error from the last sacct run
+    error: Option<String>,
+    state: TableState,
+    form: Option<QueryForm>,
+    picker: Option<ListState>,
+    /// Resubmitting and cloning are not advertised in read-only mode
+    read_only: bool,
+}
+
+impl HistoryView {
+    pub fn new(bookmarks: Vec<Bookmark>, read_only: bool) -> Self {
+        Self {
+            query: HistoryQuery::default(),
+            jobs: Vec::new(),
+            results: Vec::new(),
+            filter: QuickFilter::default(),
+            bookmarks,
+            active_bookmark: None,
+            loaded: false,
+            error: None,
+            state: TableState::default(),
+            form: None,
+            picker: None,
+            read_only,
+        }
+    }
+
+    /// Whether the query form or the bookmark picker is open
+    pub fn popup_visible(&self) -> bool {
+        self.form.is_some() || self.picker.is_some()
+    }
+
+    /// Close the query form and bookmark picker
+    pub fn hide_popups(&mut self) {
+        self.form = None;
+        self.picker = None;
+    }
+
+    /// Store the result of running the current query
+    pub fn set_results(&mut self, result: color_eyre::Result<Vec<HistoryJob>>) {
+        self.loaded = true;
+        match result {
+            Ok(jobs) => {
+                self.results = jobs;
+                self.error = None;
+            }
+            Err(e) => {
+                self.results.clear();
+                self.error = Some(e.to_string());
+            }
+        }
+        self.apply_quick_filter();
+    }
+
+    /// Show the results matching the quick filter
+    pub fn apply_quick_filter(&mut self) {
+        self.jobs = self
+            .results
+            .iter()
+            .filter(|job| self.filter.matches(*job))
+            .cloned()
+            .collect();
+        self.state
+            .select(if self.jobs.is_empty() { None } else { Some(0) });
+    }
+
+    /// The job under the cursor
+    pub fn selected_job(&self) -> Option<&HistoryJob> {
+        self.state.selected().and_then(|i| self.jobs.get(i))
+    }
+
+    /// Save the current query under a name, replacing a bookmark with the same name
+    pub fn add_bookmark(&mut self, name: &str) {
+        let bookmark = Bookmark {
+            name: name.to_string(),
+            query: self.query.clone(),
+        };
+        match self.bookmarks.iter_mut().find(|b| b.name == name) {
+            Some(existing) => *existing = bookmark,
+            None => self.bookmarks.push(bookmark),
+        }
+        self.active_bookmark = Some(name.to_string());
+    }
+
+    /// Load a bookmark's query; the caller runs it
+    fn load_bookmark(&mut self, index: usize) -> HistoryAction {
+        match self.bookmarks.get(index) {
+            Some(bookmark) => {
+                self.query = bookmark.query.clone();
+                self.active_bookmark = Some(bookmark.name.clone());
+                self.picker = None;
+                HistoryAction::RunQuery
+            }
+            None => HistoryAction::None,
+        }
+    }
+
+    /// Handle key events for the history view
+    pub fn handle_key(&mut self, key: KeyEvent) -> HistoryAction {
+        if let Some(form) = &mut self.form {
+            match key.code {
+                KeyCode::Esc => self.form = None,
+                KeyCode::Enter => {
+                    self.query = form.to_query();
+                    self.active_bookmark = None;
+                    self.form = None;
+                    return HistoryAction::RunQuery;
+                }
+                KeyCode::Down | KeyCode::Tab => {
+                    form.focus = (form.focus + 1) % form.values.len();
+                }
+                KeyCode::Up | KeyCode::BackTab => {
+                    form.focus = (form.focus + form.values.len() - 1) % form.values.len();
+                }
+                KeyCode::Char(c) => form.values[form.focus].push(c),
+                KeyCode::Backspace => {
+                    form.values[form.focus].pop();
+                }
+                _ => {}
+            }
+            return HistoryAction::None;
+        }
+
+        if let Some(picker) = &mut self.picker {
+            let selected = picker.selected().unwrap_or(0);
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.picker = None,
+                KeyCode::Up => picker.select(Some(selected.saturating_sub(1))),
+                KeyCode::Down => picker.select(Some(
+                    (selected + 1).min(self.bookmarks.len().saturating_sub(1)),
+                )),
+                KeyCode::Enter => return self.load_bookmark(selected),
+                KeyCode::Char('x') | KeyCode::Delete if selected < self.bookmarks.len() => {
+                    self.bookmarks.remove(selected);
+                    if self.bookmarks.is_empty() {
+                        self.picker = None;
+                    } else {
+                        picker.select(Some(selected.min(self.bookmarks.len() - 1)));
+                    }
+                    return HistoryAction::BookmarksChanged;
+                }
+                _ => {}
+            }
+            return HistoryAction::None;
+        }
+
+        let last = self.jobs.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::PageUp => self.state.select(Some(selected.saturating_sub(10))),
+            KeyCode::PageDown => self.state.select(Some((selected + 10).min(last))),
+            KeyCode::Home => self.state.select(Some(0)),
+            KeyCode::End => self.state.select(Some(last)),
+            KeyCode::Char('/') => self.filter.editing = true,
+            KeyCode::Char('f') => self.form = Some(QueryForm::from_query(&self.query)),
+            KeyCode::Char('r') => return HistoryAction::RunQuery,
+            KeyCode::Char('b') if !self.bookmarks.is_empty() => {
+                self.picker = Some(ListState::default().with_selected(Some(0)));
+            }
+            KeyCode::Char('B') => return HistoryAction::SaveBookmark,
+            KeyCode::Char('s') => return HistoryAction::Resubmit { edit: false },
+            KeyCode::Char('S') => return HistoryAction::Resubmit { edit: true },
+            KeyCode::Char('Z') => return HistoryAction::Clone,
+            KeyCode::Tab | KeyCode::Enter => return HistoryAction::Details,
+            KeyCode::Char('F') => return HistoryAction::Efficiency,
+            KeyCode::Char('V') => return HistoryAction::Environment,
+            // F1..F9 re-run the first nine bookmarks directly
+            KeyCode::F(n) if (1..=9).contains(&n) => return self.load_bookmark(n as usize - 1),
+            _ => {}
+        }
+        HistoryAction::None
+    }
+
+    /// Render the history view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        // The quick filter bar takes the last line
+        let area = if self.filter.is_active() && area.height > 1 {
+            let bar = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+            self.filter
+                .render(frame, bar, self.jobs.len(), self.results.len());
+            Rect::new(area.x, area.y, area.width, area.height - 1)
+        } else {
+            area
+        };
+
+        let mut title = format!("History: {}", self.query.describe());
+        if let Some(name) = &self.active_bookmark {
+            title = format!("{} [{}]", title, name);
+        }
+        if self.loaded {
+            title = format!("{} - {} job(s)", title, self.jobs.len());
+        }
+        let help = if self.read_only {
+            " f: Query | r: Rerun | /: Filter | b: Bookmarks | B: Save bookmark | F1-F9: Run bookmark "
+        } else {
+            " f: Query | r: Rerun | /: Filter | b: Bookmarks | B: Save bookmark | F1-F9: Run bookmark | s/S: Resubmit (S: edit first) | Z: Clone "
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(help).centered())
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded, self.jobs.is_empty()) {
+            (Some(e), _, _) => Some((format!("sacct failed: {}", e), Color::Red)),
+            (None, false, _) => Some(("Loading...".to_string(), Color::Gray)),
+            (None, true, true) if !self.results.is_empty() => {
+                Some(("No jobs match the filter".to_string(), Color::Gray))
+            }
+            (None, true, true) => Some(("No jobs match this query".to_string(), Color::Gray)),
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+        } else {
+            // The energy column only where acct_gather_energy is configured
+            let energy = self.jobs.iter().any(|job| job.energy.is_some());
+            let mut columns = vec![
+                "JobID",
+                "Name",
+                "User",
+                "State",
+                "Partition",
+                "Elapsed",
+                "Start",
+                "End",
+                "ExitCode",
+                "Failure",
+            ];
+            if energy {
+                columns.push("Energy");
+            }
+            columns.push("NodeList");
+            let header = Row::new(columns).style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let rows = self.jobs.iter().map(|job| {
+                let color = state_color(JobState::from_str(&job.state).unwrap_or(JobState::Other));
+                let mut cells = vec![
+                    Cell::from(job.id.clone()),
+                    Cell::from(job.name.clone()),
+                    Cell::from(job.user.clone()),
+                    Cell::from(job.state.clone()).style(Style::default().fg(color)),
+                    Cell::from(job.partition.clone()),
+                    Cell::from(job.elapsed.clone()),
+                    Cell::from(job.start.clone()),
+                    Cell::from(job.end.clone()),
+                    Cell::from(job.exit_code.clone()),
+                    Cell::from(
+                        classify_failure(&job.state, &job.exit_code)
+                            .map_or_else(|| "-".to_string(), |failure| failure.to_string()),
+                    )
+                    .style(Style::default().fg(Color::Red)),
+                ];
+                if energy {
+                    cells.push(Cell::from(
+                        job.energy.map_or_else(|| "-".to_string(), format_energy),
+                    ));
+                }
+                cells.push(Cell::from(job.node_list.clone()));
+                Row::new(cells)
+            });
+            let mut widths = vec![
+                Constraint::Length(14),
+                Constraint::Fill(2),
+                Constraint::Length(10),
+                Constraint::Length(13),
+                Constraint::Length(10),
+                Constraint::Length(11),
+                Constraint::Length(19),
+                Constraint::Length(19),
+                Constraint::Length(8),
+                Constraint::Length(10),
+            ];
+            if energy {
+                widths.push(Constraint::Length(9));
+            }
+            widths.push(Constraint::Fill(1));
+            let table = Table::new(rows, widths)
+                .header(header)
+                .block(block)
+                .row_highlight_style(Style::default().bg(Color::DarkGray));
+            frame.render_stateful_widget(table, area, &mut self.state);
+        }
+
+        if let Some(form) = &self.form {
+            render_form(frame, centered_popup_area(area, 60, 80), form);
+        }
+        if let Some(picker) = &mut self.picker {
+            let popup = centered_popup_area(area, 50, 60);
+            frame.render_widget(Clear, popup);
+            let items: Vec<ListItem> = self
+                .bookmarks
+                .iter()
+                .enumerate()
+                .map(|(i, b)| {
+                    let key = if i < 9 {
+                        format!("F{} ", i + 1)
+                    } else {
+                        "   ".to_string()
+                    };
+                    ListItem::new(format!("{}{} - {}", key, b.name, b.query.describe()))
+                })
+                .collect();
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .title(Line::from(tr("Bookmarks")).centered())
+                        .title_bottom(
+                            Line::from(tr("Enter: Run | x: Delete | Esc: Close")).centered(),
+                        )
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Cyan))
+                        .style(Style::default().bg(Color::Black)),
+                )
+                .highlight_style(Style::default().bg(Color::DarkGray));
+            frame.render_stateful_widget(list, popup, picker);
+        }
+    }
+}
+
+/// Render the query form popup
+fn render_form(frame: &mut Frame, area: Rect, form: &QueryForm) {
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .title(Line::from(tr("History Query")).centered())
+        .title_bottom(Line::from(tr("↑/↓/Tab: Switch field | Enter: Run | Esc: Cancel")).centered())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut constraints: Vec<Constraint> =
+        form.values.iter().map(|_| Constraint::Length(3)).collect();
+    constraints.push(Constraint::Min(0));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner);
+
+    for (i, (title, value)) in QueryForm::TITLES.iter().zip(&form.values).enumerate() {
+        let style = if i == form.focus {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        let input = Paragraph::new(value.as_str()).block(
+            Block::default()
+                .title(*title)
+                .borders(Borders::ALL)
+                .style(style),
+        );
+        frame.render_widget(input, chunks[i]);
+        if i == form.focus {
+            frame.set_cursor_position(Position {
+                x: chunks[i].x + 1 + value.chars().count() as u16,
+                y: chunks[i].y + 1,
+            });
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{i18n::tr, slurm::Job};
+
+/// Result of a key press in the incident panel
+#[derive(Debug, Clone)]
+pub enum IncidentAction {
+    None,
+    /// Hold these pending jobs (of all users)
+    Hold(Vec<Job>),
+    /// Leave incident mode
+    End,
+}
+
+/// Operator screen shown in incident mode: pending jobs of all users per
+/// partition, with a bulk hold for the chosen partitions
+pub struct IncidentPanel {
+    pub visible: bool,
+    /// Pending jobs of all users, by partition
+    pending: BTreeMap<String, Vec<Job>>,
+    chosen: BTreeSet<String>,
+    state: TableState,
+}
+
+impl IncidentPanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            pending: BTreeMap::new(),
+            chosen: BTreeSet::new(),
+            state: TableState::default(),
+        }
+    }
+
+    /// Show the panel for the given partitions and pending jobs
+    pub fn show(&mut self, partitions: &[String], jobs: Vec<Job>) {
+        self.pending = partitions.iter().map(|p| (p.clone(), Vec::new())).collect();
+        for job in jobs {
+            // A job may be queued in several partitions ("gpu,cpu")
+            for partition in job.partition.split(',') {
+                self.pending
+                    .entry(partition.to_string())
+                    .or_default()
+                    .push(job.clone());
+            }
+        }
+        self.chosen.retain(|p| self.pending.contains_key(p));
+        self.state.select(Some(0));
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent) -> IncidentAction {
+        let last = self.pending.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Char(' ') => {
+                if let Some(partition) = self.pending.keys().nth(selected).cloned() {
+                    if !self.chosen.remove(&partition) {
+                        self.chosen.insert(partition);
+                    }
+                }
+            }
+            KeyCode::Char('H') | KeyCode::Enter => {
+                // Jobs queued in several chosen partitions are held once
+                let mut seen = BTreeSet::new();
+                let jobs: Vec<Job> = self
+                    .chosen
+                    .iter()
+                    .flat_map(|p| self.pending[p].iter())
+                    .filter(|job| seen.insert(job.id.clone()))
+                    .cloned()
+                    .collect();
+                if !jobs.is_empty() {
+                    self.hide();
+                    return IncidentAction::Hold(jobs);
+                }
+            }
+            KeyCode::Char('o') => {
+                self.hide();
+                return IncidentAction::End;
+            }
+            _ => {}
+        }
+        IncidentAction::None
+    }
+
+    /// Render the panel
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(tr("Incident mode: hold pending jobs by partition")).centered())
+            .title_bottom(
+                Line::from(
+                    tr("Space: Choose partition | H/Enter: Hold pending jobs | o: End incident mode | Esc/q: Close"),
+                )
+                .centered(),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red))
+            .style(Style::default().bg(Color::Black));
+
+        let rows = self.pending.iter().map(|(partition, jobs)| {
+            let mark = if self.chosen.contains(partition) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let users = jobs.iter().map(|j| &j.user).collect::<BTreeSet<_>>().len();
+            Row::new(vec![
+                Cell::from(mark),
+                Cell::from(partition.as_str()),
+                Cell::from(jobs.len().to_string()),
+                Cell::from(users.to_string()),
+            ])
+        });
+        let header = Row::new(vec!["", "Partition", "Pending", "Users"])
+            .style(Style::default().fg(Color::Yellow));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Min(12),
+                Constraint::Length(8),
+                Constraint::Length(6),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
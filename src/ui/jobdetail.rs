@@ -0,0 +1,988 @@
+use std::collections::VecDeque;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Sparkline},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::{
+        burstbuffer::BufferAllocation, command::NodeAllocation, expand_hostlist, format_duration,
+        format_size, gpu::GpuReading, reasons::explain_reason, sprio::PriorityFactors,
+        sstat::JobStats, JobState,
+    },
+    ui::{jobslist::state_color, scriptdiff::diff_color},
+};
+
+/// Sections of the detail pane, in display order
+const SECTIONS: [&str; 5] = ["Job", "Resources", "Times", "Limits", "Paths"];
+
+/// Cells of the widest priority factor bar
+const FACTOR_BAR_WIDTH: usize = 20;
+
+/// Number of `sstat` samples kept for the sparklines
+const STATS_HISTORY: usize = 120;
+
+/// Section a `scontrol show job` or `sacct` field is listed under
+fn section_of(key: &str) -> &'static str {
+    const PATHS: &[&str] = &[
+        "Command",
+        "WorkDir",
+        "StdErr",
+        "StdIn",
+        "StdOut",
+        "SubmitLine",
+    ];
+    // sacct names of times and of measured usage
+    const TIMES: &[&str] = &[
+        "Submit",
+        "Eligible",
+        "Start",
+        "End",
+        "Elapsed",
+        "Suspended",
+        "Reserved",
+        "Planned",
+    ];
+    const USAGE_WORDS: &[&str] = &["RSS", "VMSize", "Pages", "Disk", "CPU"];
+    const RESOURCE_WORDS: &[&str] = &[
+        "Node",
+        "CPU",
+        "TRES",
+        "Tres",
+        "Gres",
+        "GRES",
+        "Task",
+        "Socks",
+        "Core",
+        "Mem",
+        "Features",
+        "BatchHost",
+    ];
+    if PATHS.contains(&key) {
+        "Paths"
+    } else if key.ends_with("Time")
+        || key == "LastSchedEval"
+        || key == "Deadline"
+        || TIMES.iter().any(|time| key.starts_with(time))
+    {
+        "Times"
+    } else if (key.starts_with("Max") || key.starts_with("Ave") || key.starts_with("Min"))
+        && USAGE_WORDS.iter().any(|word| key.contains(word))
+    {
+        "Resources"
+    } else if key.starts_with("TimeLimit")
+        || key.starts_with("Timelimit")
+        || key.starts_with("TimeMin")
+        || key.starts_with("Min")
+        || key.starts_with("Max")
+        || ["OverSubscribe", "Contiguous", "Licenses", "Reservation"].contains(&key)
+    {
+        "Limits"
+    } else if RESOURCE_WORDS.iter().any(|word| key.contains(word)) {
+        "Resources"
+    } else {
+        "Job"
+    }
+}
+
+/// Result of a key press in the detail pane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetailAction {
+    None,
+    /// Show the detail of this job instead, e.g. another attempt
+    Open(String),
+    /// Copy this text to the clipboard
+    Copy(String),
+    /// Write this text to a file: file name, text
+    Save(String, String),
+    /// Show the detail of these nodes, the job's
+    Nodes(Vec<String>),
+}
+
+/// Every field Slurm reports for a job, from slurmctld or accounting, grouped into sections, with a search
+/// that narrows the fields to those whose name or value contains the text.
+/// Also lists the job's environment, in one flat list.
+pub struct JobDetail {
+    pub visible: bool,
+    job_id: String,
+    /// Where the fields come from: "scontrol show job", "sacct" or the environment
+    source: String,
+    fields: Vec<(String, String)>,
+    /// Output the fields were parsed from, as printed by the command
+    raw: Option<String>,
+    /// Show the raw output instead of the parsed fields
+    raw_view: bool,
+    /// Group the fields into [`SECTIONS`]
+    sectioned: bool,
+    /// Node whose GPUs are read out live above the fields
+    gpu_node: Option<String>,
+    /// Latest GPU reading; `None` until the first one arrives
+    gpus: Option<Result<Vec<GpuReading>, String>>,
+    /// The job is running, so its resource use can be sampled with `sstat`
+    live: bool,
+    /// Show the live resources tab instead of the fields
+    live_tab: bool,
+    /// `sstat` samples, oldest first
+    stats: VecDeque<JobStats>,
+    /// Error of the latest `sstat` query, if it failed
+    stats_error: Option<String>,
+    /// All attempts of a resubmitted job with their states, the original first
+    attempts: Vec<(String, String)>,
+    /// What the job holds on each of its nodes, while it runs
+    nodes: Vec<NodeAllocation>,
+    /// Local note on the job
+    note: Option<String>,
+    /// Template the job was submitted from, with the diff from the template to
+    /// the script as submitted, or why there is none
+    template: Option<(String, Result<Vec<String>, String>)>,
+    /// Weighted priority factors of a pending job, per partition
+    priority: Option<Result<Vec<PriorityFactors>, String>>,
+    /// Burst buffers allocated to a job that asked for one
+    burst_buffer: Option<Result<Vec<BufferAllocation>, String>>,
+    search: String,
+    searching: bool,
+    scroll: u16,
+}
+
+impl JobDetail {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            job_id: String::new(),
+            source: String::new(),
+            fields: Vec::new(),
+            raw: None,
+            raw_view: false,
+            sectioned: true,
+            gpu_node: None,
+            gpus: None,
+            live: false,
+            live_tab: false,
+            stats: VecDeque::new(),
+            stats_error: None,
+            attempts: Vec::new(),
+            nodes: Vec::new(),
+            note: None,
+            template: None,
+            priority: None,
+            burst_buffer: None,
+            search: String::new(),
+            searching: false,
+            scroll: 0,
+        }
+    }
+
+    /// Show the fields of a job
+    pub fn show(&mut self, job_id: &str, source: &str, fields: Vec<(String, String)>) {
+        self.job_id = job_id.to_string();
+        self.source = source.to_string();
+        self.fields = fields;
+        self.raw = None;
+        self.raw_view = false;
+        self.sectioned = true;
+        self.gpu_node = None;
+        self.gpus = None;
+        self.live = false;
+        self.live_tab = false;
+        self.stats.clear();
+        self.stats_error = None;
+        self.attempts.clear();
+        self.nodes.clear();
+        self.note = None;
+        self.template = None;
+        self.priority = None;
+        self.burst_buffer = None;
+        self.search.clear();
+        self.searching = false;
+        self.scroll = 0;
+        self.visible = true;
+    }
+
+    /// Show key/value pairs that have no sections, like environment variables
+    pub fn show_flat(&mut self, job_id: &str, source: &str, fields: Vec<(String, String)>) {
+        self.show(job_id, source, fields);
+        self.sectioned = false;
+    }
+
+    /// Keep the output the fields were parsed from, for the raw view
+    pub fn set_raw(&mut self, raw: String) {
+        self.raw = Some(raw);
+    }
+
+    /// The raw output, or `Key=Value` lines where there is none, like for the environment
+    fn raw_text(&self) -> String {
+        match &self.raw {
+            Some(raw) => raw.clone(),
+            None => self
+                .fields
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    /// What is shown, as plain text for a support ticket: the whole raw
+    /// output, or the parsed sections narrowed by the search
+    fn export_text(&self) -> String {
+        if self.raw_view {
+            return self.raw_text();
+        }
+        self.lines()
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Read out the GPUs of this node above the fields
+    pub fn watch_gpus(&mut self, node: &str) {
+        self.gpu_node = Some(node.to_string());
+    }
+
+    /// Show a new GPU reading
+    pub fn set_gpus(&mut self, gpus: Result<Vec<GpuReading>, String>) {
+        self.gpus = Some(gpus);
+    }
+
+    /// Job whose GPUs are read out, while the pane is open
+    pub fn gpu_job(&self) -> Option<&str> {
+        (self.visible && self.gpu_node.is_some()).then_some(self.job_id.as_str())
+    }
+
+    /// Offer a live resources tab fed with `sstat` samples
+    pub fn watch_stats(&mut self) {
+        self.live = true;
+    }
+
+    /// Add an `sstat` sample to the live resources tab
+    pub fn push_stats(&mut self, stats: Result<JobStats, String>) {
+        match stats {
+            Ok(stats) => {
+                if self.stats.len() == STATS_HISTORY {
+                    self.stats.pop_front();
+                }
+                self.stats.push_back(stats);
+                self.stats_error = None;
+            }
+            Err(e) => self.stats_error = Some(e),
+        }
+    }
+
+    /// List the attempts of the job, which was resubmitted or is a resubmission
+    pub fn set_attempts(&mut self, attempts: Vec<(String, String)>) {
+        self.attempts = attempts;
+    }
+
+    /// Show the local note on the job above the fields
+    pub fn set_note(&mut self, note: Option<String>) {
+        self.note = note;
+    }
+
+    /// Show the diff from the template the job was submitted from to its script
+    pub fn set_template(&mut self, name: &str, diff: Result<Vec<String>, String>) {
+        self.template = Some((name.to_string(), diff));
+    }
+
+    /// Break down the priority of the pending job, from sprio
+    pub fn set_priority(&mut self, priority: Result<Vec<PriorityFactors>, String>) {
+        self.priority = Some(priority);
+    }
+
+    /// Show the burst buffers allocated to the job, from `scontrol show burstbuffer`
+    pub fn set_burst_buffer(&mut self, allocations: Result<Vec<BufferAllocation>, String>) {
+        self.burst_buffer = Some(allocations);
+    }
+
+    /// List what the job holds on each of its nodes
+    pub fn set_nodes(&mut self, nodes: Vec<NodeAllocation>) {
+        self.nodes = nodes;
+    }
+
+    /// Attempt before (`-1`) or after (`1`) the shown one
+    fn attempt(&self, step: isize) -> Option<String> {
+        let current = self
+            .attempts
+            .iter()
+            .position(|(id, _)| *id == self.job_id)?;
+        let index = current.checked_add_signed(step)?;
+        self.attempts.get(index).map(|(id, _)| id.clone())
+    }
+
+    /// Job whose resource use is sampled, while the pane is open
+    pub fn stats_job(&self) -> Option<&str> {
+        (self.visible && self.live).then_some(self.job_id.as_str())
+    }
+
+    /// Hide the pane
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the pane
+    pub fn handle_key(&mut self, key: KeyEvent) -> DetailAction {
+        if self.searching {
+            match key.code {
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Char(c) => self.search.push(c),
+                _ => {}
+            }
+            self.scroll = 0;
+            return DetailAction::None;
+        }
+        let last = self.lines().len().saturating_sub(1) as u16;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Tab => self.hide(),
+            KeyCode::Char('l') if self.live => self.live_tab = !self.live_tab,
+            KeyCode::Char('r') if !self.live_tab => {
+                self.raw_view = !self.raw_view;
+                self.scroll = 0;
+            }
+            KeyCode::Char('y') if !self.live_tab => return DetailAction::Copy(self.export_text()),
+            KeyCode::Char('w') if !self.live_tab => {
+                let form = if self.raw_view { "raw" } else { "detail" };
+                let file = format!("job-{}-{}.txt", self.job_id.replace('/', "_"), form);
+                return DetailAction::Save(file, self.export_text());
+            }
+            KeyCode::Char('n') if !self.live_tab && self.sectioned => {
+                let nodes = self.node_names();
+                if !nodes.is_empty() {
+                    return DetailAction::Nodes(nodes);
+                }
+            }
+            KeyCode::Char('/') if !self.live_tab => {
+                self.searching = true;
+                self.search.clear();
+            }
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(last),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::Char('[') => {
+                return self
+                    .attempt(-1)
+                    .map_or(DetailAction::None, DetailAction::Open)
+            }
+            KeyCode::Char(']') => {
+                return self
+                    .attempt(1)
+                    .map_or(DetailAction::None, DetailAction::Open)
+            }
+            _ => {}
+        }
+        DetailAction::None
+    }
+
+    /// Lines of the pane: a heading per section and one line per matching field,
+    /// or the matching lines of the raw output
+    fn lines(&self) -> Vec<Line<'_>> {
+        let search = self.search.to_lowercase();
+        if self.raw_view {
+            return self
+                .raw_text()
+                .lines()
+                .filter(|line| search.is_empty() || line.to_lowercase().contains(&search))
+                .map(|line| Line::from(line.to_string()))
+                .collect();
+        }
+        let matching: Vec<&(String, String)> = self
+            .fields
+            .iter()
+            .filter(|(key, value)| {
+                search.is_empty()
+                    || key.to_lowercase().contains(&search)
+                    || value.to_lowercase().contains(&search)
+            })
+            .collect();
+        let width = matching.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+
+        let mut lines = self.gpu_lines();
+        lines.extend(self.reason_lines());
+        let blocks = [
+            self.priority_lines(),
+            self.burst_buffer_lines(),
+            self.note_lines(),
+            self.attempt_lines(),
+            self.tres_lines(),
+            self.node_lines(),
+            self.template_lines(),
+        ];
+        for block in blocks {
+            if !lines.is_empty() && !block.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.extend(block);
+        }
+        let sections: &[&str] = if self.sectioned { &SECTIONS } else { &[""] };
+        for &section in sections {
+            let fields: Vec<_> = matching
+                .iter()
+                .filter(|(key, _)| !self.sectioned || section_of(key) == section)
+                .collect();
+            if fields.is_empty() || !self.sectioned {
+                lines.extend(
+                    fields
+                        .into_iter()
+                        .map(|(key, value)| field_line(key, value, width)),
+                );
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(
+                tr(section),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            lines.extend(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| field_line(key, value, width)),
+            );
+        }
+        lines
+    }
+
+    /// Value of a field, if the job has it
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Why a pending job waits, in plain language, with what can be done about it
+    fn reason_lines(&self) -> Vec<Line<'_>> {
+        if !self.sectioned || self.field("JobState") != Some("PENDING") {
+            return Vec::new();
+        }
+        let Some((reason, help)) = self
+            .field("Reason")
+            .and_then(|r| Some((r, explain_reason(r)?)))
+        else {
+            return Vec::new();
+        };
+        vec![
+            Line::styled(
+                trf("Pending: {reason}", &[("reason", reason)]),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Line::from(format!("  {}", tr(help.explanation))),
+            Line::styled(
+                format!("  {}", tr(help.remedy)),
+                Style::default().fg(Color::Green),
+            ),
+        ]
+    }
+
+    /// Priority section of a pending job: the weighted factors of each
+    /// partition it may run in, as bars scaled to the largest factor
+    fn priority_lines(&self) -> Vec<Line<'_>> {
+        let Some(priority) = self.priority.as_ref().filter(|_| self.sectioned) else {
+            return Vec::new();
+        };
+        let heading = |text: String| {
+            Line::styled(
+                text,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+        };
+        let partitions = match priority {
+            Err(e) => {
+                return vec![
+                    heading(tr("Priority").to_string()),
+                    Line::styled(format!("  {}", e), Style::default().fg(Color::Red)),
+                ]
+            }
+            Ok(partitions) if partitions.is_empty() => return Vec::new(),
+            Ok(partitions) => partitions,
+        };
+        let mut lines = Vec::new();
+        for entry in partitions {
+            lines.push(heading(trf(
+                "Priority {priority} in {partition}",
+                &[
+                    ("priority", &entry.priority.to_string()),
+                    ("partition", &entry.partition),
+                ],
+            )));
+            let largest = entry
+                .factors
+                .iter()
+                .map(|(_, v)| *v)
+                .max()
+                .unwrap_or(0)
+                .max(1);
+            let total = entry.priority.max(1) as f64;
+            for &(name, value) in entry.factors.iter().filter(|(_, v)| *v > 0) {
+                let filled =
+                    (value as f64 / largest as f64 * FACTOR_BAR_WIDTH as f64).round() as usize;
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {:<10}", name),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled("█".repeat(filled), Style::default().fg(Color::Green)),
+                    Span::styled(
+                        "░".repeat(FACTOR_BAR_WIDTH - filled),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(format!(
+                        " {:>8} ({:.0}%)",
+                        value,
+                        value as f64 * 100.0 / total
+                    )),
+                ]));
+            }
+            if entry.nice != 0 {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {:<10}", "Nice"),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::styled(
+                        format!("{:>+1$}", -entry.nice, FACTOR_BAR_WIDTH + 9),
+                        Style::default().fg(Color::Red),
+                    ),
+                ]));
+            }
+        }
+        lines
+    }
+
+    /// Burst buffer section: the buffer the job asked for, its stage-in or
+    /// stage-out state and the buffers allocated to it
+    fn burst_buffer_lines(&self) -> Vec<Line<'_>> {
+        let Some(allocations) = self.burst_buffer.as_ref().filter(|_| self.sectioned) else {
+            return Vec::new();
+        };
+        let mut lines = vec![Line::styled(
+            tr("Burst buffer"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )];
+        if let Some(state) = self.field("BurstBufferState").filter(|s| *s != "(null)") {
+            lines.push(Line::from(format!("  {}", state)));
+        }
+        match allocations {
+            Err(e) => lines.push(Line::styled(
+                format!("  {}", e),
+                Style::default().fg(Color::Red),
+            )),
+            Ok(allocations) if allocations.is_empty() => lines.push(Line::styled(
+                format!("  {}", tr("No buffer allocated yet")),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Ok(allocations) => {
+                for allocation in allocations {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {:<12}", allocation.pool),
+                            Style::default().fg(Color::Yellow),
+                        ),
+                        Span::raw(format!(
+                            "{:>10}  {}  {}",
+                            allocation.size, allocation.state, allocation.created
+                        )),
+                    ]));
+                }
+            }
+        }
+        lines
+    }
+
+    /// Note section: the local note on the job
+    fn note_lines(&self) -> Vec<Line<'_>> {
+        let Some(note) = self.note.as_deref().filter(|_| self.sectioned) else {
+            return Vec::new();
+        };
+        vec![
+            Line::styled(
+                tr("Note"),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Line::from(format!("  {}", note)),
+        ]
+    }
+
+    /// Template section: the diff from the template the job was submitted from
+    /// to the script as submitted, so a changed parameter stands out
+    fn template_lines(&self) -> Vec<Line<'_>> {
+        let Some((name, diff)) = self.template.as_ref().filter(|_| self.sectioned) else {
+            return Vec::new();
+        };
+        let mut lines = vec![Line::styled(
+            trf("Submitted from template {name}", &[("name", name)]),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )];
+        match diff {
+            Ok(diff) if diff.is_empty() => {
+                lines.push(Line::from(format!(
+                    "  {}",
+                    tr("The script matches the template")
+                )));
+            }
+            Ok(diff) => lines.extend(diff.iter().map(|line| {
+                Line::styled(format!("  {}", line), Style::default().fg(diff_color(line)))
+            })),
+            Err(e) => lines.push(Line::styled(
+                format!("  {}", e),
+                Style::default().fg(Color::Red),
+            )),
+        }
+        lines
+    }
+
+    /// Attempts section: where the job stands among its resubmissions, with
+    /// the outcome of each
+    fn attempt_lines(&self) -> Vec<Line<'_>> {
+        let Some(current) = self.attempts.iter().position(|(id, _)| *id == self.job_id) else {
+            return Vec::new();
+        };
+        let mut lines = vec![Line::styled(
+            trf(
+                "Attempt {n} of job originally {id}",
+                &[
+                    ("n", &(current + 1).to_string()),
+                    ("id", &self.attempts[0].0),
+                ],
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )];
+        for (i, (id, state)) in self.attempts.iter().enumerate() {
+            let (marker, style) = if i == current {
+                ("▸", Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                (" ", Style::default())
+            };
+            let color = state_color(state.parse().unwrap_or(JobState::Other));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} {:>2}. {:<14}", marker, i + 1, id), style),
+                Span::styled(state.as_str(), Style::default().fg(color)),
+            ]));
+        }
+        lines
+    }
+
+    /// Requested resources section: every TRES of `ReqTRES` (cpu, mem, node,
+    /// billing, gres/gpu, ...) next to what was allocated, and the licenses,
+    /// which often explain a long wait
+    fn tres_lines(&self) -> Vec<Line<'_>> {
+        let present = |name| self.field(name).filter(|v| !v.is_empty() && *v != "(null)");
+        let requested = present("ReqTRES").map(parse_tres).unwrap_or_default();
+        let allocated = present("AllocTRES").map(parse_tres).unwrap_or_default();
+        let licenses = present("Licenses");
+        if !self.sectioned || (requested.is_empty() && licenses.is_none()) {
+            return Vec::new();
+        }
+        let mut lines = vec![Line::styled(
+            tr("Requested resources"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )];
+        let width = requested
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .chain(licenses.map(|_| tr("Licenses").chars().count()))
+            .max()
+            .unwrap_or(0);
+        for (name, amount) in &requested {
+            let mut spans = vec![
+                Span::styled(
+                    format!("  {:<width$}  ", name),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(format!("{:<8}", amount)),
+            ];
+            let granted = allocated.iter().find(|(other, _)| other == name);
+            if let Some((_, granted)) = granted.filter(|(_, granted)| granted != amount) {
+                spans.push(Span::styled(
+                    trf("(allocated {amount})", &[("amount", granted)]),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        if let Some(licenses) = licenses {
+            lines.push(field_line(tr("Licenses"), licenses, width));
+        }
+        lines
+    }
+
+    /// Nodes the job runs or ran on; none while it is pending
+    fn node_names(&self) -> Vec<String> {
+        if !self.nodes.is_empty() {
+            return self.nodes.iter().map(|node| node.node.clone()).collect();
+        }
+        // sacct reports "None assigned" for jobs that never started
+        expand_hostlist(self.field("NodeList").unwrap_or(""))
+            .into_iter()
+            .filter(|name| !name.contains(' '))
+            .collect()
+    }
+
+    /// Nodes section of a multi-node job: every node of the compressed node
+    /// list, with the CPUs, GPUs and memory allocated on it while it runs
+    fn node_lines(&self) -> Vec<Line<'_>> {
+        let names = self.node_names();
+        if !self.sectioned || names.len() < 2 {
+            return Vec::new();
+        }
+        let width = names.iter().map(String::len).max().unwrap_or(0);
+        let gpus = self.nodes.iter().any(|node| node.gpus > 0);
+        let mut lines = vec![Line::styled(
+            trf("Nodes ({count})", &[("count", &names.len().to_string())]),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )];
+        for (i, name) in names.into_iter().enumerate() {
+            let mut spans = vec![Span::styled(
+                format!("  {:<width$}", name),
+                Style::default().fg(Color::Yellow),
+            )];
+            if let Some(node) = self.nodes.get(i) {
+                spans.push(Span::raw(format!("  {:>4} CPUs", node.cpus)));
+                if gpus {
+                    spans.push(Span::raw(format!("  {:>2} GPUs", node.gpus)));
+                }
+                if let Some(memory) = node.memory {
+                    spans.push(Span::raw(format!("  {:>7}", format_size(memory))));
+                }
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+
+    /// GPU section: one line per GPU with its utilization and memory
+    fn gpu_lines(&self) -> Vec<Line<'_>> {
+        let Some(node) = &self.gpu_node else {
+            return Vec::new();
+        };
+        let mut lines = vec![Line::styled(
+            trf("GPUs on {node}", &[("node", node)]),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )];
+        match &self.gpus {
+            None => lines.push(Line::from(format!("  {}", tr("Waiting for nvidia-smi...")))),
+            Some(Err(e)) => lines.push(Line::styled(
+                format!("  {}", e),
+                Style::default().fg(Color::Red),
+            )),
+            Some(Ok(gpus)) => lines.extend(gpus.iter().map(|gpu| {
+                let color = if gpu.utilization < 25 {
+                    Color::Red
+                } else if gpu.utilization < 50 {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("  GPU {}  ", gpu.index),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(format!("{}  ", gpu.name)),
+                    Span::styled(
+                        format!("{:>3}%", gpu.utilization),
+                        Style::default().fg(color),
+                    ),
+                    Span::raw(format!("  {} / {} MiB", gpu.memory_used, gpu.memory_total)),
+                ])
+            })),
+        }
+        lines
+    }
+
+    /// Render the pane
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(
+                Line::from(trf(
+                    "Job {id} ({source})",
+                    &[("id", &self.job_id), ("source", &self.source)],
+                ))
+                .centered(),
+            )
+            .title_bottom(Line::from(self.help_text()).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.live_tab {
+            self.render_stats(frame, inner);
+            return;
+        }
+
+        let show_search = self.searching || !self.search.is_empty();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),                                  // Fields
+                Constraint::Length(if show_search { 1 } else { 0 }), // Search bar
+            ])
+            .split(inner);
+
+        let lines = self.lines();
+        let max_scroll = lines.len().saturating_sub(chunks[0].height as usize) as u16;
+        let fields = if lines.is_empty() {
+            Paragraph::new(tr("No fields match the search"))
+        } else {
+            Paragraph::new(lines).scroll((self.scroll.min(max_scroll), 0))
+        };
+        frame.render_widget(fields, chunks[0]);
+
+        if show_search {
+            let color = if self.searching {
+                Color::Yellow
+            } else {
+                Color::Cyan
+            };
+            let line = Line::from(vec![
+                Span::styled("/", Style::default().fg(color)),
+                Span::raw(self.search.as_str()),
+            ]);
+            frame.render_widget(Paragraph::new(line), chunks[1]);
+            if self.searching {
+                let x = chunks[1].x + 1 + self.search.chars().count() as u16;
+                frame.set_cursor_position(Position::new(x, chunks[1].y));
+            }
+        }
+    }
+
+    fn help_text(&self) -> String {
+        if self.live_tab {
+            return tr("l: Fields | Tab/Esc/q: Close").to_string();
+        }
+        let mut parts = vec![tr("↑/↓/PgUp/PgDn: Scroll | /: Search")];
+        if self.live {
+            parts.push(tr("l: Live resources"));
+        }
+        if !self.attempts.is_empty() {
+            parts.push(tr("[/]: Previous/next attempt"));
+        }
+        parts.push(if self.raw_view {
+            tr("r: Parsed")
+        } else {
+            tr("r: Raw")
+        });
+        if self.sectioned && !self.node_names().is_empty() {
+            parts.push(tr("n: Nodes"));
+        }
+        parts.push(tr("y/w: Copy/Save to file"));
+        parts.push(tr("Tab/Esc/q: Close"));
+        parts.join(" | ")
+    }
+
+    /// Live resources tab: the latest `sstat` figures, each with a sparkline
+    /// of its history
+    fn render_stats(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Status
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+
+        let status = match (&self.stats_error, self.stats.len()) {
+            (Some(e), _) => Line::styled(e.as_str(), Style::default().fg(Color::Red)),
+            (None, 0) => Line::from(tr("Waiting for sstat...")),
+            (None, samples) => Line::styled(
+                trf("{count} sample(s)", &[("count", &samples.to_string())]),
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(Paragraph::new(status), chunks[0]);
+
+        let history =
+            |value: fn(&JobStats) -> u64| -> Vec<u64> { self.stats.iter().map(value).collect() };
+        let metrics = [
+            ("AveCPU", history(|s| s.ave_cpu), Color::Green),
+            ("MaxRSS", history(|s| s.max_rss), Color::Magenta),
+            ("MaxDiskRead", history(|s| s.max_disk_read), Color::Blue),
+            ("MaxDiskWrite", history(|s| s.max_disk_write), Color::Yellow),
+        ];
+        for ((name, history, color), &chunk) in metrics.into_iter().zip(&chunks[1..]) {
+            let current = match history.last() {
+                Some(&secs) if name == "AveCPU" => format_duration(secs),
+                Some(&bytes) => format_size(bytes),
+                None => "-".to_string(),
+            };
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(chunk);
+            let label = Line::from(vec![
+                Span::styled(
+                    format!("  {:<14}", name),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(current, Style::default().add_modifier(Modifier::BOLD)),
+            ]);
+            frame.render_widget(Paragraph::new(label), rows[0]);
+            // Newest samples on the right, as many as fit
+            let width = rows[1].width.saturating_sub(2) as usize;
+            let shown = &history[history.len().saturating_sub(width)..];
+            frame.render_widget(
+                Sparkline::default()
+                    .data(shown)
+                    .style(Style::default().fg(color)),
+                rows[1].inner(Margin::new(2, 0)),
+            );
+        }
+    }
+}
+
+/// A field as "  Key  value", the keys padded to `width`
+/// `name=amount` pairs of a TRES list like `cpu=4,mem=16G,node=1,billing=4,gres/gpu=1`
+fn parse_tres(tres: &str) -> Vec<(String, String)> {
+    tres.split(',')
+        .filter_map(|item| item.split_once('='))
+        .map(|(name, amount)| (name.to_string(), amount.to_string()))
+        .collect()
+}
+
+fn field_line<'a>(key: &str, value: &'a str, width: usize) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(
+            format!("  {:<width$}  ", key, width = width),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw(value),
+    ])
+}
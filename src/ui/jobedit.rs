@@ -0,0 +1,541 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+
+use crate::{
+    config::PartitionDefaults,
+    slurm::{
+        limits::{ClusterLimits, LimitField, ResourceRequest},
+        parse_time_limit,
+    },
+};
+
+/// Fields that can be changed from the job edit form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditField {
+    TimeLimit,
+    Partition,
+    QoS,
+    Account,
+    NumNodes,
+    NumCPUs,
+    Gres,
+}
+
+impl EditField {
+    /// All editable fields in display order
+    fn all() -> Vec<EditField> {
+        vec![
+            EditField::TimeLimit,
+            EditField::Partition,
+            EditField::QoS,
+            EditField::Account,
+            EditField::NumNodes,
+            EditField::NumCPUs,
+            EditField::Gres,
+        ]
+    }
+
+    /// Get the title for this field
+    fn title(&self) -> &'static str {
+        match self {
+            EditField::TimeLimit => "Time Limit",
+            EditField::Partition => "Partition",
+            EditField::QoS => "QoS",
+            EditField::Account => "Account",
+            EditField::NumNodes => "Nodes",
+            EditField::NumCPUs => "CPUs",
+            EditField::Gres => "GRES",
+        }
+    }
+
+    /// Key used both in `scontrol show job` output and `scontrol update`
+    pub fn scontrol_key(&self) -> &'static str {
+        match self {
+            EditField::TimeLimit => "TimeLimit",
+            EditField::Partition => "Partition",
+            EditField::QoS => "QOS",
+            EditField::Account => "Account",
+            EditField::NumNodes => "NumNodes",
+            EditField::NumCPUs => "NumCPUs",
+            EditField::Gres => "Gres",
+        }
+    }
+
+    /// The cluster limit checked for this field
+    fn limit_field(&self) -> Option<LimitField> {
+        match self {
+            EditField::TimeLimit => Some(LimitField::TimeLimit),
+            EditField::Partition => None,
+            EditField::QoS => Some(LimitField::QoS),
+            EditField::Account => Some(LimitField::Account),
+            EditField::NumNodes => Some(LimitField::Nodes),
+            EditField::NumCPUs => Some(LimitField::Cpus),
+            EditField::Gres => Some(LimitField::Gres),
+        }
+    }
+
+    /// Whether the field may be left empty
+    fn optional(&self) -> bool {
+        matches!(self, EditField::Account | EditField::Gres)
+    }
+
+    /// Read the current value of this field from `scontrol show job` output
+    fn current_value(&self, info: &HashMap<String, String>) -> String {
+        let value = match self {
+            // Newer Slurm versions report GRES as TRES, e.g. "gres/gpu:2"
+            EditField::Gres => info
+                .get("TresPerNode")
+                .or_else(|| info.get("Gres"))
+                .map(|v| {
+                    v.split(',')
+                        .map(|g| g.trim_start_matches("gres/"))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                }),
+            _ => info.get(self.scontrol_key()).cloned(),
+        };
+        match value {
+            Some(v) if v != "(null)" => v,
+            _ => String::new(),
+        }
+    }
+}
+
+/// A single input of the edit form
+struct FormField {
+    field: EditField,
+    value: String,
+    original: String,
+    error: Option<String>,
+}
+
+/// Popup form for changing the resources of an existing job
+pub struct JobEditForm {
+    pub visible: bool,
+    pub job_id: Option<String>,
+    fields: Vec<FormField>,
+    focus: usize,
+    /// Feedback shown above the help text (e.g. "No changes")
+    message: Option<String>,
+    /// Per-partition defaults from the config file
+    partition_defaults: HashMap<String, PartitionDefaults>,
+    /// Partition whose defaults were last applied, so typing doesn't reapply them
+    applied_partition: Option<String>,
+}
+
+/// Action to take after handling a key in the edit form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobEditAction {
+    /// Do nothing
+    None,
+    /// Close the form without changing the job
+    Close,
+    /// Submit the changed `Key=Value` parameters
+    Submit(Vec<(String, String)>),
+}
+
+impl JobEditForm {
+    pub fn new(partition_defaults: HashMap<String, PartitionDefaults>) -> Self {
+        Self {
+            visible: false,
+            job_id: None,
+            fields: Vec::new(),
+            focus: 0,
+            message: None,
+            partition_defaults,
+            applied_partition: None,
+        }
+    }
+
+    /// Show the form for a job, pre-filled from its `scontrol show job` fields
+    pub fn show(&mut self, job_id: String, info: &HashMap<String, String>) {
+        self.fields = EditField::all()
+            .into_iter()
+            .map(|field| {
+                let current = field.current_value(info);
+                FormField {
+                    field,
+                    value: current.clone(),
+                    original: current,
+                    error: None,
+                }
+            })
+            .collect();
+        self.job_id = Some(job_id);
+        self.focus = 0;
+        self.message = None;
+        self.applied_partition = Some(self.value(EditField::Partition).to_string());
+        self.visible = true;
+    }
+
+    /// Current value of a field
+    fn value(&self, field: EditField) -> &str {
+        self.fields
+            .iter()
+            .find(|f| f.field == field)
+            .map(|f| f.value.as_str())
+            .unwrap_or("")
+    }
+
+    /// Defaults configured for the partition currently entered in the form
+    fn current_defaults(&self) -> Option<&PartitionDefaults> {
+        self.partition_defaults
+            .get(self.value(EditField::Partition).trim())
+    }
+
+    /// Prefill QoS, account and GRES when a different partition has been chosen.
+    ///
+    /// Switching back to the job's own partition restores its original values.
+    fn apply_partition_defaults(&mut self) {
+        let partition = self.value(EditField::Partition).trim().to_string();
+        if self.applied_partition.as_deref() == Some(partition.as_str()) {
+            return;
+        }
+        let defaults = self.partition_defaults.get(&partition).cloned();
+        let back_to_original = self
+            .fields
+            .iter()
+            .any(|f| f.field == EditField::Partition && f.original == partition);
+        if defaults.is_none() && !back_to_original {
+            return;
+        }
+        self.applied_partition = Some(partition.clone());
+
+        for f in &mut self.fields {
+            let value = match f.field {
+                EditField::QoS | EditField::Account | EditField::Gres if back_to_original => {
+                    Some(f.original.clone())
+                }
+                EditField::QoS => defaults.as_ref().and_then(|d| d.qos.clone()),
+                EditField::Account => defaults.as_ref().and_then(|d| d.account.clone()),
+                EditField::Gres => defaults.as_ref().and_then(|d| d.gres.clone()),
+                _ => None,
+            };
+            if let Some(value) = value {
+                f.value = value;
+                f.error = None;
+            }
+        }
+        self.message = Some(if back_to_original {
+            format!("Restored the job's settings for partition {}", partition)
+        } else {
+            format!("Applied config defaults for partition {}", partition)
+        });
+    }
+
+    /// Hide the form
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Validate every field on its own and against the cluster limits, returning
+    /// true if all values are acceptable.
+    ///
+    /// Limits are only reported for fields that were changed, or when the partition
+    /// or QoS changed, so an untouched job is never blocked by them.
+    fn validate(&mut self, partitions: &[String], qos: &[String], limits: &ClusterLimits) -> bool {
+        for f in &mut self.fields {
+            // An untouched partition or QoS is not checked against the lists: a
+            // pending job may have been submitted to "a,b", or with a hidden QoS
+            let (partitions, qos) = if f.value.trim() != f.original {
+                (partitions, qos)
+            } else {
+                (&[][..], &[][..])
+            };
+            f.error = validate_field(f.field, &f.value, partitions, qos);
+        }
+
+        let changed = |field: EditField| {
+            self.fields
+                .iter()
+                .any(|f| f.field == field && f.value.trim() != f.original)
+        };
+        let scope_changed = changed(EditField::Partition) || changed(EditField::QoS);
+        let request = ResourceRequest {
+            partition: self.value(EditField::Partition).trim(),
+            qos: Some(self.value(EditField::QoS).trim()),
+            account: Some(self.value(EditField::Account).trim()),
+            time_limit: Some(self.value(EditField::TimeLimit).trim()),
+            cpus: self.value(EditField::NumCPUs).trim().parse().ok(),
+            // A node range like "2-4" is checked by its minimum
+            nodes: self
+                .value(EditField::NumNodes)
+                .split('-')
+                .next()
+                .and_then(|n| n.trim().parse().ok()),
+            gres: Some(self.value(EditField::Gres).trim()),
+            memory: None,
+        };
+        let violations: Vec<(EditField, String)> = limits
+            .check(&request)
+            .into_iter()
+            .filter_map(|(limit, msg)| {
+                let field = EditField::all()
+                    .into_iter()
+                    .find(|f| f.limit_field() == Some(limit))?;
+                (scope_changed || changed(field)).then_some((field, msg))
+            })
+            .collect();
+
+        for (field, msg) in violations {
+            if let Some(f) = self
+                .fields
+                .iter_mut()
+                .find(|f| f.field == field && f.error.is_none())
+            {
+                f.error = Some(msg);
+            }
+        }
+        self.fields.iter().all(|f| f.error.is_none())
+    }
+
+    /// Collect the parameters that differ from the job's current values
+    fn changes(&self) -> Vec<(String, String)> {
+        self.fields
+            .iter()
+            .filter(|f| f.value.trim() != f.original)
+            .map(|f| {
+                (
+                    f.field.scontrol_key().to_string(),
+                    f.value.trim().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// Handle key events for the edit form
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        partitions: &[String],
+        qos: &[String],
+        limits: &ClusterLimits,
+    ) -> JobEditAction {
+        if self.fields.is_empty() {
+            return JobEditAction::Close;
+        }
+
+        match key.code {
+            KeyCode::Esc => JobEditAction::Close,
+            KeyCode::Up | KeyCode::BackTab => {
+                self.focus = (self.focus + self.fields.len() - 1) % self.fields.len();
+                JobEditAction::None
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.focus = (self.focus + 1) % self.fields.len();
+                JobEditAction::None
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let forward = key.code == KeyCode::Right;
+                match self.fields[self.focus].field {
+                    EditField::Partition => {
+                        cycle(&mut self.fields[self.focus].value, partitions, forward);
+                        self.apply_partition_defaults();
+                    }
+                    EditField::QoS => cycle(&mut self.fields[self.focus].value, qos, forward),
+                    _ => {}
+                }
+                self.validate(partitions, qos, limits);
+                JobEditAction::None
+            }
+            KeyCode::Enter => self.submit(partitions, qos, limits),
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.submit(partitions, qos, limits)
+            }
+            KeyCode::Char(c) => {
+                self.fields[self.focus].value.push(c);
+                if self.fields[self.focus].field == EditField::Partition {
+                    self.apply_partition_defaults();
+                }
+                self.validate(partitions, qos, limits);
+                JobEditAction::None
+            }
+            KeyCode::Backspace => {
+                self.fields[self.focus].value.pop();
+                if self.fields[self.focus].field == EditField::Partition {
+                    self.apply_partition_defaults();
+                }
+                self.validate(partitions, qos, limits);
+                JobEditAction::None
+            }
+            _ => JobEditAction::None,
+        }
+    }
+
+    /// Validate and, if anything changed, return the update parameters
+    fn submit(
+        &mut self,
+        partitions: &[String],
+        qos: &[String],
+        limits: &ClusterLimits,
+    ) -> JobEditAction {
+        if !self.validate(partitions, qos, limits) {
+            self.message = Some("Fix the highlighted fields before submitting".to_string());
+            return JobEditAction::None;
+        }
+        let changes = self.changes();
+        if changes.is_empty() {
+            self.message = Some("No changes to submit".to_string());
+            return JobEditAction::None;
+        }
+        JobEditAction::Submit(changes)
+    }
+
+    /// Render the edit form
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let title = format!("Edit Job {}", self.job_id.as_deref().unwrap_or("unknown"));
+        let block = Block::default()
+            .title(Line::from(title).centered())
+            .borders(Borders::NONE)
+            .style(Style::default().bg(Color::Black));
+        frame.render_widget(block, area);
+
+        let mut constraints: Vec<Constraint> =
+            self.fields.iter().map(|_| Constraint::Length(3)).collect();
+        constraints.push(Constraint::Min(0)); // Message
+        constraints.push(Constraint::Length(3)); // Help text
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(constraints)
+            .split(area);
+
+        let max_time = self.current_defaults().and_then(|d| d.max_time.as_deref());
+        for (i, f) in self.fields.iter().enumerate() {
+            let focused = i == self.focus;
+            let mut title = match &f.error {
+                Some(err) => format!("{} ✗ {}", f.field.title(), err),
+                None if f.value.trim() != f.original => {
+                    format!("{} (was: {})", f.field.title(), f.original)
+                }
+                None => f.field.title().to_string(),
+            };
+            if let (EditField::TimeLimit, Some(max)) = (f.field, max_time) {
+                title.push_str(&format!(" [partition max {}]", max));
+            }
+            let style = match (focused, f.error.is_some()) {
+                (true, _) => Style::default().fg(Color::Cyan),
+                (false, true) => Style::default().fg(Color::Red),
+                (false, false) => Style::default(),
+            };
+            let input = Paragraph::new(f.value.clone()).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .style(style),
+            );
+            frame.render_widget(input, chunks[i]);
+
+            if focused {
+                frame.set_cursor_position(Position {
+                    x: chunks[i].x + 1 + f.value.chars().count() as u16,
+                    y: chunks[i].y + 1,
+                });
+            }
+        }
+
+        if let Some(message) = &self.message {
+            let msg = Paragraph::new(message.as_str()).style(Style::default().fg(Color::Yellow));
+            frame.render_widget(msg, chunks[self.fields.len()]);
+        }
+
+        let help_text =
+            "↑/↓/Tab: Switch field | ←/→: Cycle partition/QoS | Enter/Ctrl+a: Submit | Esc: Close";
+        let help = Paragraph::new(help_text)
+            .style(Style::default().fg(Color::Gray))
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[self.fields.len() + 1]);
+    }
+}
+
+/// Step a form value to the next (or previous) of its known options; a value
+/// that is not one of them starts over at the first
+pub fn cycle(value: &mut String, options: &[String], forward: bool) {
+    if options.is_empty() {
+        return;
+    }
+    let next = match options.iter().position(|o| o == value) {
+        Some(i) if forward => (i + 1) % options.len(),
+        Some(i) => (i + options.len() - 1) % options.len(),
+        None => 0,
+    };
+    *value = options[next].clone();
+}
+
+/// Check a single field value, returning a short error description if invalid
+fn validate_field(
+    field: EditField,
+    value: &str,
+    partitions: &[String],
+    qos: &[String],
+) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return (!field.optional()).then(|| "required".to_string());
+    }
+
+    match field {
+        EditField::TimeLimit => {
+            if parse_time_limit(value).is_none() {
+                Some("use [days-]hours:minutes:seconds or minutes".to_string())
+            } else {
+                None
+            }
+        }
+        EditField::Partition => {
+            if !partitions.is_empty() && !partitions.iter().any(|p| p == value) {
+                Some("unknown partition".to_string())
+            } else {
+                None
+            }
+        }
+        EditField::QoS => {
+            if !qos.is_empty() && !qos.iter().any(|q| q == value) {
+                Some("unknown QoS".to_string())
+            } else {
+                None
+            }
+        }
+        EditField::Account => None,
+        EditField::NumNodes => {
+            let valid = match value.split_once('-') {
+                Some((min, max)) => matches!(
+                    (min.parse::<u32>(), max.parse::<u32>()),
+                    (Ok(min), Ok(max)) if min > 0 && min <= max
+                ),
+                None => matches!(value.parse::<u32>(), Ok(n) if n > 0),
+            };
+            if valid {
+                None
+            } else {
+                Some("use a count or min-max range".to_string())
+            }
+        }
+        EditField::NumCPUs => match value.parse::<u32>() {
+            Ok(n) if n > 0 => None,
+            _ => Some("must be a positive integer".to_string()),
+        },
+        EditField::Gres => {
+            let valid = value.split(',').all(|g| {
+                !g.is_empty()
+                    && g.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "_:.-".contains(c))
+            });
+            if valid {
+                None
+            } else {
+                Some("use name[:type][:count], e.g. gpu:a100:2".to_string())
+            }
+        }
+    }
+}
@@ -6,7 +6,14 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use std::{collections::HashMap, process::Command};
+use std::{
+    collections::HashMap,
+    io::Write,
+    process::{Command, Stdio},
+    thread,
+};
+
+use crate::utils::ansi;
 
 /// JobScript viewer widget for displaying job batch scripts with syntax highlighting
 pub struct JobScript {
@@ -17,6 +24,10 @@ pub struct JobScript {
     pub scroll_position: usize,
     pub script_path: Option<String>,
     pub use_bat: bool, // If bat exists, use it for syntax highlighting
+    /// `content` highlighted by bat, line for line
+    highlighted: Option<String>,
+    /// Where the script was read from: slurmctld's stored copy or the submitted path
+    source: String,
 }
 
 impl JobScript {
@@ -30,6 +41,8 @@ impl JobScript {
             scroll_position: 0,
             script_path: None,
             use_bat,
+            highlighted: None,
+            source: String::new(),
         }
     }
 
@@ -108,7 +121,10 @@ impl JobScript {
             None => String::from("null"),
         };
 
-        let title = format!("Job Script for {}/{}", job_name, job_id);
+        let mut title = format!("Job Script for {}/{}", job_name, job_id);
+        if !self.source.is_empty() {
+            title.push_str(&format!(" ({})", self.source));
+        }
 
         let help_text =
             " [↑/↓] Scroll | [Ctrl+u/d] PageUp/Down | [Shift+↑/↓] Toggle Job| [q] Close ";
@@ -131,7 +147,7 @@ impl JobScript {
         frame.render_widget(script_paragraph, area);
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent) -> () {
+    pub fn handle_key(&mut self, key: KeyEvent) {
         match (key.modifiers, key.code) {
             (_, KeyCode::Char('q')) => {
                 // Close the script view
@@ -159,239 +175,174 @@ impl JobScript {
         }
     }
 
-    /// Create display text with optional line numbers
-    fn create_display_text(&self) -> Text {
-        if self.use_bat {
-            let lines = parse_ansi_to_spans(&self.content);
-            return Text::from(lines);
-        }
-
-        let content_lines: Vec<&str> = self.content.lines().collect();
-        let total_lines = content_lines.len();
-
-        // Calculate the width needed for line numbers
-        let line_num_width = total_lines.to_string().len() + 1; // +1 for the separator
-
-        // Create lines with line numbers
-        let mut numbered_lines: Vec<Line> = Vec::new();
-
-        for (i, line) in content_lines.iter().enumerate() {
-            let line_num = i + 1;
-            let mut spans = Vec::new();
-
-            spans.push(Span::styled(
-                format!("{:>width$} ", line_num, width = line_num_width - 1),
-                Style::default().fg(Color::DarkGray),
-            ));
-
-            spans.push(Span::raw(*line));
-            numbered_lines.push(Line::from(spans));
-        }
-
-        Text::from(numbered_lines)
+    /// Create display text with line numbers, bat's highlighting if any,
+    /// and the `#SBATCH` directives of the script header picked out
+    fn create_display_text(&self) -> Text<'_> {
+        script_text(&self.content, self.highlighted.as_deref())
     }
 
-    /// Fetch the job script content using scontrol
+    /// Fetch the job script: slurmctld's stored copy of the submitted script,
+    /// or the file at the job's `Command` path when that is unavailable
     fn fetch_script_content(&mut self) {
-        if let Some(job_id) = &self.job_id {
-            // First get job details to find BatchScript path
-            let output = Command::new("scontrol")
-                .args(["show", "job", job_id, "-o"])
-                .output();
-
-            if let Ok(output) = output {
-                if output.status.success() {
-                    let output_str = String::from_utf8_lossy(&output.stdout);
-                    let key_value_pairs = parse_scontrol_output(&output_str);
-
-                    // Get the BatchScript path
-                    if let Some(script_path) = key_value_pairs.get("Command") {
-                        self.script_path = Some(script_path.to_string());
-
-                        if self.use_bat {
-                            // If bat is installed, use it to create a syntax-highlighted version
-                            if let Some(bat_output) = create_bat_out_string(script_path) {
-                                self.content = bat_output;
-                                return;
-                            }
-                        }
-                        // If bat is not available, read the script directly
-                        self.use_bat = false;
-                        // Now read the script content
-                        if let Ok(script_content) = std::fs::read_to_string(script_path) {
-                            self.content = script_content;
-                        } else {
-                            self.content =
-                                format!("Failed to read script from path: {}", script_path);
-                        }
-                    } else {
-                        self.content =
-                            String::from("No script found for this job. Maybe it's wrapped");
-                    }
-                } else {
-                    self.content = String::from("Error retrieving job information");
+        self.highlighted = None;
+        self.source.clear();
+        let Some(job_id) = self.job_id.clone() else {
+            self.content = String::new();
+            return;
+        };
+
+        if let Some(script) = stored_batch_script(&job_id) {
+            self.content = script;
+            self.source = "stored copy".to_string();
+        } else {
+            match self.read_script_file(&job_id) {
+                Ok(script) => self.content = script,
+                Err(message) => {
+                    self.content = message;
+                    return;
                 }
-            } else {
-                self.content = String::from("Failed to execute scontrol command");
             }
-        } else {
-            self.content = String::new();
+        }
+        if self.use_bat {
+            self.highlighted = highlight_with_bat(&self.content);
         }
     }
-}
 
-/// Use bat to create a syntax-highlighted version of the script
-fn create_bat_out_string(path: &str) -> Option<String> {
-    let output = Command::new("bat")
-        .arg("--style=numbers,grid")
-        .arg("--color=always")
-        .arg("--theme")
-        .arg("Solarized (light)")
-        .arg("--terminal-width=100")
-        .arg(path)
-        .output();
-    if let Ok(output) = output {
-        if output.status.success() {
-            Some(String::from_utf8_lossy(&output.stdout).to_string())
-        } else {
-            None
+    /// Read the script from the job's `Command` path
+    fn read_script_file(&mut self, job_id: &str) -> Result<String, String> {
+        let output = Command::new("scontrol")
+            .args(["show", "job", job_id, "-o"])
+            .output()
+            .map_err(|_| String::from("Failed to execute scontrol command"))?;
+        if !output.status.success() {
+            return Err(String::from("Error retrieving job information"));
         }
-    } else {
-        None
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        let key_value_pairs = parse_scontrol_output(&output_str);
+
+        // Get the BatchScript path
+        let Some(script_path) = key_value_pairs.get("Command") else {
+            return Err(String::from(
+                "No script found for this job. Maybe it's wrapped",
+            ));
+        };
+        self.script_path = Some(script_path.to_string());
+        self.source = script_path.to_string();
+        std::fs::read_to_string(script_path)
+            .map_err(|_| format!("Failed to read script from path: {}", script_path))
     }
 }
 
-/// Parse ANSI escape sequences into ratatui spans
-fn parse_ansi_to_spans(ansi_text: &str) -> Vec<Line> {
-    use regex::Regex;
-
-    // Regex to match ANSI color escape sequences
-    let ansi_escape_re = Regex::new(r"\x1B\[([0-9;]*)m").unwrap();
-    let lines = ansi_text.lines();
-    let mut result_lines = Vec::new();
-
-    let mut current_style = Style::default();
-
-    for line in lines {
-        let mut spans = Vec::new();
-        let mut last_index = 0;
-
-        // Find all ANSI escape sequences in the line
-        for cap in ansi_escape_re.captures_iter(line) {
-            let full_match = cap.get(0).unwrap();
-            let code_match = cap.get(1).unwrap();
-
-            // Get the text before this escape sequence
-            if full_match.start() > last_index {
-                let text = &line[last_index..full_match.start()];
-                spans.push(Span::styled(text, current_style));
+/// A batch script with line numbers and its `#SBATCH` header directives picked out;
+/// other lines use bat's output in `highlighted` when given, or have comments dimmed
+pub fn script_text<'a>(content: &'a str, highlighted: Option<&'a str>) -> Text<'a> {
+    let highlighted: Vec<&str> = highlighted
+        .map(|text| text.lines().collect())
+        .unwrap_or_default();
+    let content_lines: Vec<&str> = content.lines().collect();
+    let total_lines = content_lines.len();
+
+    // Calculate the width needed for line numbers
+    let line_num_width = total_lines.to_string().len();
+
+    // sbatch stops reading directives at the first command
+    let mut in_header = true;
+    let mut numbered_lines: Vec<Line> = Vec::new();
+    for (i, line) in content_lines.iter().enumerate() {
+        let trimmed = line.trim();
+        in_header &= trimmed.is_empty() || trimmed.starts_with('#');
+
+        let mut spans = vec![Span::styled(
+            format!("{:>width$} ", i + 1, width = line_num_width),
+            Style::default().fg(Color::DarkGray),
+        )];
+        match (
+            in_header.then(|| directive_spans(line)).flatten(),
+            highlighted.get(i),
+        ) {
+            (Some(directive), _) => spans.extend(directive),
+            (None, Some(highlighted)) => {
+                spans.extend(ansi::parse_line(highlighted, &mut Style::default()))
             }
-
-            // Update style based on the ANSI code
-            let code = code_match.as_str();
-            current_style = parse_ansi_code(code, current_style);
-
-            last_index = full_match.end();
-        }
-
-        // Add remaining text after the last escape sequence
-        if last_index < line.len() {
-            let text = &line[last_index..];
-            if !text.is_empty() {
-                spans.push(Span::styled(text, current_style));
+            (None, None) if trimmed.starts_with('#') => {
+                spans.push(Span::styled(*line, Style::default().fg(Color::DarkGray)))
             }
+            (None, None) => spans.push(Span::raw(*line)),
         }
-
-        // Reset style at end of line
-        current_style = Style::default();
-
-        // Add the line if it has spans
-        if !spans.is_empty() {
-            result_lines.push(Line::from(spans));
-        } else {
-            // Add empty line
-            result_lines.push(Line::default());
-        }
+        numbered_lines.push(Line::from(spans));
     }
 
-    result_lines
+    Text::from(numbered_lines)
 }
 
-/// Convert ANSI color code to ratatui Style
-fn parse_ansi_code(code: &str, mut style: Style) -> Style {
-    // Split by semicolons
-    let parts: Vec<&str> = code.split(';').collect();
+/// The copy of the batch script slurmctld keeps while the job is in the queue,
+/// printed by `scontrol write batch_script <id> -`
+fn stored_batch_script(job_id: &str) -> Option<String> {
+    let output = Command::new("scontrol")
+        .args(["write", "batch_script", job_id, "-"])
+        .output()
+        .ok()?;
+    let script = String::from_utf8_lossy(&output.stdout).into_owned();
+    (output.status.success() && !script.is_empty()).then_some(script)
+}
 
-    // 创建迭代器以处理连续的参数
-    let mut iter = parts.iter();
+/// Use bat to highlight the script as shell, one output line per script line
+pub fn highlight_with_bat(script: &str) -> Option<String> {
+    let mut child = Command::new("bat")
+        .args([
+            "--style=plain",
+            "--color=always",
+            "--wrap=never",
+            "--paging=never",
+            "--language=bash",
+            "--theme",
+            "Solarized (light)",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // Feed the script from another thread so a full stdout pipe cannot block us
+    let mut stdin = child.stdin.take()?;
+    let script = script.to_string();
+    let writer = thread::spawn(move || stdin.write_all(script.as_bytes()));
+    let output = child.wait_with_output().ok()?;
+    writer.join().ok()?.ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
-    while let Some(&part) = iter.next() {
-        match part {
-            "0" => {
-                // Reset all attributes
-                style = Style::default();
-            }
-            "1" => {
-                // Bold
-                style = style.add_modifier(Modifier::BOLD);
-            }
-            "3" => {
-                // Italic
-                style = style.add_modifier(Modifier::ITALIC);
+/// Spans of an `#SBATCH` directive line: the prefix, option names and values
+/// in their own colors, and a trailing comment dimmed
+fn directive_spans(line: &str) -> Option<Vec<Span<'_>>> {
+    let body = line.strip_prefix("#SBATCH")?;
+    let mut spans = vec![Span::styled(
+        "#SBATCH",
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+    )];
+    let (options, comment) = body.split_at(body.find(" #").unwrap_or(body.len()));
+    let flag_style = Style::default().fg(Color::Yellow);
+    let value_style = Style::default().fg(Color::Green);
+    for word in options.split_inclusive(char::is_whitespace) {
+        match word.split_once('=') {
+            Some((flag, value)) if flag.starts_with('-') => {
+                spans.push(Span::styled(flag, flag_style));
+                spans.push(Span::raw("="));
+                spans.push(Span::styled(value, value_style));
             }
-            "4" => {
-                // Underline
-                style = style.add_modifier(Modifier::UNDERLINED);
-            }
-            // 处理 256 色模式的前景色 (38;5;n)
-            "38" => {
-                if let Some(&"5") = iter.next() {
-                    if let Some(&color_idx_str) = iter.next() {
-                        if let Ok(color_idx) = color_idx_str.parse::<u8>() {
-                            style = style.fg(Color::Indexed(color_idx));
-                        }
-                    }
-                }
-            }
-            // 处理 256 色模式的背景色 (48;5;n)
-            "48" => {
-                if let Some(&"5") = iter.next() {
-                    if let Some(&color_idx_str) = iter.next() {
-                        if let Ok(color_idx) = color_idx_str.parse::<u8>() {
-                            style = style.bg(Color::Indexed(color_idx));
-                        }
-                    }
-                }
-            }
-            // 基本 16 色前景色 (30-37, 90-97)
-            s if s.len() <= 3 && s.starts_with("3") => {
-                if let Ok(idx) = s[1..].parse::<u8>() {
-                    style = style.fg(Color::Indexed(idx));
-                }
-            }
-            s if s.len() <= 3 && s.starts_with("9") => {
-                if let Ok(idx) = s[1..].parse::<u8>() {
-                    // 亮色从 ANSI 90-97 映射到 索引 8-15
-                    style = style.fg(Color::Indexed(idx + 8));
-                }
-            }
-            // 基本 16 色背景色 (40-47, 100-107)
-            s if s.len() <= 3 && s.starts_with("4") && s != "48" => {
-                if let Ok(idx) = s[1..].parse::<u8>() {
-                    style = style.bg(Color::Indexed(idx));
-                }
-            }
-            s if s.len() <= 4 && s.starts_with("10") => {
-                if let Ok(idx) = s[2..].parse::<u8>() {
-                    // 亮背景色从 ANSI 100-107 映射到 索引 8-15
-                    style = style.bg(Color::Indexed(idx + 8));
-                }
-            }
-            _ => {}
+            _ if word.starts_with('-') => spans.push(Span::styled(word, flag_style)),
+            _ => spans.push(Span::styled(word, value_style)),
         }
     }
-
-    style
+    if !comment.is_empty() {
+        spans.push(Span::styled(comment, Style::default().fg(Color::DarkGray)));
+    }
+    Some(spans)
 }
 
 fn parse_scontrol_output(output: &str) -> HashMap<String, String> {
@@ -409,7 +360,7 @@ fn parse_scontrol_output(output: &str) -> HashMap<String, String> {
 }
 
 /// Check if bat is installed on the system
-fn is_bat_installed() -> bool {
+pub fn is_bat_installed() -> bool {
     let output = Command::new("which").arg("bat").output();
 
     match output {
@@ -5,8 +5,14 @@ use ratatui::{
     Frame,
 };
 
-use crate::slurm::{Job, JobState};
+use crate::i18n::tr;
+use crate::slurm::{
+    format_size,
+    sacct::{JobExit, JobStep},
+    Job, JobState,
+};
 use crate::ui::columns::{JobColumn, SortColumn};
+use crate::ui::quickfilter::QuickFilter;
 use std::collections::{HashMap, HashSet};
 
 /// Visible row type for grouped rendering
@@ -16,19 +22,41 @@ enum VisibleRow {
     Group { key: String, rep_job_index: usize },
     /// A concrete job row; holds the index into `jobs`
     Job { job_index: usize },
+    /// A step listed below its job; holds the job's index and the step's index in `steps`
+    Step { job_index: usize, step_index: usize },
 }
 
 /// Struct to manage the jobs list view
 pub struct JobsList {
     pub state: TableState,
+    /// Jobs shown: those from the last refresh that pass the quick filter
     pub jobs: Vec<Job>,
+    /// Jobs from the last refresh
+    all_jobs: Vec<Job>,
+    /// The `/` filter bar
+    pub filter: QuickFilter,
     pub selected_jobs: Vec<usize>,
     pub sort_column: usize,
     pub sort_ascending: bool,
+    /// IDs of watched jobs, marked in the ID column
+    pub watched: HashSet<String>,
+    /// Running jobs whose live MaxRSS is close to their memory request, with
+    /// the used fraction of the request
+    pub memory_risk: HashMap<String, f64>,
+    /// How the finished jobs in the list ended, from accounting
+    pub exits: HashMap<String, JobExit>,
+    /// Local notes on jobs, by job ID
+    pub notes: HashMap<String, String>,
+    /// Rank of pending jobs in their partition's queue and its length, by job ID
+    pub priority_ranks: HashMap<String, (usize, usize)>,
+    /// IDs of pinned jobs and array parents, always listed first
+    pinned: HashSet<String>,
     /// Mapping from group key to list of job indices belonging to the group
     group_map: HashMap<String, Vec<usize>>,
     /// Which groups are currently expanded
     expanded_groups: HashSet<String>,
+    /// Steps of the jobs expanded into their steps, by job ID
+    steps: HashMap<String, Vec<JobStep>>,
     /// Flattened rows that are actually rendered (group headers and visible jobs)
     visible_rows: Vec<VisibleRow>,
 }
@@ -38,19 +66,48 @@ impl JobsList {
         Self {
             state: TableState::default(),
             jobs: Vec::new(),
+            all_jobs: Vec::new(),
+            filter: QuickFilter::default(),
             selected_jobs: Vec::new(),
             sort_column: 0, // Default sort by job ID
             sort_ascending: true,
+            watched: HashSet::new(),
+            memory_risk: HashMap::new(),
+            exits: HashMap::new(),
+            notes: HashMap::new(),
+            priority_ranks: HashMap::new(),
+            pinned: HashSet::new(),
             group_map: HashMap::new(),
             expanded_groups: HashSet::new(),
+            steps: HashMap::new(),
             visible_rows: Vec::new(),
         }
     }
 
     /// Update the list of jobs
     pub fn update_jobs(&mut self, jobs: Vec<Job>) {
+        self.all_jobs = jobs;
+        self.filter_jobs();
+    }
+
+    /// Show the jobs matching the changed quick filter. The selection is cleared
+    /// since it refers to rows of the previous list.
+    pub fn apply_quick_filter(&mut self) {
+        self.selected_jobs.clear();
+        self.filter_jobs();
+    }
+
+    fn filter_jobs(&mut self) {
+        // Jobs are already sorted by the squeue command; pinned jobs go first,
+        // keeping that order among them (the sort is stable)
+        let mut jobs: Vec<Job> = self
+            .all_jobs
+            .iter()
+            .filter(|job| self.filter.matches(*job))
+            .cloned()
+            .collect();
+        jobs.sort_by_key(|job| !self.is_pinned(job));
         self.jobs = jobs;
-        // Jobs are already sorted by the squeue command
 
         // Rebuild grouping and visible rows on every update
         self.rebuild_groups_and_rows();
@@ -65,6 +122,47 @@ impl JobsList {
         }
     }
 
+    /// Whether a job is pinned, by its own ID or its array's parent ID
+    fn is_pinned(&self, job: &Job) -> bool {
+        self.pinned.contains(&job.id) || self.pinned.contains(&self.compute_group_key(job))
+    }
+
+    /// Pin jobs (or whole arrays by parent ID) to the top of the list, or unpin
+    /// them if all of them are pinned already. Pins are kept by ID across
+    /// refreshes. Returns whether the jobs are pinned afterwards.
+    pub fn toggle_pins(&mut self, ids: &[String]) -> bool {
+        let pin = !ids.iter().all(|id| self.pinned.contains(id));
+        for id in ids {
+            if pin {
+                self.pinned.insert(id.clone());
+            } else {
+                self.pinned.remove(id);
+            }
+        }
+
+        // Rows move, so carry the selection and the cursor over by ID
+        let selected = self.get_selected_jobs();
+        let cursor_group = self.selected_group().map(|(key, _)| key);
+        let cursor_job = self.selected_job().map(|job| job.id.clone());
+        self.filter_jobs();
+        self.selected_jobs = (0..self.jobs.len())
+            .filter(|&i| selected.contains(&self.jobs[i].id))
+            .collect();
+        let group_row = cursor_group.and_then(|key| {
+            self.visible_rows
+                .iter()
+                .position(|vr| matches!(vr, VisibleRow::Group { key: k, .. } if *k == key))
+        });
+        match (group_row, cursor_job) {
+            (Some(row), _) => self.state.select(Some(row)),
+            (None, Some(id)) => {
+                self.select_job_by_id(&id);
+            }
+            (None, None) => {}
+        }
+        pin
+    }
+
     /// Toggle job selection. If a group header is selected, toggle selection of the whole group.
     pub fn toggle_select(&mut self) {
         if let Some(visible_idx) = self.state.selected() {
@@ -92,7 +190,7 @@ impl JobsList {
                         self.selected_jobs.push(*job_index);
                     }
                 }
-                None => {}
+                Some(VisibleRow::Step { .. }) | None => {}
             }
         }
     }
@@ -182,6 +280,16 @@ impl JobsList {
         columns: &[JobColumn],
         sort_columns: &[SortColumn],
     ) {
+        // The quick filter bar takes the last line
+        let area = if self.filter.is_active() && area.height > 1 {
+            let bar = Rect::new(area.x, area.bottom() - 1, area.width, 1);
+            self.filter
+                .render(frame, bar, self.jobs.len(), self.all_jobs.len());
+            Rect::new(area.x, area.y, area.width, area.height - 1)
+        } else {
+            area
+        };
+
         // Update sorting if needed based on sort_columns
         if !sort_columns.is_empty() {
             self.update_sort(columns, sort_columns);
@@ -189,9 +297,10 @@ impl JobsList {
 
         // Check if columns are empty, show warning if so
         if columns.is_empty() {
-            let warning = Paragraph::new("No columns selected. Press 'c' to configure columns.")
-                .style(Style::default().fg(Color::Yellow))
-                .block(Block::default().title("Warning").borders(Borders::ALL));
+            let warning =
+                Paragraph::new(tr("No columns selected. Press 'c' to configure columns."))
+                    .style(Style::default().fg(Color::Yellow))
+                    .block(Block::default().title(tr("Warning")).borders(Borders::ALL));
             frame.render_widget(warning, area);
             return;
         }
@@ -200,7 +309,7 @@ impl JobsList {
         let headers: Vec<&str> = columns.iter().map(|col| col.title()).collect();
 
         // Create header cells with appropriate styling
-        let header_cells = headers.iter().enumerate().map(|(_i, &h)| {
+        let header_cells = headers.iter().map(|&h| {
             // Check if this column is in the sort list
             let is_sort_column = sort_columns.iter().any(|sc| sc.column.title() == h);
             let sort_indicator = if is_sort_column {
@@ -226,7 +335,7 @@ impl JobsList {
                     .add_modifier(Modifier::BOLD)
             };
 
-            Cell::from(format!("{}{}", h, sort_indicator)).style(header_style)
+            Cell::from(format!("{}{}", tr(h), sort_indicator)).style(header_style)
         });
 
         let header = Row::new(header_cells)
@@ -238,6 +347,10 @@ impl JobsList {
             let (job_index, group_key) = match vr {
                 VisibleRow::Group { key, rep_job_index } => (*rep_job_index, Some(key.clone())),
                 VisibleRow::Job { job_index } => (*job_index, None),
+                VisibleRow::Step {
+                    job_index,
+                    step_index,
+                } => return step_row(&self.steps[&self.jobs[*job_index].id][*step_index], columns),
             };
 
             let job = &self.jobs[job_index];
@@ -247,25 +360,26 @@ impl JobsList {
                     .get(key)
                     .map(|indices| indices.iter().any(|idx| self.selected_jobs.contains(idx)))
                     .unwrap_or(false),
-                VisibleRow::Job { job_index } => self.selected_jobs.contains(job_index),
+                VisibleRow::Job { job_index } | VisibleRow::Step { job_index, .. } => {
+                    self.selected_jobs.contains(job_index)
+                }
             };
 
-            let color = match job.state {
-                JobState::Pending => Color::Yellow,
-                JobState::Running => Color::Green,
-                JobState::Completed => Color::Blue,
-                JobState::Failed | JobState::Timeout | JobState::NodeFail | JobState::Boot => {
-                    Color::Red
-                }
-                JobState::Cancelled => Color::Magenta,
-                _ => Color::White,
+            let at_risk = group_key.is_none() && self.memory_risk.contains_key(&job.id);
+            let color = if at_risk {
+                Color::LightRed
+            } else {
+                state_color(job.state)
             };
 
-            let style = if is_selected {
+            let mut style = if is_selected {
                 Style::default().fg(color).add_modifier(Modifier::REVERSED)
             } else {
                 Style::default().fg(color)
             };
+            if at_risk {
+                style = style.add_modifier(Modifier::BOLD);
+            }
 
             // Create cells based on selected columns
             let cells: Vec<Cell> = columns
@@ -273,12 +387,8 @@ impl JobsList {
                 .map(|col| {
                     let content = match col {
                         JobColumn::Id => {
-                            if let Some(key) = &group_key {
-                                let count = self
-                                    .group_map
-                                    .get(key)
-                                    .map(|v| v.len())
-                                    .unwrap_or(1);
+                            let mut id = if let Some(key) = &group_key {
+                                let count = self.group_map.get(key).map(|v| v.len()).unwrap_or(1);
                                 let expanded = self.expanded_groups.contains(key.as_str());
                                 let marker = if expanded { "[-]" } else { "[+]" };
                                 if count > 1 {
@@ -286,9 +396,22 @@ impl JobsList {
                                 } else {
                                     job.id.clone()
                                 }
+                            } else if self.watched.contains(&job.id) {
+                                format!("{} [w]", job.id)
                             } else {
                                 job.id.clone()
+                            };
+                            if self.is_pinned(job) {
+                                id.push_str(" [p]");
                             }
+                            if let Some(used) = self
+                                .memory_risk
+                                .get(&job.id)
+                                .filter(|_| group_key.is_none())
+                            {
+                                id.push_str(&format!(" [mem {:.0}%]", used * 100.0));
+                            }
+                            id
                         }
                         JobColumn::Name => {
                             // Truncate name if too long
@@ -330,6 +453,20 @@ impl JobsList {
                             .pending_reason
                             .clone()
                             .unwrap_or_else(|| "-".to_string()),
+                        JobColumn::ExitCode => self
+                            .exits
+                            .get(&job.id)
+                            .map_or_else(|| "-".to_string(), |exit| exit.exit_code.clone()),
+                        JobColumn::Failure => self
+                            .exits
+                            .get(&job.id)
+                            .and_then(|exit| exit.failure)
+                            .map_or_else(|| "-".to_string(), |failure| failure.to_string()),
+                        JobColumn::Notes => self.notes.get(&job.id).cloned().unwrap_or_default(),
+                        JobColumn::PriorityRank => self.priority_ranks.get(&job.id).map_or_else(
+                            || "-".to_string(),
+                            |(rank, of)| format!("{}/{}", rank, of),
+                        ),
                     };
                     Cell::from(content)
                 })
@@ -380,7 +517,9 @@ impl JobsList {
                         .and_then(|indices| indices.first())
                         .and_then(|&idx| self.jobs.get(idx))
                 }
-                Some(VisibleRow::Job { job_index }) => self.jobs.get(*job_index),
+                Some(VisibleRow::Job { job_index } | VisibleRow::Step { job_index, .. }) => {
+                    self.jobs.get(*job_index)
+                }
                 None => None,
             },
             None => None,
@@ -396,12 +535,105 @@ impl JobsList {
             .collect()
     }
 
+    /// If the cursor is on an array group header, return the group key (the array's
+    /// parent job ID) and the number of member jobs
+    pub fn selected_group(&self) -> Option<(String, usize)> {
+        match self.state.selected().and_then(|i| self.visible_rows.get(i)) {
+            Some(VisibleRow::Group { key, .. }) => {
+                let count = self.group_map.get(key).map(|m| m.len()).unwrap_or(0);
+                Some((key.clone(), count))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the selection consists of exactly the members of the given group
+    pub fn selection_is_group(&self, key: &str) -> bool {
+        match self.group_map.get(key) {
+            Some(members) => {
+                self.selected_jobs.len() == members.len()
+                    && members.iter().all(|m| self.selected_jobs.contains(m))
+            }
+            None => false,
+        }
+    }
+
+    /// Move the cursor to the job with the given ID, expanding its array group if needed.
+    /// Returns false if the job is not in the list.
+    pub fn select_job_by_id(&mut self, job_id: &str) -> bool {
+        let Some(job_index) = self.jobs.iter().position(|j| j.id == job_id) else {
+            return false;
+        };
+
+        let key = self.compute_group_key(&self.jobs[job_index]);
+        if self.group_map.get(&key).is_some_and(|m| m.len() > 1)
+            && !self.expanded_groups.contains(&key)
+        {
+            self.expanded_groups.insert(key);
+            self.rebuild_groups_and_rows();
+        }
+
+        match self
+            .visible_rows
+            .iter()
+            .position(|vr| matches!(vr, VisibleRow::Job { job_index: i } if *i == job_index))
+        {
+            Some(idx) => {
+                self.state.select(Some(idx));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Job under the cursor that can be expanded into its steps: a job or step
+    /// row outside an array group
+    pub fn steps_job(&self) -> Option<String> {
+        let job_index = match self
+            .state
+            .selected()
+            .and_then(|i| self.visible_rows.get(i))?
+        {
+            VisibleRow::Job { job_index } | VisibleRow::Step { job_index, .. } => *job_index,
+            VisibleRow::Group { .. } => return None,
+        };
+        let job = &self.jobs[job_index];
+        let in_group = self
+            .group_map
+            .get(&self.compute_group_key(job))
+            .is_some_and(|members| members.len() > 1);
+        (!in_group).then(|| job.id.clone())
+    }
+
+    /// Whether the steps of a job are listed below it
+    pub fn steps_shown(&self, job_id: &str) -> bool {
+        self.steps.contains_key(job_id)
+    }
+
+    /// IDs of the jobs whose steps are listed
+    pub fn step_job_ids(&self) -> Vec<String> {
+        self.steps.keys().cloned().collect()
+    }
+
+    /// List these steps below a job, or collapse the job with `None`
+    pub fn set_steps(&mut self, job_id: &str, steps: Option<Vec<JobStep>>) {
+        match steps {
+            Some(steps) => self.steps.insert(job_id.to_string(), steps),
+            None => self.steps.remove(job_id),
+        };
+        self.rebuild_groups_and_rows();
+    }
+
     /// Toggle expand/collapse for the group under the current selection
     pub fn toggle_group_expand(&mut self) {
-        let Some(visible_idx) = self.state.selected() else { return };
+        let Some(visible_idx) = self.state.selected() else {
+            return;
+        };
         let target_key = match self.visible_rows.get(visible_idx) {
             Some(VisibleRow::Group { key, .. }) => Some(key.clone()),
-            Some(VisibleRow::Job { job_index }) => Some(self.compute_group_key(&self.jobs[*job_index])),
+            Some(VisibleRow::Job { job_index } | VisibleRow::Step { job_index, .. }) => {
+                Some(self.compute_group_key(&self.jobs[*job_index]))
+            }
             None => None,
         };
 
@@ -474,17 +706,75 @@ impl JobsList {
                 }
             }
         }
+
+        // Steps go right below their job
+        if !self.steps.is_empty() {
+            let rows = std::mem::take(&mut self.visible_rows);
+            for row in rows {
+                let job_index = match row {
+                    VisibleRow::Job { job_index } => Some(job_index),
+                    _ => None,
+                };
+                self.visible_rows.push(row);
+                let steps = job_index.and_then(|i| self.steps.get(&self.jobs[i].id));
+                if let (Some(job_index), Some(steps)) = (job_index, steps) {
+                    self.visible_rows
+                        .extend((0..steps.len()).map(|step_index| VisibleRow::Step {
+                            job_index,
+                            step_index,
+                        }));
+                }
+            }
+        }
     }
 
-    /// Compute the grouping key for a job. For array jobs like "12345_7", returns "12345".
+    /// Compute the grouping key for a job. For array jobs like "12345_7" or pending
+    /// task ranges like "12345_[8-10]", returns "12345".
     fn compute_group_key(&self, job: &Job) -> String {
         if let Some(pos) = job.id.find('_') {
             let (prefix, suffix) = job.id.split_at(pos);
             let suffix = &suffix[1..];
-            if !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()) {
+            let is_task = !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit());
+            let is_range = suffix.starts_with('[') && suffix.ends_with(']');
+            if is_task || is_range {
                 return prefix.to_string();
             }
         }
         job.id.clone()
     }
 }
+
+/// Row of a job step, below its job: step ID, name, state, elapsed time and
+/// peak memory (in the Memory column)
+fn step_row(step: &JobStep, columns: &[JobColumn]) -> Row<'static> {
+    let cells: Vec<Cell> = columns
+        .iter()
+        .map(|col| {
+            let content = match col {
+                JobColumn::Id => format!("  └ {}", step.id),
+                JobColumn::Name => step.name.clone(),
+                JobColumn::State => step.state.clone(),
+                JobColumn::Time => step.elapsed.clone(),
+                JobColumn::Memory => step.max_rss.map_or("-".to_string(), format_size),
+                _ => String::new(),
+            };
+            Cell::from(content)
+        })
+        .collect();
+    let color = step.state.parse().map_or(Color::White, state_color);
+    Row::new(cells)
+        .style(Style::default().fg(color).add_modifier(Modifier::DIM))
+        .height(1)
+}
+
+/// Color used to show a job state
+pub fn state_color(state: JobState) -> Color {
+    match state {
+        JobState::Pending => Color::Yellow,
+        JobState::Running => Color::Green,
+        JobState::Completed => Color::Blue,
+        JobState::Failed | JobState::Timeout | JobState::NodeFail | JobState::Boot => Color::Red,
+        JobState::Cancelled => Color::Magenta,
+        _ => Color::White,
+    }
+}
@@ -7,6 +7,8 @@ use ratatui::{
 };
 use std::time::Duration;
 
+use crate::i18n::{tr, trf};
+
 /// Defines the main layout of the application
 pub fn draw_main_layout(frame: &mut Frame) -> Vec<Rect> {
     let size = frame.area();
@@ -26,6 +28,31 @@ pub fn draw_main_layout(frame: &mut Frame) -> Vec<Rect> {
     vec![chunks[0], main_chunk, chunks[2]]
 }
 
+/// Split the main content area into the job list and a log pane, side by side
+/// or stacked
+pub fn split_content_area(area: Rect, side_by_side: bool) -> [Rect; 2] {
+    let direction = if side_by_side {
+        Direction::Horizontal
+    } else {
+        Direction::Vertical
+    };
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+    [chunks[0], chunks[1]]
+}
+
+/// Modes flagged in the header
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeaderModes {
+    pub dry_run: bool,
+    pub incident: bool,
+    pub read_only: bool,
+    /// A macro is being recorded
+    pub recording: bool,
+}
+
 /// Draws the application header with status information
 pub fn draw_header(
     frame: &mut Frame,
@@ -33,6 +60,7 @@ pub fn draw_header(
     status_text: &str,
     time_since_refresh: Duration,
     refresh_interval: u64,
+    modes: HeaderModes,
 ) {
     // Split the header area into title and status
     let header_chunks = Layout::default()
@@ -47,21 +75,56 @@ pub fn draw_header(
     let title = Paragraph::new(Text::from(vec![Line::from(vec![
         Span::styled("SLURMER", Style::default().fg(Color::Cyan).bold()),
         Span::raw(" - "),
-        Span::styled("Slurm Terminal UI", Style::default().fg(Color::White)),
+        Span::styled(tr("Slurm Terminal UI"), Style::default().fg(Color::White)),
     ])]))
     .block(Block::default().borders(Borders::ALL));
 
     frame.render_widget(title, header_chunks[0]);
 
     // Render the status part
-    let status_info = format!(
-        "{} | Refresh: {}s ago (auto: {}s)",
-        status_text,
-        time_since_refresh.as_secs(),
-        refresh_interval
+    let auto_refresh = if modes.incident {
+        tr("paused").to_string()
+    } else {
+        format!("{}s", refresh_interval)
+    };
+    let status_info = trf(
+        "{status} | Refresh: {ago}s ago (auto: {auto})",
+        &[
+            ("status", status_text),
+            ("ago", &time_since_refresh.as_secs().to_string()),
+            ("auto", &auto_refresh),
+        ],
     );
 
-    let status = Paragraph::new(status_info)
+    let mut spans = Vec::new();
+    if modes.incident {
+        spans.push(Span::styled(
+            tr(" INCIDENT MODE "),
+            Style::default().fg(Color::White).bg(Color::Red).bold(),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    if modes.recording {
+        spans.push(Span::styled(
+            tr("REC "),
+            Style::default().fg(Color::Red).bold(),
+        ));
+    }
+    if modes.read_only {
+        spans.push(Span::styled(
+            tr("READ-ONLY "),
+            Style::default().fg(Color::Green).bold(),
+        ));
+    }
+    if modes.dry_run {
+        spans.push(Span::styled(
+            tr("DRY-RUN "),
+            Style::default().fg(Color::Yellow).bold(),
+        ));
+    }
+    spans.push(Span::raw(status_info));
+
+    let status = Paragraph::new(Line::from(spans))
         .block(Block::default().borders(Borders::ALL))
         .style(Style::default());
 
@@ -69,35 +132,32 @@ pub fn draw_header(
 }
 
 /// Draws the application footer with help text and status
-pub fn draw_footer(frame: &mut Frame, area: Rect, job_stat: (usize, usize, usize)) {
+pub fn draw_footer(
+    frame: &mut Frame,
+    area: Rect,
+    hints: &[(&str, &str)],
+    job_stat: (usize, usize, usize),
+    tooltip: Option<(String, String)>,
+) {
     // Controls help (lower part of footer)
     let color_style = Style::default().fg(Color::Cyan);
-    let text_hashmap = [
-        ("Esc", "Quit"),
-        ("↑/↓", "Navigate"),
-        ("Space", "Select"),
-        ("Enter", "Script"),
-        ("f", "Filter"),
-        ("c", "Columns"),
-        ("v", "Log"),
-        ("a", "SelectAll"),
-        ("r", "Refresh"),
-        ("x", "Cancel"),
-    ];
-
-    let mut footer_text: Vec<Span> = text_hashmap
+
+    let mut footer_text: Vec<Span> = hints
         .iter()
         .flat_map(|(key, description)| {
             vec![
                 Span::styled(*key, color_style),
                 Span::raw(": "),
-                Span::raw(*description),
+                Span::raw(tr(description)),
                 Span::raw(" "),
             ]
         })
         .collect();
 
-    footer_text.push(Span::styled("Job Stat: ", Style::default().fg(Color::Cyan)));
+    footer_text.push(Span::styled(
+        tr("Job Stat: "),
+        Style::default().fg(Color::Cyan),
+    ));
     footer_text.push(Span::styled(
         format!("P[ {} ] ", job_stat.0),
         Style::default().fg(Color::Yellow),
@@ -107,12 +167,19 @@ pub fn draw_footer(frame: &mut Frame, area: Rect, job_stat: (usize, usize, usize
         Style::default().fg(Color::Green),
     ));
     footer_text.push(Span::styled(
-        format!("Other[ {} ]", job_stat.2),
+        format!("{}[ {} ]", tr("Other"), job_stat.2),
         Style::default().fg(Color::Blue),
     ));
 
-    let footer =
-        Paragraph::new(Line::from(footer_text)).block(Block::default().borders(Borders::ALL));
+    // A tooltip about the job under the cursor goes into the top border
+    let mut block = Block::default().borders(Borders::ALL);
+    if let Some((label, text)) = tooltip {
+        block = block.title(Line::from(vec![
+            Span::styled(format!(" {}: ", label), Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{} ", text)),
+        ]));
+    }
+    let footer = Paragraph::new(Line::from(footer_text)).block(block);
 
     frame.render_widget(footer, area);
 }
@@ -0,0 +1,270 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::users::{leaderboard, QueuedJob, UserUsage},
+    utils::get_username,
+};
+
+/// Cells of the CPU share bar
+const BAR_WIDTH: usize = 10;
+
+/// Result of a key press in the leaderboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardAction {
+    None,
+    /// Fetch the queue again
+    Refresh,
+}
+
+/// Column the users are ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UserSort {
+    Cpus,
+    Gpus,
+    Nodes,
+    Pending,
+    User,
+}
+
+impl UserSort {
+    fn next(self) -> Self {
+        match self {
+            UserSort::Cpus => UserSort::Gpus,
+            UserSort::Gpus => UserSort::Nodes,
+            UserSort::Nodes => UserSort::Pending,
+            UserSort::Pending => UserSort::User,
+            UserSort::User => UserSort::Cpus,
+        }
+    }
+}
+
+/// The current queue added up per user: what their running jobs hold and what
+/// they have pending, in all partitions or one
+pub struct LeaderboardView {
+    jobs: Vec<QueuedJob>,
+    /// Every partition of the queue, for cycling the filter
+    partitions: Vec<String>,
+    /// Only jobs of this partition count
+    partition: Option<String>,
+    sort: UserSort,
+    /// Error from the last scontrol
+    error: Option<String>,
+    pub loaded: bool,
+    state: TableState,
+    user: String,
+}
+
+impl LeaderboardView {
+    pub fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            partitions: Vec::new(),
+            partition: None,
+            sort: UserSort::Cpus,
+            error: None,
+            loaded: false,
+            state: TableState::default(),
+            user: get_username(),
+        }
+    }
+
+    /// Show a freshly fetched queue, keeping the cursor and the partition filter
+    pub fn set_jobs(&mut self, result: color_eyre::Result<Vec<QueuedJob>>) {
+        match result {
+            Ok(jobs) => {
+                let mut partitions: Vec<String> = jobs
+                    .iter()
+                    .flat_map(|job| job.partitions.iter().cloned())
+                    .collect();
+                partitions.sort();
+                partitions.dedup();
+                self.partitions = partitions;
+                self.jobs = jobs;
+                self.error = None;
+                self.clamp_selection();
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.ranked().len();
+        let selected = self
+            .state
+            .selected()
+            .unwrap_or(0)
+            .min(count.saturating_sub(1));
+        self.state.select((count > 0).then_some(selected));
+    }
+
+    /// Users of the partition filter in the order of the sort column
+    fn ranked(&self) -> Vec<UserUsage> {
+        let mut users = leaderboard(&self.jobs, self.partition.as_deref());
+        users.sort_by(|a, b| {
+            let key = |u: &UserUsage| match self.sort {
+                UserSort::Cpus => (u.cpus, u.gpus),
+                UserSort::Gpus => (u.gpus, u.cpus),
+                UserSort::Nodes => (u.nodes, u.cpus),
+                UserSort::Pending => (u.pending_jobs, u.pending_cpus),
+                UserSort::User => (0, 0),
+            };
+            key(b).cmp(&key(a)).then_with(|| a.user.cmp(&b.user))
+        });
+        users
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> LeaderboardAction {
+        let last = self.ranked().len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Char('s') => self.sort = self.sort.next(),
+            KeyCode::Char('p') => {
+                // All partitions, then each in turn
+                self.partition = match &self.partition {
+                    None => self.partitions.first().cloned(),
+                    Some(current) => self
+                        .partitions
+                        .iter()
+                        .skip_while(|p| *p != current)
+                        .nth(1)
+                        .cloned(),
+                };
+                self.clamp_selection();
+            }
+            KeyCode::Char('r') => return LeaderboardAction::Refresh,
+            _ => {}
+        }
+        LeaderboardAction::None
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let title = match &self.partition {
+            Some(partition) => trf("Users in {partition}", &[("partition", partition)]),
+            None => tr("Users in all partitions").to_string(),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(
+                Line::from(tr(" ↑/↓: Navigate | s: Sort | p: Partition | r: Refresh ")).centered(),
+            )
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("scontrol failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            (None, true) if self.jobs.is_empty() => {
+                Some(("No running or pending jobs".to_string(), Color::Gray))
+            }
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let users = self.ranked();
+        let total_cpus = users.iter().map(|u| u.cpus).sum::<u32>().max(1);
+        let columns = [
+            ("User", Some(UserSort::User)),
+            ("Running", None),
+            ("CPUs", Some(UserSort::Cpus)),
+            ("CPU share", None),
+            ("GPUs", Some(UserSort::Gpus)),
+            ("Nodes", Some(UserSort::Nodes)),
+            ("Pending", Some(UserSort::Pending)),
+            ("Pending CPUs", None),
+        ];
+        let header = Row::new(columns.iter().map(|(name, sort)| {
+            if *sort == Some(self.sort) {
+                // Users go by name, the counts largest first
+                let arrow = if self.sort == UserSort::User {
+                    "▲"
+                } else {
+                    "▼"
+                };
+                format!("{} {}", name, arrow)
+            } else {
+                name.to_string()
+            }
+        }))
+        .style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows: Vec<Row> = users
+            .iter()
+            .map(|usage| {
+                let style = if usage.user == self.user {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(usage.user.clone()),
+                    Cell::from(usage.running_jobs.to_string()),
+                    Cell::from(usage.cpus.to_string()),
+                    Cell::from(share_bar(usage.cpus as f64 / total_cpus as f64)),
+                    Cell::from(usage.gpus.to_string()),
+                    Cell::from(usage.nodes.to_string()),
+                    Cell::from(usage.pending_jobs.to_string()),
+                    Cell::from(usage.pending_cpus.to_string()),
+                ])
+                .style(style)
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(9),
+                Constraint::Length(8),
+                Constraint::Length(BAR_WIDTH as u16 + 6),
+                Constraint::Length(8),
+                Constraint::Length(9),
+                Constraint::Length(11),
+                Constraint::Length(14),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
+
+/// Bar of a user's share of the running CPUs: red from a half, yellow from a quarter
+fn share_bar(share: f64) -> Line<'static> {
+    let filled = ((share * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let color = match share {
+        s if s >= 0.5 => Color::Red,
+        s if s >= 0.25 => Color::Yellow,
+        _ => Color::Green,
+    };
+    Line::from(vec![
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "░".repeat(BAR_WIDTH - filled),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(format!(" {:>3.0}%", share * 100.0)),
+    ])
+}
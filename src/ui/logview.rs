@@ -8,9 +8,17 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-use std::{collections::HashMap, iter::once, path::PathBuf, process::Command, time::Duration};
+use std::{collections::HashMap, path::PathBuf, process::Command, time::Duration};
 
-use crate::utils::file_watcher::{FileWatcherError, FileWatcherHandle};
+use crate::utils::{
+    ansi,
+    file_watcher::{FileWatcherError, FileWatcherHandle},
+    step_attach::{StepAttach, StepOutput},
+    truncate_front,
+};
+
+/// Keep at most this much streamed `sattach` output per tab
+const MAX_ATTACH_BYTES: usize = 4 * 1024 * 1024;
 
 /// Type of log to view
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -49,6 +57,17 @@ pub struct LogView {
     refresh_interval: Duration,
     /// Indicates the status of the current log file
     file_status: LogFileStatus,
+    /// Live `sattach` stream used instead of tailing the log files
+    attach: Option<StepAttach>,
+    /// Output received from the attached step
+    attach_stdout: String,
+    attach_stderr: String,
+    /// Short note shown in the title (e.g. why attaching failed)
+    notice: Option<String>,
+    /// Keep the view on the newest output, like `tail -f`
+    follow: bool,
+    /// Render ANSI colors in the output; when off the escape codes are removed
+    colors: bool,
 }
 
 /// Status of the log file being watched
@@ -63,7 +82,7 @@ enum LogFileStatus {
 }
 
 impl LogView {
-    pub fn new() -> Self {
+    pub fn new(strip_ansi: bool) -> Self {
         Self {
             visible: false,
             job_id: None,
@@ -76,6 +95,12 @@ impl LogView {
             file_receiver: None,
             refresh_interval: Duration::from_secs(2),
             file_status: LogFileStatus::NotFound,
+            attach: None,
+            attach_stdout: String::new(),
+            attach_stderr: String::new(),
+            notice: None,
+            follow: true,
+            colors: !strip_ansi,
         }
     }
 
@@ -88,6 +113,7 @@ impl LogView {
     /// Hide the log view
     pub fn hide(&mut self) {
         self.visible = false;
+        self.detach();
         // Stop watching files when hiding the view
         if let Some(watcher) = &mut self.file_watcher {
             watcher.set_file_path(None);
@@ -96,11 +122,13 @@ impl LogView {
 
     /// Change the job being viewed
     pub fn change_job(&mut self, job_id: String) {
+        self.detach();
         self.job_id = Some(job_id);
         self.stdout_path = None;
         self.stderr_path = None;
         // self.content = String::new();
         self.scroll_position = 0;
+        self.follow = true;
         self.file_status = LogFileStatus::NotFound;
 
         // Fetch the log file paths
@@ -121,7 +149,59 @@ impl LogView {
     pub fn toggle_tab(&mut self) {
         self.current_tab.toggle();
         self.scroll_position = 0;
-        self.update_watched_file();
+        self.follow = true;
+        if self.attach.is_some() {
+            self.content = self.attach_buffer().clone();
+        } else {
+            self.update_watched_file();
+        }
+    }
+
+    /// Switch between tailing the log files and streaming the newest running
+    /// step of the job through `sattach`
+    pub fn toggle_attach(&mut self) {
+        if self.attach.is_some() {
+            self.detach();
+            self.update_watched_file();
+            return;
+        }
+
+        let Some(job_id) = self.job_id.clone() else {
+            return;
+        };
+        let Some(step_id) = find_running_step(&job_id) else {
+            self.notice = Some("no running step to attach to".to_string());
+            return;
+        };
+
+        match StepAttach::spawn(&step_id) {
+            Ok(attach) => {
+                if let Some(watcher) = &mut self.file_watcher {
+                    watcher.set_file_path(None);
+                }
+                self.attach = Some(attach);
+                self.attach_stdout.clear();
+                self.attach_stderr.clear();
+                self.content.clear();
+                self.scroll_position = 0;
+                self.notice = None;
+            }
+            Err(e) => self.notice = Some(format!("failed to run sattach: {}", e)),
+        }
+    }
+
+    /// Stop streaming from `sattach`, if attached
+    fn detach(&mut self) {
+        self.attach = None;
+        self.notice = None;
+    }
+
+    /// Streamed output for the current tab
+    fn attach_buffer(&self) -> &String {
+        match self.current_tab {
+            LogTab::StdOut => &self.attach_stdout,
+            LogTab::StdErr => &self.attach_stderr,
+        }
     }
 
     /// Update the file being watched based on job_id and current_tab
@@ -164,11 +244,13 @@ impl LogView {
         //         .map_or(true, |instant| instant.elapsed() >= self.refresh_interval)
         // };
 
-        if self.file_receiver.is_some() {
-            let receiver = self.file_receiver.as_ref().unwrap();
-
+        if let Some(receiver) = self.file_receiver.clone() {
             // Check for new content from the file watcher
             while let Ok(result) = receiver.try_recv() {
+                // File updates are ignored while streaming from sattach
+                if self.attach.is_some() {
+                    continue;
+                }
                 match result {
                     Ok(content) => {
                         // if !content.is_empty() {
@@ -178,6 +260,7 @@ impl LogView {
                         //     // Got empty content but file exists, keep waiting
                         //     self.file_status = LogFileStatus::Waiting;
                         // }
+                        self.keep_view(&content);
                         self.content = content;
                     }
                     Err(e) => {
@@ -189,6 +272,46 @@ impl LogView {
 
             // self.last_refresh = Some(Instant::now());
         }
+
+        if let Some(attach) = &mut self.attach {
+            for output in attach.drain() {
+                let (buffer, text) = match output {
+                    StepOutput::Stdout(text) => (&mut self.attach_stdout, text),
+                    StepOutput::Stderr(text) => (&mut self.attach_stderr, text),
+                };
+                buffer.push_str(&text);
+                truncate_front(buffer, MAX_ATTACH_BYTES);
+            }
+            if !attach.is_running() && self.notice.is_none() {
+                self.notice = Some("sattach exited".to_string());
+            }
+            let content = self.attach_buffer().clone();
+            self.keep_view(&content);
+            self.content = content;
+        }
+    }
+
+    /// Before showing `new` content: stay at the bottom when following, and
+    /// otherwise keep the visible lines in place as output is appended
+    fn keep_view(&mut self, new: &str) {
+        if self.follow {
+            self.scroll_position = 0;
+        } else {
+            // The scroll position counts lines from the bottom
+            let added = new
+                .lines()
+                .count()
+                .saturating_sub(self.content.lines().count());
+            self.scroll_position += added;
+        }
+    }
+
+    /// Toggle following the newest output; following jumps to the bottom
+    pub fn toggle_follow(&mut self) {
+        self.follow = !self.follow;
+        if self.follow {
+            self.scroll_position = 0;
+        }
     }
 
     /// Scroll the log view up
@@ -196,6 +319,7 @@ impl LogView {
         if self.scroll_position > 0 {
             self.scroll_position -= 1;
         }
+        self.follow = self.scroll_position == 0;
     }
 
     /// Scroll the log view down
@@ -207,12 +331,14 @@ impl LogView {
         if self.scroll_position < line_count.saturating_sub(1) {
             self.scroll_position += 1;
         }
+        self.follow = self.scroll_position == 0;
     }
 
     /// Page up in the log view
     pub fn page_up(&mut self) {
         // Move up by a page (10 lines)
         self.scroll_position = self.scroll_position.saturating_sub(10);
+        self.follow = self.scroll_position == 0;
     }
 
     /// Page down in the log view
@@ -225,6 +351,7 @@ impl LogView {
         } else {
             line_count.saturating_sub(1)
         };
+        self.follow = self.scroll_position == 0;
     }
 
     /// Render the log view
@@ -232,20 +359,41 @@ impl LogView {
         if !self.visible {
             return;
         }
+        self.draw(
+            frame,
+            area,
+            " [↑/↓] Scroll | [Shift+↑/↓] Toggle Job | [o] Toggle stdout/stderr | [f] Follow | [c] Colors | [t] Attach/detach step | [q] Close ",
+        );
+    }
 
+    /// Render as the log pane of the split layout, which takes no keys
+    pub fn render_pane(&self, frame: &mut Frame, area: Rect) {
+        self.draw(frame, area, " [L] Layout ");
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect, help_text: &str) {
         let log_area = area;
         // Clear the whole area to avoid residual characters when lines shrink
         frame.render_widget(Clear, log_area);
 
-        let title = match &self.job_id {
+        let mut title = match &self.job_id {
             Some(id) => format!("Job {} - {}", id, self.current_tab.as_str()),
             None => format!("Log View - {}", self.current_tab.as_str()),
         };
-
-        let help_text =
-            " [↑/↓] Scroll | [Shift+↑/↓] Toggle Job | [o] Toggle stdout/stderr | [q] Close ";
+        if let Some(attach) = &self.attach {
+            title.push_str(&format!(" (sattach {})", attach.step_id));
+        }
+        title.push_str(if self.follow {
+            " [following]"
+        } else {
+            " [paused]"
+        });
+        if let Some(notice) = &self.notice {
+            title.push_str(&format!(" - {}", notice));
+        }
 
         let log_text = match (self.file_status, self.content.is_empty()) {
+            _ if self.attach.is_some() => self.content.clone(),
             (LogFileStatus::NotFound, _) => format!(
                 "No {} log file found for job {}",
                 self.current_tab.as_str(),
@@ -269,7 +417,7 @@ impl LogView {
             inner_height,
             inner_width,
             self.scroll_position,
-            false,
+            self.colors,
         );
         // eprintln!("fit_text: {}", log_text);
 
@@ -289,21 +437,37 @@ impl LogView {
         frame.render_widget(Clear, inner_area);
 
         // Finally render text without a block onto inner area to fill precisely
-        let content_paragraph = Paragraph::new(
-            Self::fit_text(&log_text, inner_height, inner_width, self.scroll_position, false),
-        )
+        let content_paragraph = Paragraph::new(Self::fit_text(
+            &log_text,
+            inner_height,
+            inner_width,
+            self.scroll_position,
+            self.colors,
+        ))
         .style(Style::default().fg(Color::White).bg(Color::Black))
         .wrap(Wrap { trim: false });
 
         frame.render_widget(content_paragraph, inner_area);
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent) -> () {
+    pub fn handle_key(&mut self, key: KeyEvent) {
         match (key.modifiers, key.code) {
             (_, KeyCode::Char('o')) => {
                 // Toggle between stdout and stderr logs
                 self.toggle_tab();
             }
+            (_, KeyCode::Char('t')) => {
+                // Stream the running step through sattach instead of the log file
+                self.toggle_attach();
+            }
+            (_, KeyCode::Char('f')) => {
+                // Follow the newest output or pause where the view is
+                self.toggle_follow();
+            }
+            (_, KeyCode::Char('c')) => {
+                // Render or strip ANSI colors
+                self.colors = !self.colors;
+            }
             (_, KeyCode::Char('q')) => {
                 // Close the log view
                 self.hide();
@@ -330,137 +494,260 @@ impl LogView {
         }
     }
 
-    fn fit_text(s: &str, lines: usize, cols: usize, offset: usize, _wrap: bool) -> Text {
-        // Process text by handling carriage returns
-        let processed_lines: Vec<String> = s
-            .lines()
-            .map(|line| {
-                // For each line, if it contains carriage returns, keep only the content after the last one
-                if line.contains('\r') {
-                    let parts: Vec<&str> = line.split('\r').collect();
-                    parts.last().unwrap_or(&"").to_string()
-                } else {
-                    line.to_string()
-                }
-            })
-            .collect();
-
-        // Join the processed lines back together
-        let processed_text = processed_lines.join("\n");
-
-        // Process the clean text (without intermediate carriage returns)
-        let lines_iter = processed_text.lines();
-
-        // Create the iterator for processing text lines
-        let line_spans = lines_iter
-            .rev()
-            .skip(offset)
-            .flat_map(|l| {
-                let chunks = Self::chunked_string(l, cols, cols.saturating_sub(2));
-                chunks
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, chunk)| {
-                        if i == 0 {
-                            Line::raw(chunk.to_string())
-                        } else {
-                            Line::default().spans(vec![
-                                Span::styled("↪ ", Style::default().add_modifier(Modifier::DIM)),
-                                Span::raw(chunk.to_string()),
-                            ])
-                        }
-                    })
-                    .rev()
-            })
-            .take(lines);
+    /// Rows of `s` that fill `lines` x `cols`, ending `offset` lines above
+    /// the bottom, with long lines wrapped. With `colors` ANSI escape codes
+    /// are rendered as styles, otherwise they are removed.
+    fn fit_text(s: &str, lines: usize, cols: usize, offset: usize, colors: bool) -> Text<'static> {
+        let all: Vec<&str> = s.lines().collect();
+        let end = all.len().saturating_sub(offset);
+        // Every line takes at least one row, so earlier lines cannot be visible
+        let start = end.saturating_sub(lines);
+
+        // Colors carry over from earlier lines until they are reset
+        let mut style = Style::default();
+        if colors {
+            for line in &all[..start] {
+                ansi::skip_line(line, &mut style);
+            }
+        }
 
-        // Collect and build the final Text widget
-        let collected_lines: Vec<Line> = line_spans.collect();
+        let mut rows: Vec<Vec<Span<'static>>> = Vec::new();
+        for line in &all[start..end] {
+            // Keep only the content after the last carriage return, like a terminal
+            let (overwritten, line) = line.rsplit_once('\r').unwrap_or(("", line));
+            let spans = if colors {
+                ansi::skip_line(overwritten, &mut style);
+                ansi::parse_line(line, &mut style)
+            } else {
+                vec![Span::raw(ansi::strip(line))]
+            };
+            for (i, chunk) in chunk_spans(spans, cols, cols.saturating_sub(2))
+                .into_iter()
+                .enumerate()
+            {
+                let mut row = Vec::new();
+                if i > 0 {
+                    row.push(Span::styled(
+                        "↪ ",
+                        Style::default().add_modifier(Modifier::DIM),
+                    ));
+                }
+                row.extend(chunk);
+                rows.push(row);
+            }
+        }
 
         // Right-pad each line to full width to ensure clearing of previous longer content
-        let padded: Vec<Line> = collected_lines
+        let skip = rows.len().saturating_sub(lines);
+        let padded: Vec<Line> = rows
             .into_iter()
-            .map(|line| {
-                let mut spans = line.spans;
-                let current_width: usize = spans
-                    .iter()
-                    .map(|sp| sp.content.chars().count())
-                    .sum();
+            .skip(skip)
+            .map(|mut spans| {
+                let current_width: usize = spans.iter().map(|sp| sp.content.chars().count()).sum();
                 if current_width < cols {
-                    let pad_len = cols - current_width;
-                    let pad = " ".repeat(pad_len);
-                    spans.push(Span::raw(pad));
+                    spans.push(Span::raw(" ".repeat(cols - current_width)));
                 }
                 Line::default().spans(spans)
             })
             .collect();
 
-        Text::from(padded.into_iter().rev().collect::<Vec<Line>>())
+        Text::from(padded)
     }
 
-    fn chunked_string(s: &str, first_chunk_size: usize, chunk_size: usize) -> Vec<&str> {
-        let stepped_indices = s
-            .char_indices()
-            .map(|(i, _)| i)
-            .enumerate()
-            .filter(|&(i, _)| {
-                if i > (first_chunk_size) {
-                    chunk_size > 0 && (i - first_chunk_size) % chunk_size == 0
-                } else {
-                    i == 0 || i == first_chunk_size
-                }
-            })
-            .map(|(_, e)| e)
-            .collect::<Vec<_>>();
-        let windows = stepped_indices.windows(2).collect::<Vec<_>>();
+    /// Fetch the stdout and stderr paths for the current job, from
+    /// slurmctld or, once the job has finished, from accounting
+    fn fetch_log_paths(&mut self) {
+        let Some(job_id) = &self.job_id else {
+            self.file_status = LogFileStatus::NotFound;
+            return;
+        };
+        let Some(fields) = scontrol_job_fields(job_id).or_else(|| sacct_job_fields(job_id)) else {
+            self.file_status = LogFileStatus::Error;
+            return;
+        };
+
+        self.stdout_path = log_path(&fields, "StdOut");
+        self.stderr_path = log_path(&fields, "StdErr");
 
-        let iter = windows.iter().map(|w| &s[w[0]..w[1]]);
-        let last_index = *stepped_indices.last().unwrap_or(&0);
-        iter.chain(once(&s[last_index..])).collect()
+        // Check if we have a valid path for the current tab
+        let has_path = match self.current_tab {
+            LogTab::StdOut => self.stdout_path.is_some(),
+            LogTab::StdErr => self.stderr_path.is_some(),
+        };
+        self.file_status = if has_path {
+            LogFileStatus::Waiting
+        } else {
+            LogFileStatus::NotFound
+        };
     }
+}
 
-    /// Fetch the stdout and stderr paths for the current job
-    fn fetch_log_paths(&mut self) {
-        if let Some(job_id) = &self.job_id {
-            let output = Command::new("scontrol")
-                .args(["show", "job", job_id, "-o"])
-                .output();
-
-            if let Ok(output) = output {
-                if output.status.success() {
-                    let output_str = String::from_utf8_lossy(&output.stdout);
-                    let key_value_pairs = parse_scontrol_output(&output_str);
-
-                    self.stdout_path = key_value_pairs.get("StdOut").map(|s| s.to_string());
-                    self.stderr_path = key_value_pairs.get("StdErr").map(|s| s.to_string());
-
-                    // Check if we have valid paths for the current tab
-                    let has_path = match self.current_tab {
-                        LogTab::StdOut => {
-                            self.stdout_path.is_some()
-                                && !self.stdout_path.as_ref().unwrap().is_empty()
-                        }
-                        LogTab::StdErr => {
-                            self.stderr_path.is_some()
-                                && !self.stderr_path.as_ref().unwrap().is_empty()
-                        }
-                    };
-
-                    if has_path {
-                        self.file_status = LogFileStatus::Waiting;
-                    } else {
-                        self.file_status = LogFileStatus::NotFound;
-                    }
-                } else {
-                    self.file_status = LogFileStatus::Error;
-                }
-            } else {
-                self.file_status = LogFileStatus::Error;
+/// Fields of a job known to slurmctld, as printed by `scontrol show job`
+fn scontrol_job_fields(job_id: &str) -> Option<HashMap<String, String>> {
+    let output = Command::new("scontrol")
+        .args(["show", "job", job_id, "-o"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_scontrol_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Fields of a finished job from `sacct`, renamed to their `scontrol` names
+fn sacct_job_fields(job_id: &str) -> Option<HashMap<String, String>> {
+    let output = Command::new("sacct")
+        .args(["--parsable2", "--allocations", "--format=ALL"])
+        .arg(format!("--jobs={}", job_id))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut lines = text.lines();
+    let (header, row) = (lines.next()?, lines.next()?);
+    let row: HashMap<&str, &str> = header.split('|').zip(row.split('|')).collect();
+
+    let mut fields = HashMap::new();
+    for (sacct, scontrol) in [
+        ("JobIDRaw", "JobId"),
+        ("JobName", "JobName"),
+        ("User", "UserId"),
+        ("WorkDir", "WorkDir"),
+        ("StdOut", "StdOut"),
+        ("StdErr", "StdErr"),
+        ("NodeList", "BatchHost"),
+    ] {
+        if let Some(value) = row.get(sacct).filter(|value| !value.is_empty()) {
+            fields.insert(scontrol.to_string(), value.to_string());
+        }
+    }
+    // Array tasks are listed as "<array job>_<task>"
+    if let Some((array_job, task)) = row.get("JobID").and_then(|id| id.split_once('_')) {
+        fields.insert("ArrayJobId".to_string(), array_job.to_string());
+        fields.insert("ArrayTaskId".to_string(), task.to_string());
+    }
+    Some(fields)
+}
+
+/// Path of the job's `StdOut` or `StdErr` with its filename patterns expanded.
+/// Without a recorded path, output goes to sbatch's default `slurm-%j.out`
+/// (`slurm-%A_%a.out` for array tasks) in the working directory; stderr
+/// shares the stdout file unless it was redirected.
+fn log_path(fields: &HashMap<String, String>, key: &str) -> Option<String> {
+    let pattern = match fields.get(key).filter(|path| !path.is_empty()) {
+        Some(path) => path.as_str(),
+        None if key == "StdErr" => return log_path(fields, "StdOut"),
+        None if fields.contains_key("ArrayTaskId") => "slurm-%A_%a.out",
+        None => "slurm-%j.out",
+    };
+    let path = expand_filename_pattern(pattern, fields);
+    if path.starts_with('/') {
+        return Some(path);
+    }
+    let work_dir = fields.get("WorkDir")?;
+    Some(
+        PathBuf::from(work_dir)
+            .join(path)
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// Expand the filename patterns of `sbatch --output` (`%j`, `%x`, `%A_%a`,
+/// ...) with the fields of the job. A number after `%` zero-pads the value,
+/// e.g. `%4a`; unknown patterns are kept as they are.
+fn expand_filename_pattern(pattern: &str, fields: &HashMap<String, String>) -> String {
+    let field = |key: &str| fields.get(key).map(String::as_str);
+    let job_id = field("JobId").unwrap_or_default();
+    let mut expanded = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            expanded.push(c);
+            continue;
+        }
+        let mut width = String::new();
+        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+            width.push(digit);
+        }
+        let Some(code) = chars.next() else {
+            expanded.push('%');
+            expanded.push_str(&width);
+            break;
+        };
+        let value = match code {
+            '%' => Some("%".to_string()),
+            'j' | 'J' => Some(job_id.to_string()),
+            'A' => Some(field("ArrayJobId").unwrap_or(job_id).to_string()),
+            'a' => Some(field("ArrayTaskId").unwrap_or("4294967294").to_string()),
+            'x' => field("JobName").map(str::to_string),
+            // UserId reads "name(uid)"
+            'u' => field("UserId").map(|user| user.split('(').next().unwrap_or(user).to_string()),
+            'N' => field("BatchHost").map(str::to_string),
+            'n' | 't' => Some("0".to_string()),
+            's' => Some("batch".to_string()),
+            _ => None,
+        };
+        match value {
+            Some(value) => {
+                let width = width.parse().unwrap_or(0);
+                expanded.push_str(&format!("{:0>width$}", value, width = width));
+            }
+            None => {
+                expanded.push('%');
+                expanded.push_str(&width);
+                expanded.push(code);
+            }
+        }
+    }
+    expanded
+}
+
+/// Find the newest running srun step of a job (e.g. "12345.2") with `squeue --steps`
+fn find_running_step(job_id: &str) -> Option<String> {
+    let output = Command::new("squeue")
+        .args(["-h", "--steps", "-j", job_id, "-o", "%i"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Skip the batch and extern steps, which sattach cannot attach to
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let step_id = line.trim();
+            let (_, step) = step_id.rsplit_once('.')?;
+            step.parse::<u32>().ok().map(|n| (n, step_id.to_string()))
+        })
+        .max_by_key(|(n, _)| *n)
+        .map(|(_, step_id)| step_id)
+}
+
+/// Split styled spans into rows of `first` characters, then of `rest` characters
+fn chunk_spans(spans: Vec<Span<'static>>, first: usize, rest: usize) -> Vec<Vec<Span<'static>>> {
+    let mut rows = vec![Vec::new()];
+    let mut room = first;
+    for span in spans {
+        let mut chars = span.content.chars().peekable();
+        while chars.peek().is_some() {
+            if room == 0 {
+                rows.push(Vec::new());
+                room = rest.max(1);
+            }
+            let piece: String = chars.by_ref().take(room).collect();
+            room -= piece.chars().count();
+            if let Some(row) = rows.last_mut() {
+                row.push(Span::styled(piece, span.style));
             }
-        } else {
-            self.file_status = LogFileStatus::NotFound;
         }
     }
+    rows
 }
 
 fn parse_scontrol_output(output: &str) -> HashMap<String, String> {
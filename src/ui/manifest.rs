@@ -0,0 +1,380 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use std::path::{Path, PathBuf};
+
+use crate::{config::JobTemplate, i18n::tr, manifest::Manifest, utils::expand_home};
+
+/// A manifest confirmed for submission
+#[derive(Debug, Clone)]
+pub struct ManifestRequest {
+    pub path: String,
+    pub template: String,
+    /// One job array instead of a job per row
+    pub array: bool,
+    pub rows: usize,
+}
+
+/// Action to take after handling a key in the bulk submission panel
+#[derive(Debug, Clone)]
+pub enum ManifestAction {
+    None,
+    Close,
+    /// Choose the manifest in the file browser, starting in this directory
+    Browse(PathBuf),
+    Submit(ManifestRequest),
+}
+
+/// Inputs of the panel, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Path,
+    Template,
+    Mode,
+}
+
+/// Popup submitting a job per row of a manifest file, built from a template,
+/// and reporting the job each row became
+pub struct ManifestPanel {
+    pub visible: bool,
+    templates: Vec<JobTemplate>,
+    path: String,
+    template: usize,
+    array: bool,
+    focus: Focus,
+    /// The manifest as last read, or why it could not be
+    manifest: Option<Result<Manifest, String>>,
+    /// Per row: the submitted job ID, or why the row cannot be or was not submitted
+    outcomes: Vec<Option<Result<String, String>>>,
+    /// Whether `outcomes` are the results of a submission rather than a check
+    submitted: bool,
+    /// Feedback shown above the help text, such as the summary of a submission
+    message: Option<String>,
+    state: TableState,
+}
+
+impl ManifestPanel {
+    pub fn new(templates: Vec<JobTemplate>) -> Self {
+        Self {
+            visible: false,
+            templates,
+            path: String::new(),
+            template: 0,
+            array: false,
+            focus: Focus::Path,
+            manifest: None,
+            outcomes: Vec::new(),
+            submitted: false,
+            message: None,
+            state: TableState::default(),
+        }
+    }
+
+    /// Show the panel; the manifest entered last time is read again
+    pub fn show(&mut self, partitions: &[String]) {
+        self.focus = Focus::Path;
+        self.message = None;
+        self.reload(partitions);
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Use a manifest chosen in the file browser
+    pub fn set_path(&mut self, path: &Path, partitions: &[String]) {
+        self.path = path.to_string_lossy().into_owned();
+        self.focus = Focus::Template;
+        self.reload(partitions);
+    }
+
+    /// Show the outcome of submitting the rows, with a summary
+    pub fn set_results(&mut self, results: Vec<Result<String, String>>) {
+        let submitted = results.iter().filter(|r| r.is_ok()).count();
+        let mut summary = format!("Submitted {} of {} rows", submitted, results.len());
+        let ids: Vec<&str> = results.iter().filter_map(|r| r.as_deref().ok()).collect();
+        if let (Some(first), Some(last)) = (ids.first(), ids.last()) {
+            summary.push_str(&format!(" as jobs {} to {}", first, last));
+        }
+        self.message = Some(summary);
+        self.outcomes = results.into_iter().map(Some).collect();
+        self.submitted = true;
+    }
+
+    /// Read the manifest and check every row against the chosen template
+    fn reload(&mut self, partitions: &[String]) {
+        self.outcomes.clear();
+        self.submitted = false;
+        if self.path.trim().is_empty() {
+            self.manifest = None;
+            return;
+        }
+        let manifest = Manifest::read(self.path.trim()).map_err(|e| e.to_string());
+        if let (Ok(manifest), Some(template)) = (&manifest, self.templates.get(self.template)) {
+            self.outcomes = if self.array {
+                let error = manifest.plan_array(template, partitions).err();
+                manifest
+                    .rows
+                    .iter()
+                    .map(|_| error.clone().map(Err))
+                    .collect()
+            } else {
+                manifest
+                    .plan_jobs(template, partitions)
+                    .into_iter()
+                    .map(|planned| planned.err().map(Err))
+                    .collect()
+            };
+        }
+        self.state.select(Some(0));
+        self.manifest = Some(manifest);
+    }
+
+    /// Directory the file browser starts in
+    fn browse_dir(&self) -> PathBuf {
+        let path = PathBuf::from(expand_home(self.path.trim()));
+        match path.parent() {
+            Some(dir) if dir.is_dir() => dir.to_path_buf(),
+            _ => std::env::current_dir().unwrap_or_default(),
+        }
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent, partitions: &[String]) -> ManifestAction {
+        let rows = match &self.manifest {
+            Some(Ok(manifest)) => manifest.rows.len(),
+            _ => 0,
+        };
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc => return ManifestAction::Close,
+            KeyCode::Up | KeyCode::BackTab => {
+                self.leave_path(partitions);
+                self.focus = match self.focus {
+                    Focus::Path => Focus::Mode,
+                    Focus::Template => Focus::Path,
+                    Focus::Mode => Focus::Template,
+                };
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.leave_path(partitions);
+                self.focus = match self.focus {
+                    Focus::Path => Focus::Template,
+                    Focus::Template => Focus::Mode,
+                    Focus::Mode => Focus::Path,
+                };
+            }
+            KeyCode::PageUp => self.state.select(Some(selected.saturating_sub(10))),
+            KeyCode::PageDown => {
+                self.state
+                    .select(Some((selected + 10).min(rows.saturating_sub(1))));
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let forward = key.code == KeyCode::Right;
+                match self.focus {
+                    Focus::Path => return ManifestAction::None,
+                    Focus::Template if !self.templates.is_empty() => {
+                        let n = self.templates.len();
+                        self.template = if forward {
+                            (self.template + 1) % n
+                        } else {
+                            (self.template + n - 1) % n
+                        };
+                    }
+                    Focus::Template => {}
+                    Focus::Mode => self.array = !self.array,
+                }
+                self.reload(partitions);
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return ManifestAction::Browse(self.browse_dir());
+            }
+            KeyCode::Enter => return self.submit(partitions),
+            KeyCode::Char(c) if self.focus == Focus::Path => {
+                self.path.push(c);
+            }
+            KeyCode::Backspace if self.focus == Focus::Path => {
+                self.path.pop();
+            }
+            _ => {}
+        }
+        ManifestAction::None
+    }
+
+    /// Read the manifest again when leaving its field, so typing a path does not
+    /// read every partial one
+    fn leave_path(&mut self, partitions: &[String]) {
+        if self.focus == Focus::Path {
+            self.reload(partitions);
+        }
+    }
+
+    /// Check the rows once more and ask for the submission when all can go
+    fn submit(&mut self, partitions: &[String]) -> ManifestAction {
+        self.reload(partitions);
+        let Some(template) = self.templates.get(self.template) else {
+            self.message = Some("No templates configured ([[templates]] in config.toml)".into());
+            return ManifestAction::None;
+        };
+        let rows = match &self.manifest {
+            None => {
+                self.message = Some("Enter the path of a CSV, TSV or JSON manifest".to_string());
+                return ManifestAction::None;
+            }
+            Some(Err(e)) => {
+                self.message = Some(e.clone());
+                return ManifestAction::None;
+            }
+            Some(Ok(manifest)) => manifest.rows.len(),
+        };
+        let failing = self.outcomes.iter().filter(|o| o.is_some()).count();
+        if failing > 0 {
+            self.message = Some(format!("Fix the {} highlighted rows first", failing));
+            return ManifestAction::None;
+        }
+        self.message = None;
+        ManifestAction::Submit(ManifestRequest {
+            path: self.path.trim().to_string(),
+            template: template.name.clone(),
+            array: self.array,
+            rows,
+        })
+    }
+
+    /// Render the panel
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(tr("Bulk Submission")).centered())
+            .borders(Borders::NONE)
+            .style(Style::default().bg(Color::Black));
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3), // Manifest
+                Constraint::Length(3), // Template and mode
+                Constraint::Min(3),    // Rows
+                Constraint::Length(2), // Message
+                Constraint::Length(3), // Help text
+            ])
+            .split(area);
+        let options = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        let style = |focus: Focus| {
+            if self.focus == focus {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            }
+        };
+        let path_title = match &self.manifest {
+            Some(Err(e)) => format!("Manifest ✗ {}", e),
+            _ => "Manifest (CSV, TSV or JSON)".to_string(),
+        };
+        let path = Paragraph::new(self.path.as_str()).block(
+            Block::default()
+                .title(path_title)
+                .borders(Borders::ALL)
+                .style(style(Focus::Path)),
+        );
+        frame.render_widget(path, chunks[0]);
+        if self.focus == Focus::Path {
+            frame.set_cursor_position(Position {
+                x: chunks[0].x + 1 + self.path.chars().count() as u16,
+                y: chunks[0].y + 1,
+            });
+        }
+
+        let template = self
+            .templates
+            .get(self.template)
+            .map_or("", |t| t.name.as_str());
+        let template = Paragraph::new(format!("◀ {} ▶", template)).block(
+            Block::default()
+                .title("Template")
+                .borders(Borders::ALL)
+                .style(style(Focus::Template)),
+        );
+        frame.render_widget(template, options[0]);
+        let mode = if self.array {
+            "one job array, a task per row"
+        } else {
+            "a job per row"
+        };
+        let mode = Paragraph::new(format!("◀ {} ▶", mode)).block(
+            Block::default()
+                .title("Submit as")
+                .borders(Borders::ALL)
+                .style(style(Focus::Mode)),
+        );
+        frame.render_widget(mode, options[1]);
+
+        self.render_rows(frame, chunks[2]);
+
+        if let Some(message) = &self.message {
+            let msg = Paragraph::new(message.as_str())
+                .style(Style::default().fg(Color::Yellow))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(msg, chunks[3]);
+        }
+
+        let help_text = "↑/↓/Tab: Switch field | ←/→: Cycle template or mode | Ctrl+o: Choose manifest | PgUp/PgDn: Scroll rows | Enter: Submit | Esc: Close";
+        let help = Paragraph::new(tr(help_text))
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[4]);
+    }
+
+    /// The manifest's rows, each with its job or problem
+    fn render_rows(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().title(tr("Jobs")).borders(Borders::ALL);
+        let Some(Ok(manifest)) = &self.manifest else {
+            frame.render_widget(block, area);
+            return;
+        };
+
+        let mut header: Vec<String> = vec!["#".to_string()];
+        header.extend(manifest.columns.iter().cloned());
+        header.push("Job".to_string());
+        let rows = manifest.rows.iter().enumerate().map(|(i, row)| {
+            let outcome = match self.outcomes.get(i).and_then(Option::as_ref) {
+                Some(Ok(job_id)) => {
+                    Cell::from(job_id.clone()).style(Style::default().fg(Color::Green))
+                }
+                Some(Err(e)) => {
+                    Cell::from(format!("✗ {}", e)).style(Style::default().fg(Color::Red))
+                }
+                None if self.submitted => Cell::from(""),
+                None => Cell::from("ready").style(Style::default().fg(Color::Gray)),
+            };
+            let mut cells = vec![Cell::from((i + 1).to_string())];
+            cells.extend(row.iter().map(|value| Cell::from(value.clone())));
+            cells.push(outcome);
+            Row::new(cells)
+        });
+
+        let mut widths = vec![Constraint::Length(4)];
+        widths.extend(manifest.columns.iter().map(|_| Constraint::Max(20)));
+        widths.push(Constraint::Fill(1));
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(block)
+            .row_highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
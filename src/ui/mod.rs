@@ -1,6 +1,42 @@
+pub mod actions;
+pub mod alert;
+pub mod allocform;
+pub mod auditview;
+pub mod burstbuffers;
 pub mod columns;
+pub mod confirm;
+pub mod dashboard;
+pub mod dependency;
+pub mod depgraph;
+pub mod diagnostics;
+pub mod efficiency;
+pub mod eta;
+pub mod fairshare;
+pub mod filebrowser;
 pub mod filter;
+pub mod gpus;
+pub mod history;
+pub mod incident;
+pub mod jobdetail;
+pub mod jobedit;
 pub mod jobscript;
 pub mod jobslist;
 pub mod layout;
+pub mod leaderboard;
 pub mod logview;
+pub mod manifest;
+pub mod modules;
+pub mod nodedetail;
+pub mod nodegrid;
+pub mod partitions;
+pub mod picker;
+pub mod preview;
+pub mod prompt;
+pub mod qos;
+pub mod quickfilter;
+pub mod reservations;
+pub mod retries;
+pub mod scriptdiff;
+pub mod submissions;
+pub mod submitform;
+pub mod timeline;
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::i18n::{tr, trf};
+
+/// Result of a key press in the module search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleAction {
+    None,
+    Close,
+    /// Look up the modules matching this query
+    Search(String),
+    /// Add `module load` lines for these modules to the script
+    Insert(Vec<String>),
+}
+
+/// Popup searching environment modules and picking the ones a script loads
+pub struct ModulePanel {
+    pub visible: bool,
+    /// Script the chosen modules are added to
+    pub script: String,
+    query: String,
+    /// Query the results are for, so Enter searches again once it changes
+    searched: Option<String>,
+    /// Matching modules, or why the search failed
+    results: Result<Vec<String>, String>,
+    state: ListState,
+    /// Modules marked with Space
+    marked: HashSet<String>,
+}
+
+impl ModulePanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            script: String::new(),
+            query: String::new(),
+            searched: None,
+            results: Ok(Vec::new()),
+            state: ListState::default(),
+            marked: HashSet::new(),
+        }
+    }
+
+    /// Show the search for a script; the last query and its results are kept
+    pub fn show(&mut self, script: String) {
+        self.script = script;
+        self.marked.clear();
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Show the modules found for a query
+    pub fn set_results(&mut self, query: String, results: Result<Vec<String>, String>) {
+        self.state
+            .select(results.as_ref().ok().filter(|r| !r.is_empty()).map(|_| 0));
+        self.searched = Some(query);
+        self.results = results;
+        self.marked.clear();
+    }
+
+    fn modules(&self) -> &[String] {
+        self.results.as_deref().unwrap_or(&[])
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent) -> ModuleAction {
+        let last = self.modules().len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc => return ModuleAction::Close,
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Char(' ') => {
+                if let Some(module) = self.modules().get(selected).cloned() {
+                    if !self.marked.remove(&module) {
+                        self.marked.insert(module);
+                    }
+                    self.state.select(Some((selected + 1).min(last)));
+                }
+            }
+            KeyCode::Enter => {
+                let query = self.query.trim().to_string();
+                if self.searched.as_deref() != Some(query.as_str()) {
+                    return ModuleAction::Search(query);
+                }
+                // Marked modules in list order, else the highlighted one
+                let mut chosen: Vec<String> = self
+                    .modules()
+                    .iter()
+                    .filter(|m| self.marked.contains(*m))
+                    .cloned()
+                    .collect();
+                if chosen.is_empty() {
+                    chosen.extend(self.modules().get(selected).cloned());
+                }
+                if !chosen.is_empty() {
+                    return ModuleAction::Insert(chosen);
+                }
+            }
+            KeyCode::Char(c) => self.query.push(c),
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            _ => {}
+        }
+        ModuleAction::None
+    }
+
+    /// Render the panel
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(trf("Modules for {script}", &[("script", &self.script)])).centered())
+            .title_bottom(
+                Line::from(tr(
+                    "Enter: Search, then insert | Space: Mark | ↑/↓: Select | Esc: Close",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(inner);
+
+        let input = Paragraph::new(self.query.as_str()).block(
+            Block::default()
+                .title(tr("Search modules"))
+                .borders(Borders::ALL),
+        );
+        frame.render_widget(input, chunks[0]);
+        frame.set_cursor_position(Position {
+            x: chunks[0].x + 1 + self.query.chars().count() as u16,
+            y: chunks[0].y + 1,
+        });
+
+        let items: Vec<ListItem> = match &self.results {
+            Err(e) => vec![ListItem::new(e.as_str()).style(Style::default().fg(Color::Red))],
+            Ok(modules) if modules.is_empty() => {
+                let text = match &self.searched {
+                    Some(query) => format!("No modules match {:?}", query),
+                    None => "Type part of a module name and press Enter".to_string(),
+                };
+                vec![ListItem::new(text).style(Style::default().fg(Color::DarkGray))]
+            }
+            Ok(modules) => modules
+                .iter()
+                .map(|module| {
+                    let mark = if self.marked.contains(module) {
+                        "[x] "
+                    } else {
+                        "[ ] "
+                    };
+                    ListItem::new(format!("{}{}", mark, module))
+                })
+                .collect(),
+        };
+        let list =
+            List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, chunks[1], &mut self.state);
+    }
+}
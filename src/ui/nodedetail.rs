@@ -0,0 +1,368 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::node::{NodeDetail, NodeState},
+};
+
+/// Result of a key press in the node detail popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeAction {
+    None,
+    /// Fetch the detail of this node
+    Load(String),
+    /// Set the state of a node, with the reason given for a drain or down
+    Update {
+        node: String,
+        state: NodeState,
+        reason: Option<String>,
+    },
+}
+
+/// Popup with the state, features, gres, allocation and jobs of a node; ←/→
+/// switch between the nodes it was opened for, e.g. those of a multi-node job
+pub struct NodePanel {
+    pub visible: bool,
+    nodes: Vec<String>,
+    index: usize,
+    /// Detail of the current node, or why it could not be fetched
+    detail: Option<Result<NodeDetail, String>>,
+    scroll: u16,
+    /// Drain, resume and down are offered (`node_actions` under `[admin]`,
+    /// and not in read-only mode)
+    admin: bool,
+    /// State about to be set, waiting for its reason or a confirming Enter
+    pending: Option<NodeState>,
+    reason: String,
+    /// Outcome of the last state change
+    result: Option<Result<String, String>>,
+}
+
+impl NodePanel {
+    pub fn new(admin: bool, read_only: bool) -> Self {
+        Self {
+            visible: false,
+            nodes: Vec::new(),
+            index: 0,
+            detail: None,
+            scroll: 0,
+            admin: admin && !read_only,
+            pending: None,
+            reason: String::new(),
+            result: None,
+        }
+    }
+
+    /// Show the panel for these nodes; returns the first one to load
+    pub fn show(&mut self, nodes: Vec<String>) -> Option<String> {
+        let first = nodes.first().cloned();
+        self.nodes = nodes;
+        self.index = 0;
+        self.detail = None;
+        self.scroll = 0;
+        self.pending = None;
+        self.result = None;
+        self.visible = first.is_some();
+        first
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Show the fetched detail of the current node
+    pub fn set_detail(&mut self, detail: Result<NodeDetail, String>) {
+        self.detail = Some(detail);
+    }
+
+    /// Show the outcome of a state change
+    pub fn set_result(&mut self, result: Result<String, String>) {
+        self.result = Some(result);
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent) -> NodeAction {
+        if let Some(state) = self.pending {
+            return self.handle_pending_key(key, state);
+        }
+        match key.code {
+            KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Left | KeyCode::Right if self.nodes.len() > 1 => {
+                self.index = if key.code == KeyCode::Right {
+                    (self.index + 1) % self.nodes.len()
+                } else {
+                    (self.index + self.nodes.len() - 1) % self.nodes.len()
+                };
+                self.detail = None;
+                self.scroll = 0;
+                return NodeAction::Load(self.nodes[self.index].clone());
+            }
+            KeyCode::Char('r') => {
+                if let Some(node) = self.nodes.get(self.index) {
+                    return NodeAction::Load(node.clone());
+                }
+            }
+            KeyCode::Char(c @ ('d' | 'u' | 'x')) if self.admin => {
+                self.pending = Some(match c {
+                    'd' => NodeState::Drain,
+                    'u' => NodeState::Resume,
+                    _ => NodeState::Down,
+                });
+                self.reason.clear();
+                self.result = None;
+            }
+            _ => {}
+        }
+        NodeAction::None
+    }
+
+    /// Keys while a state change waits for its reason or confirmation;
+    /// Backspace on an empty reason cancels it
+    fn handle_pending_key(&mut self, key: KeyEvent, state: NodeState) -> NodeAction {
+        match key.code {
+            KeyCode::Enter => {
+                let reason = self.reason.trim().to_string();
+                if state.needs_reason() && reason.is_empty() {
+                    self.result = Some(Err("Enter a reason first".to_string()));
+                    return NodeAction::None;
+                }
+                self.pending = None;
+                if let Some(node) = self.nodes.get(self.index) {
+                    return NodeAction::Update {
+                        node: node.clone(),
+                        state,
+                        reason: state.needs_reason().then_some(reason),
+                    };
+                }
+            }
+            KeyCode::Backspace if self.reason.is_empty() => self.pending = None,
+            KeyCode::Backspace => {
+                self.reason.pop();
+            }
+            KeyCode::Char(c) if state.needs_reason() => self.reason.push(c),
+            _ => {}
+        }
+        NodeAction::None
+    }
+
+    /// Lines of the detail: state and drain reason first, then hardware,
+    /// allocation, times and the jobs on the node
+    fn lines(detail: &NodeDetail) -> Vec<Line<'_>> {
+        let heading = |text: &str| {
+            Line::styled(
+                text.to_string(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )
+        };
+        let mut lines = Vec::new();
+
+        let state = detail.field("State").unwrap_or("UNKNOWN");
+        let color = if ["DOWN", "DRAIN", "FAIL", "NOT_RESPONDING"]
+            .iter()
+            .any(|s| state.contains(s))
+        {
+            Color::Red
+        } else if state.contains("ALLOC") || state.contains("MIX") {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<16}", tr("State")),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(state.to_string(), Style::default().fg(color)),
+        ]));
+        if let Some(reason) = detail.field("Reason") {
+            // The reason ends with "[user@time]" on most Slurm versions
+            let mut text = reason.to_string();
+            if let (Some(user), false) = (detail.field("ReasonUser"), reason.contains('[')) {
+                text.push_str(&format!(" [{}", user));
+                if let Some(time) = detail.field("ReasonTime") {
+                    text.push_str(&format!("@{}", time));
+                }
+                text.push(']');
+            }
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<16}", tr("Reason")),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(text, Style::default().fg(Color::Red)),
+            ]));
+        }
+
+        let mut section = |title: &str, fields: &[(&str, String)]| {
+            let present: Vec<&(&str, String)> = fields
+                .iter()
+                .filter(|(_, value)| !value.is_empty())
+                .collect();
+            if present.is_empty() {
+                return;
+            }
+            lines.push(Line::from(""));
+            lines.push(heading(title));
+            for (key, value) in present {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:<16}", key), Style::default().fg(Color::Yellow)),
+                    Span::raw(value.clone()),
+                ]));
+            }
+        };
+        let value = |key: &str| detail.field(key).unwrap_or("").to_string();
+        let of = |used: &str, total: &str| match (detail.field(used), detail.field(total)) {
+            (Some(used), Some(total)) => format!("{} / {}", used, total),
+            (None, Some(total)) => total.to_string(),
+            _ => String::new(),
+        };
+
+        let mut features = value("ActiveFeatures");
+        let available = value("AvailableFeatures");
+        if available != features {
+            features = format!("{} (available: {})", features, available);
+        }
+        section(
+            tr("Node"),
+            &[
+                ("Partitions", value("Partitions")),
+                ("Features", features),
+                ("Gres", value("Gres")),
+                ("Configured TRES", value("CfgTRES")),
+            ],
+        );
+        let load = detail
+            .field("CPULoad")
+            .map(|load| format!(", load {}", load))
+            .unwrap_or_default();
+        let free = detail
+            .field("FreeMem")
+            .map(|free| format!(" MB, {} MB free", free))
+            .unwrap_or_default();
+        let memory = of("AllocMem", "RealMemory");
+        section(
+            tr("Allocation"),
+            &[
+                ("CPUs", format!("{}{}", of("CPUAlloc", "CPUTot"), load)),
+                (
+                    "Memory",
+                    if memory.is_empty() {
+                        memory
+                    } else {
+                        format!("{}{}", memory, free)
+                    },
+                ),
+                ("Gres used", value("GresUsed")),
+                ("Allocated TRES", value("AllocTRES")),
+            ],
+        );
+        section(
+            tr("Times"),
+            &[
+                ("Boot", value("BootTime")),
+                ("slurmd start", value("SlurmdStartTime")),
+                ("Last busy", value("LastBusyTime")),
+            ],
+        );
+
+        lines.push(Line::from(""));
+        lines.push(heading(&trf(
+            "Running jobs ({count})",
+            &[("count", &detail.jobs.len().to_string())],
+        )));
+        if !detail.jobs.is_empty() {
+            lines.push(Line::from(format!("  {}", detail.jobs.join(" "))));
+        }
+        lines
+    }
+
+    /// Render the panel
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let name = self.nodes.get(self.index).map_or("", String::as_str);
+        let mut title = trf("Node {node}", &[("node", name)]);
+        if self.nodes.len() > 1 {
+            title.push_str(&format!(" ({}/{})", self.index + 1, self.nodes.len()));
+        }
+        let help = if self.nodes.len() > 1 {
+            tr("←/→: Other nodes | ↑/↓/PgUp/PgDn: Scroll | r: Refresh | Esc/q: Close")
+        } else {
+            tr("↑/↓/PgUp/PgDn: Scroll | r: Refresh | Esc/q: Close")
+        };
+        let help = if self.admin {
+            format!("{} | {}", tr("d: Drain | u: Resume | x: Down"), help)
+        } else {
+            help.to_string()
+        };
+        let block = Block::default()
+            .title(Line::from(title).centered())
+            .title_bottom(Line::from(help).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let footer = self.pending.is_some() || self.result.is_some();
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),                             // Detail
+                Constraint::Length(if footer { 1 } else { 0 }), // State change
+            ])
+            .split(inner);
+
+        let paragraph = match &self.detail {
+            None => Paragraph::new(tr("Loading...")),
+            Some(Err(e)) => Paragraph::new(e.as_str()).style(Style::default().fg(Color::Red)),
+            Some(Ok(detail)) => Paragraph::new(Self::lines(detail)).scroll((self.scroll, 0)),
+        };
+        frame.render_widget(paragraph.wrap(Wrap { trim: false }), chunks[0]);
+
+        if let Some(state) = self.pending {
+            let prompt = if state.needs_reason() {
+                trf(
+                    "Set {node} to {state}, reason: ",
+                    &[("node", name), ("state", state.as_str())],
+                )
+            } else {
+                trf(
+                    "Set {node} to {state}? Enter: Confirm | Backspace: Cancel",
+                    &[("node", name), ("state", state.as_str())],
+                )
+            };
+            let width = prompt.chars().count() + self.reason.chars().count();
+            let line = Line::from(vec![
+                Span::styled(prompt, Style::default().fg(Color::Yellow)),
+                Span::raw(self.reason.as_str()),
+            ]);
+            frame.render_widget(Paragraph::new(line), chunks[1]);
+            if state.needs_reason() {
+                let x = chunks[1].x + width as u16;
+                frame.set_cursor_position(Position::new(x, chunks[1].y));
+            }
+        } else if let Some(result) = &self.result {
+            let (text, color) = match result {
+                Ok(text) => (text, Color::Green),
+                Err(e) => (e, Color::Red),
+            };
+            let line = Paragraph::new(text.as_str()).style(Style::default().fg(color));
+            frame.render_widget(line, chunks[1]);
+        }
+    }
+}
@@ -0,0 +1,423 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::{
+        format_power,
+        node::{NodeCategory, NodeCell},
+    },
+};
+
+/// Columns taken by one node: its block and a gap
+const CELL_WIDTH: u16 = 3;
+
+/// Categories in legend order
+const CATEGORIES: [NodeCategory; 6] = [
+    NodeCategory::Idle,
+    NodeCategory::Mixed,
+    NodeCategory::Allocated,
+    NodeCategory::Drain,
+    NodeCategory::Down,
+    NodeCategory::Other,
+];
+
+/// Jobs of the node under the cursor described below the grid
+const MAX_JOBS: usize = 3;
+
+/// Result of a key press in the node grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeGridAction {
+    None,
+    /// Fetch the nodes again
+    Refresh,
+    /// Show the node detail of this node
+    Open(String),
+    /// Back to the partitions view
+    Close,
+}
+
+/// One line of the grid: the switch it starts, if any, and indexes of its nodes
+struct GridRow {
+    label: Option<String>,
+    nodes: Vec<usize>,
+}
+
+/// Every node of the cluster as one colored cell, like a graphical `sinfo -N`,
+/// grouped by leaf switch when the cluster has a network topology
+pub struct NodeGridView {
+    nodes: Vec<NodeCell>,
+    /// Error from the last scontrol
+    error: Option<String>,
+    pub loaded: bool,
+    /// Index of the node under the cursor
+    selected: usize,
+    /// One line per switch instead of filling the width
+    by_switch: bool,
+    /// Lines at the last render, for moving the cursor
+    rows: Vec<GridRow>,
+}
+
+impl NodeGridView {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            error: None,
+            loaded: false,
+            selected: 0,
+            by_switch: true,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Show freshly fetched nodes, keeping the cursor on the same node
+    pub fn set_nodes(&mut self, result: color_eyre::Result<Vec<NodeCell>>) {
+        match result {
+            Ok(nodes) => {
+                let current = self.nodes.get(self.selected).map(|n| n.name.clone());
+                self.selected = current
+                    .and_then(|name| nodes.iter().position(|n| n.name == name))
+                    .unwrap_or(self.selected)
+                    .min(nodes.len().saturating_sub(1));
+                self.nodes = nodes;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    fn has_topology(&self) -> bool {
+        self.nodes.iter().any(|n| n.switch.is_some())
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> NodeGridAction {
+        match key.code {
+            KeyCode::Left | KeyCode::Right => {
+                let order: Vec<usize> = self.rows.iter().flat_map(|r| r.nodes.clone()).collect();
+                if let Some(i) = order.iter().position(|&n| n == self.selected) {
+                    let next = if key.code == KeyCode::Left {
+                        i.checked_sub(1)
+                    } else {
+                        Some(i + 1)
+                    };
+                    if let Some(&node) = next.and_then(|next| order.get(next)) {
+                        self.selected = node;
+                    }
+                }
+            }
+            KeyCode::Up | KeyCode::Down => {
+                let position = self.rows.iter().enumerate().find_map(|(r, row)| {
+                    let c = row.nodes.iter().position(|&n| n == self.selected)?;
+                    Some((r, c))
+                });
+                if let Some((r, c)) = position {
+                    let next = if key.code == KeyCode::Up {
+                        r.checked_sub(1)
+                    } else {
+                        Some(r + 1)
+                    };
+                    if let Some(row) = next.and_then(|next| self.rows.get(next)) {
+                        self.selected = row.nodes[c.min(row.nodes.len() - 1)];
+                    }
+                }
+            }
+            KeyCode::Home => {
+                if let Some(&first) = self.rows.first().and_then(|r| r.nodes.first()) {
+                    self.selected = first;
+                }
+            }
+            KeyCode::End => {
+                if let Some(&last) = self.rows.last().and_then(|r| r.nodes.last()) {
+                    self.selected = last;
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(node) = self.nodes.get(self.selected) {
+                    return NodeGridAction::Open(node.name.clone());
+                }
+            }
+            KeyCode::Char('t') => self.by_switch = !self.by_switch,
+            KeyCode::Char('g') => return NodeGridAction::Close,
+            KeyCode::Char('r') => return NodeGridAction::Refresh,
+            _ => {}
+        }
+        NodeGridAction::None
+    }
+
+    /// Lay the nodes out in lines of `width` columns: filling every line, or
+    /// one switch after the other behind a column of switch names as wide as
+    /// the returned label width
+    fn layout(&self, width: u16) -> (Vec<GridRow>, usize) {
+        if !(self.by_switch && self.has_topology()) {
+            let columns = (width / CELL_WIDTH).max(1) as usize;
+            let indexes: Vec<usize> = (0..self.nodes.len()).collect();
+            let rows = indexes
+                .chunks(columns)
+                .map(|nodes| GridRow {
+                    label: None,
+                    nodes: nodes.to_vec(),
+                })
+                .collect();
+            return (rows, 0);
+        }
+
+        // Switches in the order of their first node; nodes outside the topology last
+        let mut groups: Vec<(Option<&str>, Vec<usize>)> = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let switch = node.switch.as_deref();
+            match groups.iter_mut().find(|(name, _)| *name == switch) {
+                Some((_, nodes)) => nodes.push(i),
+                None => groups.push((switch, vec![i])),
+            }
+        }
+        groups.sort_by_key(|(name, _)| name.is_none());
+
+        let label_width = groups
+            .iter()
+            .map(|(name, _)| name.unwrap_or("-").chars().count())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let columns = (width.saturating_sub(label_width as u16) / CELL_WIDTH).max(1) as usize;
+        let mut rows = Vec::new();
+        for (name, nodes) in groups {
+            for (i, chunk) in nodes.chunks(columns).enumerate() {
+                rows.push(GridRow {
+                    label: (i == 0).then(|| name.unwrap_or("-").to_string()),
+                    nodes: chunk.to_vec(),
+                });
+            }
+        }
+        (rows, label_width)
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title(tr("Node grid"))
+            .title_bottom(
+                Line::from(tr(
+                    " ←/→/↑/↓: Navigate | Enter: Node detail | t: Group by switch | g: Partitions | r: Refresh ",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("scontrol failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            (None, true) if self.nodes.is_empty() => {
+                Some(("No nodes found".to_string(), Color::Gray))
+            }
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Legend
+                Constraint::Min(1),    // Grid
+                Constraint::Length(3), // Node under the cursor
+            ])
+            .split(inner);
+
+        let mut legend = Vec::new();
+        for category in CATEGORIES {
+            let count = self.nodes.iter().filter(|n| n.category == category).count();
+            if count > 0 {
+                legend.push(Span::styled("██", Style::default().fg(color(category))));
+                legend.push(Span::raw(format!(" {} {}   ", category.label(), count)));
+            }
+        }
+        legend.push(Span::raw("▓▓"));
+        legend.push(Span::raw(format!(" {}", tr("shares a job"))));
+        let watts: Vec<u64> = self
+            .nodes
+            .iter()
+            .filter_map(|n| n.current_watts.map(u64::from))
+            .collect();
+        if !watts.is_empty() {
+            legend.push(Span::styled(
+                format!(
+                    "   {} {}",
+                    format_power(watts.iter().sum()),
+                    trf(
+                        "drawn by {count} node(s)",
+                        &[("count", &watts.len().to_string())]
+                    )
+                ),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(legend)), chunks[0]);
+
+        let (rows, label_width) = self.layout(chunks[1].width);
+        self.rows = rows;
+        let selected = self.nodes.get(self.selected);
+        // Nodes running a job of the node under the cursor
+        let placement: HashSet<&str> = match selected {
+            Some(node) => self
+                .nodes
+                .iter()
+                .filter(|other| other.jobs.iter().any(|job| node.jobs.contains(job)))
+                .map(|other| other.name.as_str())
+                .collect(),
+            None => HashSet::new(),
+        };
+
+        // Scroll whole lines so the cursor stays in sight
+        let height = chunks[1].height.max(1) as usize;
+        let cursor_row = self
+            .rows
+            .iter()
+            .position(|row| row.nodes.contains(&self.selected))
+            .unwrap_or(0);
+        let first_row = cursor_row.saturating_sub(height - 1);
+        let lines: Vec<Line> = self.rows[first_row..]
+            .iter()
+            .take(height)
+            .map(|row| {
+                let mut spans = Vec::new();
+                if label_width > 0 {
+                    let label = row.label.as_deref().unwrap_or("");
+                    // A switch whose nodes are all out of service is an outage
+                    let out = row.label.is_some()
+                        && self
+                            .nodes
+                            .iter()
+                            .filter(|n| n.switch.as_deref().unwrap_or("-") == label)
+                            .all(|n| {
+                                matches!(n.category, NodeCategory::Down | NodeCategory::Drain)
+                            });
+                    let style = if out {
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::Cyan)
+                    };
+                    spans.push(Span::styled(format!("{:<label_width$}", label), style));
+                }
+                spans.extend(row.nodes.iter().map(|&i| {
+                    let node = &self.nodes[i];
+                    let style = Style::default().fg(color(node.category));
+                    if i == self.selected {
+                        Span::styled("▐▌ ", style.bg(Color::White))
+                    } else if placement.contains(node.name.as_str()) {
+                        Span::styled("▓▓ ", style)
+                    } else {
+                        Span::styled("██ ", style)
+                    }
+                }));
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+        if let Some(node) = selected {
+            let mut info = vec![Line::from(vec![
+                Span::styled(
+                    node.name.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    node.state.clone(),
+                    Style::default().fg(color(node.category)),
+                ),
+                Span::raw(format!(
+                    "  CPUs {}/{}  {}",
+                    node.cpus_allocated, node.cpus_total, node.partitions
+                )),
+                Span::styled(
+                    node.switch
+                        .as_ref()
+                        .map(|switch| format!("  switch {}", switch))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    node.current_watts
+                        .map(|current| {
+                            let average = node.average_watts.unwrap_or(current);
+                            format!(
+                                "  {} (avg {})",
+                                format_power(current as u64),
+                                format_power(average as u64)
+                            )
+                        })
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ])];
+            if let Some(reason) = &node.reason {
+                info.push(Line::styled(
+                    reason.clone(),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            // Where the node's jobs run: how many nodes and switches they span
+            let jobs: Vec<String> = node
+                .jobs
+                .iter()
+                .take(MAX_JOBS)
+                .map(|job| {
+                    let nodes: Vec<&NodeCell> =
+                        self.nodes.iter().filter(|n| n.jobs.contains(job)).collect();
+                    let mut switches: Vec<&str> =
+                        nodes.iter().filter_map(|n| n.switch.as_deref()).collect();
+                    switches.sort();
+                    switches.dedup();
+                    if switches.is_empty() {
+                        format!("{}: {} node(s)", job, nodes.len())
+                    } else {
+                        format!(
+                            "{}: {} node(s) on {} switch(es)",
+                            job,
+                            nodes.len(),
+                            switches.len()
+                        )
+                    }
+                })
+                .collect();
+            if !jobs.is_empty() {
+                let more = node.jobs.len().saturating_sub(MAX_JOBS);
+                let more = if more > 0 {
+                    format!(" (+{})", more)
+                } else {
+                    String::new()
+                };
+                info.push(Line::raw(format!("Jobs {}{}", jobs.join(", "), more)));
+            }
+            frame.render_widget(Paragraph::new(info), chunks[2]);
+        }
+    }
+}
+
+fn color(category: NodeCategory) -> Color {
+    match category {
+        NodeCategory::Idle => Color::Green,
+        NodeCategory::Mixed => Color::Yellow,
+        NodeCategory::Allocated => Color::Blue,
+        NodeCategory::Drain => Color::Magenta,
+        NodeCategory::Down => Color::Red,
+        NodeCategory::Other => Color::Gray,
+    }
+}
@@ -0,0 +1,240 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    i18n::tr,
+    slurm::partition::{PartitionInfo, StateCounts},
+};
+
+/// Cells of a utilization bar
+const BAR_WIDTH: usize = 10;
+
+/// Result of a key press in the partitions view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionsAction {
+    None,
+    /// Fetch the partitions again
+    Refresh,
+    /// Switch to the node grid
+    NodeGrid,
+}
+
+/// Every partition with its limits, defaults, flags and utilization
+pub struct PartitionsView {
+    /// Partitions in `scontrol` order
+    partitions: Vec<PartitionInfo>,
+    /// Least-loaded partitions first instead of `scontrol` order
+    by_load: bool,
+    /// Error from the last `scontrol show partition`
+    error: Option<String>,
+    pub loaded: bool,
+    state: TableState,
+}
+
+impl PartitionsView {
+    pub fn new() -> Self {
+        Self {
+            partitions: Vec::new(),
+            by_load: false,
+            error: None,
+            loaded: false,
+            state: TableState::default(),
+        }
+    }
+
+    /// Show freshly fetched partitions, keeping the cursor where it was
+    pub fn set_partitions(&mut self, result: color_eyre::Result<Vec<PartitionInfo>>) {
+        match result {
+            Ok(partitions) => {
+                let last = partitions.len().saturating_sub(1);
+                let selected = self.state.selected().unwrap_or(0).min(last);
+                self.state
+                    .select((!partitions.is_empty()).then_some(selected));
+                self.partitions = partitions;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> PartitionsAction {
+        let last = self.partitions.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Home => self.state.select(Some(0)),
+            KeyCode::End => self.state.select(Some(last)),
+            KeyCode::Char('g') => return PartitionsAction::NodeGrid,
+            KeyCode::Char('r') => return PartitionsAction::Refresh,
+            KeyCode::Char('s') => self.by_load = !self.by_load,
+            _ => {}
+        }
+        PartitionsAction::None
+    }
+
+    /// Partitions in display order; sorted by load, partitions that are not
+    /// up come last and ties go to the shorter backlog
+    fn sorted(&self) -> Vec<&PartitionInfo> {
+        let mut partitions: Vec<&PartitionInfo> = self.partitions.iter().collect();
+        if self.by_load {
+            partitions.sort_by(|a, b| {
+                (a.state != "UP")
+                    .cmp(&(b.state != "UP"))
+                    .then(load(a).total_cmp(&load(b)))
+                    .then(a.pending.cmp(&b.pending))
+            });
+        }
+        partitions
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let mut title = tr("Partitions").to_string();
+        if self.loaded {
+            title = format!("{} - {}", title, self.partitions.len());
+        }
+        if self.by_load {
+            title = format!("{} ({})", title, tr("least loaded first"));
+        }
+        let block = Block::default()
+            .title(title)
+            .title_bottom(
+                Line::from(tr(
+                    " ↑/↓: Navigate | s: Sort by load | g: Node grid | r: Refresh ",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("scontrol failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let header = Row::new(vec![
+            "Partition",
+            "State",
+            "CPUs",
+            "Nodes",
+            "Pending",
+            "Max time",
+            "Max nodes",
+            "Defaults",
+            "Allowed QoS",
+            "Flags",
+        ])
+        .style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows = self.sorted().into_iter().map(|p| {
+            let name = if p.default {
+                format!("{}*", p.name)
+            } else {
+                p.name.clone()
+            };
+            let state_color = if p.state == "UP" {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            let cpus = usage_bar(
+                &p.cpus,
+                format!(
+                    "{:>3.0}%",
+                    p.cpus.allocated as f64 * 100.0 / p.cpus.total.max(1) as f64
+                ),
+            );
+            let nodes = usage_bar(&p.nodes, format!("{}/{}", p.nodes.allocated, p.nodes.total));
+            let pending_color = match p.pending {
+                0 => Color::DarkGray,
+                n if n as f64 > p.cpus.idle as f64 => Color::Red,
+                _ => Color::Yellow,
+            };
+            let defaults = [p.default_time.as_deref(), p.default_memory.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(p.state.clone()).style(Style::default().fg(state_color)),
+                Cell::from(cpus),
+                Cell::from(nodes),
+                Cell::from(p.pending.to_string()).style(Style::default().fg(pending_color)),
+                Cell::from(p.max_time.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(p.max_nodes.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(defaults),
+                Cell::from(p.allow_qos.clone().unwrap_or_else(|| "ALL".to_string())),
+                Cell::from(p.flags.join(" ")),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Length(9),
+                Constraint::Length(BAR_WIDTH as u16 + 5),
+                Constraint::Length(BAR_WIDTH as u16 + 10),
+                Constraint::Length(8),
+                Constraint::Length(12),
+                Constraint::Length(10),
+                Constraint::Length(22),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
+
+/// Load used to rank partitions: CPUs when sinfo reported them, else nodes
+fn load(partition: &PartitionInfo) -> f64 {
+    if partition.cpus.total > 0 {
+        partition.cpus.load()
+    } else {
+        partition.nodes.load()
+    }
+}
+
+/// Bar of allocated (█, colored by load), unavailable (▒) and idle (░)
+/// shares, followed by a label
+pub fn usage_bar(counts: &StateCounts, label: String) -> Line<'static> {
+    let total = counts.total.max(1) as f64;
+    let cells = |n: u32| ((n as f64 / total * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let allocated = cells(counts.allocated);
+    let other = cells(counts.other).min(BAR_WIDTH - allocated);
+    let idle = BAR_WIDTH - allocated - other;
+    let color = match counts.load() {
+        load if load >= 0.9 => Color::Red,
+        load if load >= 0.5 => Color::Yellow,
+        _ => Color::Green,
+    };
+    Line::from(vec![
+        Span::styled("█".repeat(allocated), Style::default().fg(color)),
+        Span::styled("▒".repeat(other), Style::default().fg(Color::Red)),
+        Span::styled("░".repeat(idle), Style::default().fg(Color::DarkGray)),
+        Span::raw(format!(" {}", label)),
+    ])
+}
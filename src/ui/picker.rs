@@ -0,0 +1,91 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::i18n::tr;
+
+/// Result of a key press in the picker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickerAction {
+    None,
+    /// The highlighted item was chosen
+    Chosen(String),
+}
+
+/// Popup for choosing one value from a list, e.g. a partition
+pub struct Picker {
+    pub visible: bool,
+    title: String,
+    items: Vec<String>,
+    state: ListState,
+}
+
+impl Picker {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            title: String::new(),
+            items: Vec::new(),
+            state: ListState::default(),
+        }
+    }
+
+    /// Show the picker with the given choices
+    pub fn show(&mut self, title: &str, items: Vec<String>) {
+        self.title = title.to_string();
+        self.state.select((!items.is_empty()).then_some(0));
+        self.items = items;
+        self.visible = true;
+    }
+
+    /// Hide the picker
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the picker
+    pub fn handle_key(&mut self, key: KeyEvent) -> PickerAction {
+        let last = self.items.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Enter => {
+                if let Some(item) = self.items.get(selected).cloned() {
+                    self.hide();
+                    return PickerAction::Chosen(item);
+                }
+            }
+            _ => {}
+        }
+        PickerAction::None
+    }
+
+    /// Render the picker
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| ListItem::new(item.as_str()))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(Line::from(self.title.as_str()).centered())
+                    .title_bottom(Line::from(tr("Enter: Choose | Esc/q: Cancel")).centered())
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .style(Style::default().bg(Color::Black)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
@@ -0,0 +1,264 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::command::CommandStatus,
+};
+
+/// What the preview panel is showing the commands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreviewMode {
+    /// Dry-run: the commands were not run
+    DryRun,
+    /// Waiting for the user to confirm running the commands
+    Confirm,
+    /// The commands are running in the background
+    Running,
+    /// All commands have run or the rest were cancelled
+    Finished,
+}
+
+/// Result of a key press in the preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewAction {
+    None,
+    /// Run the commands shown for confirmation
+    Run,
+    /// Do not run the commands, or stop running the remaining ones
+    Abort,
+}
+
+/// Panel listing the Slurm commands an action runs: a dry-run preview, a
+/// review step before a rate-limited batch, and that batch's progress
+pub struct CommandPreview {
+    pub visible: bool,
+    title: String,
+    note: String,
+    commands: Vec<String>,
+    statuses: Vec<Option<CommandStatus>>,
+    mode: PreviewMode,
+    scroll: u16,
+}
+
+impl CommandPreview {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            title: String::new(),
+            note: String::new(),
+            commands: Vec::new(),
+            statuses: Vec::new(),
+            mode: PreviewMode::DryRun,
+            scroll: 0,
+        }
+    }
+
+    /// Show the commands that would run for an action
+    pub fn show(&mut self, title: &str, commands: Vec<String>) {
+        self.open(
+            PreviewMode::DryRun,
+            title,
+            tr("Nothing was run. These commands would have been executed:"),
+            commands,
+        );
+    }
+
+    /// Show the commands of an action and ask whether to run them
+    pub fn show_confirm(&mut self, title: &str, note: &str, commands: Vec<String>) {
+        self.open(PreviewMode::Confirm, title, note, commands);
+    }
+
+    /// Show the commands of an action that is already running in the background
+    pub fn show_progress(&mut self, title: &str, note: &str, commands: Vec<String>) {
+        self.open(PreviewMode::Running, title, note, commands);
+    }
+
+    fn open(&mut self, mode: PreviewMode, title: &str, note: &str, commands: Vec<String>) {
+        self.mode = mode;
+        self.title = title.to_string();
+        self.note = note.to_string();
+        self.statuses = vec![None; commands.len()];
+        self.commands = commands;
+        self.scroll = 0;
+        self.visible = true;
+    }
+
+    /// Switch from confirmation to showing progress
+    pub fn set_running(&mut self) {
+        self.mode = PreviewMode::Running;
+    }
+
+    /// Whether the panel is tracking a batch that is still running
+    pub fn is_running(&self) -> bool {
+        self.mode == PreviewMode::Running
+    }
+
+    /// Record the outcome of the command at `index`
+    pub fn set_status(&mut self, index: usize, status: CommandStatus) {
+        if let Some(slot) = self.statuses.get_mut(index) {
+            *slot = Some(status);
+        }
+    }
+
+    /// Mark the batch as over, with a summary line
+    pub fn set_finished(&mut self, note: String) {
+        self.mode = PreviewMode::Finished;
+        self.note = note;
+    }
+
+    /// Hide the preview
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the preview
+    pub fn handle_key(&mut self, key: KeyEvent) -> PreviewAction {
+        match (self.mode, key.code) {
+            (PreviewMode::Confirm, KeyCode::Char('y') | KeyCode::Enter) => {
+                return PreviewAction::Run;
+            }
+            (PreviewMode::Confirm, KeyCode::Char('n') | KeyCode::Char('q')) => {
+                self.hide();
+                return PreviewAction::Abort;
+            }
+            (PreviewMode::Running, KeyCode::Char('x')) => return PreviewAction::Abort,
+            (_, KeyCode::Enter | KeyCode::Char('q')) => self.hide(),
+            (_, KeyCode::Up) => self.scroll = self.scroll.saturating_sub(1),
+            (_, KeyCode::Down) if (self.scroll as usize) + 1 < self.commands.len() => {
+                self.scroll += 1;
+            }
+            _ => {}
+        }
+        PreviewAction::None
+    }
+
+    /// Render the preview
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let finished = self.statuses.iter().filter(|s| s.is_some()).count();
+        let (title, color) = match self.mode {
+            PreviewMode::DryRun => (
+                trf("Dry run: {title}", &[("title", &self.title)]),
+                Color::Yellow,
+            ),
+            PreviewMode::Confirm => (
+                trf("Review: {title}", &[("title", &self.title)]),
+                Color::Yellow,
+            ),
+            PreviewMode::Running => (
+                trf(
+                    "Running: {title} ({done}/{total})",
+                    &[
+                        ("title", &self.title),
+                        ("done", &finished.to_string()),
+                        ("total", &self.commands.len().to_string()),
+                    ],
+                ),
+                Color::Cyan,
+            ),
+            PreviewMode::Finished => (
+                trf("Done: {title}", &[("title", &self.title)]),
+                Color::Green,
+            ),
+        };
+        let block = Block::default()
+            .title(Line::from(title).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let ran = matches!(self.mode, PreviewMode::Running | PreviewMode::Finished);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),                       // Explanation
+                Constraint::Length(if ran { 2 } else { 0 }), // Progress
+                Constraint::Min(1),                          // Commands
+                Constraint::Length(1),                       // Help
+            ])
+            .split(inner);
+
+        let note = Paragraph::new(self.note.as_str())
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(note, chunks[0]);
+
+        if ran {
+            let failed = self
+                .statuses
+                .iter()
+                .filter(|s| matches!(s, Some(status) if *status != CommandStatus::Success))
+                .count();
+            let total = self.commands.len().max(1);
+            let label = trf(
+                "{done}/{total} command(s), {failed} failed",
+                &[
+                    ("done", &finished.to_string()),
+                    ("total", &self.commands.len().to_string()),
+                    ("failed", &failed.to_string()),
+                ],
+            );
+            let gauge_color = if failed > 0 { Color::Red } else { Color::Green };
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(gauge_color).bg(Color::DarkGray))
+                .ratio(finished as f64 / total as f64)
+                .label(label);
+            let area = Rect {
+                height: 1,
+                ..chunks[1]
+            };
+            frame.render_widget(gauge, area);
+        }
+
+        let lines: Vec<Line> = self
+            .commands
+            .iter()
+            .zip(&self.statuses)
+            .map(|(command, status)| {
+                let marker = match status {
+                    _ if matches!(self.mode, PreviewMode::DryRun | PreviewMode::Confirm) => {
+                        Span::raw("$ ")
+                    }
+                    None => Span::styled("· ", Style::default().fg(Color::DarkGray)),
+                    Some(CommandStatus::Success) => {
+                        Span::styled("✓ ", Style::default().fg(Color::Green))
+                    }
+                    Some(_) => Span::styled("✗ ", Style::default().fg(Color::Red)),
+                };
+                let mut line = Line::from(vec![marker, Span::raw(command.as_str())]);
+                if let Some(status @ (CommandStatus::Failed { .. } | CommandStatus::NotRun(_))) =
+                    status
+                {
+                    line.push_span(Span::styled(
+                        format!("  ({})", status),
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                line
+            })
+            .collect();
+        let commands = Paragraph::new(lines)
+            .style(Style::default().fg(Color::Cyan))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(commands, chunks[2]);
+
+        let help = match self.mode {
+            PreviewMode::Confirm => "↑/↓: Scroll | y/Enter: Run | n/Esc: Abort",
+            PreviewMode::Running => "↑/↓: Scroll | x: Stop remaining | Enter/Esc/q: Hide",
+            _ => "↑/↓: Scroll | Enter/Esc/q: Close",
+        };
+        let help = Paragraph::new(tr(help)).style(Style::default().fg(Color::Gray));
+        frame.render_widget(help, chunks[3]);
+    }
+}
@@ -0,0 +1,147 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// What the text entered in the prompt will be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// Signal to send to the target jobs
+    Signal,
+    /// Name for a saved history query
+    BookmarkName,
+    /// New name for the job under the cursor
+    JobName,
+    /// Nice value for the target jobs
+    Nice,
+    /// New time limit for the running job under the cursor
+    TimeLimit,
+    /// A `:` command, e.g. `cancel-filtered`
+    Command,
+    /// Local note on the job under the cursor
+    JobNote,
+    /// Command to submit with `sbatch --wrap`
+    Wrap,
+}
+
+/// Single-line text prompt shown on top of the jobs list
+pub struct InputPrompt {
+    pub visible: bool,
+    pub kind: PromptKind,
+    /// Text typed so far
+    pub input: String,
+    title: String,
+    /// Explanation shown below the input
+    hint: String,
+    /// Validation error from the last submit
+    error: Option<String>,
+}
+
+/// Action to take after handling a key in the prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptAction {
+    /// Do nothing
+    None,
+    /// Close the prompt without doing anything
+    Close,
+    /// Use the entered (trimmed) text
+    Submit(String),
+}
+
+impl InputPrompt {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            kind: PromptKind::Signal,
+            input: String::new(),
+            title: String::new(),
+            hint: String::new(),
+            error: None,
+        }
+    }
+
+    /// Show the prompt with an optional pre-filled value
+    pub fn show(&mut self, kind: PromptKind, title: &str, hint: &str, initial: &str) {
+        self.kind = kind;
+        self.title = title.to_string();
+        self.hint = hint.to_string();
+        self.input = initial.to_string();
+        self.error = None;
+        self.visible = true;
+    }
+
+    /// Hide the prompt
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Keep the prompt open and show why the input was rejected
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Handle key events for the prompt
+    pub fn handle_key(&mut self, key: KeyEvent) -> PromptAction {
+        match key.code {
+            KeyCode::Esc => PromptAction::Close,
+            KeyCode::Enter => PromptAction::Submit(self.input.trim().to_string()),
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.error = None;
+                PromptAction::None
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.error = None;
+                PromptAction::None
+            }
+            _ => PromptAction::None,
+        }
+    }
+
+    /// Render the prompt
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(self.title.as_str()).centered())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Input
+                Constraint::Min(1),    // Hint or error
+            ])
+            .split(inner);
+
+        let input_style = if self.error.is_some() {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+        let input = Paragraph::new(self.input.as_str())
+            .block(Block::default().borders(Borders::ALL).style(input_style));
+        frame.render_widget(input, chunks[0]);
+
+        frame.set_cursor_position(Position {
+            x: chunks[0].x + 1 + self.input.chars().count() as u16,
+            y: chunks[0].y + 1,
+        });
+
+        let (text, style) = match &self.error {
+            Some(err) => (err.as_str(), Style::default().fg(Color::Red)),
+            None => (self.hint.as_str(), Style::default().fg(Color::Gray)),
+        };
+        let hint = Paragraph::new(text).style(style).wrap(Wrap { trim: true });
+        frame.render_widget(hint, chunks[1]);
+    }
+}
@@ -0,0 +1,210 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+
+use crate::{i18n::tr, slurm::qos::QosInfo};
+
+/// Result of a key press in the QoS view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QosAction {
+    None,
+    /// Fetch the QoS again
+    Refresh,
+}
+
+/// Every QoS with its priority and limits, marking the ones I may use
+pub struct QosView {
+    qos: Vec<QosInfo>,
+    /// Only the QoS my associations may use
+    mine_only: bool,
+    /// Error from the last `sacctmgr show qos`
+    error: Option<String>,
+    pub loaded: bool,
+    state: TableState,
+}
+
+impl QosView {
+    pub fn new() -> Self {
+        Self {
+            qos: Vec::new(),
+            mine_only: false,
+            error: None,
+            loaded: false,
+            state: TableState::default(),
+        }
+    }
+
+    /// Show freshly fetched QoS, keeping the cursor where it was
+    pub fn set_qos(&mut self, result: color_eyre::Result<Vec<QosInfo>>) {
+        match result {
+            Ok(qos) => {
+                self.qos = qos;
+                self.clamp_selection();
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    /// QoS in display order
+    fn shown(&self) -> Vec<&QosInfo> {
+        self.qos
+            .iter()
+            .filter(|q| !self.mine_only || q.allowed != Some(false))
+            .collect()
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.shown().len();
+        let selected = self
+            .state
+            .selected()
+            .unwrap_or(0)
+            .min(count.saturating_sub(1));
+        self.state.select((count > 0).then_some(selected));
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> QosAction {
+        let last = self.shown().len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Home => self.state.select(Some(0)),
+            KeyCode::End => self.state.select(Some(last)),
+            KeyCode::Char('m') => {
+                self.mine_only = !self.mine_only;
+                self.clamp_selection();
+            }
+            KeyCode::Char('r') => return QosAction::Refresh,
+            _ => {}
+        }
+        QosAction::None
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        // Borrows only the QoS list, leaving the table state free
+        let shown: Vec<&QosInfo> = self
+            .qos
+            .iter()
+            .filter(|q| !self.mine_only || q.allowed != Some(false))
+            .collect();
+        let mut title = tr("QoS").to_string();
+        if self.loaded {
+            title = format!("{} - {}", title, shown.len());
+        }
+        if self.mine_only {
+            title = format!("{} ({})", title, tr("usable by me"));
+        }
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(tr(" ↑/↓: Navigate | m: Only mine | r: Refresh ")).centered())
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("sacctmgr failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // QoS
+                Constraint::Length(5), // Selected QoS
+            ])
+            .split(inner);
+
+        let dash = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        let header = Row::new(vec![
+            "QoS",
+            "Usable",
+            "Priority",
+            "MaxWall",
+            "MaxTRESPerUser",
+            "MaxJobsPU",
+            "MaxSubmitPU",
+            "MaxTRES (job)",
+        ])
+        .style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows = shown.iter().map(|q| {
+            let (usable, color) = match (q.allowed, q.default) {
+                (Some(true), true) => ("yes*", Color::Green),
+                (Some(true), false) => ("yes", Color::Green),
+                (Some(false), _) => ("no", Color::DarkGray),
+                (None, _) => ("?", Color::Gray),
+            };
+            let style = if q.allowed == Some(false) {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(q.name.clone()),
+                Cell::from(usable).style(Style::default().fg(color)),
+                Cell::from(q.priority.to_string()),
+                Cell::from(dash(&q.max_wall)),
+                Cell::from(dash(&q.max_tres_per_user)),
+                Cell::from(dash(&q.max_jobs_per_user)),
+                Cell::from(dash(&q.max_submit_per_user)),
+                Cell::from(dash(&q.max_tres)),
+            ])
+            .style(style)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Length(7),
+                Constraint::Length(9),
+                Constraint::Length(12),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+
+        let selected = self.state.selected().and_then(|i| shown.get(i)).copied();
+        let detail = selected.map(|q| {
+            let label = |text: &str| {
+                Span::styled(format!("{:<14}", text), Style::default().fg(Color::Yellow))
+            };
+            let lines = vec![
+                Line::from(vec![label("GrpTRES"), Span::raw(dash(&q.grp_tres))]),
+                Line::from(vec![label("Preempts"), Span::raw(dash(&q.preempt))]),
+                Line::from(vec![label("Flags"), Span::raw(dash(&q.flags))]),
+            ];
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().title(q.name.clone()).borders(Borders::TOP))
+        });
+        frame.render_stateful_widget(table, chunks[0], &mut self.state);
+        if let Some(detail) = detail {
+            frame.render_widget(detail, chunks[1]);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{
+    i18n::trf,
+    slurm::{failure::classify_failure, sacct::HistoryJob, Job},
+};
+
+/// Rows a quick filter can match: named text fields per row
+pub trait Filterable {
+    /// Field names (lowercase, usable as `name:value` terms) and their values
+    fn filter_fields(&self) -> Vec<(&'static str, String)>;
+}
+
+impl Filterable for Job {
+    fn filter_fields(&self) -> Vec<(&'static str, String)> {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        vec![
+            ("id", self.id.clone()),
+            ("name", self.name.clone()),
+            ("user", self.user.clone()),
+            ("state", self.state.to_string()),
+            ("partition", self.partition.clone()),
+            ("qos", self.qos.clone()),
+            ("node", optional(&self.node)),
+            ("account", optional(&self.account)),
+            ("reason", optional(&self.pending_reason)),
+            ("workdir", optional(&self.work_dir)),
+        ]
+    }
+}
+
+impl Filterable for HistoryJob {
+    fn filter_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("id", self.id.clone()),
+            ("name", self.name.clone()),
+            ("user", self.user.clone()),
+            ("state", self.state.clone()),
+            ("partition", self.partition.clone()),
+            ("exitcode", self.exit_code.clone()),
+            (
+                "failure",
+                classify_failure(&self.state, &self.exit_code)
+                    .map(|failure| failure.to_string())
+                    .unwrap_or_default(),
+            ),
+            ("node", self.node_list.clone()),
+        ]
+    }
+}
+
+/// The `/` filter bar: narrows the rows of a view as you type.
+///
+/// The text is split on whitespace and every term must match. A term matches
+/// when any field contains it (case-insensitive); `field:text` only looks at
+/// that field and a leading `!` excludes matching rows, e.g.
+/// `state:fail !user:alice`.
+#[derive(Debug, Default)]
+pub struct QuickFilter {
+    /// Whether keys go to the filter bar
+    pub editing: bool,
+    text: String,
+}
+
+impl QuickFilter {
+    /// Whether the bar is shown: while typing or while a filter is set
+    pub fn is_active(&self) -> bool {
+        self.editing || !self.text.is_empty()
+    }
+
+    /// The filter text as typed
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Handle a key while editing. Returns whether the filter text changed.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Enter => self.editing = false,
+            KeyCode::Esc => {
+                self.editing = false;
+                if !self.text.is_empty() {
+                    self.text.clear();
+                    return true;
+                }
+            }
+            KeyCode::Backspace => return self.text.pop().is_some(),
+            KeyCode::Char(c) => {
+                self.text.push(c);
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Whether a row passes the filter
+    pub fn matches<T: Filterable>(&self, item: &T) -> bool {
+        if self.text.trim().is_empty() {
+            return true;
+        }
+        let fields = item.filter_fields();
+        self.text.split_whitespace().all(|term| {
+            let (negate, term) = match term.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, term),
+            };
+            let term = term.to_lowercase();
+            let (field, needle) = match term.split_once(':') {
+                Some((field, needle)) if fields.iter().any(|(name, _)| *name == field) => {
+                    (Some(field), needle)
+                }
+                _ => (None, term.as_str()),
+            };
+            let found = fields
+                .iter()
+                .filter(|(name, _)| field.is_none_or(|f| f == *name))
+                .any(|(_, value)| value.to_lowercase().contains(needle));
+            found != negate
+        })
+    }
+
+    /// Render the bar with the number of rows shown out of the total
+    pub fn render(&self, frame: &mut Frame, area: Rect, shown: usize, total: usize) {
+        let count = trf(
+            "  ({shown} of {total})",
+            &[("shown", &shown.to_string()), ("total", &total.to_string())],
+        );
+        let color = if self.editing {
+            Color::Yellow
+        } else {
+            Color::Cyan
+        };
+        let line = Line::from(vec![
+            Span::styled("/", Style::default().fg(color)),
+            Span::raw(self.text.as_str()),
+            Span::styled(count, Style::default().fg(Color::DarkGray)),
+        ]);
+        frame.render_widget(Paragraph::new(line), area);
+        if self.editing {
+            let x = area.x + 1 + self.text.chars().count() as u16;
+            frame.set_cursor_position(Position::new(x, area.y));
+        }
+    }
+}
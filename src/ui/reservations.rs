@@ -0,0 +1,306 @@
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, TimeDelta, Weekday};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+
+use crate::{
+    i18n::tr,
+    slurm::reservation::{Clash, Reservation},
+};
+
+/// Days ahead shown by the maintenance calendar, today included
+const CALENDAR_DAYS: i64 = 14;
+
+/// Width of the reservation names left of the calendar
+const LABEL_WIDTH: usize = 16;
+
+/// Time format of the clash warnings
+const CLASH_TIME: &str = "%m-%d %H:%M";
+
+/// Result of a key press in the reservations view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReservationsAction {
+    None,
+    /// Fetch the reservations again
+    Refresh,
+}
+
+/// Every reservation, highlighting those that hold back my pending jobs
+pub struct ReservationsView {
+    reservations: Vec<Reservation>,
+    /// Error from the last `scontrol show reservation`
+    error: Option<String>,
+    pub loaded: bool,
+    state: TableState,
+}
+
+impl ReservationsView {
+    pub fn new() -> Self {
+        Self {
+            reservations: Vec::new(),
+            error: None,
+            loaded: false,
+            state: TableState::default(),
+        }
+    }
+
+    /// Show freshly fetched reservations, keeping the cursor where it was
+    pub fn set_reservations(&mut self, result: color_eyre::Result<Vec<Reservation>>) {
+        match result {
+            Ok(reservations) => {
+                let last = reservations.len().saturating_sub(1);
+                let selected = self.state.selected().unwrap_or(0).min(last);
+                self.state
+                    .select((!reservations.is_empty()).then_some(selected));
+                self.reservations = reservations;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.loaded = true;
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) -> ReservationsAction {
+        let last = self.reservations.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Home => self.state.select(Some(0)),
+            KeyCode::End => self.state.select(Some(last)),
+            KeyCode::Char('r') => return ReservationsAction::Refresh,
+            _ => {}
+        }
+        ReservationsAction::None
+    }
+
+    /// Render the view
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let mut title = tr("Reservations").to_string();
+        if self.loaded {
+            title = format!("{} - {}", title, self.reservations.len());
+        }
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(tr(" ↑/↓: Navigate | r: Refresh ")).centered())
+            .borders(Borders::ALL);
+
+        let message = match (&self.error, self.loaded) {
+            (Some(e), _) => Some((format!("scontrol failed: {}", e), Color::Red)),
+            (None, false) => Some(("Loading...".to_string(), Color::Gray)),
+            (None, true) if self.reservations.is_empty() => {
+                Some(("No reservations".to_string(), Color::Gray))
+            }
+            _ => None,
+        };
+        if let Some((text, color)) = message {
+            let paragraph = Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        let now = Local::now().naive_local();
+        let calendar = self.calendar_lines(now);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),                            // Reservations
+                Constraint::Length(calendar.len() as u16 + 1), // Maintenance calendar
+                Constraint::Length(6),                         // Selected reservation
+            ])
+            .split(inner);
+
+        let header = Row::new(vec![
+            "Name", "State", "Start", "End", "Nodes", "Users", "Accounts", "Flags",
+        ])
+        .style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let rows = self.reservations.iter().map(|res| {
+            let style = if !res.affected.is_empty() {
+                Style::default().fg(Color::Yellow)
+            } else if res.state != "ACTIVE" {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            let state_color = if res.state == "ACTIVE" {
+                Color::Green
+            } else {
+                Color::Gray
+            };
+            Row::new(vec![
+                Cell::from(res.name.clone()),
+                Cell::from(res.state.clone()).style(Style::default().fg(state_color)),
+                Cell::from(res.start.clone()),
+                Cell::from(res.end.clone()),
+                Cell::from(format!("{} ({})", res.nodes, res.node_count)),
+                Cell::from(res.users.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(res.accounts.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(res.flags.join(",")),
+            ])
+            .style(style)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(9),
+                Constraint::Length(20),
+                Constraint::Length(20),
+                Constraint::Length(22),
+                Constraint::Length(14),
+                Constraint::Length(14),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(table, chunks[0], &mut self.state);
+
+        if !calendar.is_empty() {
+            let calendar = Paragraph::new(calendar).block(
+                Block::default()
+                    .title(tr("Maintenance calendar"))
+                    .borders(Borders::TOP),
+            );
+            frame.render_widget(calendar, chunks[1]);
+        }
+
+        let Some(res) = self.state.selected().and_then(|i| self.reservations.get(i)) else {
+            return;
+        };
+        let label =
+            |text: &str| Span::styled(format!("{:<14}", text), Style::default().fg(Color::Yellow));
+        let affected = if res.affected.is_empty() {
+            Span::styled("none affected", Style::default().fg(Color::DarkGray))
+        } else {
+            Span::styled(res.affected.join(" "), Style::default().fg(Color::Yellow))
+        };
+        let mut lines = vec![
+            Line::from(vec![label("Nodes"), Span::raw(res.nodes.clone())]),
+            Line::from(vec![
+                label("Partition"),
+                Span::raw(res.partition.clone().unwrap_or_else(|| "-".to_string())),
+            ]),
+            Line::from(vec![label("My pending"), affected]),
+        ];
+        if res.is_maintenance() {
+            let clashes = if res.clashes.is_empty() {
+                Span::styled("none", Style::default().fg(Color::DarkGray))
+            } else {
+                let ids: Vec<&str> = res.clashes.iter().map(|c| c.job_id.as_str()).collect();
+                Span::styled(ids.join(" "), Style::default().fg(Color::Red))
+            };
+            lines.push(Line::from(vec![label("May run into"), clashes]));
+        }
+        let detail = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .title(res.name.clone())
+                .borders(Borders::TOP),
+        );
+        frame.render_widget(detail, chunks[2]);
+    }
+
+    /// The coming days with one row per maintenance window in them, then a
+    /// warning for each of my pending jobs that may run into one; nothing
+    /// without maintenance ahead
+    fn calendar_lines(&self, now: NaiveDateTime) -> Vec<Line<'static>> {
+        let today = now.date();
+        let days: Vec<NaiveDate> = (0..CALENDAR_DAYS)
+            .map(|i| today + TimeDelta::days(i))
+            .collect();
+        let horizon = (today + TimeDelta::days(CALENDAR_DAYS)).and_time(Default::default());
+        let windows: Vec<(&Reservation, NaiveDateTime, NaiveDateTime)> = self
+            .reservations
+            .iter()
+            .filter(|res| res.is_maintenance())
+            .filter_map(|res| res.window().map(|(start, end)| (res, start, end)))
+            .filter(|(_, start, end)| *end > now && *start < horizon)
+            .collect();
+        if windows.is_empty() {
+            return Vec::new();
+        }
+
+        let mut header = vec![Span::raw(" ".repeat(LABEL_WIDTH))];
+        header.extend(days.iter().map(|day| {
+            let style = if *day == today {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else if matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            Span::styled(format!("{:>2} ", day.day()), style)
+        }));
+        let mut lines = vec![Line::from(header)];
+
+        let covers = |start: NaiveDateTime, end: Option<NaiveDateTime>, day: NaiveDate| {
+            let day_start = day.and_time(Default::default());
+            start < day_start + TimeDelta::days(1) && end.is_none_or(|end| end > day_start)
+        };
+        for (res, start, end) in &windows {
+            // Red when one of my jobs may still be running at its start
+            let color = if res.clashes.is_empty() {
+                Color::Yellow
+            } else {
+                Color::Red
+            };
+            let mut spans = vec![Span::styled(
+                format!("{:<width$.width$}", res.name, width = LABEL_WIDTH - 1) + " ",
+                Style::default().fg(color),
+            )];
+            spans.extend(days.iter().map(|day| {
+                if covers(*start, Some(*end), *day) {
+                    Span::styled("██ ", Style::default().fg(color))
+                } else {
+                    Span::styled("·  ", Style::default().fg(Color::DarkGray))
+                }
+            }));
+            lines.push(Line::from(spans));
+        }
+
+        for (res, _, _) in &windows {
+            for clash in &res.clashes {
+                lines.push(clash_line(clash, &res.name));
+            }
+        }
+        lines
+    }
+}
+
+/// Warning that a pending job may still be running when maintenance starts
+fn clash_line(clash: &Clash, reservation: &str) -> Line<'static> {
+    let end = clash.end.map_or("no limit".to_string(), |end| {
+        end.format(CLASH_TIME).to_string()
+    });
+    let job = if clash.name.is_empty() {
+        clash.job_id.clone()
+    } else {
+        format!("{} ({})", clash.job_id, clash.name)
+    };
+    Line::from(Span::styled(
+        format!(
+            "⚠ {} may run {} → {}, into {}",
+            job,
+            clash.start.format(CLASH_TIME),
+            end,
+            reservation
+        ),
+        Style::default().fg(Color::Red),
+    ))
+}
@@ -0,0 +1,150 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+use std::time::Instant;
+
+use crate::{
+    i18n::tr,
+    slurm::{command::shell_quote, retry::RetryQueue},
+};
+
+/// Result of a key press in the pending submissions panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAction {
+    None,
+    /// Try the submission with this ID again right away
+    RetryNow(u64),
+    /// Stop retrying the submission with this ID
+    Cancel(u64),
+}
+
+/// Panel listing the submissions waiting to be retried
+pub struct RetryPanel {
+    pub visible: bool,
+    state: TableState,
+}
+
+impl RetryPanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            state: TableState::default(),
+        }
+    }
+
+    /// Show the panel with the oldest submission selected
+    pub fn show(&mut self) {
+        self.state.select(Some(0));
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent, queue: &RetryQueue) -> RetryAction {
+        let last = queue.entries.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0).min(last);
+        let id = queue.entries.get(selected).map(|entry| entry.id);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::Char('r') => {
+                if let Some(id) = id {
+                    return RetryAction::RetryNow(id);
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(id) = id {
+                    return RetryAction::Cancel(id);
+                }
+            }
+            _ => {}
+        }
+        RetryAction::None
+    }
+
+    /// Render the panel; `paused` while incident mode holds the retries back
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, queue: &RetryQueue, paused: bool) {
+        frame.render_widget(Clear, area);
+
+        let title = if paused {
+            tr("Pending Submissions - paused during incident mode")
+        } else {
+            tr("Pending Submissions")
+        };
+        let block = Block::default()
+            .title(Line::from(title).centered())
+            .title_bottom(
+                Line::from(tr(
+                    "↑/↓: Select | r: Retry now | d/Del: Cancel | Esc/q: Close",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+
+        if queue.entries.is_empty() {
+            let empty = Table::new(
+                vec![Row::new(vec![tr(
+                    "No submissions are waiting to be retried",
+                )])],
+                [Constraint::Percentage(100)],
+            )
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let now = Instant::now();
+        let rows = queue.entries.iter().map(|entry| {
+            let (status, color) = match entry.next_attempt {
+                Some(_) if paused => ("paused".to_string(), Color::Gray),
+                Some(at) if at > now => (
+                    format!("retry in {}s", (at - now).as_secs() + 1),
+                    Color::Yellow,
+                ),
+                Some(_) => ("retrying".to_string(), Color::Yellow),
+                None => ("gave up".to_string(), Color::Red),
+            };
+            let command = entry
+                .args
+                .iter()
+                .map(|arg| shell_quote(arg))
+                .collect::<Vec<_>>()
+                .join(" ");
+            Row::new(vec![
+                Cell::from(entry.attempts.to_string()),
+                Cell::from(status).style(Style::default().fg(color)),
+                Cell::from(entry.error.trim().to_string()),
+                Cell::from(format!("sbatch {}", command)),
+            ])
+        });
+
+        let header = Row::new(vec!["Attempts", "Status", "Last error", "Command"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(14),
+                Constraint::Percentage(35),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
@@ -0,0 +1,118 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use similar::TextDiff;
+
+/// Unified diff of the batch scripts of two jobs
+pub struct ScriptDiff {
+    pub visible: bool,
+    title: String,
+    lines: Vec<String>,
+    scroll_position: usize,
+}
+
+impl ScriptDiff {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            title: String::new(),
+            lines: Vec::new(),
+            scroll_position: 0,
+        }
+    }
+
+    /// Show the diff from the script of job `old_id` to that of job `new_id`
+    pub fn show(&mut self, old_id: &str, old: &str, new_id: &str, new: &str) {
+        self.title = format!("Script diff: {} → {}", old_id, new_id);
+        self.lines = if old == new {
+            vec!["The batch scripts are identical".to_string()]
+        } else {
+            unified_diff(
+                old,
+                new,
+                &format!("job {}", old_id),
+                &format!("job {}", new_id),
+            )
+        };
+        self.scroll_position = 0;
+        self.visible = true;
+    }
+
+    /// Hide the diff view
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the diff view
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let max = self.lines.len().saturating_sub(1);
+        match (key.modifiers, key.code) {
+            (_, KeyCode::Char('q')) => self.hide(),
+            (_, KeyCode::Up) => self.scroll_position = self.scroll_position.saturating_sub(1),
+            (_, KeyCode::Down) => self.scroll_position = (self.scroll_position + 1).min(max),
+            (_, KeyCode::PageUp) | (KeyModifiers::CONTROL, KeyCode::Char('u')) => {
+                self.scroll_position = self.scroll_position.saturating_sub(10);
+            }
+            (_, KeyCode::PageDown) | (KeyModifiers::CONTROL, KeyCode::Char('d')) => {
+                self.scroll_position = (self.scroll_position + 10).min(max);
+            }
+            _ => {}
+        }
+    }
+
+    /// Render the diff view
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|line| Line::styled(line.as_str(), Style::default().fg(diff_color(line))))
+            .collect();
+
+        let help_text = " [↑/↓] Scroll | [Ctrl+u/d] PageUp/Down | [q] Close ";
+        let diff = Paragraph::new(Text::from(lines))
+            .block(
+                Block::default()
+                    .title(format!("{}{}", self.title, help_text))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .style(Style::default().bg(Color::Black)),
+            )
+            .scroll((self.scroll_position as u16, 0));
+
+        frame.render_widget(diff, area);
+    }
+}
+
+/// Lines of the unified diff from `old` to `new`, with three lines of context
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> Vec<String> {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_name, new_name)
+        .to_string()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Color of a unified diff line: additions green, removals red, hunks cyan
+pub fn diff_color(line: &str) -> Color {
+    if line.starts_with("+++") || line.starts_with("---") {
+        Color::White
+    } else if line.starts_with('+') {
+        Color::Green
+    } else if line.starts_with('-') {
+        Color::Red
+    } else if line.starts_with("@@") {
+        Color::Cyan
+    } else {
+        Color::Gray
+    }
+}
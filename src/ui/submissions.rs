@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    i18n::tr,
+    slurm::{command::shell_quote, JobState},
+    submissions::Submission,
+    ui::jobslist::state_color,
+};
+
+/// Result of a key press in the submission history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionsAction {
+    None,
+    /// Run sbatch again with these arguments
+    Resubmit(Vec<String>),
+}
+
+/// Panel listing the jobs submitted through slurmer, newest first
+pub struct SubmissionsPanel {
+    pub visible: bool,
+    entries: Vec<Submission>,
+    /// Accounting state of the submitted jobs, by job ID
+    states: HashMap<String, String>,
+    /// Whether each entry's script changed since it was submitted, checked on show
+    changed: Vec<bool>,
+    state: TableState,
+}
+
+impl SubmissionsPanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            entries: Vec::new(),
+            states: HashMap::new(),
+            changed: Vec::new(),
+            state: TableState::default(),
+        }
+    }
+
+    /// Show the submissions with the newest selected
+    pub fn show(&mut self, entries: Vec<Submission>, states: HashMap<String, String>) {
+        self.state.select((!entries.is_empty()).then_some(0));
+        self.changed = entries.iter().map(Submission::script_changed).collect();
+        self.entries = entries;
+        self.states = states;
+        self.visible = true;
+    }
+
+    /// Hide the panel
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the panel
+    pub fn handle_key(&mut self, key: KeyEvent) -> SubmissionsAction {
+        let last = self.entries.len().saturating_sub(1);
+        let selected = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.hide(),
+            KeyCode::Up => self.state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down => self.state.select(Some((selected + 1).min(last))),
+            KeyCode::PageUp => self.state.select(Some(selected.saturating_sub(10))),
+            KeyCode::PageDown => self.state.select(Some((selected + 10).min(last))),
+            KeyCode::Home => self.state.select(Some(0)),
+            KeyCode::End => self.state.select(Some(last)),
+            KeyCode::Enter | KeyCode::Char('s') => {
+                if let Some(entry) = self.entries.get(selected) {
+                    let args = entry.args.clone();
+                    self.hide();
+                    return SubmissionsAction::Resubmit(args);
+                }
+            }
+            _ => {}
+        }
+        SubmissionsAction::None
+    }
+
+    /// Render the panel
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(tr("Submission History")).centered())
+            .title_bottom(
+                Line::from(tr(
+                    "↑/↓/PgUp/PgDn: Scroll | Enter/s: Submit again | Esc/q: Close",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+
+        if self.entries.is_empty() {
+            let empty = Table::new(
+                vec![Row::new(vec![tr(
+                    "Nothing has been submitted through slurmer yet",
+                )])],
+                [Constraint::Percentage(100)],
+            )
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let rows = self
+            .entries
+            .iter()
+            .zip(&self.changed)
+            .map(|(entry, changed)| {
+                let (job, outcome, color) = match (&entry.job_id, &entry.error) {
+                    (_, Some(error)) => (
+                        "-".to_string(),
+                        format!("sbatch failed: {}", error),
+                        Color::Red,
+                    ),
+                    (Some(job_id), None) => match self.states.get(job_id) {
+                        Some(state) => {
+                            let color = state_color(state.parse().unwrap_or(JobState::Other));
+                            (job_id.clone(), state.clone(), color)
+                        }
+                        None => (job_id.clone(), "SUBMITTED".to_string(), Color::Gray),
+                    },
+                    (None, None) => ("?".to_string(), "SUBMITTED".to_string(), Color::Gray),
+                };
+                let script = if *changed {
+                    Cell::from("changed").style(Style::default().fg(Color::Yellow))
+                } else {
+                    Cell::from("")
+                };
+                let command = entry
+                    .args
+                    .iter()
+                    .map(|arg| shell_quote(arg))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                Row::new(vec![
+                    Cell::from(entry.time.clone()),
+                    Cell::from(job),
+                    Cell::from(outcome).style(Style::default().fg(color)),
+                    script,
+                    Cell::from(format!("sbatch {}", command)),
+                ])
+            });
+
+        let header = Row::new(vec!["Time", "Job", "Outcome", "Script", "Command"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(19),
+                Constraint::Length(10),
+                Constraint::Percentage(20),
+                Constraint::Length(7),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::default().bg(Color::DarkGray));
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
@@ -0,0 +1,1122 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{JobTemplate, PartitionDefaults},
+    i18n::tr,
+    slurm::{
+        balance::AccountBalance,
+        command::{JobRequest, SlurmCommand},
+        limits::ClusterLimits,
+        lint::{lint_script, path_problems},
+        parse_time_limit,
+        sweep::{array_range, expand_sweep, params_path, write_params, PARAMS_VAR},
+    },
+    ui::{
+        dependency::parse_dependency,
+        jobedit::cycle,
+        jobscript::{highlight_with_bat, is_bat_installed, script_text},
+    },
+    utils::expand_home,
+};
+
+/// Fields of the submission form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitField {
+    Script,
+    WorkDir,
+    Partition,
+    TimeLimit,
+    Cpus,
+    Memory,
+    Gpus,
+    Account,
+    JobName,
+    Dependency,
+    Notify,
+    MailUser,
+    Sweep,
+}
+
+/// Events `--mail-type` can send mail about, offered as checkboxes, with their labels
+const MAIL_TYPES: [(&str, &str); 6] = [
+    ("BEGIN", "Begin"),
+    ("END", "End"),
+    ("FAIL", "Fail"),
+    ("REQUEUE", "Requeue"),
+    ("TIME_LIMIT_80", "80% time"),
+    ("ARRAY_TASKS", "Per task"),
+];
+
+impl SubmitField {
+    /// All fields in display order
+    fn all() -> Vec<SubmitField> {
+        vec![
+            SubmitField::Script,
+            SubmitField::WorkDir,
+            SubmitField::Partition,
+            SubmitField::TimeLimit,
+            SubmitField::Cpus,
+            SubmitField::Memory,
+            SubmitField::Gpus,
+            SubmitField::Account,
+            SubmitField::JobName,
+            SubmitField::Dependency,
+            SubmitField::Notify,
+            SubmitField::MailUser,
+            SubmitField::Sweep,
+        ]
+    }
+
+    /// Get the title for this field
+    pub fn title(&self) -> &'static str {
+        match self {
+            SubmitField::Script => "Script",
+            SubmitField::WorkDir => "Working Directory",
+            SubmitField::Partition => "Partition",
+            SubmitField::TimeLimit => "Time Limit",
+            SubmitField::Cpus => "CPUs per Task",
+            SubmitField::Memory => "Memory",
+            SubmitField::Gpus => "GPUs",
+            SubmitField::Account => "Account",
+            SubmitField::JobName => "Job Name",
+            SubmitField::Dependency => "Dependency",
+            SubmitField::Notify => "Mail Notifications",
+            SubmitField::MailUser => "Mail To",
+            SubmitField::Sweep => "Parameter Sweep",
+        }
+    }
+
+    /// Whether the field may be left empty, leaving the choice to the script or Slurm
+    pub fn optional(&self) -> bool {
+        !matches!(self, SubmitField::Script)
+    }
+
+    /// The sbatch option for a non-empty value; salloc and srun take the same ones
+    pub fn sbatch_arg(&self, value: &str) -> Option<String> {
+        match self {
+            SubmitField::Script | SubmitField::Sweep => None,
+            SubmitField::WorkDir => Some(format!("--chdir={}", expand_home(value))),
+            SubmitField::Partition => Some(format!("--partition={}", value)),
+            SubmitField::TimeLimit => Some(format!("--time={}", value)),
+            SubmitField::Cpus => Some(format!("--cpus-per-task={}", value)),
+            SubmitField::Memory => Some(format!("--mem={}", value)),
+            SubmitField::Gpus => Some(format!("--gres=gpu:{}", value)),
+            SubmitField::Account => Some(format!("--account={}", value)),
+            SubmitField::JobName => Some(format!("--job-name={}", value)),
+            SubmitField::Dependency => Some(format!("--dependency={}", value)),
+            SubmitField::Notify => Some(format!("--mail-type={}", value)),
+            SubmitField::MailUser => Some(format!("--mail-user={}", value)),
+        }
+    }
+}
+
+/// A single input of the submission form
+struct FormField {
+    field: SubmitField,
+    value: String,
+    error: Option<String>,
+}
+
+/// Popup form for submitting a batch script with `sbatch`
+pub struct SubmitForm {
+    pub visible: bool,
+    fields: Vec<FormField>,
+    focus: usize,
+    /// Feedback shown above the help text (e.g. "Fix the highlighted fields")
+    message: Option<String>,
+    /// Templates from the config file, offered with Ctrl+t
+    templates: Vec<JobTemplate>,
+    /// Where the script picker starts when no script is entered yet
+    scripts_dir: Option<String>,
+    /// Per-partition defaults from the config file
+    partition_defaults: HashMap<String, PartitionDefaults>,
+    /// Partition whose defaults were last applied, so typing doesn't reapply them
+    applied_partition: Option<String>,
+    /// The script as last read, shown next to the fields
+    preview: Option<ScriptPreview>,
+    preview_scroll: u16,
+    use_bat: bool,
+    /// Problems found in the script on the last submit; submitting again with the
+    /// same warnings goes ahead
+    warnings: Vec<String>,
+    /// Paths the job needs but cannot use, found on the last submit; these block it
+    path_errors: Vec<String>,
+    /// Parameters of the sweep, when one is entered and valid
+    sweep: Option<Vec<String>>,
+    /// Start estimate of `sbatch --test-only` for the values as they were, or its error
+    estimate: Option<Result<String, String>>,
+    /// Template the form was last filled from, recorded with the submission
+    template: Option<String>,
+    /// Balance of the account last looked up, or why it could not be
+    balance: Option<(String, Result<AccountBalance, String>)>,
+    /// Checkbox of the notifications field that ←/→ moved to
+    mail_cursor: usize,
+    /// Address filled in once a notification is ticked, from the config file
+    mail_user: Option<String>,
+}
+
+/// Contents of the script named in the form
+struct ScriptPreview {
+    path: String,
+    /// The script text, or why it could not be read
+    content: Result<String, String>,
+    /// `content` highlighted by bat, line for line
+    highlighted: Option<String>,
+}
+
+/// Action to take after handling a key in the submission form
+#[derive(Debug, Clone)]
+pub enum SubmitAction {
+    /// Do nothing
+    None,
+    /// Close the form without submitting
+    Close,
+    /// Run this `sbatch` command
+    Submit(SlurmCommand),
+    /// Let the user pick one of these templates
+    PickTemplate(Vec<String>),
+    /// Open this script in the user's editor
+    EditScript(String),
+    /// Choose the script in a file picker starting in this directory
+    BrowseScript(PathBuf),
+    /// Run this command with `--test-only` for the scheduler's start estimate
+    TestOnly(SlurmCommand),
+    /// Build the dependency, starting from this spec, in the dependency editor
+    EditDependency(String),
+    /// Search environment modules to load in this script
+    SearchModules(String),
+}
+
+impl SubmitForm {
+    pub fn new(
+        templates: Vec<JobTemplate>,
+        scripts_dir: Option<String>,
+        partition_defaults: HashMap<String, PartitionDefaults>,
+        mail_user: Option<String>,
+    ) -> Self {
+        Self {
+            visible: false,
+            fields: Vec::new(),
+            focus: 0,
+            message: None,
+            templates,
+            scripts_dir,
+            partition_defaults,
+            applied_partition: None,
+            preview: None,
+            preview_scroll: 0,
+            use_bat: is_bat_installed(),
+            warnings: Vec::new(),
+            path_errors: Vec::new(),
+            sweep: None,
+            estimate: None,
+            template: None,
+            balance: None,
+            mail_cursor: 0,
+            mail_user,
+        }
+    }
+
+    /// Show the form; values entered last time are kept so a similar job is quick to submit
+    pub fn show(&mut self) {
+        if self.fields.is_empty() {
+            self.fields = SubmitField::all()
+                .into_iter()
+                .map(|field| FormField {
+                    field,
+                    value: String::new(),
+                    error: None,
+                })
+                .collect();
+        }
+        self.focus = 0;
+        self.message = None;
+        self.refresh_preview();
+        self.visible = true;
+    }
+
+    /// Current value of a field
+    fn value(&self, field: SubmitField) -> &str {
+        self.fields
+            .iter()
+            .find(|f| f.field == field)
+            .map(|f| f.value.trim())
+            .unwrap_or("")
+    }
+
+    /// Current value of the script field, with `~/` expanded
+    fn script_path(&self) -> String {
+        expand_home(self.value(SubmitField::Script))
+    }
+
+    /// Directory to start choosing a script in: the entered script's directory,
+    /// the configured scripts directory, or the current directory
+    fn browse_dir(&self) -> PathBuf {
+        let script = self.script_path();
+        let entered = Path::new(&script).parent().filter(|dir| dir.is_dir());
+        match (entered, &self.scripts_dir) {
+            (Some(dir), _) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            (_, Some(dir)) => PathBuf::from(expand_home(dir)),
+            _ => PathBuf::from("."),
+        }
+    }
+
+    /// Fill the form from an existing job, to submit a changed copy of it
+    pub fn show_clone(&mut self, job_id: &str, request: JobRequest) {
+        self.show();
+        for f in &mut self.fields {
+            f.value = match f.field {
+                SubmitField::Script => request.script.clone(),
+                SubmitField::WorkDir => request.work_dir.clone(),
+                SubmitField::Partition => request.partition.clone(),
+                SubmitField::TimeLimit => request.time_limit.clone(),
+                SubmitField::Cpus => request.cpus_per_task.clone(),
+                SubmitField::Memory => request.memory.clone(),
+                SubmitField::Gpus => request.gpus.clone(),
+                SubmitField::Account => request.account.clone(),
+                SubmitField::JobName => request.name.clone(),
+                SubmitField::Dependency | SubmitField::Sweep => String::new(),
+                // Where mail goes is up to the user, not the job being cloned
+                SubmitField::Notify | SubmitField::MailUser => continue,
+            };
+            f.error = None;
+        }
+        // The job's own values stay, even where the partition has other defaults
+        self.applied_partition = Some(request.partition);
+        self.template = None;
+        self.refresh_preview();
+        self.message = Some(format!(
+            "Cloned job {}; change what you need and submit",
+            job_id
+        ));
+    }
+
+    /// Use a script chosen in the file picker
+    pub fn set_script(&mut self, path: &Path) {
+        if let Some(f) = self
+            .fields
+            .iter_mut()
+            .find(|f| f.field == SubmitField::Script)
+        {
+            f.value = path.to_string_lossy().into_owned();
+            f.error = None;
+        }
+        self.refresh_preview();
+    }
+
+    /// Expand the sweep field into its parameters
+    fn update_sweep(&mut self) {
+        let spec = self.value(SubmitField::Sweep);
+        self.sweep = (!spec.is_empty())
+            .then(|| expand_sweep(spec).ok())
+            .flatten();
+    }
+
+    /// Read the script again, e.g. after it was changed in the editor
+    pub fn refresh_preview(&mut self) {
+        self.forget_checks();
+        let path = self.script_path();
+        if path.is_empty() {
+            self.preview = None;
+            return;
+        }
+        if self.preview.as_ref().is_none_or(|p| p.path != path) {
+            self.preview_scroll = 0;
+        }
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string());
+        let highlighted = match &content {
+            Ok(text) if self.use_bat => highlight_with_bat(text),
+            _ => None,
+        };
+        self.preview = Some(ScriptPreview {
+            path,
+            content,
+            highlighted,
+        });
+    }
+
+    /// Fill the form from the named template. Fields the template leaves unset are
+    /// cleared, except the script when the template names none.
+    ///
+    /// A skeleton is written to `<job name>.sbatch` in the current directory; an
+    /// existing file of that name is used as is rather than overwritten.
+    pub fn apply_template(&mut self, name: &str) {
+        let Some(template) = self.templates.iter().find(|t| t.name == name).cloned() else {
+            return;
+        };
+        let mut script = template.script.clone();
+        self.template = Some(template.name.clone());
+        self.message = Some(format!("Applied template {}", name));
+        if let Some(skeleton) = &template.skeleton {
+            let stem: String = template
+                .job_name
+                .as_deref()
+                .unwrap_or(&template.name)
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '-' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let path = format!("{}.sbatch", stem);
+            self.message = Some(if Path::new(&path).exists() {
+                format!("Applied template {}; using the existing {}", name, path)
+            } else if let Err(e) = fs::write(&path, skeleton) {
+                format!("Failed to write {}: {}", path, e)
+            } else {
+                format!("Applied template {}; wrote its skeleton to {}", name, path)
+            });
+            script = Some(path);
+        }
+
+        for f in &mut self.fields {
+            let value = match f.field {
+                SubmitField::Script => match &script {
+                    Some(script) => script.clone(),
+                    None => continue,
+                },
+                SubmitField::WorkDir => String::new(),
+                SubmitField::Partition => template.partition.clone().unwrap_or_default(),
+                SubmitField::TimeLimit => template.time.clone().unwrap_or_default(),
+                SubmitField::Cpus => template.cpus.map(|n| n.to_string()).unwrap_or_default(),
+                SubmitField::Memory => template.memory.clone().unwrap_or_default(),
+                SubmitField::Gpus => template.gpus.clone().unwrap_or_default(),
+                SubmitField::Account => template.account.clone().unwrap_or_default(),
+                SubmitField::JobName => template.job_name.clone().unwrap_or_default(),
+                SubmitField::Dependency | SubmitField::Sweep => String::new(),
+                SubmitField::Notify | SubmitField::MailUser => continue,
+            };
+            f.value = value;
+            f.error = None;
+        }
+        // Partition defaults only fill what the template leaves unset
+        let message = self.message.take();
+        self.applied_partition = None;
+        self.apply_partition_defaults();
+        self.message = message;
+        self.refresh_preview();
+    }
+
+    /// Template the form was last filled from
+    pub fn template(&self) -> Option<&str> {
+        self.template.as_deref()
+    }
+
+    /// Defaults configured for the partition currently entered in the form
+    fn current_defaults(&self) -> Option<&PartitionDefaults> {
+        self.partition_defaults
+            .get(self.value(SubmitField::Partition))
+    }
+
+    /// Prefill account, time limit, CPUs, memory and GPUs when a different
+    /// partition has been chosen. Only empty fields and values the previous
+    /// partition's defaults filled in are replaced.
+    fn apply_partition_defaults(&mut self) {
+        let partition = self.value(SubmitField::Partition).to_string();
+        if self.applied_partition.as_deref() == Some(partition.as_str()) {
+            return;
+        }
+        let old = self
+            .applied_partition
+            .as_ref()
+            .and_then(|p| self.partition_defaults.get(p))
+            .cloned();
+        let new = self.partition_defaults.get(&partition).cloned();
+        self.applied_partition = Some(partition.clone());
+        if old.is_none() && new.is_none() {
+            return;
+        }
+
+        for f in &mut self.fields {
+            let old = old.as_ref().and_then(|d| default_value(d, f.field));
+            let current = f.value.trim();
+            if current.is_empty() || old.as_deref() == Some(current) {
+                f.value = new
+                    .as_ref()
+                    .and_then(|d| default_value(d, f.field))
+                    .unwrap_or_default();
+                f.error = None;
+            }
+        }
+        if new.is_some() {
+            self.message = Some(format!(
+                "Applied config defaults for partition {}",
+                partition
+            ));
+        }
+    }
+
+    /// Use a spec built in the dependency editor
+    pub fn set_dependency(&mut self, spec: String) {
+        if let Some(f) = self
+            .fields
+            .iter_mut()
+            .find(|f| f.field == SubmitField::Dependency)
+        {
+            f.value = spec;
+            f.error = None;
+        }
+        self.forget_checks();
+    }
+
+    /// Show feedback from an action taken outside the form
+    pub fn set_message(&mut self, message: String) {
+        self.message = Some(message);
+    }
+
+    /// Show the result of `sbatch --test-only`
+    pub fn set_estimate(&mut self, estimate: Result<String, String>) {
+        self.message = None;
+        self.estimate = Some(estimate);
+    }
+
+    /// The account to look up before this key is handled: keys that leave a field
+    /// or submit check an account that changed since the last lookup
+    pub fn balance_needed(&self, key: KeyEvent) -> Option<String> {
+        let leaves = matches!(
+            key.code,
+            KeyCode::Up | KeyCode::Down | KeyCode::Tab | KeyCode::BackTab | KeyCode::Enter
+        ) || (key.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key.code, KeyCode::Char('a') | KeyCode::Char('r')));
+        let account = self.value(SubmitField::Account).trim();
+        let checked = self.balance.as_ref().map(|(account, _)| account.as_str());
+        (leaves && !account.is_empty() && checked != Some(account)).then(|| account.to_string())
+    }
+
+    /// Show the balance of an account next to the account field
+    pub fn set_balance(&mut self, account: String, balance: Result<AccountBalance, String>) {
+        self.balance = Some((account, balance));
+    }
+
+    /// Balance of the entered account, when it was looked up
+    fn current_balance(&self) -> Option<&Result<AccountBalance, String>> {
+        let account = self.value(SubmitField::Account).trim();
+        match &self.balance {
+            Some((checked, balance)) if !account.is_empty() && checked == account => Some(balance),
+            _ => None,
+        }
+    }
+
+    /// Drop lint warnings and the start estimate once the values they were for change
+    fn forget_checks(&mut self) {
+        self.warnings.clear();
+        self.path_errors.clear();
+        self.estimate = None;
+    }
+
+    /// Hide the form
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Validate every field, returning true if all values are acceptable
+    fn validate(&mut self, partitions: &[String]) -> bool {
+        let max_time = self
+            .current_defaults()
+            .and_then(|d| d.max_time.clone())
+            .and_then(|max| Some((parse_time_limit(&max)?, max)));
+        for f in &mut self.fields {
+            f.error = validate_field(f.field, &f.value, partitions);
+            if let (SubmitField::TimeLimit, None, Some((max_secs, max))) =
+                (f.field, &f.error, &max_time)
+            {
+                if parse_time_limit(f.value.trim()).is_some_and(|secs| secs > *max_secs) {
+                    f.error = Some(format!("above the partition max {}", max));
+                }
+            }
+        }
+        self.update_sweep();
+        self.fields.iter().all(|f| f.error.is_none())
+    }
+
+    /// The `sbatch` command for the entered values
+    fn command(&self) -> SlurmCommand {
+        let mut args: Vec<String> = self
+            .fields
+            .iter()
+            .filter(|f| !f.value.trim().is_empty())
+            .filter_map(|f| f.field.sbatch_arg(f.value.trim()))
+            .collect();
+        if let Some(params) = &self.sweep {
+            // Tasks find their parameter through the file's absolute path
+            let file = params_path(&self.script_path());
+            let file = std::path::absolute(&file).unwrap_or(file.into());
+            args.push(format!("--array={}", array_range(params.len())));
+            args.push(format!("--export=ALL,{}={}", PARAMS_VAR, file.display()));
+        }
+        args.push(self.script_path());
+        SlurmCommand::new("sbatch", args, Vec::new())
+    }
+
+    /// GPU choices for the entered partition, or for every partition when none is
+    fn gpu_choices(&self, limits: &ClusterLimits) -> Vec<String> {
+        if let Some(part) = limits.partitions.get(self.value(SubmitField::Partition)) {
+            return part.gpu_choices();
+        }
+        let mut choices: Vec<String> = limits
+            .partitions
+            .values()
+            .flat_map(|part| part.gpu_choices())
+            .collect();
+        choices.sort();
+        choices.dedup();
+        choices
+    }
+
+    /// Tick or clear the notification under the cursor, filling in the configured
+    /// address when the first one is ticked
+    fn toggle_mail_type(&mut self) {
+        let (kind, _) = MAIL_TYPES[self.mail_cursor];
+        let mut ticked: Vec<String> = self
+            .value(SubmitField::Notify)
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        match ticked.iter().position(|t| t == kind) {
+            Some(i) => {
+                ticked.remove(i);
+            }
+            None => ticked.push(kind.to_string()),
+        }
+        // Keep the checkbox order so the command does not depend on the clicks
+        ticked.sort_by_key(|t| MAIL_TYPES.iter().position(|(k, _)| k == t));
+        let empty = ticked.is_empty();
+        for f in &mut self.fields {
+            match f.field {
+                SubmitField::Notify => f.value = ticked.join(","),
+                SubmitField::MailUser if !empty && f.value.trim().is_empty() => {
+                    f.value = self.mail_user.clone().unwrap_or_default()
+                }
+                _ => continue,
+            }
+            f.error = None;
+        }
+        self.forget_checks();
+    }
+
+    /// Handle key events for the submission form
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        partitions: &[String],
+        limits: &ClusterLimits,
+    ) -> SubmitAction {
+        if self.fields.is_empty() {
+            return SubmitAction::Close;
+        }
+
+        match key.code {
+            KeyCode::Esc => SubmitAction::Close,
+            KeyCode::Up | KeyCode::BackTab => {
+                self.leave_field();
+                self.focus = (self.focus + self.fields.len() - 1) % self.fields.len();
+                SubmitAction::None
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.leave_field();
+                self.focus = (self.focus + 1) % self.fields.len();
+                SubmitAction::None
+            }
+            KeyCode::PageUp => {
+                self.preview_scroll = self.preview_scroll.saturating_sub(10);
+                SubmitAction::None
+            }
+            KeyCode::PageDown => {
+                self.preview_scroll = self.preview_scroll.saturating_add(10);
+                SubmitAction::None
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let forward = key.code == KeyCode::Right;
+                match self.fields[self.focus].field {
+                    SubmitField::Partition => {
+                        let f = &mut self.fields[self.focus];
+                        cycle(&mut f.value, partitions, forward);
+                        f.error = None;
+                        self.apply_partition_defaults();
+                        self.forget_checks();
+                    }
+                    SubmitField::Gpus => {
+                        let choices = self.gpu_choices(limits);
+                        if choices.is_empty() {
+                            self.message = Some("No GPUs found in sinfo's gres".to_string());
+                        }
+                        let f = &mut self.fields[self.focus];
+                        cycle(&mut f.value, &choices, forward);
+                        f.error = None;
+                        self.forget_checks();
+                    }
+                    SubmitField::Notify => {
+                        let step = if forward { 1 } else { MAIL_TYPES.len() - 1 };
+                        self.mail_cursor = (self.mail_cursor + step) % MAIL_TYPES.len();
+                    }
+                    _ => {}
+                }
+                SubmitAction::None
+            }
+            KeyCode::Enter => self.submit(partitions, limits),
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let path = self.script_path();
+                if path.is_empty() {
+                    self.message = Some("Enter the path of the script to edit".to_string());
+                    return SubmitAction::None;
+                }
+                SubmitAction::EditScript(path)
+            }
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let path = self.script_path();
+                if path.is_empty() || !Path::new(&path).is_file() {
+                    self.message = Some("Enter the path of an existing script first".to_string());
+                    return SubmitAction::None;
+                }
+                SubmitAction::SearchModules(path)
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                SubmitAction::BrowseScript(self.browse_dir())
+            }
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if self.templates.is_empty() {
+                    self.message =
+                        Some("No templates configured ([[templates]] in config.toml)".to_string());
+                    return SubmitAction::None;
+                }
+                let names = self.templates.iter().map(|t| t.name.clone()).collect();
+                SubmitAction::PickTemplate(names)
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.submit(partitions, limits)
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                SubmitAction::EditDependency(self.value(SubmitField::Dependency).to_string())
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if !self.validate(partitions) {
+                    self.message = Some("Fix the highlighted fields first".to_string());
+                    return SubmitAction::None;
+                }
+                self.message = Some("Asking the scheduler for a start estimate...".to_string());
+                SubmitAction::TestOnly(self.command())
+            }
+            // The notifications are checkboxes rather than text
+            KeyCode::Char(_) | KeyCode::Backspace
+                if self.fields[self.focus].field == SubmitField::Notify =>
+            {
+                if key.code == KeyCode::Char(' ') {
+                    self.toggle_mail_type();
+                }
+                SubmitAction::None
+            }
+            KeyCode::Char(c) => {
+                let f = &mut self.fields[self.focus];
+                f.value.push(c);
+                f.error = None;
+                self.forget_checks();
+                SubmitAction::None
+            }
+            KeyCode::Backspace => {
+                let f = &mut self.fields[self.focus];
+                f.value.pop();
+                f.error = None;
+                self.forget_checks();
+                SubmitAction::None
+            }
+            _ => SubmitAction::None,
+        }
+    }
+
+    /// Reload the preview when leaving the script field, so typing a path does not
+    /// read every partial one
+    fn leave_field(&mut self) {
+        match self.fields[self.focus].field {
+            SubmitField::Script => self.refresh_preview(),
+            SubmitField::Partition => self.apply_partition_defaults(),
+            SubmitField::Sweep => self.update_sweep(),
+            _ => {}
+        }
+    }
+
+    /// Validate, lint the script and return the command to run. Warnings are shown
+    /// first and the next submit with nothing changed goes ahead despite them.
+    fn submit(&mut self, partitions: &[String], limits: &ClusterLimits) -> SubmitAction {
+        if !self.validate(partitions) {
+            self.message = Some("Fix the highlighted fields before submitting".to_string());
+            return SubmitAction::None;
+        }
+        let command = self.command();
+        let path = self.script_path();
+        let script = fs::read_to_string(&path).unwrap_or_default();
+        let options = &command.args[..command.args.len() - 1];
+        self.path_errors = path_problems(&script, options);
+        if !self.path_errors.is_empty() {
+            self.message = Some("Fix the paths listed above the script first".to_string());
+            return SubmitAction::None;
+        }
+        let mut warnings = lint_script(&path, &script, options, limits);
+        if let Some(Ok(AccountBalance {
+            warning: Some(warning),
+            ..
+        })) = self.current_balance()
+        {
+            warnings.push(warning.clone());
+        }
+        if !warnings.is_empty() && warnings != self.warnings {
+            self.warnings = warnings;
+            self.message = Some("Press Enter again to submit anyway".to_string());
+            return SubmitAction::None;
+        }
+        if let Some(params) = &self.sweep {
+            let file = params_path(&path);
+            if let Err(e) = write_params(&file, params) {
+                self.message = Some(format!("Failed to write {}: {}", file, e));
+                return SubmitAction::None;
+            }
+        }
+        SubmitAction::Submit(command)
+    }
+
+    /// Render the submission form
+    pub fn render(&self, frame: &mut Frame, area: Rect, limits: Option<&ClusterLimits>) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(Line::from(tr("Submit Job")).centered())
+            .borders(Borders::NONE)
+            .style(Style::default().bg(Color::Black));
+        frame.render_widget(block, area);
+
+        // Fields on the left, the script on the right
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .margin(1)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(area);
+
+        let mut constraints: Vec<Constraint> =
+            self.fields.iter().map(|_| Constraint::Length(3)).collect();
+        constraints.push(Constraint::Min(0)); // Message
+        constraints.push(Constraint::Length(4)); // Help text
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(columns[0]);
+
+        let max_time = self.current_defaults().and_then(|d| d.max_time.as_deref());
+        let gpus = limits
+            .and_then(|l| l.partitions.get(self.value(SubmitField::Partition)))
+            .map(|part| part.gpu_summary())
+            .filter(|summary| !summary.is_empty());
+        for (i, f) in self.fields.iter().enumerate() {
+            let focused = i == self.focus;
+            let mut title = match (&f.error, &self.sweep) {
+                (Some(err), _) => format!("{} ✗ {}", f.field.title(), err),
+                (None, Some(params)) if f.field == SubmitField::Sweep => format!(
+                    "{} ({} tasks, --array={})",
+                    f.field.title(),
+                    params.len(),
+                    array_range(params.len())
+                ),
+                (None, _) if f.field == SubmitField::Sweep => {
+                    format!("{} (optional: a, b, c or data/*.csv)", f.field.title())
+                }
+                (None, _) if f.field == SubmitField::Notify && focused => {
+                    format!("{} (←/→: Move | Space: Tick)", f.field.title())
+                }
+                (None, _) if f.field.optional() => format!("{} (optional)", f.field.title()),
+                (None, _) => f.field.title().to_string(),
+            };
+            if let (SubmitField::TimeLimit, Some(max), None) = (f.field, max_time, &f.error) {
+                title.push_str(&format!(" [partition max {}]", max));
+            }
+            if let (SubmitField::Gpus, Some(gpus), None) = (f.field, &gpus, &f.error) {
+                title.push_str(&format!(" [{} per node, ←/→]", gpus));
+            }
+            if let (SubmitField::Account, None, Some(balance)) =
+                (f.field, &f.error, self.current_balance())
+            {
+                match balance {
+                    Ok(balance) => title.push_str(&format!(" [{}]", balance.summary)),
+                    Err(e) => title.push_str(&format!(" [balance unknown: {}]", e)),
+                }
+            }
+            let style = match (focused, f.error.is_some()) {
+                (true, _) => Style::default().fg(Color::Cyan),
+                (false, true) => Style::default().fg(Color::Red),
+                (false, false) => Style::default(),
+            };
+            let (text, cursor) = if f.field == SubmitField::Notify {
+                self.mail_checkboxes(focused)
+            } else {
+                (Line::from(f.value.clone()), f.value.chars().count())
+            };
+            let input = Paragraph::new(text).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .style(style),
+            );
+            frame.render_widget(input, chunks[i]);
+
+            if focused {
+                frame.set_cursor_position(Position {
+                    x: chunks[i].x + 1 + cursor as u16,
+                    y: chunks[i].y + 1,
+                });
+            }
+        }
+
+        if let Some(message) = &self.message {
+            let msg = Paragraph::new(message.as_str())
+                .style(Style::default().fg(Color::Yellow))
+                .wrap(Wrap { trim: true });
+            frame.render_widget(msg, chunks[self.fields.len()]);
+        }
+
+        let help_text = "↑/↓/Tab: Switch field | ←/→: Cycle partition or GPUs | Ctrl+t: Template | Ctrl+o: Choose script | Ctrl+e: Edit script | Ctrl+l: Load modules | Ctrl+d: Dependencies | PgUp/PgDn: Scroll script | Ctrl+r: Test (--test-only) | Enter/Ctrl+a: Submit | Esc: Close";
+        let help = Paragraph::new(tr(help_text))
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[self.fields.len() + 1]);
+
+        // The exact command goes first, with the scheduler's estimate when asked for
+        let command = self.command().to_string();
+        let width = columns[1].width.saturating_sub(2).max(1) as usize;
+        let mut lines = vec![Line::from(command.clone())];
+        let mut height = command.chars().count().div_ceil(width);
+        if let Some(estimate) = &self.estimate {
+            let (text, color) = match estimate {
+                Ok(text) => (text, Color::Green),
+                Err(e) => (e, Color::Red),
+            };
+            lines.push(Line::styled(text.clone(), Style::default().fg(color)));
+            height += text.chars().count().div_ceil(width).max(1);
+        }
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length((height as u16 + 2).min(columns[1].height / 3)),
+                Constraint::Min(0),
+            ])
+            .split(columns[1]);
+        let command = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().title(tr("Command")).borders(Borders::ALL));
+        frame.render_widget(command, rows[0]);
+
+        // Problems from the last submit go above the script they refer to
+        let count = self.path_errors.len() + self.warnings.len();
+        let right = if count == 0 {
+            rows[1]
+        } else {
+            let height = (count as u16 + 2).min(rows[1].height / 3);
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(height), Constraint::Min(0)])
+                .split(rows[1]);
+            let errors = self
+                .path_errors
+                .iter()
+                .map(|e| Line::styled(format!("✗ {}", e), Style::default().fg(Color::Red)));
+            let warnings = self
+                .warnings
+                .iter()
+                .map(|w| Line::styled(format!("⚠ {}", w), Style::default().fg(Color::Yellow)));
+            let (title, color) = if self.path_errors.is_empty() {
+                (tr("Warnings"), Color::Yellow)
+            } else {
+                (tr("Cannot submit"), Color::Red)
+            };
+            let warnings = Paragraph::new(errors.chain(warnings).collect::<Vec<_>>())
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(color)),
+                );
+            frame.render_widget(warnings, rows[0]);
+            rows[1]
+        };
+        self.render_preview(frame, right);
+    }
+
+    /// Render the script with its `#SBATCH` directives highlighted
+    fn render_preview(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let paragraph = match &self.preview {
+            None => Paragraph::new(tr("Enter a script path to preview it"))
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block.title(tr("Script"))),
+            Some(preview) => {
+                let text = match &preview.content {
+                    Ok(content) => script_text(content, preview.highlighted.as_deref()),
+                    Err(e) => Text::styled(e.clone(), Style::default().fg(Color::Red)),
+                };
+                Paragraph::new(text)
+                    .block(block.title(preview.path.as_str()))
+                    .scroll((self.preview_scroll, 0))
+            }
+        };
+        frame.render_widget(paragraph, area);
+    }
+}
+
+impl SubmitForm {
+    /// The notification checkboxes, with the one under the cursor highlighted when
+    /// focused, and the cursor column
+    fn mail_checkboxes(&self, focused: bool) -> (Line<'static>, usize) {
+        let ticked: Vec<&str> = self.value(SubmitField::Notify).split(',').collect();
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for (i, (kind, label)) in MAIL_TYPES.iter().enumerate() {
+            let mark = if ticked.contains(kind) { "x" } else { " " };
+            let text = format!("[{}] {}", mark, label);
+            if i == self.mail_cursor {
+                cursor = spans.iter().map(|s: &Span| s.width()).sum::<usize>() + 1;
+            }
+            let style = if focused && i == self.mail_cursor {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(text, style));
+            spans.push(Span::raw(" "));
+        }
+        (Line::from(spans), cursor)
+    }
+}
+
+/// Value the partition defaults give a field, if any
+fn default_value(defaults: &PartitionDefaults, field: SubmitField) -> Option<String> {
+    match field {
+        SubmitField::Account => defaults.account.clone(),
+        SubmitField::TimeLimit => defaults.time.clone(),
+        SubmitField::Cpus => defaults.cpus.map(|n| n.to_string()),
+        SubmitField::Memory => defaults.memory.clone(),
+        // The form asks for the count (and type) of `--gres=gpu:...`
+        SubmitField::Gpus => defaults
+            .gres
+            .as_deref()
+            .and_then(|gres| gres.strip_prefix("gpu:"))
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Check a single field value, returning a short error description if invalid
+pub fn validate_field(field: SubmitField, value: &str, partitions: &[String]) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return (!field.optional()).then(|| "required".to_string());
+    }
+
+    match field {
+        SubmitField::Script => {
+            let path = expand_home(value);
+            match fs::metadata(&path) {
+                Err(e) if e.kind() == ErrorKind::NotFound => Some("file not found".to_string()),
+                Err(e) => Some(format!("cannot be accessed: {}", e)),
+                Ok(metadata) if metadata.is_dir() => Some("is a directory".to_string()),
+                Ok(_) => fs::File::open(&path)
+                    .err()
+                    .map(|e| format!("cannot be read: {}", e)),
+            }
+        }
+        SubmitField::Partition => {
+            if !partitions.is_empty() && !partitions.iter().any(|p| p == value) {
+                Some("unknown partition".to_string())
+            } else {
+                None
+            }
+        }
+        SubmitField::TimeLimit => {
+            if parse_time_limit(value).is_none() {
+                Some("use [days-]hours:minutes:seconds or minutes".to_string())
+            } else {
+                None
+            }
+        }
+        SubmitField::Cpus => match value.parse::<u32>() {
+            Ok(n) if n > 0 => None,
+            _ => Some("must be a positive integer".to_string()),
+        },
+        SubmitField::Memory => {
+            // sbatch accepts megabytes or a K/M/G/T suffix, e.g. "4000" or "16G"
+            let digits = value.trim_end_matches(|c: char| "KMGTkmgt".contains(c));
+            let valid = value.len() - digits.len() <= 1
+                && !digits.is_empty()
+                && digits.chars().all(|c| c.is_ascii_digit());
+            if valid {
+                None
+            } else {
+                Some("use a size like 4000 or 16G".to_string())
+            }
+        }
+        SubmitField::Gpus => {
+            // A count, optionally with a type, e.g. "2" or "a100:2"
+            let count = value.rsplit(':').next().unwrap_or("");
+            match count.parse::<u32>() {
+                Ok(n) if n > 0 => None,
+                _ => Some("use a count or type:count, e.g. a100:2".to_string()),
+            }
+        }
+        SubmitField::Account => {
+            if value.chars().any(char::is_whitespace) {
+                Some("must not contain spaces".to_string())
+            } else {
+                None
+            }
+        }
+        SubmitField::WorkDir => match fs::metadata(expand_home(value)) {
+            Err(e) if e.kind() == ErrorKind::NotFound => Some("directory not found".to_string()),
+            Err(e) => Some(format!("cannot be accessed: {}", e)),
+            Ok(metadata) if !metadata.is_dir() => Some("not a directory".to_string()),
+            Ok(_) => None,
+        },
+        SubmitField::JobName => None,
+        SubmitField::Dependency => {
+            let (entries, _) = parse_dependency(value);
+            let valid = entries
+                .iter()
+                .all(|e| !e.kind.is_empty() && (e.target.is_some() || e.kind == "singleton"));
+            if valid {
+                None
+            } else {
+                Some("use type:job, e.g. afterok:123,afterany:124".to_string())
+            }
+        }
+        SubmitField::Notify => value
+            .split(',')
+            .find(|t| !MAIL_TYPES.iter().any(|(kind, _)| kind == t))
+            .map(|t| format!("unknown mail type {}", t)),
+        SubmitField::MailUser => {
+            if value.contains(char::is_whitespace) || value.contains(',') {
+                Some("one address or user name".to_string())
+            } else {
+                None
+            }
+        }
+        SubmitField::Sweep => expand_sweep(value).err(),
+    }
+}
@@ -0,0 +1,272 @@
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    i18n::{tr, trf},
+    slurm::{first_host, sacct::TimelineJob, JobState},
+    ui::jobslist::state_color,
+};
+
+/// Spans of time the view can show, in hours, with their labels
+const WINDOWS: [(i64, &str); 5] = [(6, "6h"), (12, "12h"), (24, "1d"), (72, "3d"), (168, "7d")];
+
+/// Index into [`WINDOWS`] shown first
+const DEFAULT_WINDOW: usize = 2;
+
+/// How far back jobs are loaded; the widest window
+pub const TIMELINE_START: &str = "now-7days";
+
+/// Width of the job labels left of the bars
+const LABEL_WIDTH: u16 = 28;
+
+/// How the rows of the timeline are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grouping {
+    /// By the (first) node the job ran on
+    Node,
+    /// By array job, single jobs together
+    Array,
+}
+
+/// Gantt chart of the user's recent jobs: one bar per job from submit over
+/// start to end (or now), grouped by node or array, to spot scheduling gaps
+/// and overlaps
+pub struct TimelineView {
+    pub visible: bool,
+    jobs: Vec<TimelineJob>,
+    grouping: Grouping,
+    window: usize,
+    now: NaiveDateTime,
+    scroll: usize,
+}
+
+impl TimelineView {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            jobs: Vec::new(),
+            grouping: Grouping::Node,
+            window: DEFAULT_WINDOW,
+            now: Local::now().naive_local(),
+            scroll: 0,
+        }
+    }
+
+    /// Show these jobs, loaded since [`TIMELINE_START`]
+    pub fn show(&mut self, mut jobs: Vec<TimelineJob>) {
+        jobs.sort_by_key(|job| (job.submit, job.id.clone()));
+        self.jobs = jobs;
+        self.now = Local::now().naive_local();
+        self.scroll = 0;
+        self.visible = true;
+    }
+
+    /// Hide the view
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Handle key events for the view
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Enter => self.hide(),
+            KeyCode::Char('g') => {
+                self.grouping = match self.grouping {
+                    Grouping::Node => Grouping::Array,
+                    Grouping::Array => Grouping::Node,
+                };
+                self.scroll = 0;
+            }
+            KeyCode::Char('+') => self.window = self.window.saturating_sub(1),
+            KeyCode::Char('-') => self.window = (self.window + 1).min(WINDOWS.len() - 1),
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll += 1,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll += 10,
+            KeyCode::Home => self.scroll = 0,
+            _ => {}
+        }
+    }
+
+    /// Start of the time axis
+    fn axis_start(&self) -> NaiveDateTime {
+        self.now - TimeDelta::hours(WINDOWS[self.window].0)
+    }
+
+    /// Jobs in the window, grouped, groups ordered by their first submit
+    fn groups(&self) -> Vec<(String, Vec<&TimelineJob>)> {
+        let start = self.axis_start();
+        let mut groups: Vec<(String, Vec<&TimelineJob>)> = Vec::new();
+        for job in &self.jobs {
+            if job.end.is_some_and(|end| end < start) {
+                continue;
+            }
+            let key = match self.grouping {
+                Grouping::Node if job.start.is_none() => tr("Not started").to_string(),
+                Grouping::Node => {
+                    first_host(&job.node_list).unwrap_or_else(|| tr("Not started").to_string())
+                }
+                Grouping::Array => match job.id.split_once('_') {
+                    Some((array, _)) => trf("Array {id}", &[("id", array)]),
+                    None => tr("Single jobs").to_string(),
+                },
+            };
+            match groups.iter_mut().find(|(name, _)| *name == key) {
+                Some((_, jobs)) => jobs.push(job),
+                None => groups.push((key, vec![job])),
+            }
+        }
+        groups
+    }
+
+    /// Render the view
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(Clear, area);
+
+        let grouping = match self.grouping {
+            Grouping::Node => tr("node"),
+            Grouping::Array => tr("array"),
+        };
+        let block = Block::default()
+            .title(
+                Line::from(trf(
+                    "Timeline of the last {window}, by {grouping}",
+                    &[("window", WINDOWS[self.window].1), ("grouping", grouping)],
+                ))
+                .centered(),
+            )
+            .title_bottom(
+                Line::from(tr(
+                    "g: Group by node/array | +/-: Zoom | ↑/↓/PgUp/PgDn: Scroll | Enter/Esc/q: Close",
+                ))
+                .centered(),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let label_width = LABEL_WIDTH.min(inner.width / 3) as usize;
+        let width = (inner.width as usize).saturating_sub(label_width);
+        let mut lines = vec![
+            self.axis_line(label_width, width),
+            Line::from(vec![
+                Span::raw(" ".repeat(label_width)),
+                Span::styled("· ", Style::default().fg(Color::DarkGray)),
+                Span::raw(format!("{}  ", tr("waiting"))),
+                Span::styled("█ ", Style::default().fg(Color::Green)),
+                Span::raw(tr("running (colored by state)")),
+            ]),
+        ];
+
+        let mut rows = Vec::new();
+        for (name, jobs) in self.groups() {
+            rows.push(Line::styled(
+                name,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            for job in jobs {
+                let label: String = format!(" {} {}", job.id, job.name)
+                    .chars()
+                    .take(label_width.saturating_sub(1))
+                    .collect();
+                let mut spans = vec![Span::raw(format!("{:<label_width$}", label))];
+                spans.extend(self.bar(job, width));
+                rows.push(Line::from(spans));
+            }
+        }
+        if rows.is_empty() {
+            rows.push(Line::styled(
+                tr("No jobs in this time window"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        let height = (inner.height as usize).saturating_sub(lines.len());
+        let scroll = self.scroll.min(rows.len().saturating_sub(height));
+        lines.extend(rows.into_iter().skip(scroll));
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// Time labels along the axis, one every quarter of the width
+    fn axis_line(&self, label_width: usize, width: usize) -> Line<'static> {
+        let format = if WINDOWS[self.window].0 > 24 {
+            "%m-%d %H:%M"
+        } else {
+            "%H:%M"
+        };
+        let start = self.axis_start();
+        let span = (self.now - start).num_seconds() as f64;
+        let mut axis = " ".repeat(width);
+        for quarter in 0..4 {
+            let column = width * quarter / 4;
+            let time = start + TimeDelta::seconds((span * quarter as f64 / 4.0) as i64);
+            let label = format!("|{}", time.format(format));
+            if column + label.len() <= width {
+                axis.replace_range(column..column + label.len(), &label);
+            }
+        }
+        Line::from(vec![
+            Span::raw(" ".repeat(label_width)),
+            Span::styled(axis, Style::default().fg(Color::DarkGray)),
+        ])
+    }
+
+    /// Bar of one job: dots while it waited, a block while it ran
+    fn bar(&self, job: &TimelineJob, width: usize) -> Vec<Span<'static>> {
+        let start = self.axis_start();
+        let span = (self.now - start).num_seconds().max(1) as f64;
+        let column = |time: NaiveDateTime| {
+            let offset = (time - start).num_seconds() as f64 / span * width as f64;
+            offset.clamp(0.0, width as f64) as usize
+        };
+        let state: JobState = job.state.parse().unwrap_or(JobState::Other);
+
+        let mut cells = vec![(' ', Style::default()); width];
+        let waited_until = job.start.unwrap_or(self.now);
+        if let Some(submit) = job.submit {
+            let color = if job.start.is_none() {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+            let (from, to) = (column(submit), column(waited_until));
+            for cell in cells.iter_mut().take(to).skip(from) {
+                *cell = ('·', Style::default().fg(color));
+            }
+        }
+        if let Some(started) = job.start {
+            let from = column(started);
+            // Runs of a few seconds still get one cell
+            let to = column(job.end.unwrap_or(self.now)).max(from + 1).min(width);
+            for cell in cells.iter_mut().take(to).skip(from) {
+                *cell = ('█', Style::default().fg(state_color(state)));
+            }
+        }
+
+        // Merge cells of the same style into spans
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut run = String::new();
+        let mut run_style = Style::default();
+        for (c, style) in cells {
+            if style != run_style && !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), run_style));
+            }
+            run_style = style;
+            run.push(c);
+        }
+        if !run.is_empty() {
+            spans.push(Span::styled(run, run_style));
+        }
+        spans
+    }
+}
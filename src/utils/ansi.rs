@@ -0,0 +1,154 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+/// Piece of a line of terminal output
+enum Segment<'a> {
+    /// Printable text
+    Text(&'a str),
+    /// Parameters of an SGR (`ESC [ ... m`) sequence, e.g. "1;31"
+    Sgr(&'a str),
+}
+
+/// Split a line into text and SGR sequences; other escape sequences (cursor
+/// movement, OSC titles and links, ...) are dropped
+fn segments(line: &str) -> Vec<Segment<'_>> {
+    let bytes = line.as_bytes();
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != 0x1b {
+            i += 1;
+            continue;
+        }
+        if text_start < i {
+            segments.push(Segment::Text(&line[text_start..i]));
+        }
+        i += 1;
+        match bytes.get(i) {
+            // CSI: parameter bytes up to a final byte in 0x40..=0x7e
+            Some(b'[') => {
+                let start = i + 1;
+                let end = bytes[start..]
+                    .iter()
+                    .position(|b| (0x40..=0x7e).contains(b))
+                    .map_or(bytes.len(), |n| start + n);
+                if bytes.get(end) == Some(&b'm') {
+                    segments.push(Segment::Sgr(&line[start..end]));
+                }
+                i = end + 1;
+            }
+            // OSC: up to BEL or ST (ESC \)
+            Some(b']') => {
+                while i < bytes.len() && bytes[i] != 0x07 && !bytes[i..].starts_with(b"\x1b\\") {
+                    i += 1;
+                }
+                i += if bytes[i..].starts_with(b"\x1b\\") {
+                    2
+                } else {
+                    1
+                };
+            }
+            // Two-byte sequences such as ESC = or ESC 7
+            Some(b) if b.is_ascii() => i += 1,
+            _ => {}
+        }
+        text_start = i.min(bytes.len());
+    }
+    if text_start < bytes.len() {
+        segments.push(Segment::Text(&line[text_start..]));
+    }
+    segments
+}
+
+/// Styled spans of a line with ANSI escape codes. `style` is the style in
+/// effect at the start of the line and is updated, as colors carry over to
+/// the next line until they are reset.
+pub fn parse_line(line: &str, style: &mut Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for segment in segments(line) {
+        match segment {
+            Segment::Text(text) => spans.push(Span::styled(text.to_string(), *style)),
+            Segment::Sgr(params) => apply_sgr(params, style),
+        }
+    }
+    spans
+}
+
+/// Update `style` with the SGR sequences of a line without building spans
+pub fn skip_line(line: &str, style: &mut Style) {
+    for segment in segments(line) {
+        if let Segment::Sgr(params) = segment {
+            apply_sgr(params, style);
+        }
+    }
+}
+
+/// A line with its escape codes removed
+pub fn strip(line: &str) -> String {
+    segments(line)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(text) => Some(text),
+            Segment::Sgr(_) => None,
+        })
+        .collect()
+}
+
+/// Apply the parameters of one SGR sequence; an empty list resets like `0`
+fn apply_sgr(params: &str, style: &mut Style) {
+    let codes: Vec<u16> = params
+        .split([';', ':'])
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+    let mut codes = codes.iter().copied();
+    while let Some(code) = codes.next() {
+        *style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 | 6 => style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            8 => style.add_modifier(Modifier::HIDDEN),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            25 => style.remove_modifier(Modifier::SLOW_BLINK),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            28 => style.remove_modifier(Modifier::HIDDEN),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(Color::Indexed((code - 30) as u8)),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => *style,
+            },
+            39 => Style { fg: None, ..*style },
+            40..=47 => style.bg(Color::Indexed((code - 40) as u8)),
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => *style,
+            },
+            49 => Style { bg: None, ..*style },
+            90..=97 => style.fg(Color::Indexed((code - 90 + 8) as u8)),
+            100..=107 => style.bg(Color::Indexed((code - 100 + 8) as u8)),
+            _ => *style,
+        };
+    }
+}
+
+/// Color of a `38;5;n` or `38;2;r;g;b` sequence (same for 48), after the 38
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match codes.next()? {
+        5 => Some(Color::Indexed(codes.next()? as u8)),
+        2 => {
+            let (r, g, b) = (codes.next()?, codes.next()?, codes.next()?);
+            Some(Color::Rgb(r as u8, g as u8, b as u8))
+        }
+        _ => None,
+    }
+}
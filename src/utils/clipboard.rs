@@ -0,0 +1,87 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+/// Copy text to the system clipboard.
+///
+/// Uses the first available clipboard tool (`pbcopy`, `wl-copy`, `xclip`, `xsel`)
+/// and falls back to the OSC 52 terminal escape sequence, which also works when
+/// slurmer runs on a login node over SSH.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let candidates: [(&str, &[&str], bool); 4] = [
+        ("pbcopy", &[], cfg!(target_os = "macos")),
+        (
+            "wl-copy",
+            &[],
+            std::env::var_os("WAYLAND_DISPLAY").is_some(),
+        ),
+        (
+            "xclip",
+            &["-selection", "clipboard"],
+            std::env::var_os("DISPLAY").is_some(),
+        ),
+        (
+            "xsel",
+            &["--clipboard", "--input"],
+            std::env::var_os("DISPLAY").is_some(),
+        ),
+    ];
+
+    for (cmd, args, usable) in candidates {
+        if usable && pipe_to_command(cmd, args, text).is_ok() {
+            return Ok(());
+        }
+    }
+
+    // OSC 52: ask the terminal emulator to set the clipboard
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    stdout.flush()
+}
+
+/// Feed text to the stdin of a clipboard command
+fn pipe_to_command(cmd: &str, args: &[&str], text: &str) -> io::Result<()> {
+    let mut child = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{} exited with {}", cmd, status)))
+    }
+}
+
+/// Minimal standard base64 encoder for the OSC 52 payload
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        if chunk.len() > 1 {
+            out.push(ALPHABET[(n >> 6) as usize & 63] as char);
+        } else {
+            out.push('=');
+        }
+        if chunk.len() > 2 {
+            out.push(ALPHABET[n as usize & 63] as char);
+        } else {
+            out.push('=');
+        }
+    }
+    out
+}
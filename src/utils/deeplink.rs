@@ -0,0 +1,64 @@
+use std::fmt;
+
+/// URL scheme used for shareable job links
+const LINK_SCHEME: &str = "slurmer://";
+
+/// A job reference encoded as `slurmer://<cluster>/job/<id>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobLink {
+    /// Cluster the job belongs to; `None` when only a bare job ID was given
+    pub cluster: Option<String>,
+    pub job_id: String,
+}
+
+impl JobLink {
+    pub fn new(cluster: &str, job_id: &str) -> Self {
+        Self {
+            cluster: Some(cluster.to_string()),
+            job_id: job_id.to_string(),
+        }
+    }
+
+    /// Parse a deep link, or a bare job ID such as `12345` or `12345_7`
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if let Some(rest) = s.strip_prefix(LINK_SCHEME) {
+            let mut parts = rest.trim_end_matches('/').split('/');
+            let cluster = parts.next().filter(|c| !c.is_empty())?;
+            let kind = parts.next()?;
+            let job_id = parts.next()?;
+            if kind != "job" || parts.next().is_some() || !is_job_id(job_id) {
+                return None;
+            }
+            Some(Self::new(cluster, job_id))
+        } else if is_job_id(s) {
+            Some(Self {
+                cluster: None,
+                job_id: s.to_string(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for JobLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}/job/{}",
+            LINK_SCHEME,
+            self.cluster.as_deref().unwrap_or("default"),
+            self.job_id
+        )
+    }
+}
+
+/// Job IDs are digits, optionally followed by `_<task>` or `+<component>`
+fn is_job_id(s: &str) -> bool {
+    let base = s.split(['_', '+']).next().unwrap_or("");
+    !base.is_empty()
+        && base.chars().all(|c| c.is_ascii_digit())
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || c == '_' || c == '+' || c == '[' || c == ']' || c == '-')
+}
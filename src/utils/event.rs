@@ -1,6 +1,9 @@
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::{
-    sync::mpsc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
     thread,
     time::{Duration, Instant},
 };
@@ -47,25 +50,45 @@ pub struct EventHandler {
     /// Thread handle for the event handler
     #[allow(dead_code)]
     handle: thread::JoinHandle<()>,
+    /// Set while another process owns the terminal
+    paused: Arc<AtomicBool>,
+    /// Held by the event thread while it polls the terminal
+    poll_lock: Arc<Mutex<()>>,
 }
 
 impl EventHandler {
     /// Create a new event handler with the given configuration
     pub fn new(config: EventConfig) -> Self {
         let (tx, rx) = mpsc::channel();
+        let paused = Arc::new(AtomicBool::new(false));
+        let poll_lock = Arc::new(Mutex::new(()));
         let handle = {
             let tx = tx.clone();
+            let paused = Arc::clone(&paused);
+            let poll_lock = Arc::clone(&poll_lock);
             thread::spawn(move || {
                 let tick_rate = config.tick_rate;
                 let mut last_tick = Instant::now();
 
                 loop {
+                    if paused.load(Ordering::Acquire) {
+                        thread::sleep(Duration::from_millis(20));
+                        continue;
+                    }
+
                     let timeout = tick_rate
                         .checked_sub(last_tick.elapsed())
                         .unwrap_or(Duration::from_secs(0));
 
-                    if event::poll(timeout).expect("Failed to poll for events") {
-                        match event::read().expect("Failed to read event") {
+                    let guard = poll_lock.lock().unwrap_or_else(|e| e.into_inner());
+                    let ready = event::poll(timeout).expect("Failed to poll for events");
+                    let read = ready.then(|| event::read().expect("Failed to read event"));
+                    drop(guard);
+
+                    if let Some(read) = read {
+                        match read {
+                            // The sends stay in the bodies, out of the guards
+                            #[allow(clippy::collapsible_match)]
                             CrosstermEvent::Key(key) => {
                                 if tx.send(Event::Key(key)).is_err() {
                                     return;
@@ -79,6 +102,7 @@ impl EventHandler {
                                     return;
                                 }
                             }
+                            #[allow(clippy::collapsible_match)]
                             CrosstermEvent::Resize(width, height) => {
                                 if tx.send(Event::Resize(width, height)).is_err() {
                                     return;
@@ -98,6 +122,24 @@ impl EventHandler {
             })
         };
 
-        Self { rx, tx, handle }
+        Self {
+            rx,
+            tx,
+            handle,
+            paused,
+            poll_lock,
+        }
+    }
+
+    /// Stop reading terminal input while `f` runs, so that a child process
+    /// can use stdin without the event thread stealing keystrokes
+    pub fn suspend<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.paused.store(true, Ordering::Release);
+        // Wait for a poll that is already in progress to finish
+        let guard = self.poll_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let result = f();
+        drop(guard);
+        self.paused.store(false, Ordering::Release);
+        result
     }
 }
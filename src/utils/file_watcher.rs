@@ -1,6 +1,6 @@
 use std::{
     fmt,
-    fs::File,
+    fs::{File, Metadata},
     io::{self, Read, Seek},
     path::{Path, PathBuf},
     thread,
@@ -13,15 +13,28 @@ use crossbeam::{
 };
 use notify::{event::ModifyKind, RecursiveMode, Watcher};
 
+use crate::{slurm::format_size, utils::truncate_front};
+
 type JobOutput = Result<String, FileWatcherError>;
 
+/// Most of a log file kept in memory: the end of larger files, like `tail`
+const MAX_LOG_BYTES: u64 = 8 * 1024 * 1024;
+
 struct FileReader {
     content_sender: Sender<io::Result<String>>,
     receiver: Receiver<()>,
     file_path: PathBuf,
     interval: Duration,
+    /// The last [`MAX_LOG_BYTES`] of the file at most
     content: String,
+    /// Offset up to which the file has been read
     pos: u64,
+    /// Bytes of a UTF-8 character cut off by the end of the last read
+    pending: Vec<u8>,
+    /// Identity of the file read, to notice when it is replaced (rotated)
+    file_id: Option<u64>,
+    /// Whether the content has been sent at least once
+    sent: bool,
 }
 
 struct FileWatcher {
@@ -60,10 +73,10 @@ impl FileWatcher {
         interval: Duration,
     ) -> Self {
         FileWatcher {
-            app: app,
-            receiver: receiver,
+            app,
+            receiver,
             file_path: None,
-            interval: interval,
+            interval,
         }
     }
 
@@ -71,11 +84,8 @@ impl FileWatcher {
         let (watch_sender, watch_receiver) = unbounded();
         let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
             let event = res.unwrap();
-            match event.kind {
-                notify::EventKind::Modify(ModifyKind::Data(_)) => {
-                    watch_sender.send(event.paths).unwrap();
-                }
-                _ => {}
+            if let notify::EventKind::Modify(ModifyKind::Data(_)) = event.kind {
+                watch_sender.send(event.paths).unwrap();
             };
         })
         .unwrap();
@@ -91,7 +101,7 @@ impl FileWatcher {
                             (_watch_sender, _watch_receiver) = unbounded::<()>();
 
                             if let Some(p) = &self.file_path {
-                                watcher.unwatch(p).expect(format!("Failed to unwatch {:?}", p).as_str());
+                                watcher.unwatch(p).unwrap_or_else(|_| panic!("Failed to unwatch {:?}", p));
                                 self.file_path = None;
                             }
 
@@ -100,7 +110,7 @@ impl FileWatcher {
                                 match res {
                                     Ok(_) => {
                                         self.file_path = Some(p.clone());
-                                        let i = self.interval.clone();
+                                        let i = self.interval;
                                         thread::spawn(move || FileReader::new(_content_sender, _watch_receiver, p, i).run());
                                     },
                                     Err(e) => self.app.send(Err(FileWatcherError::Watcher(e))).unwrap()
@@ -113,7 +123,7 @@ impl FileWatcher {
                 }
                 recv(watch_receiver) -> _ => { _watch_sender.send(()).unwrap(); }
                 recv(_content_receiver) -> msg => {
-                    self.app.send(msg.unwrap().map_err(|e| FileWatcherError::File(e))).unwrap();
+                    self.app.send(msg.unwrap().map_err(FileWatcherError::File)).unwrap();
                 }
             }
         }
@@ -128,12 +138,15 @@ impl FileReader {
         interval: Duration,
     ) -> Self {
         FileReader {
-            content_sender: content_sender,
-            receiver: receiver,
-            file_path: file_path,
-            interval: interval,
+            content_sender,
+            receiver,
+            file_path,
+            interval,
             content: "".to_string(),
             pos: 0,
+            pending: Vec::new(),
+            file_id: None,
+            sent: false,
         }
     }
 
@@ -151,15 +164,97 @@ impl FileReader {
     }
 
     fn update(&mut self) -> Result<(), SendError<io::Result<String>>> {
-        let s = File::open(&self.file_path).and_then(|mut f| {
-            // avoid reading the whole file every time
-            self.pos = f.seek(io::SeekFrom::Start(self.pos))?;
-            self.pos += f.read_to_string(&mut self.content)? as u64;
-            Ok(self.content.clone())
-        });
-        // let s = fs::read_to_string(&self.file_path); // alternative: always read the whole file
-        self.content_sender.send(s)
+        match self.read_new() {
+            Ok(false) if self.sent => Ok(()),
+            Ok(_) => {
+                self.sent = true;
+                self.content_sender.send(Ok(self.content.clone()))
+            }
+            Err(e) => self.content_sender.send(Err(e)),
+        }
+    }
+
+    /// Read what was appended since the last read, never more than
+    /// [`MAX_LOG_BYTES`]; returns whether the content changed
+    fn read_new(&mut self) -> io::Result<bool> {
+        let mut file = File::open(&self.file_path)?;
+        let metadata = file.metadata()?;
+        let len = metadata.len();
+        let id = file_id(&metadata);
+
+        // A shorter or different file was truncated or rotated: start over
+        let mut changed = false;
+        if len < self.pos || self.file_id.is_some_and(|known| known != id) {
+            self.content = "[log file was truncated or replaced; reading it again]\n".to_string();
+            self.pos = 0;
+            self.pending.clear();
+            changed = true;
+        }
+        self.file_id = Some(id);
+        if len == self.pos {
+            return Ok(changed);
+        }
+
+        // Skip to the last window of a large file, like `tail`
+        let mut start = self.pos;
+        let skipped = len - start > MAX_LOG_BYTES;
+        if skipped {
+            start = len - MAX_LOG_BYTES;
+            self.content = format!("[{} of earlier output not shown]\n", format_size(start));
+            self.pending.clear();
+        }
+        file.seek(io::SeekFrom::Start(start))?;
+        let mut bytes = std::mem::take(&mut self.pending);
+        file.take(len - start).read_to_end(&mut bytes)?;
+        self.pos = len;
+        if skipped {
+            // The first line of the window is usually cut in the middle
+            let first_line = bytes.iter().position(|&b| b == b'\n').map_or(0, |i| i + 1);
+            bytes.drain(..first_line);
+        }
+
+        self.pending = bytes.split_off(utf8_boundary(&bytes));
+        self.content.push_str(&String::from_utf8_lossy(&bytes));
+        truncate_front(&mut self.content, MAX_LOG_BYTES as usize);
+        Ok(true)
+    }
+}
+
+/// Identity of a file, which changes when a log is rotated and a new file
+/// takes its name
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &Metadata) -> u64 {
+    0
+}
+
+/// Length of `bytes` without a UTF-8 character cut off at the end, which the
+/// next read completes
+fn utf8_boundary(bytes: &[u8]) -> usize {
+    for i in (bytes.len().saturating_sub(3)..bytes.len()).rev() {
+        let byte = bytes[i];
+        // Continuation byte: look further back for the start of the character
+        if byte & 0xC0 == 0x80 {
+            continue;
+        }
+        let width = match byte {
+            0xF0.. => 4,
+            0xE0.. => 3,
+            0xC0.. => 2,
+            _ => 1,
+        };
+        return if i + width > bytes.len() {
+            i
+        } else {
+            bytes.len()
+        };
     }
+    bytes.len()
 }
 
 impl FileWatcherHandle {
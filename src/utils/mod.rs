@@ -1,11 +1,41 @@
+pub mod ansi;
+pub mod clipboard;
+pub mod deeplink;
 pub mod event;
 pub mod file_watcher;
+pub mod poller;
+pub mod shell;
+pub mod step_attach;
 
 /// Returns the current username from the environment
 pub fn get_username() -> String {
     std::env::var("USER").unwrap_or_else(|_| "unknown".to_string())
 }
 
+/// Path with a leading `~/` replaced by the home directory, for paths that are
+/// not passed through a shell
+pub fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
+
+/// Drop whole lines from the front of `buffer` until it fits in `max_bytes`
+pub fn truncate_front(buffer: &mut String, max_bytes: usize) {
+    if buffer.len() <= max_bytes {
+        return;
+    }
+    let excess = buffer.len() - max_bytes;
+    // Searching bytes is safe: a newline byte always sits on a char boundary
+    let cut = buffer.as_bytes()[excess..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|i| excess + i + 1)
+        .unwrap_or(buffer.len());
+    buffer.drain(..cut);
+}
+
 /// Truncate a string to a maximum length, appending an ellipsis if truncated
 pub fn _truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -0,0 +1,49 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossbeam::channel::{unbounded, Receiver};
+
+/// Runs a query every `interval` on a background thread and hands over the
+/// results. The thread stops when the poller is dropped.
+pub struct Poller<T> {
+    receiver: Receiver<T>,
+    stop: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> Poller<T> {
+    /// Start polling; the first query runs right away
+    pub fn spawn(interval: Duration, mut query: impl FnMut() -> T + Send + 'static) -> Self {
+        let (sender, receiver) = unbounded();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                let started = Instant::now();
+                if sender.send(query()).is_err() {
+                    break;
+                }
+                while started.elapsed() < interval && !stopped.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(100));
+                }
+            }
+        });
+        Self { receiver, stop }
+    }
+
+    /// Results received since the last call, oldest first
+    pub fn drain(&self) -> Vec<T> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl<T> Drop for Poller<T> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,28 @@
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    io,
+    process::{Command, ExitStatus},
+};
+
+/// Run an interactive program on the real terminal.
+///
+/// The TUI's raw mode, alternate screen and mouse capture are switched off while
+/// the program runs and restored afterwards, even if it fails to start. The
+/// caller must stop reading terminal events for the duration and redraw the
+/// whole screen once this returns.
+pub fn run_interactive(program: &str, args: &[String]) -> io::Result<ExitStatus> {
+    let mut stdout = io::stdout();
+    disable_raw_mode()?;
+    execute!(stdout, LeaveAlternateScreen, DisableMouseCapture, Show)?;
+
+    let status = Command::new(program).args(args).status();
+
+    enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    status
+}
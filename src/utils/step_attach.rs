@@ -0,0 +1,85 @@
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    process::{Child, Command, Stdio},
+    thread,
+};
+
+use crossbeam::channel::{unbounded, Receiver, Sender};
+
+/// A chunk of output streamed from an attached job step
+pub enum StepOutput {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Live connection to a running job step through `sattach <jobid>.<stepid>`.
+///
+/// Output is read on background threads; the `sattach` process is killed when
+/// the handle is dropped.
+pub struct StepAttach {
+    pub step_id: String,
+    child: Child,
+    receiver: Receiver<StepOutput>,
+}
+
+impl StepAttach {
+    /// Spawn `sattach` for the given step (e.g. "12345.0")
+    pub fn spawn(step_id: &str) -> io::Result<Self> {
+        let mut child = Command::new("sattach")
+            .arg(step_id)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (sender, receiver) = unbounded();
+        if let Some(stdout) = child.stdout.take() {
+            let sender = sender.clone();
+            thread::spawn(move || forward_lines(stdout, sender, StepOutput::Stdout));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            thread::spawn(move || forward_lines(stderr, sender, StepOutput::Stderr));
+        }
+
+        Ok(Self {
+            step_id: step_id.to_string(),
+            child,
+            receiver,
+        })
+    }
+
+    /// Collect all output received since the last call
+    pub fn drain(&self) -> Vec<StepOutput> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Whether the `sattach` process is still running
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+impl Drop for StepAttach {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Send every line read from `reader` until EOF or the receiver goes away
+fn forward_lines<R: Read>(reader: R, sender: Sender<StepOutput>, wrap: fn(String) -> StepOutput) {
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buf).into_owned();
+                if sender.send(wrap(line)).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use crate::slurm::{Job, JobState};
+
+/// A change of a watched job noticed on refresh
+#[derive(Debug, Clone)]
+pub enum WatchEvent {
+    /// The job is still queued but in another state
+    Changed {
+        id: String,
+        name: String,
+        from: JobState,
+        to: JobState,
+    },
+    /// The job is no longer in the queue; `last` is the state it was last seen in
+    Left {
+        id: String,
+        name: String,
+        last: JobState,
+    },
+}
+
+/// Jobs the user asked to be alerted about, with the last state seen for each
+#[derive(Debug, Default)]
+pub struct Watchlist {
+    jobs: BTreeMap<String, (String, JobState)>,
+}
+
+impl Watchlist {
+    /// Start watching a job, or stop if it is already watched. Returns whether
+    /// the job is watched afterwards.
+    pub fn toggle(&mut self, job: &Job) -> bool {
+        if self.jobs.remove(&job.id).is_some() {
+            return false;
+        }
+        self.jobs
+            .insert(job.id.clone(), (job.name.clone(), job.state));
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// IDs of the watched jobs
+    pub fn ids(&self) -> Vec<String> {
+        self.jobs.keys().cloned().collect()
+    }
+
+    /// Compare the watched jobs with what squeue lists for them now. Jobs that
+    /// left the queue are no longer watched.
+    pub fn update(&mut self, queued: &[Job]) -> Vec<WatchEvent> {
+        let mut events = Vec::new();
+        self.jobs.retain(
+            |id, (name, state)| match queued.iter().find(|job| job.id == *id) {
+                Some(job) => {
+                    if job.state != *state {
+                        events.push(WatchEvent::Changed {
+                            id: id.clone(),
+                            name: name.clone(),
+                            from: *state,
+                            to: job.state,
+                        });
+                        *state = job.state;
+                    }
+                    true
+                }
+                None => {
+                    events.push(WatchEvent::Left {
+                        id: id.clone(),
+                        name: name.clone(),
+                        last: *state,
+                    });
+                    false
+                }
+            },
+        );
+        events
+    }
+}